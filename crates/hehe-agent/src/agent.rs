@@ -1,20 +1,29 @@
+use crate::approval::ToolApprover;
 use crate::config::AgentConfig;
 use crate::error::{AgentError, Result};
 use crate::event::AgentEvent;
 use crate::executor::Executor;
+use crate::persona::{AgentDefinition, Role};
 use crate::response::AgentResponse;
 use crate::session::Session;
+use crate::state::{AgentLifecycle, AgentState};
+use hehe_core::{AgentId, Context};
 use hehe_llm::LlmProvider;
 use hehe_tools::{ToolExecutor, ToolRegistry};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::Stream;
 
 pub struct Agent {
-    config: AgentConfig,
+    id: AgentId,
+    config: RwLock<AgentConfig>,
     llm: Arc<dyn LlmProvider>,
     tools: Option<Arc<ToolExecutor>>,
+    tool_approver: Option<Arc<dyn ToolApprover>>,
+    roles: HashMap<String, Role>,
+    lifecycle: Arc<AgentLifecycle>,
 }
 
 impl Agent {
@@ -22,8 +31,55 @@ impl Agent {
         AgentBuilder::new()
     }
 
-    pub fn config(&self) -> &AgentConfig {
-        &self.config
+    /// Shorthand for [`AgentBuilder::from_config_file`]; the returned
+    /// builder still needs `llm` (and `tool_registry`, if the definition
+    /// names any `tools`) before [`AgentBuilder::build`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<AgentBuilder> {
+        AgentBuilder::from_config_file(path)
+    }
+
+    pub fn id(&self) -> AgentId {
+        self.id
+    }
+
+    /// The named prompt presets loaded from this agent's definition file, if any.
+    pub fn roles(&self) -> &HashMap<String, Role> {
+        &self.roles
+    }
+
+    /// Switches the agent's active persona to the role named `name`,
+    /// overwriting `system_prompt` (and `temperature`, if the role sets one)
+    /// on the current config via [`Self::update_config`]. Takes effect for
+    /// the next turn, same as any other `update_config` call.
+    pub fn switch_role(&self, name: &str) -> Result<()> {
+        let role = self
+            .roles
+            .get(name)
+            .ok_or_else(|| AgentError::config(format!("unknown role: {name}")))?;
+
+        let mut config = self.config();
+        config.system_prompt = role.system_prompt.clone();
+        if let Some(temperature) = role.temperature {
+            config = config.with_temperature(temperature);
+        }
+        self.update_config(config);
+        Ok(())
+    }
+
+    /// A snapshot of the agent's current config. A turn already in
+    /// progress keeps using the config it started with (see
+    /// [`Self::process_with_context`]); only turns started after
+    /// [`Self::update_config`] returns observe the new values.
+    pub fn config(&self) -> AgentConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Atomically replaces the agent's config, e.g. for hot-reloading
+    /// `system_prompt`/`model`/`temperature` without restarting the
+    /// process. Takes effect for the next turn; in-flight turns keep the
+    /// config they started with.
+    pub fn update_config(&self, config: AgentConfig) {
+        *self.config.write().unwrap() = config;
     }
 
     pub fn llm(&self) -> &Arc<dyn LlmProvider> {
@@ -34,32 +90,119 @@ impl Agent {
         Session::new()
     }
 
+    /// Current lifecycle state, for callers (e.g. the server) that want to report it.
+    pub fn state(&self) -> AgentState {
+        self.lifecycle.state()
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        self.lifecycle.pause()?;
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        self.lifecycle.resume()?;
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        self.lifecycle.stop()?;
+        Ok(())
+    }
+
     pub async fn chat(&self, session: &Session, message: &str) -> Result<String> {
         let response = self.process(session, message).await?;
         Ok(response.text)
     }
 
     pub async fn process(&self, session: &Session, message: &str) -> Result<AgentResponse> {
-        let executor = Executor::new(self.config.clone(), self.llm.clone(), self.tools.clone());
-        executor.execute(session, message).await
+        self.process_with_context(&Context::new(), session, message).await
+    }
+
+    /// Like [`Self::process`], but lets the caller supply a `Context` carrying
+    /// its own cancellation token and/or deadline — e.g. an HTTP handler that
+    /// wants the turn aborted if the client disconnects.
+    pub async fn process_with_context(
+        &self,
+        ctx: &Context,
+        session: &Session,
+        message: &str,
+    ) -> Result<AgentResponse> {
+        self.begin_turn()?;
+        self.lifecycle.wait_while_paused().await;
+        if self.lifecycle.is_stopped() {
+            return Err(AgentError::Stopped);
+        }
+
+        let executor = self.build_executor();
+        executor.execute(ctx, session, message).await
     }
 
     pub fn chat_stream(
         &self,
         session: &Session,
         message: &str,
+    ) -> impl Stream<Item = AgentEvent> + Send {
+        self.chat_stream_with_context(Context::new(), session, message)
+    }
+
+    /// Like [`Self::chat_stream`], but lets the caller supply a `Context`
+    /// carrying its own cancellation token and/or deadline.
+    pub fn chat_stream_with_context(
+        &self,
+        ctx: Context,
+        session: &Session,
+        message: &str,
     ) -> impl Stream<Item = AgentEvent> + Send {
         let (tx, rx) = mpsc::channel(100);
-        let executor = Executor::new(self.config.clone(), self.llm.clone(), self.tools.clone());
+        let executor = self.build_executor();
         let session = session.clone();
         let message = message.to_string();
+        let begin = self.begin_turn();
+        let lifecycle = self.lifecycle.clone();
 
         tokio::spawn(async move {
-            let _ = executor.execute_stream(&session, &message, tx).await;
+            if let Err(e) = begin {
+                let _ = tx.send(AgentEvent::error(e.to_string())).await;
+                return;
+            }
+
+            lifecycle.wait_while_paused().await;
+            if lifecycle.is_stopped() {
+                let _ = tx
+                    .send(AgentEvent::error("agent is stopped; refusing new sessions"))
+                    .await;
+                return;
+            }
+
+            let _ = executor.execute_stream(&ctx, &session, &message, tx).await;
         });
 
         ReceiverStream::new(rx)
     }
+
+    /// Transitions an idle agent into `Running`, refusing to start new work on a
+    /// stopped agent. A running or paused agent is left untouched here; pausing is
+    /// handled separately by `wait_while_paused`.
+    fn begin_turn(&self) -> Result<()> {
+        if self.lifecycle.is_stopped() {
+            return Err(AgentError::Stopped);
+        }
+        if self.lifecycle.state() == AgentState::Idle {
+            self.lifecycle.start()?;
+        }
+        Ok(())
+    }
+
+    /// Builds a fresh [`Executor`] for one turn, carrying over this agent's
+    /// current config snapshot plus its `tool_approver`, if any.
+    fn build_executor(&self) -> Executor {
+        let executor = Executor::new(self.config(), self.llm.clone(), self.tools.clone());
+        match &self.tool_approver {
+            Some(approver) => executor.with_tool_approver(Arc::clone(approver)),
+            None => executor,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -72,8 +215,14 @@ pub struct AgentBuilder {
     max_tokens: Option<usize>,
     max_iterations: Option<usize>,
     tools_enabled: Option<bool>,
+    max_parallel_tools: Option<usize>,
     llm: Option<Arc<dyn LlmProvider>>,
     tool_registry: Option<Arc<ToolRegistry>>,
+    tool_allowlist: Option<Vec<String>>,
+    tool_approver: Option<Arc<dyn ToolApprover>>,
+    max_llm_retries: Option<usize>,
+    required_tools: Vec<String>,
+    roles: HashMap<String, Role>,
 }
 
 impl AgentBuilder {
@@ -81,6 +230,24 @@ impl AgentBuilder {
         Self::default()
     }
 
+    /// Loads an [`AgentDefinition`] from `path` (TOML, or JSON for a `.json`
+    /// extension) and pre-fills a builder from it. The caller still supplies
+    /// `llm` (and `tool_registry`, if the definition names any `tools`)
+    /// before calling [`Self::build`], which is where unknown tool names
+    /// surface as `AgentError::Config`.
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let definition = AgentDefinition::load_from_file(path)?;
+        let required_tools = definition.tools.clone();
+        let roles = definition.roles.clone();
+
+        Ok(Self {
+            config: Some(definition.into_config()),
+            required_tools,
+            roles,
+            ..Self::default()
+        })
+    }
+
     pub fn config(mut self, config: AgentConfig) -> Self {
         self.config = Some(config);
         self
@@ -121,6 +288,22 @@ impl AgentBuilder {
         self
     }
 
+    /// Caps how many tool calls from a single LLM turn run concurrently;
+    /// defaults to the number of available CPUs (see
+    /// [`AgentConfig::max_parallel_tools`]).
+    pub fn max_parallel_tools(mut self, max_parallel_tools: usize) -> Self {
+        self.max_parallel_tools = Some(max_parallel_tools);
+        self
+    }
+
+    /// Caps how many times a transient LLM error (see [`hehe_llm::LlmError::is_retryable`])
+    /// is retried with exponential backoff before the turn fails (see
+    /// [`AgentConfig::max_llm_retries`]).
+    pub fn max_llm_retries(mut self, max_llm_retries: usize) -> Self {
+        self.max_llm_retries = Some(max_llm_retries);
+        self
+    }
+
     pub fn llm(mut self, llm: Arc<dyn LlmProvider>) -> Self {
         self.llm = Some(llm);
         self
@@ -131,6 +314,22 @@ impl AgentBuilder {
         self
     }
 
+    /// Restricts this agent to the named subset of `tool_registry`, via
+    /// [`ToolRegistry::subset`], instead of exposing every tool the registry
+    /// carries. Lets several agents share one backing catalog while each
+    /// running with a distinct, least-privilege tool surface.
+    pub fn tool_allowlist(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tool_allowlist = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Gates dangerous tool calls behind `approver` (see [`ToolApprover`]);
+    /// without one, dangerous tools run unprompted.
+    pub fn tool_approver(mut self, approver: Arc<dyn ToolApprover>) -> Self {
+        self.tool_approver = Some(approver);
+        self
+    }
+
     pub fn build(self) -> Result<Agent> {
         let llm = self.llm.ok_or_else(|| AgentError::config("LLM provider is required"))?;
 
@@ -157,12 +356,55 @@ impl AgentBuilder {
         if let Some(enabled) = self.tools_enabled {
             config.tools_enabled = enabled;
         }
+        if let Some(max) = self.max_parallel_tools {
+            config.max_parallel_tools = max;
+        }
+        if let Some(max) = self.max_llm_retries {
+            config.max_llm_retries = max;
+        }
 
-        let tools = self.tool_registry.map(|registry| {
+        let tool_registry = match self.tool_allowlist {
+            Some(allowlist) => self.tool_registry.map(|registry| {
+                let names: Vec<&str> = allowlist.iter().map(String::as_str).collect();
+                Arc::new(registry.subset(&names))
+            }),
+            None => self.tool_registry,
+        };
+
+        if !self.required_tools.is_empty() {
+            let missing: Vec<&str> = self
+                .required_tools
+                .iter()
+                .filter(|name| {
+                    !tool_registry
+                        .as_ref()
+                        .is_some_and(|registry| registry.contains(name))
+                })
+                .map(|name| name.as_str())
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(AgentError::config(format!(
+                    "agent definition names unknown tool(s): {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+
+        let tools = tool_registry.map(|registry| {
             Arc::new(ToolExecutor::new(registry))
         });
 
-        Ok(Agent { config, llm, tools })
+        let id = AgentId::new();
+        Ok(Agent {
+            id,
+            config: RwLock::new(config),
+            llm,
+            tools,
+            tool_approver: self.tool_approver,
+            roles: self.roles,
+            lifecycle: Arc::new(AgentLifecycle::new(id)),
+        })
     }
 }
 
@@ -257,6 +499,159 @@ mod tests {
         assert_eq!(response.iterations, 1);
     }
 
+    #[test]
+    fn test_builder_max_parallel_tools_overrides_default() {
+        let agent = Agent::builder()
+            .system_prompt("You are helpful.")
+            .max_parallel_tools(2)
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        assert_eq!(agent.config().max_parallel_tools, 2);
+    }
+
+    #[test]
+    fn test_builder_max_llm_retries_overrides_default() {
+        let agent = Agent::builder()
+            .system_prompt("You are helpful.")
+            .max_llm_retries(1)
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        assert_eq!(agent.config().max_llm_retries, 1);
+    }
+
+    struct DenyAllApprover;
+
+    #[async_trait]
+    impl ToolApprover for DenyAllApprover {
+        async fn approve(&self, _call: &hehe_core::message::ToolUse, _def: &hehe_core::ToolDefinition) -> crate::approval::Decision {
+            crate::approval::Decision::Deny
+        }
+    }
+
+    #[test]
+    fn test_builder_accepts_tool_approver() {
+        let result = Agent::builder()
+            .system_prompt("You are helpful.")
+            .tool_approver(Arc::new(DenyAllApprover))
+            .llm(Arc::new(MockLlm))
+            .build();
+
+        assert!(result.is_ok());
+    }
+
+    fn write_temp_file(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("hehe-agent-test-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_builder_from_config_file_loads_config_and_roles() {
+        let path = write_temp_file(
+            "persona.toml",
+            r#"
+                system_prompt = "You are a support bot."
+                model = "gpt-4o"
+                temperature = 0.2
+
+                [roles.triage]
+                system_prompt = "Triage incoming tickets."
+                temperature = 0.1
+            "#,
+        );
+
+        let agent = AgentBuilder::from_config_file(&path)
+            .unwrap()
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(agent.config().model, "gpt-4o");
+        assert_eq!(agent.config().temperature, 0.2);
+
+        agent.switch_role("triage").unwrap();
+        assert_eq!(agent.config().system_prompt, "Triage incoming tickets.");
+        assert_eq!(agent.config().temperature, 0.1);
+
+        assert!(agent.switch_role("nonexistent").is_err());
+    }
+
+    #[test]
+    fn test_builder_from_config_file_rejects_unknown_tool() {
+        let path = write_temp_file(
+            "persona-unknown-tool.toml",
+            r#"
+                system_prompt = "You are a support bot."
+                model = "gpt-4o"
+                tools = ["nonexistent_tool"]
+            "#,
+        );
+
+        let result = AgentBuilder::from_config_file(&path)
+            .unwrap()
+            .llm(Arc::new(MockLlm))
+            .tool_registry(Arc::new(ToolRegistry::new()))
+            .build();
+
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(result, Err(AgentError::Config(_))));
+    }
+
+    struct NoopTool {
+        def: hehe_core::ToolDefinition,
+    }
+
+    impl NoopTool {
+        fn new(name: &str) -> Self {
+            Self {
+                def: hehe_core::ToolDefinition::new(name, format!("{name} tool")),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl hehe_tools::Tool for NoopTool {
+        fn definition(&self) -> &hehe_core::ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(
+            &self,
+            _ctx: &hehe_core::Context,
+            _input: serde_json::Value,
+        ) -> hehe_tools::Result<hehe_tools::ToolOutput> {
+            Ok(hehe_tools::ToolOutput::text("ok"))
+        }
+    }
+
+    #[test]
+    fn test_builder_tool_allowlist_restricts_registry_to_named_tools() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(NoopTool::new("read_file"))).unwrap();
+        registry.register(Arc::new(NoopTool::new("write_file"))).unwrap();
+        registry.register(Arc::new(NoopTool::new("shell"))).unwrap();
+
+        let agent = Agent::builder()
+            .system_prompt("You are helpful.")
+            .llm(Arc::new(MockLlm))
+            .tool_registry(Arc::new(registry))
+            .tool_allowlist(["read_file"])
+            .build()
+            .unwrap();
+
+        let registry = agent.tools.as_ref().unwrap().registry();
+        assert!(registry.contains("read_file"));
+        assert!(!registry.contains("write_file"));
+        assert!(!registry.contains("shell"));
+    }
+
     #[tokio::test]
     async fn test_session_persistence() {
         let agent = Agent::builder()
@@ -272,4 +667,85 @@ mod tests {
 
         assert_eq!(session.message_count(), 4);
     }
+
+    #[tokio::test]
+    async fn test_agent_starts_idle_then_running() {
+        let agent = Agent::builder()
+            .system_prompt("You are helpful.")
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        assert_eq!(agent.state(), AgentState::Idle);
+
+        let session = agent.create_session();
+        agent.chat(&session, "Hi").await.unwrap();
+
+        assert_eq!(agent.state(), AgentState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_stopped_agent_refuses_new_sessions() {
+        let agent = Agent::builder()
+            .system_prompt("You are helpful.")
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        agent.stop().unwrap();
+
+        let session = agent.create_session();
+        let result = agent.chat(&session, "Hi").await;
+
+        assert!(matches!(result, Err(AgentError::Stopped)));
+    }
+
+    #[tokio::test]
+    async fn test_pause_blocks_process_until_resumed() {
+        let agent = Arc::new(
+            Agent::builder()
+                .system_prompt("You are helpful.")
+                .llm(Arc::new(MockLlm))
+                .build()
+                .unwrap(),
+        );
+
+        agent.chat(&agent.create_session(), "warm up").await.unwrap();
+        agent.pause().unwrap();
+
+        let session = agent.create_session();
+        let waiting = {
+            let agent = agent.clone();
+            tokio::spawn(async move { agent.chat(&session, "Hi").await })
+        };
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        agent.resume().unwrap();
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(1), waiting)
+            .await
+            .expect("paused process should resume once unblocked")
+            .unwrap();
+
+        assert_eq!(result.unwrap(), "Hello from mock!");
+    }
+
+    #[tokio::test]
+    async fn test_update_config_is_visible_on_the_next_turn() {
+        let agent = Agent::builder()
+            .system_prompt("Original prompt")
+            .model("mock")
+            .llm(Arc::new(MockLlm))
+            .build()
+            .unwrap();
+
+        assert_eq!(agent.config().system_prompt, "Original prompt");
+
+        agent.update_config(
+            AgentConfig::new("mock", "Updated prompt").with_temperature(0.9),
+        );
+
+        assert_eq!(agent.config().system_prompt, "Updated prompt");
+        assert_eq!(agent.config().temperature, 0.9);
+    }
 }
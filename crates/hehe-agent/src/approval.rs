@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use hehe_core::message::ToolUse;
+use hehe_core::ToolDefinition;
+
+/// Outcome of a [`ToolApprover`] consulted before a dangerous tool call runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// Run this call only; later calls to the same tool are re-prompted.
+    Allow,
+    /// Refuse this call; the executor turns it into an error result for the model.
+    Deny,
+    /// Run this call and remember the grant on the session, so later calls to
+    /// the same tool in the same conversation skip the approver entirely.
+    AllowForSession,
+}
+
+/// Gate consulted before a tool flagged [`ToolDefinition::is_dangerous`] runs.
+/// Implementations back a CLI confirmation prompt, a server-side consent UI,
+/// or a static policy — whatever a frontend needs to get human sign-off
+/// before, say, a shell command or file write executes. Wired in via
+/// [`crate::AgentBuilder::tool_approver`]; with none configured, dangerous
+/// tools run unprompted, same as before this hook existed.
+#[async_trait]
+pub trait ToolApprover: Send + Sync {
+    async fn approve(&self, call: &ToolUse, def: &ToolDefinition) -> Decision;
+}
@@ -0,0 +1,39 @@
+use hehe_core::Message;
+
+/// Estimates how many tokens a message will cost against a model's context
+/// window. Implementations can plug in a real tokenizer for a specific
+/// provider; [`HeuristicTokenCounter`] is the default and good enough for
+/// budgeting without depending on any particular model's vocabulary.
+pub trait TokenCounter: Send + Sync {
+    fn count(&self, message: &Message) -> usize;
+}
+
+/// Estimates token count as `chars / 4` (plus one), a widely used rule of
+/// thumb for English text across most tokenizers. The characters counted
+/// are those of the message's serialized JSON form, so tool calls and tool
+/// results count too, along with the fixed envelope (id, role, timestamp).
+#[derive(Default)]
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count(&self, message: &Message) -> usize {
+        let chars = serde_json::to_string(message).map(|s| s.len()).unwrap_or(0);
+        chars / 4 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_scales_with_message_length() {
+        let counter = HeuristicTokenCounter;
+        let short = counter.count(&Message::user("hi"));
+        let long = counter.count(&Message::user("a".repeat(400)));
+
+        // 398 extra text characters add about 100 tokens on top of the
+        // envelope both messages share.
+        assert!(long >= short + 90);
+    }
+}
@@ -27,6 +27,27 @@ pub struct AgentConfig {
 
     #[serde(default = "default_tools_enabled")]
     pub tools_enabled: bool,
+
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+
+    #[serde(default = "default_llm_timeout_secs")]
+    pub llm_timeout_secs: u64,
+
+    #[serde(default = "default_max_llm_retries")]
+    pub max_llm_retries: usize,
+
+    #[serde(default = "default_llm_retry_base_backoff_ms")]
+    pub llm_retry_base_backoff_ms: u64,
+
+    #[serde(default = "default_llm_retry_max_backoff_ms")]
+    pub llm_retry_max_backoff_ms: u64,
+
+    #[serde(default = "default_context_token_budget")]
+    pub context_token_budget: usize,
+
+    #[serde(default = "default_keep_recent_messages")]
+    pub keep_recent_messages: usize,
 }
 
 fn default_name() -> String {
@@ -57,6 +78,34 @@ fn default_tools_enabled() -> bool {
     true
 }
 
+fn default_max_parallel_tools() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+fn default_llm_timeout_secs() -> u64 {
+    120
+}
+
+fn default_max_llm_retries() -> usize {
+    3
+}
+
+fn default_llm_retry_base_backoff_ms() -> u64 {
+    200
+}
+
+fn default_llm_retry_max_backoff_ms() -> u64 {
+    5000
+}
+
+fn default_context_token_budget() -> usize {
+    8000
+}
+
+fn default_keep_recent_messages() -> usize {
+    10
+}
+
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
@@ -69,6 +118,13 @@ impl Default for AgentConfig {
             max_context_messages: default_max_context_messages(),
             tool_timeout_secs: default_tool_timeout_secs(),
             tools_enabled: default_tools_enabled(),
+            max_parallel_tools: default_max_parallel_tools(),
+            llm_timeout_secs: default_llm_timeout_secs(),
+            max_llm_retries: default_max_llm_retries(),
+            llm_retry_base_backoff_ms: default_llm_retry_base_backoff_ms(),
+            llm_retry_max_backoff_ms: default_llm_retry_max_backoff_ms(),
+            context_token_budget: default_context_token_budget(),
+            keep_recent_messages: default_keep_recent_messages(),
         }
     }
 }
@@ -117,9 +173,38 @@ impl AgentConfig {
         self
     }
 
+    pub fn with_max_parallel_tools(mut self, max_parallel_tools: usize) -> Self {
+        self.max_parallel_tools = max_parallel_tools;
+        self
+    }
+
+    pub fn with_llm_timeout(mut self, timeout: Duration) -> Self {
+        self.llm_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    pub fn with_max_llm_retries(mut self, max_llm_retries: usize) -> Self {
+        self.max_llm_retries = max_llm_retries;
+        self
+    }
+
+    pub fn with_context_token_budget(mut self, budget: usize) -> Self {
+        self.context_token_budget = budget;
+        self
+    }
+
+    pub fn with_keep_recent_messages(mut self, keep_recent_messages: usize) -> Self {
+        self.keep_recent_messages = keep_recent_messages;
+        self
+    }
+
     pub fn tool_timeout(&self) -> Duration {
         Duration::from_secs(self.tool_timeout_secs)
     }
+
+    pub fn llm_timeout(&self) -> Duration {
+        Duration::from_secs(self.llm_timeout_secs)
+    }
 }
 
 #[cfg(test)]
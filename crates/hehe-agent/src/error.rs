@@ -1,6 +1,9 @@
+use crate::response::AgentResponse;
+use crate::state::InvalidTransition;
 use hehe_core::error::Error as CoreError;
 use hehe_llm::LlmError;
 use hehe_tools::ToolError;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,6 +17,15 @@ pub enum AgentError {
     #[error("Max iterations reached: {0}")]
     MaxIterationsReached(usize),
 
+    #[error("Wall-time budget of {0:?} exceeded")]
+    BudgetExceeded(Duration),
+
+    #[error("Invalid agent state transition: {0}")]
+    InvalidTransition(#[from] InvalidTransition),
+
+    #[error("Agent is stopped and refuses new sessions")]
+    Stopped,
+
     #[error("LLM error: {0}")]
     Llm(#[from] LlmError),
 
@@ -23,8 +35,16 @@ pub enum AgentError {
     #[error("Core error: {0}")]
     Core(#[from] CoreError),
 
+    /// The turn's `Context` was cancelled mid-flight (e.g. an HTTP handler
+    /// dropped the client). Carries the tool calls and iterations collected
+    /// before the cancellation was noticed.
     #[error("Cancelled")]
-    Cancelled,
+    Cancelled(Box<AgentResponse>),
+
+    /// The turn's `Context` deadline passed mid-flight. Carries the same
+    /// partial progress as [`Self::Cancelled`].
+    #[error("Deadline exceeded")]
+    Deadline(Box<AgentResponse>),
 
     #[error("Internal error: {0}")]
     Internal(String),
@@ -40,4 +60,17 @@ impl AgentError {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    pub fn budget_exceeded(budget: Duration) -> Self {
+        Self::BudgetExceeded(budget)
+    }
+
+    /// The tool calls and iterations collected before a [`Self::Cancelled`]
+    /// or [`Self::Deadline`] error cut the turn short, if any.
+    pub fn partial_response(&self) -> Option<&AgentResponse> {
+        match self {
+            Self::Cancelled(response) | Self::Deadline(response) => Some(response),
+            _ => None,
+        }
+    }
 }
@@ -8,6 +8,10 @@ pub enum AgentEvent {
         session_id: Id,
     },
 
+    IterationStart {
+        iteration: usize,
+    },
+
     TextDelta {
         delta: String,
     },
@@ -22,10 +26,21 @@ pub enum AgentEvent {
         input: serde_json::Value,
     },
 
+    /// Sent ahead of [`Self::ToolUseStart`] when `name` is
+    /// flagged `dangerous` and hasn't already been approved for this
+    /// session, so a client can render an approval dialog equivalent to
+    /// the CLI's confirmation prompt before the call actually runs.
+    ConfirmationRequired {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+
     ToolUseEnd {
         id: String,
         output: String,
         is_error: bool,
+        duration_ms: u64,
     },
 
     Thinking {
@@ -36,6 +51,15 @@ pub enum AgentEvent {
         session_id: Id,
     },
 
+    /// A transient LLM error was retried mid-turn (see
+    /// [`crate::executor::Executor::complete_stream_with_retry`]); `attempt`
+    /// is the retry number about to be made and `delay_ms` how long it waited
+    /// first, so subscribers can surface "reconnecting..." in a UI.
+    Retry {
+        attempt: usize,
+        delay_ms: u64,
+    },
+
     Error {
         message: String,
     },
@@ -46,6 +70,10 @@ impl AgentEvent {
         Self::MessageStart { session_id }
     }
 
+    pub fn iteration_start(iteration: usize) -> Self {
+        Self::IterationStart { iteration }
+    }
+
     pub fn text_delta(delta: impl Into<String>) -> Self {
         Self::TextDelta {
             delta: delta.into(),
@@ -64,11 +92,29 @@ impl AgentEvent {
         }
     }
 
-    pub fn tool_use_end(id: impl Into<String>, output: impl Into<String>, is_error: bool) -> Self {
+    pub fn confirmation_required(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        input: serde_json::Value,
+    ) -> Self {
+        Self::ConfirmationRequired {
+            id: id.into(),
+            name: name.into(),
+            input,
+        }
+    }
+
+    pub fn tool_use_end(
+        id: impl Into<String>,
+        output: impl Into<String>,
+        is_error: bool,
+        duration_ms: u64,
+    ) -> Self {
         Self::ToolUseEnd {
             id: id.into(),
             output: output.into(),
             is_error,
+            duration_ms,
         }
     }
 
@@ -82,6 +128,10 @@ impl AgentEvent {
         Self::MessageEnd { session_id }
     }
 
+    pub fn retry(attempt: usize, delay_ms: u64) -> Self {
+        Self::Retry { attempt, delay_ms }
+    }
+
     pub fn error(message: impl Into<String>) -> Self {
         Self::Error {
             message: message.into(),
@@ -95,6 +145,24 @@ impl AgentEvent {
     pub fn is_end(&self) -> bool {
         matches!(self, Self::MessageEnd { .. } | Self::Error { .. })
     }
+
+    /// The `type` tag each variant serializes under, in declaration order.
+    /// Kept in sync by hand so callers (e.g. a `/api/v1/version` capabilities
+    /// response) can advertise which event types a client should expect
+    /// without duplicating the enum.
+    pub const EVENT_TYPES: &'static [&'static str] = &[
+        "message_start",
+        "iteration_start",
+        "text_delta",
+        "text_complete",
+        "tool_use_start",
+        "confirmation_required",
+        "tool_use_end",
+        "thinking",
+        "message_end",
+        "retry",
+        "error",
+    ];
 }
 
 #[cfg(test)]
@@ -122,6 +190,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_use_end_carries_duration() {
+        let event = AgentEvent::tool_use_end("call_123", "done", false, 42);
+
+        if let AgentEvent::ToolUseEnd { id, output, is_error, duration_ms } = event {
+            assert_eq!(id, "call_123");
+            assert_eq!(output, "done");
+            assert!(!is_error);
+            assert_eq!(duration_ms, 42);
+        } else {
+            panic!("Expected ToolUseEnd event");
+        }
+    }
+
+    #[test]
+    fn test_confirmation_required_event() {
+        let event = AgentEvent::confirmation_required("call_123", "shell", serde_json::json!({"cmd": "rm -rf /"}));
+
+        if let AgentEvent::ConfirmationRequired { id, name, input } = event {
+            assert_eq!(id, "call_123");
+            assert_eq!(name, "shell");
+            assert!(input.get("cmd").is_some());
+        } else {
+            panic!("Expected ConfirmationRequired event");
+        }
+    }
+
     #[test]
     fn test_is_end() {
         assert!(AgentEvent::message_end(Id::new()).is_end());
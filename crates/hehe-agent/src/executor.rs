@@ -1,14 +1,19 @@
+use crate::approval::{Decision, ToolApprover};
+use crate::compaction::{HeuristicTokenCounter, TokenCounter};
 use crate::config::AgentConfig;
 use crate::error::{AgentError, Result};
 use crate::event::AgentEvent;
 use crate::response::{AgentResponse, ToolCallRecord};
 use crate::session::Session;
+use futures::stream::{self, StreamExt};
+use hehe_core::event::TokenUsage;
 use hehe_core::message::{ContentBlock, ToolResult, ToolUse};
+use hehe_core::stream::{StreamAggregator, StreamChunk};
 use hehe_core::{Context, Message};
-use hehe_llm::{CompletionRequest, LlmProvider};
+use hehe_llm::{CompletionRequest, CompletionResponse, LlmError, LlmProvider};
 use hehe_tools::ToolExecutor;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
@@ -16,6 +21,8 @@ pub struct Executor {
     config: AgentConfig,
     llm: Arc<dyn LlmProvider>,
     tools: Option<Arc<ToolExecutor>>,
+    token_counter: Arc<dyn TokenCounter>,
+    approver: Option<Arc<dyn ToolApprover>>,
 }
 
 impl Executor {
@@ -24,10 +31,35 @@ impl Executor {
         llm: Arc<dyn LlmProvider>,
         tools: Option<Arc<ToolExecutor>>,
     ) -> Self {
-        Self { config, llm, tools }
+        Self {
+            config,
+            llm,
+            tools,
+            token_counter: Arc::new(HeuristicTokenCounter),
+            approver: None,
+        }
+    }
+
+    /// Overrides the default chars/4 [`TokenCounter`] heuristic, e.g. with a
+    /// real tokenizer for the configured model.
+    pub fn with_token_counter(mut self, token_counter: Arc<dyn TokenCounter>) -> Self {
+        self.token_counter = token_counter;
+        self
+    }
+
+    /// Gates dangerous tool calls behind `approver` (see [`ToolApprover`]).
+    /// With none set, dangerous tools run unprompted.
+    pub fn with_tool_approver(mut self, approver: Arc<dyn ToolApprover>) -> Self {
+        self.approver = Some(approver);
+        self
     }
 
-    pub async fn execute(&self, session: &Session, user_input: &str) -> Result<AgentResponse> {
+    pub async fn execute(
+        &self,
+        ctx: &Context,
+        session: &Session,
+        user_input: &str,
+    ) -> Result<AgentResponse> {
         let user_message = Message::user(user_input);
         session.add_message(user_message);
 
@@ -35,6 +67,10 @@ impl Executor {
         let mut iterations = 0;
 
         loop {
+            if let Some(err) = self.check_done(ctx, session, &all_tool_calls, iterations) {
+                return Err(err);
+            }
+
             iterations += 1;
             session.increment_iterations();
 
@@ -44,8 +80,23 @@ impl Executor {
 
             info!(iteration = iterations, "Starting agent loop iteration");
 
+            self.ensure_context_fits(ctx, session).await?;
+
             let request = self.build_request(session);
-            let response = self.llm.complete(request).await?;
+            let response = match self.complete_with_retry(ctx, request).await {
+                Ok(response) => response,
+                Err(AgentError::Llm(LlmError::ContextLengthExceeded { .. })) => {
+                    warn!("Context length exceeded; forcing compaction and retrying turn");
+                    self.force_compact(ctx, session).await?;
+                    let retried_request = self.build_request(session);
+                    self.complete_with_retry(ctx, retried_request).await?
+                }
+                Err(e) => return Err(e),
+            };
+
+            if let Some(err) = self.check_done(ctx, session, &all_tool_calls, iterations) {
+                return Err(err);
+            }
 
             let tool_uses = response.message.tool_uses();
 
@@ -71,7 +122,7 @@ impl Executor {
             }
             session.add_message(Message::new(hehe_core::Role::Assistant, assistant_content));
 
-            let tool_results = self.execute_tools(&tool_uses).await;
+            let tool_results = self.execute_tools(ctx, session, &tool_uses).await;
 
             for (tu, (output, duration_ms, is_error)) in tool_uses.iter().zip(&tool_results) {
                 all_tool_calls.push(ToolCallRecord {
@@ -81,6 +132,8 @@ impl Executor {
                     output: output.clone(),
                     is_error: *is_error,
                     duration_ms: *duration_ms,
+                    step: Some(iterations - 1),
+                    parent_id: None,
                 });
             }
 
@@ -102,15 +155,70 @@ impl Executor {
         }
     }
 
+    /// Checks `ctx` for cancellation or a passed deadline, returning the
+    /// matching error pre-loaded with progress collected so far. Called at
+    /// the top of each loop iteration and again right after the LLM call
+    /// returns, since both are points where an in-flight turn can be aborted.
+    fn check_done(
+        &self,
+        ctx: &Context,
+        session: &Session,
+        tool_calls: &[ToolCallRecord],
+        iterations: usize,
+    ) -> Option<AgentError> {
+        if ctx.is_cancelled() {
+            let response = AgentResponse::new(session.id().clone(), "")
+                .with_tool_calls(tool_calls.to_vec())
+                .with_iterations(iterations);
+            return Some(AgentError::Cancelled(Box::new(response)));
+        }
+
+        if ctx.is_timeout() {
+            let response = AgentResponse::new(session.id().clone(), "")
+                .with_tool_calls(tool_calls.to_vec())
+                .with_iterations(iterations);
+            return Some(AgentError::Deadline(Box::new(response)));
+        }
+
+        None
+    }
+
+    /// Whether `tool_name` is dangerous and not yet granted for this
+    /// session — the same test [`Self::check_approval`] starts from — so a
+    /// streaming caller can surface [`AgentEvent::ConfirmationRequired`]
+    /// for it ahead of time. Whether an approver is configured is not
+    /// checked here.
+    fn needs_confirmation(&self, tool_name: &str, session: &Session) -> bool {
+        match &self.tools {
+            Some(tools) => tools.is_dangerous(tool_name) && !session.is_tool_approved(tool_name),
+            None => false,
+        }
+    }
+
+    /// Derives a context for a single LLM call or tool batch from the turn's
+    /// `ctx`: it shares `ctx`'s cancellation (so aborting `ctx` aborts
+    /// in-flight work), but its deadline is the tighter of `ctx`'s remaining
+    /// time and `timeout`.
+    fn scoped_ctx(ctx: &Context, timeout: Duration) -> Context {
+        let child = ctx.child();
+        let capped = child.remaining().map(|r| r.min(timeout)).unwrap_or(timeout);
+        child.with_timeout(capped)
+    }
+
+    /// Like [`Self::execute`], but drives `complete_stream` and the tool
+    /// executor directly so `tx` receives a full timeline of the turn
+    /// (`iteration_start`, `text_delta`, `tool_use_start`/`tool_use_end`)
+    /// instead of only start/text/end.
     pub async fn execute_stream(
         &self,
+        ctx: &Context,
         session: &Session,
         user_input: &str,
         tx: mpsc::Sender<AgentEvent>,
     ) -> Result<AgentResponse> {
         let _ = tx.send(AgentEvent::message_start(session.id().clone())).await;
 
-        let result = self.execute(session, user_input).await;
+        let result = self.run_stream_loop(ctx, session, user_input, &tx).await;
 
         match &result {
             Ok(response) => {
@@ -125,6 +233,365 @@ impl Executor {
         result
     }
 
+    async fn run_stream_loop(
+        &self,
+        ctx: &Context,
+        session: &Session,
+        user_input: &str,
+        tx: &mpsc::Sender<AgentEvent>,
+    ) -> Result<AgentResponse> {
+        let user_message = Message::user(user_input);
+        session.add_message(user_message);
+
+        let mut all_tool_calls = Vec::new();
+        let mut iterations = 0;
+
+        loop {
+            if let Some(err) = self.check_done(ctx, session, &all_tool_calls, iterations) {
+                return Err(err);
+            }
+
+            iterations += 1;
+            session.increment_iterations();
+
+            if iterations > self.config.max_iterations {
+                return Err(AgentError::MaxIterationsReached(self.config.max_iterations));
+            }
+
+            let _ = tx.send(AgentEvent::iteration_start(iterations)).await;
+            info!(iteration = iterations, "Starting agent loop iteration");
+
+            self.ensure_context_fits(ctx, session).await?;
+
+            let request = self.build_request(session);
+            let response = match self.complete_stream_with_retry(ctx, request, tx).await {
+                Ok(response) => response,
+                Err(AgentError::Llm(LlmError::ContextLengthExceeded { .. })) => {
+                    warn!("Context length exceeded; forcing compaction and retrying turn");
+                    self.force_compact(ctx, session).await?;
+                    let retried_request = self.build_request(session);
+                    self.complete_stream_with_retry(ctx, retried_request, tx).await?
+                }
+                Err(e) => return Err(e),
+            };
+
+            if let Some(err) = self.check_done(ctx, session, &all_tool_calls, iterations) {
+                return Err(err);
+            }
+
+            let tool_uses = response.message.tool_uses();
+
+            if tool_uses.is_empty() {
+                let text = response.text_content();
+                session.add_message(Message::assistant(&text));
+
+                return Ok(AgentResponse::new(session.id().clone(), text)
+                    .with_tool_calls(all_tool_calls)
+                    .with_iterations(iterations));
+            }
+
+            let mut assistant_content = Vec::new();
+            if !response.text_content().is_empty() {
+                assistant_content.push(ContentBlock::text(response.text_content()));
+            }
+            for tu in &tool_uses {
+                assistant_content.push(ContentBlock::tool_use(ToolUse::new(
+                    &tu.id,
+                    &tu.name,
+                    tu.input.clone(),
+                )));
+            }
+            session.add_message(Message::new(hehe_core::Role::Assistant, assistant_content));
+
+            for tu in &tool_uses {
+                if self.needs_confirmation(&tu.name, session) {
+                    let _ = tx
+                        .send(AgentEvent::confirmation_required(&tu.id, &tu.name, tu.input.clone()))
+                        .await;
+                }
+
+                let _ = tx
+                    .send(AgentEvent::tool_use_start(&tu.id, &tu.name, tu.input.clone()))
+                    .await;
+            }
+
+            let tool_results = self.execute_tools(ctx, session, &tool_uses).await;
+
+            for (tu, (output, duration_ms, is_error)) in tool_uses.iter().zip(&tool_results) {
+                let _ = tx
+                    .send(AgentEvent::tool_use_end(&tu.id, output.clone(), *is_error, *duration_ms))
+                    .await;
+
+                all_tool_calls.push(ToolCallRecord {
+                    id: tu.id.clone(),
+                    name: tu.name.clone(),
+                    input: tu.input.clone(),
+                    output: output.clone(),
+                    is_error: *is_error,
+                    duration_ms: *duration_ms,
+                    step: Some(iterations - 1),
+                    parent_id: None,
+                });
+            }
+
+            session.increment_tool_calls(tool_results.len());
+
+            let tool_result_content: Vec<ContentBlock> = tool_uses
+                .iter()
+                .zip(&tool_results)
+                .map(|(tu, (output, _, is_error))| {
+                    if *is_error {
+                        ContentBlock::tool_result(ToolResult::error(&tu.id, output))
+                    } else {
+                        ContentBlock::tool_result(ToolResult::success(&tu.id, output))
+                    }
+                })
+                .collect();
+
+            session.add_message(Message::tool(tool_result_content));
+        }
+    }
+
+    /// Like [`Self::complete_with_retry`], but streams the response a chunk
+    /// at a time, forwarding text as it arrives instead of waiting for the
+    /// full completion. If the stream errors before yielding a `StopReason`
+    /// (connection dropped mid-turn), this reconnects by calling
+    /// `complete_stream` again from scratch and sends an [`AgentEvent::Retry`]
+    /// so subscribers know the turn is being resumed rather than abandoned.
+    async fn complete_stream_with_retry(
+        &self,
+        ctx: &Context,
+        request: CompletionRequest,
+        tx: &mpsc::Sender<AgentEvent>,
+    ) -> Result<CompletionResponse> {
+        let call_ctx = Self::scoped_ctx(ctx, self.config.llm_timeout());
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.stream_once(&request, tx).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    if attempt > self.config.max_llm_retries || !error.is_retryable() {
+                        return Err(AgentError::from(error));
+                    }
+
+                    let delay = match &error {
+                        LlmError::RateLimited {
+                            retry_after_ms: Some(ms),
+                            ..
+                        } => Duration::from_millis(*ms),
+                        _ => self.backoff_for(attempt as u32),
+                    };
+
+                    if let Some(remaining) = call_ctx.remaining() {
+                        if delay >= remaining {
+                            warn!(
+                                provider = self.llm.name(),
+                                attempt,
+                                "Giving up on LLM stream retry: backoff would exceed remaining deadline"
+                            );
+                            return Err(AgentError::from(error));
+                        }
+                    }
+
+                    let delay_ms = delay.as_millis() as u64;
+                    warn!(
+                        provider = self.llm.name(),
+                        attempt,
+                        delay_ms,
+                        error = %error,
+                        "Retrying LLM stream after transient error"
+                    );
+                    let _ = tx.send(AgentEvent::retry(attempt as usize, delay_ms)).await;
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Runs a single streaming completion, forwarding each `TextDelta` chunk
+    /// to `tx` as it arrives and aggregating the full response with
+    /// [`StreamAggregator`] so the caller still gets a normal
+    /// `CompletionResponse` to drive the agent loop.
+    async fn stream_once(
+        &self,
+        request: &CompletionRequest,
+        tx: &mpsc::Sender<AgentEvent>,
+    ) -> std::result::Result<CompletionResponse, LlmError> {
+        let mut chunks = self.llm.complete_stream(request.clone()).await?;
+        let mut aggregator = StreamAggregator::new();
+
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            if let StreamChunk::TextDelta { text } = &chunk {
+                let _ = tx.send(AgentEvent::text_delta(text.clone())).await;
+            }
+            aggregator.push(chunk);
+        }
+
+        if let Some((code, message)) = aggregator.error() {
+            return Err(LlmError::Api {
+                provider: self.llm.name().to_string(),
+                message: format!("{code}: {message}"),
+            });
+        }
+
+        Ok(self.response_from_aggregator(aggregator))
+    }
+
+    fn response_from_aggregator(&self, aggregator: StreamAggregator) -> CompletionResponse {
+        let mut content = Vec::new();
+        if !aggregator.text().is_empty() {
+            content.push(ContentBlock::text(aggregator.text()));
+        }
+        for tu in aggregator.tool_uses() {
+            let input = serde_json::from_str(&tu.input_json).unwrap_or(serde_json::Value::Null);
+            content.push(ContentBlock::tool_use(ToolUse::new(&tu.id, &tu.name, input)));
+        }
+
+        let message = Message::new(hehe_core::Role::Assistant, content);
+        let id = aggregator
+            .message_id()
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+
+        let mut response = CompletionResponse::new(id, self.llm.name(), message)
+            .with_usage(TokenUsage::new(aggregator.input_tokens(), aggregator.output_tokens()));
+
+        if let Some(reason) = aggregator.stop_reason() {
+            response = response.with_stop_reason(reason.clone());
+        }
+
+        response
+    }
+
+    /// Calls `self.llm.complete`, retrying transient failures (rate limits,
+    /// timeouts, network errors — see [`LlmError::is_retryable`]) up to
+    /// `max_llm_retries` times with exponential backoff and jitter. A
+    /// `RateLimited` error with a server-supplied `retry_after_ms` uses that
+    /// delay verbatim instead of the computed backoff. A retry is skipped if
+    /// it would run past `ctx`'s deadline.
+    async fn complete_with_retry(
+        &self,
+        ctx: &Context,
+        request: CompletionRequest,
+    ) -> Result<CompletionResponse> {
+        let call_ctx = Self::scoped_ctx(ctx, self.config.llm_timeout());
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.llm.complete(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    if attempt > self.config.max_llm_retries || !error.is_retryable() {
+                        return Err(AgentError::from(error));
+                    }
+
+                    let delay = match &error {
+                        LlmError::RateLimited {
+                            retry_after_ms: Some(ms),
+                            ..
+                        } => Duration::from_millis(*ms),
+                        _ => self.backoff_for(attempt as u32),
+                    };
+
+                    if let Some(remaining) = call_ctx.remaining() {
+                        if delay >= remaining {
+                            warn!(
+                                provider = self.llm.name(),
+                                attempt,
+                                "Giving up on LLM retry: backoff would exceed remaining deadline"
+                            );
+                            return Err(AgentError::from(error));
+                        }
+                    }
+
+                    warn!(
+                        provider = self.llm.name(),
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %error,
+                        "Retrying LLM completion after transient error"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Exponential backoff from `llm_retry_base_backoff_ms`, capped at
+    /// `llm_retry_max_backoff_ms` and jittered by up to +/-25% so concurrent
+    /// callers retrying the same failure don't all wake up in lockstep.
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let base = Duration::from_millis(self.config.llm_retry_base_backoff_ms);
+        let cap = Duration::from_millis(self.config.llm_retry_max_backoff_ms);
+        let backoff = (base * 2u32.saturating_pow(attempt.saturating_sub(1))).min(cap);
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let jitter = 0.75 + (nanos % 500) as f64 / 1000.0;
+        backoff.mul_f64(jitter)
+    }
+
+    /// Checks whether `session`'s full history would exceed
+    /// `context_token_budget`; if so, summarizes it down via
+    /// [`Self::force_compact`] before the next `build_request` call.
+    async fn ensure_context_fits(&self, ctx: &Context, session: &Session) -> Result<()> {
+        let messages = session.messages();
+        let total: usize = messages.iter().map(|m| self.token_counter.count(m)).sum();
+
+        if total <= self.config.context_token_budget {
+            return Ok(());
+        }
+
+        self.force_compact(ctx, session).await
+    }
+
+    /// Folds every message except the most recent `keep_recent_messages`
+    /// into a single synthetic summary message, obtained via a dedicated
+    /// summarization `complete` call. No-op if the session is already within
+    /// that window (nothing left to fold).
+    async fn force_compact(&self, ctx: &Context, session: &Session) -> Result<()> {
+        let messages = session.messages();
+        let keep_recent = self.config.keep_recent_messages;
+
+        if messages.len() <= keep_recent {
+            return Ok(());
+        }
+
+        let to_summarize = &messages[..messages.len() - keep_recent];
+        let transcript = to_summarize
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.text_content()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let summary_request = CompletionRequest::new(
+            &self.config.model,
+            vec![Message::user(format!(
+                "Summarize the following conversation history concisely, preserving key \
+                 facts, decisions, and open questions so the conversation can continue \
+                 without this detail:\n\n{transcript}"
+            ))],
+        )
+        .with_system("You compact conversation history for an AI agent's context window.");
+
+        let summary_response = self.complete_with_retry(ctx, summary_request).await?;
+        let summary = Message::assistant(format!(
+            "[Summary of earlier conversation] {}",
+            summary_response.text_content()
+        ));
+
+        session.compact(keep_recent, summary);
+        Ok(())
+    }
+
     fn build_request(&self, session: &Session) -> CompletionRequest {
         let messages = session.last_messages(self.config.max_context_messages);
 
@@ -148,8 +615,28 @@ impl Executor {
         request
     }
 
+    /// Runs one future per tool call concurrently, bounded by
+    /// `max_parallel_tools` (sized from the host's CPU count unless
+    /// overridden, see [`hehe_tools::default_concurrency`]), since tool
+    /// calls within a single LLM turn are usually independent. Results are
+    /// reassembled in the original `tool_uses` order — which is the order
+    /// the model emitted their call ids in — so callers can zip them back
+    /// against the request. A call with the same name and arguments as an
+    /// earlier one in this session is served from `session`'s result cache
+    /// instead of re-run. A call whose arguments fail schema validation
+    /// against the tool's declared parameters never reaches the tool at
+    /// all; it comes back as an `is_error` entry describing what was wrong.
+    ///
+    /// Tools flagged `dangerous` on their [`hehe_core::ToolDefinition`] are
+    /// excluded from the concurrent pool and run one at a time instead, in
+    /// call order, after their own approval check — so an approval prompt
+    /// for one dangerous call is never left racing a batch of unrelated
+    /// tool calls. A tool that panics has its panic caught and turned into
+    /// an `is_error` entry rather than aborting the rest of the batch.
     async fn execute_tools(
         &self,
+        ctx: &Context,
+        session: &Session,
         tool_uses: &[&ToolUse],
     ) -> Vec<(String, u64, bool)> {
         let Some(tools) = &self.tools else {
@@ -159,29 +646,161 @@ impl Executor {
                 .collect();
         };
 
-        let ctx = Context::new().with_timeout(self.config.tool_timeout());
-        let mut results = Vec::with_capacity(tool_uses.len());
+        let ctx = Self::scoped_ctx(ctx, self.config.tool_timeout());
+        let max_parallel = self.config.max_parallel_tools.max(1);
 
-        for tu in tool_uses {
-            let start = Instant::now();
-            debug!(tool = %tu.name, id = %tu.id, "Executing tool");
+        let (dangerous, concurrent): (Vec<(usize, &ToolUse)>, Vec<(usize, &ToolUse)>) = tool_uses
+            .iter()
+            .enumerate()
+            .map(|(index, tu)| (index, *tu))
+            .partition(|(_, tu)| tools.is_dangerous(&tu.name));
 
-            let result = tools.execute(&ctx, &tu.name, tu.input.clone()).await;
-            let duration_ms = start.elapsed().as_millis() as u64;
+        let mut results: Vec<Option<(String, u64, bool)>> = (0..tool_uses.len()).map(|_| None).collect();
 
-            match result {
-                Ok(output) => {
-                    info!(tool = %tu.name, duration_ms, is_error = output.is_error, "Tool completed");
-                    results.push((output.content, duration_ms, output.is_error));
-                }
-                Err(e) => {
-                    warn!(tool = %tu.name, error = %e, "Tool execution failed");
-                    results.push((e.to_string(), duration_ms, true));
-                }
+        for (index, tu) in dangerous {
+            let entry = Self::run_one(&ctx, tools, &self.approver, session, tu).await;
+            results[index] = Some(entry);
+        }
+
+        let calls = concurrent.into_iter().map(|(index, tu)| {
+            let tools = Arc::clone(tools);
+            let ctx = ctx.clone();
+            let tu = tu.clone();
+            let approver = self.approver.clone();
+            let session = session.clone();
+
+            async move {
+                let entry = Self::run_one(&ctx, &tools, &approver, &session, &tu).await;
+                (index, entry)
             }
+        });
+
+        let concurrent_results: Vec<(usize, (String, u64, bool))> = stream::iter(calls)
+            .buffer_unordered(max_parallel)
+            .collect()
+            .await;
+
+        for (index, entry) in concurrent_results {
+            results[index] = Some(entry);
         }
 
         results
+            .into_iter()
+            .map(|r| r.expect("every index is filled exactly once"))
+            .collect()
+    }
+
+    /// One call's full path: approval gate, schema validation, cache
+    /// lookup/populate, then execution. Execution itself runs on its own
+    /// spawned task, so a tool whose `execute` panics can't take the rest of
+    /// the calling batch down with it — the panic is caught via the task's
+    /// `JoinError` and turned into an `is_error` entry instead.
+    async fn run_one(
+        ctx: &Context,
+        tools: &Arc<ToolExecutor>,
+        approver: &Option<Arc<dyn ToolApprover>>,
+        session: &Session,
+        tu: &ToolUse,
+    ) -> (String, u64, bool) {
+        if let Some(denial) = Self::check_approval(tools, approver, session, tu).await {
+            return denial;
+        }
+
+        let input = match Self::validate_input(tools, tu) {
+            Ok(input) => input,
+            Err(entry) => return entry,
+        };
+
+        if let Some((content, is_error)) = session.cached_tool_result(&tu.name, &input) {
+            debug!(tool = %tu.name, id = %tu.id, "Serving tool result from cache");
+            return (content, 0, is_error);
+        }
+
+        debug!(tool = %tu.name, id = %tu.id, "Executing tool");
+        let start = Instant::now();
+
+        let spawned_tools = Arc::clone(tools);
+        let spawned_ctx = ctx.clone();
+        let spawned_name = tu.name.clone();
+        let spawned_input = input.clone();
+        let result = tokio::spawn(async move {
+            spawned_tools.execute(&spawned_ctx, &spawned_name, spawned_input).await
+        })
+        .await;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(Ok(output)) => {
+                info!(tool = %tu.name, duration_ms, is_error = output.is_error, "Tool completed");
+                session.cache_tool_result(&tu.name, &input, output.content.clone(), output.is_error);
+                (output.content, duration_ms, output.is_error)
+            }
+            Ok(Err(e)) => {
+                warn!(tool = %tu.name, error = %e, "Tool execution failed");
+                (e.to_string(), duration_ms, true)
+            }
+            Err(join_error) => {
+                warn!(tool = %tu.name, error = %join_error, "Tool panicked during execution");
+                (format!("Tool '{}' panicked: {join_error}", tu.name), duration_ms, true)
+            }
+        }
+    }
+
+    /// Validates `tu.input` against its tool's declared [`hehe_core::ToolParameter`]
+    /// schema, if the tool is known to `tools`'s registry (an unknown name is
+    /// left for [`hehe_tools::ToolExecutor::execute`] itself to reject).
+    /// Returns the normalized input (with defaults filled in) on success, or
+    /// a ready-to-surface error entry listing every mismatch found — so the
+    /// model can self-correct on its next turn instead of the tool panicking
+    /// on bad input.
+    fn validate_input(
+        tools: &Arc<ToolExecutor>,
+        tu: &ToolUse,
+    ) -> std::result::Result<serde_json::Value, (String, u64, bool)> {
+        let Some(tool) = tools.registry().get(&tu.name) else {
+            return Ok(tu.input.clone());
+        };
+
+        tool.definition().parameters.validate(&tu.input).map_err(|errors| {
+            let message = format!(
+                "Invalid arguments for tool '{}': {}",
+                tu.name,
+                hehe_core::tool::describe_validation_errors(&errors)
+            );
+            warn!(tool = %tu.name, %message, "Tool call failed schema validation");
+            (message, 0, true)
+        })
+    }
+
+    /// If `tu` names a dangerous tool not yet granted for this session,
+    /// consults `approver` and returns a ready-to-surface error entry on
+    /// `Deny`, or records the grant on `AllowForSession`. Returns `None`
+    /// (proceed with execution) when the tool isn't dangerous, is already
+    /// approved, or no approver is configured.
+    async fn check_approval(
+        tools: &Arc<ToolExecutor>,
+        approver: &Option<Arc<dyn ToolApprover>>,
+        session: &Session,
+        tu: &ToolUse,
+    ) -> Option<(String, u64, bool)> {
+        if !tools.is_dangerous(&tu.name) || session.is_tool_approved(&tu.name) {
+            return None;
+        }
+        let approver = approver.as_ref()?;
+        let def = tools.registry().get(&tu.name)?.definition().clone();
+
+        match approver.approve(tu, &def).await {
+            Decision::Allow => None,
+            Decision::AllowForSession => {
+                session.approve_tool_for_session(&tu.name);
+                None
+            }
+            Decision::Deny => {
+                warn!(tool = %tu.name, "Tool call denied by approval hook");
+                Some((format!("Tool call '{}' was denied by the approval hook", tu.name), 0, true))
+            }
+        }
     }
 }
 
@@ -254,37 +873,665 @@ mod tests {
         let executor = Executor::new(config, llm, None);
         let session = Session::new();
 
-        let response = executor.execute(&session, "Hi").await.unwrap();
+        let response = executor.execute(&Context::new(), &session, "Hi").await.unwrap();
 
         assert_eq!(response.text(), "Hello!");
         assert_eq!(response.iterations, 1);
         assert!(!response.has_tool_calls());
     }
 
+    struct SleepTool {
+        def: hehe_core::ToolDefinition,
+        millis: u64,
+    }
+
+    #[async_trait]
+    impl hehe_tools::Tool for SleepTool {
+        fn definition(&self) -> &hehe_core::ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(
+            &self,
+            _ctx: &hehe_core::Context,
+            input: serde_json::Value,
+        ) -> hehe_tools::Result<hehe_tools::ToolOutput> {
+            tokio::time::sleep(std::time::Duration::from_millis(self.millis)).await;
+            Ok(hehe_tools::ToolOutput::text(input.to_string()))
+        }
+    }
+
     #[tokio::test]
-    async fn test_executor_max_iterations() {
-        let config = AgentConfig::new("mock", "You are helpful.").with_max_iterations(2);
+    async fn test_execute_tools_runs_concurrently_and_preserves_order() {
+        let mut registry = hehe_tools::ToolRegistry::new();
+        registry
+            .register(Arc::new(SleepTool {
+                def: hehe_core::ToolDefinition::new("sleep", "Sleeps"),
+                millis: 50,
+            }))
+            .unwrap();
+        let tools = Arc::new(ToolExecutor::new(Arc::new(registry)));
 
-        let tool_response = Message::new(
-            hehe_core::Role::Assistant,
-            vec![ContentBlock::tool_use(ToolUse::new(
-                "call_1",
-                "test_tool",
-                serde_json::json!({}),
-            ))],
-        );
+        let config = AgentConfig::new("mock", "You are helpful.").with_max_parallel_tools(4);
+        let llm = Arc::new(MockLlm::new(vec![]));
+        let executor = Executor::new(config, llm, Some(tools));
 
-        let llm = Arc::new(MockLlm::new(vec![
-            CompletionResponse::new("resp-1", "mock", tool_response.clone()),
-            CompletionResponse::new("resp-2", "mock", tool_response.clone()),
-            CompletionResponse::new("resp-3", "mock", tool_response),
-        ]));
+        let tool_uses: Vec<ToolUse> = (0..4)
+            .map(|i| ToolUse::new(format!("call_{i}"), "sleep", serde_json::json!({"i": i})))
+            .collect();
+        let tool_use_refs: Vec<&ToolUse> = tool_uses.iter().collect();
 
-        let executor = Executor::new(config, llm, None);
         let session = Session::new();
+        let start = Instant::now();
+        let results = executor.execute_tools(&Context::new(), &session, &tool_use_refs).await;
+        let elapsed = start.elapsed();
 
-        let result = executor.execute(&session, "Hi").await;
+        assert_eq!(results.len(), 4);
+        for (i, (output, _, is_error)) in results.iter().enumerate() {
+            assert!(!is_error);
+            assert!(output.contains(&i.to_string()));
+        }
+        assert!(
+            elapsed < std::time::Duration::from_millis(150),
+            "expected concurrent execution, took {elapsed:?}"
+        );
+    }
 
-        assert!(matches!(result, Err(AgentError::MaxIterationsReached(2))));
+    struct ScriptedApprover {
+        decision: Decision,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ScriptedApprover {
+        fn new(decision: Decision) -> Self {
+            Self {
+                decision,
+                calls: std::sync::atomic::AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ToolApprover for ScriptedApprover {
+        async fn approve(&self, _call: &ToolUse, _def: &hehe_core::ToolDefinition) -> Decision {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.decision
+        }
+    }
+
+    fn dangerous_tool_executor(config: AgentConfig, approver: Arc<ScriptedApprover>) -> (Executor, Arc<ToolExecutor>) {
+        let mut registry = hehe_tools::ToolRegistry::new();
+        registry
+            .register(Arc::new(SleepTool {
+                def: hehe_core::ToolDefinition::new("rm", "Deletes things").dangerous(),
+                millis: 0,
+            }))
+            .unwrap();
+        let tools = Arc::new(ToolExecutor::new(Arc::new(registry)));
+        let executor = Executor::new(config, Arc::new(MockLlm::new(vec![])), Some(Arc::clone(&tools)))
+            .with_tool_approver(approver);
+        (executor, tools)
+    }
+
+    #[tokio::test]
+    async fn test_execute_tools_denies_dangerous_call_without_running_it() {
+        let approver = Arc::new(ScriptedApprover::new(Decision::Deny));
+        let (executor, _tools) = dangerous_tool_executor(AgentConfig::new("mock", "You are helpful."), approver);
+        let session = Session::new();
+        let tool_use = ToolUse::new("call_1", "rm", serde_json::json!({}));
+
+        let results = executor.execute_tools(&Context::new(), &session, &[&tool_use]).await;
+
+        assert_eq!(results.len(), 1);
+        let (output, _, is_error) = &results[0];
+        assert!(is_error);
+        assert!(output.contains("denied"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_tools_allow_for_session_is_not_reprompted() {
+        let approver = Arc::new(ScriptedApprover::new(Decision::AllowForSession));
+        let (executor, _tools) =
+            dangerous_tool_executor(AgentConfig::new("mock", "You are helpful."), Arc::clone(&approver));
+        let session = Session::new();
+        let tool_use = ToolUse::new("call_1", "rm", serde_json::json!({}));
+
+        for _ in 0..2 {
+            let results = executor.execute_tools(&Context::new(), &session, &[&tool_use]).await;
+            assert!(!results[0].2);
+        }
+
+        assert_eq!(approver.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    struct CountingTool {
+        def: hehe_core::ToolDefinition,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl hehe_tools::Tool for CountingTool {
+        fn definition(&self) -> &hehe_core::ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(
+            &self,
+            _ctx: &hehe_core::Context,
+            input: serde_json::Value,
+        ) -> hehe_tools::Result<hehe_tools::ToolOutput> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(hehe_tools::ToolOutput::text(input.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_tools_reuses_cached_result_for_identical_call() {
+        let tool = Arc::new(CountingTool {
+            def: hehe_core::ToolDefinition::new("lookup", "Looks something up"),
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let mut registry = hehe_tools::ToolRegistry::new();
+        registry.register(Arc::clone(&tool) as Arc<dyn hehe_tools::Tool>).unwrap();
+        let tools = Arc::new(ToolExecutor::new(Arc::new(registry)));
+        let executor = Executor::new(AgentConfig::new("mock", "You are helpful."), Arc::new(MockLlm::new(vec![])), Some(tools));
+        let session = Session::new();
+
+        let first = ToolUse::new("call_1", "lookup", serde_json::json!({"q": "rust"}));
+        let second = ToolUse::new("call_2", "lookup", serde_json::json!({"q": "rust"}));
+
+        let first_results = executor.execute_tools(&Context::new(), &session, &[&first]).await;
+        let second_results = executor.execute_tools(&Context::new(), &session, &[&second]).await;
+
+        assert_eq!(first_results[0].0, second_results[0].0);
+        assert_eq!(tool.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    struct PanickingTool {
+        def: hehe_core::ToolDefinition,
+    }
+
+    #[async_trait]
+    impl hehe_tools::Tool for PanickingTool {
+        fn definition(&self) -> &hehe_core::ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(
+            &self,
+            _ctx: &hehe_core::Context,
+            _input: serde_json::Value,
+        ) -> hehe_tools::Result<hehe_tools::ToolOutput> {
+            panic!("boom");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_tools_converts_tool_panic_to_error_without_losing_the_batch() {
+        let mut registry = hehe_tools::ToolRegistry::new();
+        registry
+            .register(Arc::new(PanickingTool {
+                def: hehe_core::ToolDefinition::new("explode", "Always panics"),
+            }))
+            .unwrap();
+        registry
+            .register(Arc::new(SleepTool {
+                def: hehe_core::ToolDefinition::new("sleep", "Sleeps"),
+                millis: 0,
+            }))
+            .unwrap();
+        let tools = Arc::new(ToolExecutor::new(Arc::new(registry)));
+        let executor = Executor::new(AgentConfig::new("mock", "You are helpful."), Arc::new(MockLlm::new(vec![])), Some(tools));
+        let session = Session::new();
+
+        let panicking = ToolUse::new("call_1", "explode", serde_json::json!({}));
+        let fine = ToolUse::new("call_2", "sleep", serde_json::json!({}));
+
+        let results = executor
+            .execute_tools(&Context::new(), &session, &[&panicking, &fine])
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].2);
+        assert!(results[0].0.contains("panicked"));
+        assert!(!results[1].2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_tools_rejects_arguments_failing_schema_validation() {
+        let tool = Arc::new(CountingTool {
+            def: hehe_core::ToolDefinition::new("lookup", "Looks something up")
+                .with_required_param("query", hehe_core::ToolParameter::string()),
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let mut registry = hehe_tools::ToolRegistry::new();
+        registry.register(Arc::clone(&tool) as Arc<dyn hehe_tools::Tool>).unwrap();
+        let tools = Arc::new(ToolExecutor::new(Arc::new(registry)));
+        let executor = Executor::new(AgentConfig::new("mock", "You are helpful."), Arc::new(MockLlm::new(vec![])), Some(tools));
+        let session = Session::new();
+
+        let call = ToolUse::new("call_1", "lookup", serde_json::json!({}));
+        let results = executor.execute_tools(&Context::new(), &session, &[&call]).await;
+
+        assert_eq!(results.len(), 1);
+        let (output, _, is_error) = &results[0];
+        assert!(is_error);
+        assert!(output.contains("query"));
+        assert_eq!(tool.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    struct FlakyLlm {
+        failures_left: std::sync::atomic::AtomicUsize,
+        error: fn() -> LlmError,
+    }
+
+    impl FlakyLlm {
+        fn new(failures: usize, error: fn() -> LlmError) -> Self {
+            Self {
+                failures_left: std::sync::atomic::AtomicUsize::new(failures),
+                error,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for FlakyLlm {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            if self.failures_left.fetch_sub(1, std::sync::atomic::Ordering::SeqCst) > 0 {
+                return Err((self.error)());
+            }
+            Ok(CompletionResponse::new("resp-1", "flaky", Message::assistant("Recovered")))
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+            Ok(Box::pin(stream::empty()))
+        }
+
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_retries_retryable_llm_errors() {
+        let config = AgentConfig::new("flaky", "You are helpful.")
+            .with_max_llm_retries(3);
+        let llm = Arc::new(FlakyLlm::new(2, || LlmError::Network("down".to_string())));
+
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let response = executor.execute(&Context::new(), &session, "Hi").await.unwrap();
+
+        assert_eq!(response.text(), "Recovered");
+    }
+
+    #[tokio::test]
+    async fn test_execute_does_not_retry_non_retryable_llm_errors() {
+        let config = AgentConfig::new("flaky", "You are helpful.")
+            .with_max_llm_retries(3);
+        let llm = Arc::new(FlakyLlm::new(1, || {
+            LlmError::Api {
+                provider: "flaky".to_string(),
+                message: "bad request".to_string(),
+            }
+        }));
+
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let result = executor.execute(&Context::new(), &session, "Hi").await;
+
+        assert!(matches!(result, Err(AgentError::Llm(LlmError::Api { .. }))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_honors_rate_limited_retry_after_ms() {
+        let config = AgentConfig::new("flaky", "You are helpful.")
+            .with_max_llm_retries(3);
+        let llm = Arc::new(FlakyLlm::new(1, || LlmError::RateLimited {
+            provider: "flaky".to_string(),
+            retry_after_ms: Some(10),
+        }));
+
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let start = Instant::now();
+        let response = executor.execute(&Context::new(), &session, "Hi").await.unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(response.text(), "Recovered");
+        assert!(elapsed >= std::time::Duration::from_millis(10));
+    }
+
+    struct ContextOverflowOnceLlm {
+        first_call: std::sync::atomic::AtomicBool,
+    }
+
+    impl ContextOverflowOnceLlm {
+        fn new() -> Self {
+            Self {
+                first_call: std::sync::atomic::AtomicBool::new(true),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for ContextOverflowOnceLlm {
+        fn name(&self) -> &str {
+            "overflow-once"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            if self.first_call.swap(false, std::sync::atomic::Ordering::SeqCst) {
+                return Err(LlmError::ContextLengthExceeded { max_tokens: 100 });
+            }
+            Ok(CompletionResponse::new("resp-1", "overflow-once", Message::assistant("Recovered")))
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+            Ok(Box::pin(stream::empty()))
+        }
+
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "overflow-once"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_compacts_and_retries_on_context_length_exceeded() {
+        let config = AgentConfig::new("overflow-once", "You are helpful.")
+            .with_max_llm_retries(3)
+            .with_keep_recent_messages(2);
+        let llm = Arc::new(ContextOverflowOnceLlm::new());
+
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+        for i in 0..5 {
+            session.add_message(Message::user(format!("old message {i}")));
+        }
+
+        let response = executor.execute(&Context::new(), &session, "Hi").await.unwrap();
+
+        assert_eq!(response.text(), "Recovered");
+
+        let messages = session.messages();
+        assert!(messages
+            .iter()
+            .any(|m| m.text_content().starts_with("[Summary of earlier conversation]")));
+    }
+
+    struct HugeTokenCounter;
+
+    impl TokenCounter for HugeTokenCounter {
+        fn count(&self, _message: &Message) -> usize {
+            1_000_000
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_context_fits_compacts_proactively_when_over_budget() {
+        let config = AgentConfig::new("mock", "You are helpful.")
+            .with_context_token_budget(10)
+            .with_keep_recent_messages(1);
+        let llm = Arc::new(MockLlm::new(vec![CompletionResponse::new(
+            "resp-1",
+            "mock",
+            Message::assistant("summary text"),
+        )]));
+        let executor = Executor::new(config, llm, None).with_token_counter(Arc::new(HugeTokenCounter));
+        let session = Session::new();
+        for i in 0..5 {
+            session.add_message(Message::user(format!("old message {i}")));
+        }
+
+        executor.ensure_context_fits(&Context::new(), &session).await.unwrap();
+
+        let messages = session.messages();
+        assert_eq!(messages.len(), 2, "expected summary + 1 kept recent message");
+        assert!(messages[0].text_content().contains("summary text"));
+    }
+
+    #[tokio::test]
+    async fn test_executor_max_iterations() {
+        let config = AgentConfig::new("mock", "You are helpful.").with_max_iterations(2);
+
+        let tool_response = Message::new(
+            hehe_core::Role::Assistant,
+            vec![ContentBlock::tool_use(ToolUse::new(
+                "call_1",
+                "test_tool",
+                serde_json::json!({}),
+            ))],
+        );
+
+        let llm = Arc::new(MockLlm::new(vec![
+            CompletionResponse::new("resp-1", "mock", tool_response.clone()),
+            CompletionResponse::new("resp-2", "mock", tool_response.clone()),
+            CompletionResponse::new("resp-3", "mock", tool_response),
+        ]));
+
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let result = executor.execute(&Context::new(), &session, "Hi").await;
+
+        assert!(matches!(result, Err(AgentError::MaxIterationsReached(2))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_cancelled_with_partial_progress() {
+        let config = AgentConfig::new("mock", "You are helpful.");
+        let llm = Arc::new(MockLlm::new(vec![]));
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let ctx = Context::new();
+        ctx.cancel();
+
+        let result = executor.execute(&ctx, &session, "Hi").await;
+
+        match result {
+            Err(AgentError::Cancelled(response)) => {
+                assert_eq!(response.iterations, 0);
+                assert!(!response.has_tool_calls());
+            }
+            other => panic!("expected Cancelled, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_returns_deadline_once_timeout_elapses() {
+        let config = AgentConfig::new("mock", "You are helpful.");
+        let llm = Arc::new(MockLlm::new(vec![]));
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let ctx = Context::new().with_timeout(Duration::from_millis(1));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let result = executor.execute(&ctx, &session, "Hi").await;
+
+        assert!(matches!(result, Err(AgentError::Deadline(_))));
+    }
+
+    struct StreamingLlm;
+
+    #[async_trait]
+    impl LlmProvider for StreamingLlm {
+        fn name(&self) -> &str {
+            "streaming"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            Ok(CompletionResponse::new("id", "streaming", Message::assistant("unused")))
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+            let chunks = vec![
+                StreamChunk::TextDelta { text: "Hel".to_string() },
+                StreamChunk::TextDelta { text: "lo!".to_string() },
+                StreamChunk::MessageEnd { stop_reason: Some(hehe_core::stream::StopReason::EndTurn) },
+            ];
+            Ok(Box::pin(stream::iter(chunks.into_iter().map(Ok))))
+        }
+
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "streaming"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_stream_emits_granular_events() {
+        let config = AgentConfig::new("streaming", "You are helpful.");
+        let llm = Arc::new(StreamingLlm);
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let response = executor
+            .execute_stream(&Context::new(), &session, "Hi", tx)
+            .await
+            .unwrap();
+
+        assert_eq!(response.text(), "Hello!");
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+
+        assert!(matches!(events[0], AgentEvent::MessageStart { .. }));
+        assert!(matches!(events[1], AgentEvent::IterationStart { iteration: 1 }));
+        assert!(events.iter().any(|e| matches!(e, AgentEvent::TextDelta { delta } if delta == "Hel")));
+        assert!(events.iter().any(|e| matches!(e, AgentEvent::TextDelta { delta } if delta == "lo!")));
+        assert!(matches!(events.last(), Some(AgentEvent::MessageEnd { .. })));
+    }
+
+    struct FlakyStreamingLlm {
+        failures_left: std::sync::atomic::AtomicUsize,
+    }
+
+    impl FlakyStreamingLlm {
+        fn new(failures: usize) -> Self {
+            Self {
+                failures_left: std::sync::atomic::AtomicUsize::new(failures),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for FlakyStreamingLlm {
+        fn name(&self) -> &str {
+            "flaky-streaming"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            Ok(CompletionResponse::new("id", "flaky-streaming", Message::assistant("unused")))
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+
+            if self.failures_left.fetch_sub(1, std::sync::atomic::Ordering::SeqCst) > 0 {
+                // Drops mid-stream: yields text but errors before a `MessageEnd`.
+                let chunks: Vec<std::result::Result<StreamChunk, LlmError>> = vec![
+                    Ok(StreamChunk::TextDelta { text: "Par".to_string() }),
+                    Err(LlmError::Network("connection reset".to_string())),
+                ];
+                return Ok(Box::pin(stream::iter(chunks)));
+            }
+
+            let chunks = vec![
+                StreamChunk::TextDelta { text: "Recovered!".to_string() },
+                StreamChunk::MessageEnd { stop_reason: Some(hehe_core::stream::StopReason::EndTurn) },
+            ];
+            Ok(Box::pin(stream::iter(chunks.into_iter().map(Ok))))
+        }
+
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "flaky-streaming"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_stream_reconnects_after_mid_stream_drop() {
+        let config = AgentConfig::new("flaky-streaming", "You are helpful.").with_max_llm_retries(2);
+        let llm = Arc::new(FlakyStreamingLlm::new(1));
+        let executor = Executor::new(config, llm, None);
+        let session = Session::new();
+
+        let (tx, mut rx) = mpsc::channel(100);
+        let response = executor
+            .execute_stream(&Context::new(), &session, "Hi", tx)
+            .await
+            .unwrap();
+
+        assert_eq!(response.text(), "Recovered!");
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, AgentEvent::Retry { attempt: 1, .. })));
     }
 }
@@ -1,24 +1,39 @@
 pub mod error;
 pub mod config;
+pub mod approval;
+pub mod compaction;
 pub mod event;
+pub mod persona;
 pub mod session;
 pub mod response;
+pub mod state;
 pub mod executor;
+pub mod tool_loop;
 pub mod agent;
 
 pub use error::{AgentError, Result};
 pub use config::AgentConfig;
+pub use approval::{Decision, ToolApprover};
+pub use compaction::{HeuristicTokenCounter, TokenCounter};
 pub use event::AgentEvent;
-pub use session::{Session, SessionStats};
+pub use persona::{AgentDefinition, Role};
+pub use session::{MessagePage, Session, SessionStats};
 pub use response::{AgentResponse, ToolCallRecord};
+pub use state::AgentState;
+pub use tool_loop::{ToolLoopConfig, ToolLoopDriver, ToolLoopEvent, ToolLoopOutcome};
 pub use agent::{Agent, AgentBuilder};
 
 pub mod prelude {
     pub use crate::error::{AgentError, Result};
     pub use crate::config::AgentConfig;
+    pub use crate::approval::{Decision, ToolApprover};
+    pub use crate::compaction::{HeuristicTokenCounter, TokenCounter};
     pub use crate::event::AgentEvent;
+    pub use crate::persona::{AgentDefinition, Role};
     pub use crate::session::{Session, SessionStats};
     pub use crate::response::{AgentResponse, ToolCallRecord};
+    pub use crate::state::AgentState;
+    pub use crate::tool_loop::{ToolLoopConfig, ToolLoopDriver, ToolLoopEvent, ToolLoopOutcome};
     pub use crate::agent::{Agent, AgentBuilder};
 }
 
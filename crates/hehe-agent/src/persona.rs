@@ -0,0 +1,152 @@
+use crate::config::AgentConfig;
+use crate::error::{AgentError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A named prompt preset an agent can switch to at runtime via
+/// [`crate::Agent::switch_role`], e.g. to move a support bot between a
+/// "triage" persona and an "escalation" one without rebuilding it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Role {
+    pub system_prompt: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+}
+
+/// Declarative, file-loadable shape of an [`AgentConfig`] plus the tools and
+/// roles it ships with. Deserialized by [`crate::AgentBuilder::from_config_file`]
+/// so a persona can be version-controlled and shared as a TOML (or JSON)
+/// document instead of rebuilt in code.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AgentDefinition {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub system_prompt: String,
+    pub model: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    #[serde(default)]
+    pub max_iterations: Option<usize>,
+    #[serde(default)]
+    pub tools_enabled: Option<bool>,
+    /// Names looked up in the `ToolRegistry` passed to the builder; an
+    /// unknown name fails [`crate::AgentBuilder::build`] with `AgentError::Config`.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
+}
+
+impl AgentDefinition {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content)
+            .map_err(|e| AgentError::config(format!("failed to parse agent definition: {e}")))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content)
+            .map_err(|e| AgentError::config(format!("failed to parse agent definition: {e}")))
+    }
+
+    /// Loads a definition from `path`, parsing as JSON if the extension is
+    /// `.json` and as TOML otherwise.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AgentError::config(format!("failed to read agent definition {}: {e}", path.display()))
+        })?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        }
+    }
+
+    /// Maps the file's config fields onto an [`AgentConfig`]; `tools` and
+    /// `roles` are consumed separately by the builder, since resolving tool
+    /// names and storing roles both happen outside `AgentConfig` itself.
+    pub(crate) fn into_config(self) -> AgentConfig {
+        let mut config = AgentConfig::new(self.model, self.system_prompt);
+
+        if let Some(name) = self.name {
+            config = config.with_name(name);
+        }
+        if let Some(temperature) = self.temperature {
+            config = config.with_temperature(temperature);
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            config = config.with_max_tokens(max_tokens);
+        }
+        if let Some(max_iterations) = self.max_iterations {
+            config = config.with_max_iterations(max_iterations);
+        }
+        if let Some(tools_enabled) = self.tools_enabled {
+            config = config.with_tools_enabled(tools_enabled);
+        }
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_definition_from_toml() {
+        let toml = r#"
+            system_prompt = "You are a helpful assistant."
+            model = "gpt-4o"
+            temperature = 0.2
+            tools = ["read_file", "write_file"]
+
+            [roles.triage]
+            system_prompt = "You are triaging incoming support tickets."
+            temperature = 0.1
+
+            [roles.escalation]
+            system_prompt = "You are handling an escalated, high-priority ticket."
+        "#;
+
+        let definition = AgentDefinition::from_toml(toml).unwrap();
+
+        assert_eq!(definition.model, "gpt-4o");
+        assert_eq!(definition.temperature, Some(0.2));
+        assert_eq!(definition.tools, vec!["read_file", "write_file"]);
+        assert_eq!(definition.roles.len(), 2);
+        assert_eq!(definition.roles["triage"].temperature, Some(0.1));
+    }
+
+    #[test]
+    fn test_agent_definition_into_config_maps_fields() {
+        let definition = AgentDefinition {
+            name: Some("support-bot".to_string()),
+            system_prompt: "You are helpful.".to_string(),
+            model: "gpt-4o".to_string(),
+            temperature: Some(0.5),
+            max_tokens: Some(2048),
+            max_iterations: Some(8),
+            tools_enabled: Some(false),
+            tools: vec![],
+            roles: HashMap::new(),
+        };
+
+        let config = definition.into_config();
+
+        assert_eq!(config.name, "support-bot");
+        assert_eq!(config.model, "gpt-4o");
+        assert_eq!(config.temperature, 0.5);
+        assert_eq!(config.max_tokens, Some(2048));
+        assert_eq!(config.max_iterations, 8);
+        assert!(!config.tools_enabled);
+    }
+
+    #[test]
+    fn test_agent_definition_rejects_invalid_toml() {
+        let result = AgentDefinition::from_toml("not = [valid");
+        assert!(matches!(result, Err(AgentError::Config(_))));
+    }
+}
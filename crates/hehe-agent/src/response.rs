@@ -1,6 +1,7 @@
 use hehe_core::{Id, Metadata};
 use hehe_tools::ToolOutput;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ToolCallRecord {
@@ -10,6 +11,15 @@ pub struct ToolCallRecord {
     pub output: String,
     pub is_error: bool,
     pub duration_ms: u64,
+    /// Which step of the turn's execution this call belongs to (0-based).
+    /// `None` for records that predate step tracking.
+    #[serde(default)]
+    pub step: Option<usize>,
+    /// The `id` of the call this one is chained from — e.g. a `write_file`
+    /// whose `path` argument came from an earlier `search_files` result.
+    /// `None` for calls with no known dependency on a prior call.
+    #[serde(default)]
+    pub parent_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -66,6 +76,37 @@ impl AgentResponse {
     pub fn failed_tool_calls(&self) -> impl Iterator<Item = &ToolCallRecord> {
         self.tool_calls.iter().filter(|tc| tc.is_error)
     }
+
+    /// Groups `tool_calls` by [`ToolCallRecord::step`], in ascending step
+    /// order, preserving each step's internal call order. Calls with no
+    /// `step` set (records that predate step tracking) are omitted.
+    pub fn tool_calls_by_step(&self) -> Vec<(usize, Vec<&ToolCallRecord>)> {
+        let mut by_step: BTreeMap<usize, Vec<&ToolCallRecord>> = BTreeMap::new();
+        for call in &self.tool_calls {
+            if let Some(step) = call.step {
+                by_step.entry(step).or_default().push(call);
+            }
+        }
+        by_step.into_iter().collect()
+    }
+
+    /// Walks [`ToolCallRecord::parent_id`] links back from `id` to the root
+    /// of its call chain, returning the calls in execution order (the root
+    /// first, the call matching `id` last). Returns an empty vector if `id`
+    /// isn't found among `tool_calls`.
+    pub fn call_chain(&self, id: &str) -> Vec<&ToolCallRecord> {
+        let mut chain = Vec::new();
+        let mut current = self.tool_calls.iter().find(|tc| tc.id == id);
+        while let Some(call) = current {
+            chain.push(call);
+            current = call
+                .parent_id
+                .as_deref()
+                .and_then(|parent_id| self.tool_calls.iter().find(|tc| tc.id == parent_id));
+        }
+        chain.reverse();
+        chain
+    }
 }
 
 impl ToolCallRecord {
@@ -83,6 +124,8 @@ impl ToolCallRecord {
             output: output.content.clone(),
             is_error: output.is_error,
             duration_ms,
+            step: None,
+            parent_id: None,
         }
     }
 
@@ -100,8 +143,20 @@ impl ToolCallRecord {
             output: error_msg.into(),
             is_error: true,
             duration_ms,
+            step: None,
+            parent_id: None,
         }
     }
+
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn with_parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +179,8 @@ mod tests {
             output: "file content".to_string(),
             is_error: false,
             duration_ms: 100,
+            step: None,
+            parent_id: None,
         };
 
         let response = AgentResponse::new(Id::new(), "Done!")
@@ -148,4 +205,48 @@ mod tests {
         assert!(record.is_error);
         assert_eq!(record.output, "Permission denied");
     }
+
+    #[test]
+    fn test_tool_calls_by_step_groups_and_orders_ascending() {
+        let response = AgentResponse::new(Id::new(), "Done!").with_tool_calls(vec![
+            ToolCallRecord::success("call_1", "search_files", serde_json::json!({}), &ToolOutput::text("a.rs"), 10)
+                .with_step(0),
+            ToolCallRecord::success("call_2", "read_file", serde_json::json!({}), &ToolOutput::text("contents"), 20)
+                .with_step(0),
+            ToolCallRecord::success("call_3", "write_file", serde_json::json!({}), &ToolOutput::text("ok"), 30)
+                .with_step(1),
+        ]);
+
+        let steps = response.tool_calls_by_step();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].0, 0);
+        assert_eq!(steps[0].1.len(), 2);
+        assert_eq!(steps[1].0, 1);
+        assert_eq!(steps[1].1.len(), 1);
+        assert_eq!(steps[1].1[0].id, "call_3");
+    }
+
+    #[test]
+    fn test_call_chain_walks_parent_links_to_the_root() {
+        let response = AgentResponse::new(Id::new(), "Done!").with_tool_calls(vec![
+            ToolCallRecord::success("call_1", "search_files", serde_json::json!({}), &ToolOutput::text("a.rs"), 10)
+                .with_step(0),
+            ToolCallRecord::success("call_2", "read_file", serde_json::json!({}), &ToolOutput::text("contents"), 20)
+                .with_step(1)
+                .with_parent("call_1"),
+            ToolCallRecord::success("call_3", "write_file", serde_json::json!({}), &ToolOutput::text("ok"), 30)
+                .with_step(2)
+                .with_parent("call_2"),
+        ]);
+
+        let chain = response.call_chain("call_3");
+        let ids: Vec<&str> = chain.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["call_1", "call_2", "call_3"]);
+    }
+
+    #[test]
+    fn test_call_chain_is_empty_for_unknown_id() {
+        let response = AgentResponse::new(Id::new(), "Done!");
+        assert!(response.call_chain("missing").is_empty());
+    }
 }
@@ -1,5 +1,7 @@
+use hehe_core::utils::hash::{canonical_json, hash_string};
 use hehe_core::{Id, Message, Metadata, Timestamp};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -19,10 +21,28 @@ impl Default for SessionStats {
     }
 }
 
+/// One index-based page of messages, returned by [`Session::messages_page`].
+/// `next_offset` is the offset to request for the next page, or `None` once
+/// the end of the transcript has been reached.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MessagePage {
+    pub messages: Vec<Message>,
+    pub next_offset: Option<usize>,
+}
+
 #[derive(Debug)]
 struct SessionInner {
     messages: Vec<Message>,
     stats: SessionStats,
+    /// Tool names granted `AllowForSession` by a `ToolApprover`. Deliberately
+    /// not part of `SessionSnapshot`: a restored session re-prompts for
+    /// dangerous tools rather than silently carrying grants across restarts.
+    approved_tools: HashSet<String>,
+    /// Memoized `(tool_name, canonical args)` results, keyed by
+    /// `hash_string(tool_name + canonical_json(args))`. Not part of
+    /// `SessionSnapshot`: a restored session re-executes rather than trusting
+    /// stale tool output across restarts.
+    tool_result_cache: HashMap<u64, (String, bool)>,
 }
 
 #[derive(Clone, Debug)]
@@ -33,6 +53,55 @@ pub struct Session {
     inner: Arc<RwLock<SessionInner>>,
 }
 
+/// On-the-wire shape of a [`Session`]: its identity plus a snapshot of the message
+/// history and stats at the moment of serialization. Deserializing builds a fresh
+/// `Session` seeded with that snapshot, ready to keep accumulating messages.
+#[derive(Serialize, Deserialize)]
+struct SessionSnapshot {
+    id: Id,
+    created_at: Timestamp,
+    metadata: Metadata,
+    messages: Vec<Message>,
+    stats: SessionStats,
+}
+
+impl Serialize for Session {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let inner = self.inner.read().unwrap();
+        SessionSnapshot {
+            id: self.id,
+            created_at: self.created_at,
+            metadata: self.metadata.clone(),
+            messages: inner.messages.clone(),
+            stats: inner.stats.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Session {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let snapshot = SessionSnapshot::deserialize(deserializer)?;
+        Ok(Self {
+            id: snapshot.id,
+            created_at: snapshot.created_at,
+            metadata: snapshot.metadata,
+            inner: Arc::new(RwLock::new(SessionInner {
+                messages: snapshot.messages,
+                stats: snapshot.stats,
+                approved_tools: HashSet::new(),
+                tool_result_cache: HashMap::new(),
+            })),
+        })
+    }
+}
+
 impl Session {
     pub fn new() -> Self {
         Self {
@@ -42,6 +111,8 @@ impl Session {
             inner: Arc::new(RwLock::new(SessionInner {
                 messages: Vec::new(),
                 stats: SessionStats::default(),
+                approved_tools: HashSet::new(),
+                tool_result_cache: HashMap::new(),
             })),
         }
     }
@@ -54,6 +125,8 @@ impl Session {
             inner: Arc::new(RwLock::new(SessionInner {
                 messages: Vec::new(),
                 stats: SessionStats::default(),
+                approved_tools: HashSet::new(),
+                tool_result_cache: HashMap::new(),
             })),
         }
     }
@@ -102,6 +175,56 @@ impl Session {
         }
     }
 
+    /// Messages strictly before `timestamp`, most recent first among the
+    /// matches, capped to `limit`, then restored to chronological order —
+    /// the page a UI fetches when the user scrolls back past the oldest
+    /// message it currently holds.
+    pub fn messages_before(&self, timestamp: Timestamp, limit: usize) -> Vec<Message> {
+        let inner = self.inner.read().unwrap();
+        let mut page: Vec<Message> = inner
+            .messages
+            .iter()
+            .filter(|m| m.created_at < timestamp)
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect();
+        page.reverse();
+        page
+    }
+
+    /// Messages strictly after `timestamp`, oldest first, capped to
+    /// `limit` — what a reconnecting client fetches to catch up on
+    /// messages it doesn't have yet.
+    pub fn messages_after(&self, timestamp: Timestamp, limit: usize) -> Vec<Message> {
+        self.inner
+            .read()
+            .unwrap()
+            .messages
+            .iter()
+            .filter(|m| m.created_at > timestamp)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// One index-based page of `limit` messages starting at `offset`, plus
+    /// the offset to request next. An `offset` at or past the end of the
+    /// transcript returns an empty page with no next offset.
+    pub fn messages_page(&self, offset: usize, limit: usize) -> MessagePage {
+        let inner = self.inner.read().unwrap();
+        let len = inner.messages.len();
+        if offset >= len {
+            return MessagePage { messages: Vec::new(), next_offset: None };
+        }
+
+        let end = (offset + limit).min(len);
+        MessagePage {
+            messages: inner.messages[offset..end].to_vec(),
+            next_offset: if end < len { Some(end) } else { None },
+        }
+    }
+
     pub fn clear(&self) {
         let mut inner = self.inner.write().unwrap();
         inner.messages.clear();
@@ -121,6 +244,71 @@ impl Session {
         inner.stats.iteration_count += 1;
     }
 
+    /// Whether a `ToolApprover` has already granted `tool_name`
+    /// `AllowForSession` earlier in this conversation.
+    pub fn is_tool_approved(&self, tool_name: &str) -> bool {
+        self.inner.read().unwrap().approved_tools.contains(tool_name)
+    }
+
+    /// Records an `AllowForSession` grant so later calls to `tool_name` in
+    /// this conversation skip the approver.
+    pub fn approve_tool_for_session(&self, tool_name: &str) {
+        self.inner.write().unwrap().approved_tools.insert(tool_name.to_string());
+    }
+
+    /// Builds the cache key for a tool call, combining its name and
+    /// canonicalized arguments so argument-key order never causes a spurious
+    /// cache miss.
+    fn tool_cache_key(tool_name: &str, args: &serde_json::Value) -> u64 {
+        hash_string(&format!("{tool_name}{}", canonical_json(args)))
+    }
+
+    /// Returns the memoized `(content, is_error)` result of an earlier call
+    /// to `tool_name` with identical `args` in this session, if any.
+    pub fn cached_tool_result(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+    ) -> Option<(String, bool)> {
+        let key = Self::tool_cache_key(tool_name, args);
+        self.inner.read().unwrap().tool_result_cache.get(&key).cloned()
+    }
+
+    /// Memoizes the `(content, is_error)` result of calling `tool_name` with
+    /// `args`, so a later identical call in this session can be served from
+    /// cache instead of re-executed.
+    pub fn cache_tool_result(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        content: String,
+        is_error: bool,
+    ) {
+        let key = Self::tool_cache_key(tool_name, args);
+        self.inner
+            .write()
+            .unwrap()
+            .tool_result_cache
+            .insert(key, (content, is_error));
+    }
+
+    /// Replaces every message except the most recent `keep_recent` with a
+    /// single synthetic `summary` message in their place. Used by
+    /// context-window compaction to keep long-running sessions within a
+    /// model's token budget without losing the substance of earlier turns.
+    pub fn compact(&self, keep_recent: usize, summary: Message) {
+        let mut inner = self.inner.write().unwrap();
+        let len = inner.messages.len();
+        if keep_recent >= len {
+            return;
+        }
+
+        let mut messages = Vec::with_capacity(keep_recent + 1);
+        messages.push(summary);
+        messages.extend_from_slice(&inner.messages[len - keep_recent..]);
+        inner.messages = messages;
+    }
+
     pub fn truncate_messages(&self, max_messages: usize) {
         let mut inner = self.inner.write().unwrap();
         if inner.messages.len() > max_messages {
@@ -182,6 +370,32 @@ mod tests {
         assert_eq!(session.message_count(), 5);
     }
 
+    #[test]
+    fn test_session_compact_keeps_recent_and_prepends_summary() {
+        let session = Session::new();
+        for i in 0..10 {
+            session.add_message(Message::user(format!("Message {}", i)));
+        }
+
+        session.compact(3, Message::assistant("summary of earlier turns"));
+
+        let messages = session.messages();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].text_content(), "summary of earlier turns");
+        assert_eq!(messages[1].text_content(), "Message 7");
+        assert_eq!(messages[3].text_content(), "Message 9");
+    }
+
+    #[test]
+    fn test_session_compact_is_noop_when_already_within_window() {
+        let session = Session::new();
+        session.add_message(Message::user("only message"));
+
+        session.compact(5, Message::assistant("summary"));
+
+        assert_eq!(session.message_count(), 1);
+    }
+
     #[test]
     fn test_session_stats() {
         let session = Session::new();
@@ -195,6 +409,66 @@ mod tests {
         assert_eq!(stats.iteration_count, 1);
     }
 
+    #[test]
+    fn test_session_tool_approval_cache() {
+        let session = Session::new();
+
+        assert!(!session.is_tool_approved("shell"));
+
+        session.approve_tool_for_session("shell");
+
+        assert!(session.is_tool_approved("shell"));
+        assert!(!session.is_tool_approved("http"));
+    }
+
+    #[test]
+    fn test_session_messages_before_and_after() {
+        let session = Session::new();
+        for i in 0..5 {
+            session.add_message(Message::user(format!("Message {}", i)));
+        }
+        let messages = session.messages();
+        let midpoint = messages[2].created_at;
+
+        let before = session.messages_before(midpoint, 10);
+        assert_eq!(before.len(), 2);
+        assert_eq!(before[0].text_content(), "Message 0");
+        assert_eq!(before[1].text_content(), "Message 1");
+
+        let after = session.messages_after(midpoint, 10);
+        assert_eq!(after.len(), 2);
+        assert_eq!(after[0].text_content(), "Message 3");
+        assert_eq!(after[1].text_content(), "Message 4");
+
+        let limited_before = session.messages_before(midpoint, 1);
+        assert_eq!(limited_before.len(), 1);
+        assert_eq!(limited_before[0].text_content(), "Message 1");
+    }
+
+    #[test]
+    fn test_session_messages_page() {
+        let session = Session::new();
+        for i in 0..5 {
+            session.add_message(Message::user(format!("Message {}", i)));
+        }
+
+        let page = session.messages_page(0, 2);
+        assert_eq!(page.messages.len(), 2);
+        assert_eq!(page.next_offset, Some(2));
+
+        let page = session.messages_page(page.next_offset.unwrap(), 2);
+        assert_eq!(page.messages.len(), 2);
+        assert_eq!(page.next_offset, Some(4));
+
+        let page = session.messages_page(page.next_offset.unwrap(), 2);
+        assert_eq!(page.messages.len(), 1);
+        assert_eq!(page.next_offset, None);
+
+        let page = session.messages_page(10, 2);
+        assert!(page.messages.is_empty());
+        assert_eq!(page.next_offset, None);
+    }
+
     #[test]
     fn test_session_clone_shares_state() {
         let session1 = Session::new();
@@ -204,4 +478,19 @@ mod tests {
 
         assert_eq!(session2.message_count(), 1);
     }
+
+    #[test]
+    fn test_session_serde_round_trip() {
+        let session = Session::new();
+        session.add_message(Message::user("Hello"));
+        session.add_message(Message::assistant("Hi there!"));
+        session.increment_tool_calls(1);
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.id(), session.id());
+        assert_eq!(restored.message_count(), session.message_count());
+        assert_eq!(restored.stats().tool_call_count, 1);
+    }
 }
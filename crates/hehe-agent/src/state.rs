@@ -0,0 +1,178 @@
+use hehe_core::event::{Event, EventKind, EventPayload};
+use hehe_core::AgentId;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::Notify;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentState {
+    Idle,
+    Running,
+    Paused,
+    Stopped,
+    Errored,
+}
+
+impl AgentState {
+    fn allows(self, next: AgentState) -> bool {
+        use AgentState::*;
+        matches!(
+            (self, next),
+            (Idle, Running)
+                | (Running, Paused)
+                | (Running, Stopped)
+                | (Running, Errored)
+                | (Paused, Running)
+                | (Paused, Stopped)
+                | (Errored, Idle)
+        )
+    }
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("cannot transition agent from {from:?} to {to:?}")]
+pub struct InvalidTransition {
+    pub from: AgentState,
+    pub to: AgentState,
+}
+
+/// Tracks an agent's run state and enforces legal transitions between the states
+/// backing `EventKind::Agent{Started,Stopped,Paused,Resumed}`.
+pub struct AgentLifecycle {
+    agent_id: AgentId,
+    state: RwLock<AgentState>,
+    notify: Notify,
+}
+
+impl AgentLifecycle {
+    pub fn new(agent_id: AgentId) -> Self {
+        Self {
+            agent_id,
+            state: RwLock::new(AgentState::Idle),
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn state(&self) -> AgentState {
+        *self.state.read().unwrap()
+    }
+
+    fn transition(&self, next: AgentState, kind: EventKind) -> Result<Event, InvalidTransition> {
+        let mut state = self.state.write().unwrap();
+        if !state.allows(next) {
+            return Err(InvalidTransition { from: *state, to: next });
+        }
+        *state = next;
+        drop(state);
+        self.notify.notify_waiters();
+
+        Ok(Event::new(kind).with_payload(EventPayload::Agent {
+            agent_id: self.agent_id,
+        }))
+    }
+
+    pub fn start(&self) -> Result<Event, InvalidTransition> {
+        self.transition(AgentState::Running, EventKind::AgentStarted)
+    }
+
+    pub fn pause(&self) -> Result<Event, InvalidTransition> {
+        self.transition(AgentState::Paused, EventKind::AgentPaused)
+    }
+
+    pub fn resume(&self) -> Result<Event, InvalidTransition> {
+        self.transition(AgentState::Running, EventKind::AgentResumed)
+    }
+
+    pub fn stop(&self) -> Result<Event, InvalidTransition> {
+        self.transition(AgentState::Stopped, EventKind::AgentStopped)
+    }
+
+    pub fn fail(&self) -> Result<Event, InvalidTransition> {
+        self.transition(AgentState::Errored, EventKind::AgentError)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state() == AgentState::Running
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state() == AgentState::Paused
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state() == AgentState::Stopped
+    }
+
+    /// Waits until the agent leaves the `Paused` state (resumed or stopped).
+    pub async fn wait_while_paused(&self) {
+        loop {
+            if self.state() != AgentState::Paused {
+                return;
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle_starts_idle() {
+        let lifecycle = AgentLifecycle::new(AgentId::new());
+        assert_eq!(lifecycle.state(), AgentState::Idle);
+    }
+
+    #[test]
+    fn test_lifecycle_happy_path() {
+        let lifecycle = AgentLifecycle::new(AgentId::new());
+
+        lifecycle.start().unwrap();
+        assert!(lifecycle.is_running());
+
+        lifecycle.pause().unwrap();
+        assert!(lifecycle.is_paused());
+
+        lifecycle.resume().unwrap();
+        assert!(lifecycle.is_running());
+
+        lifecycle.stop().unwrap();
+        assert!(lifecycle.is_stopped());
+    }
+
+    #[test]
+    fn test_cannot_resume_from_stopped() {
+        let lifecycle = AgentLifecycle::new(AgentId::new());
+        lifecycle.start().unwrap();
+        lifecycle.stop().unwrap();
+
+        let err = lifecycle.resume().unwrap_err();
+        assert_eq!(err.from, AgentState::Stopped);
+        assert_eq!(err.to, AgentState::Running);
+    }
+
+    #[tokio::test]
+    async fn test_wait_while_paused_unblocks_on_resume() {
+        let lifecycle = std::sync::Arc::new(AgentLifecycle::new(AgentId::new()));
+        lifecycle.start().unwrap();
+        lifecycle.pause().unwrap();
+
+        let waiter = {
+            let lifecycle = lifecycle.clone();
+            tokio::spawn(async move {
+                lifecycle.wait_while_paused().await;
+            })
+        };
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        lifecycle.resume().unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), waiter)
+            .await
+            .expect("wait_while_paused should unblock after resume")
+            .unwrap();
+    }
+}
@@ -0,0 +1,370 @@
+use crate::error::{AgentError, Result};
+use hehe_core::event::TokenUsage;
+use hehe_core::message::{ContentBlock, ToolResult, ToolUse};
+use hehe_core::{Context, Message, Role};
+use hehe_llm::{CompletionRequest, CompletionResponse, LlmProvider};
+use hehe_tools::executor::default_concurrency;
+use hehe_tools::ToolExecutor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{debug, info};
+
+/// Configures [`ToolLoopDriver`]: how many assistant/tool rounds it's willing to
+/// run, how long it's willing to run for, and how many tool calls from a single
+/// turn it dispatches at once.
+#[derive(Clone, Debug)]
+pub struct ToolLoopConfig {
+    pub max_steps: usize,
+    pub wall_time_budget: Option<Duration>,
+    pub concurrency: usize,
+}
+
+impl Default for ToolLoopConfig {
+    fn default() -> Self {
+        Self {
+            max_steps: 10,
+            wall_time_budget: None,
+            concurrency: default_concurrency(),
+        }
+    }
+}
+
+impl ToolLoopConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn with_wall_time_budget(mut self, budget: Duration) -> Self {
+        self.wall_time_budget = Some(budget);
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+/// The terminal result of [`ToolLoopDriver::run`]/[`ToolLoopDriver::run_stream`]:
+/// every message exchanged (the original messages plus each assistant/tool-result
+/// turn appended), the [`CompletionResponse`] that ended the loop (the one with
+/// no more `ToolUse` blocks), and the [`TokenUsage`] summed across every step.
+#[derive(Clone, Debug)]
+pub struct ToolLoopOutcome {
+    pub messages: Vec<Message>,
+    pub final_response: CompletionResponse,
+    pub usage: TokenUsage,
+}
+
+/// One increment of progress out of [`ToolLoopDriver::run`]/[`ToolLoopDriver::run_stream`],
+/// in the order they happen, so a caller can render partial output as the loop runs.
+#[derive(Clone, Debug)]
+pub enum ToolLoopEvent {
+    /// The assistant produced this turn (text and/or `ToolUse` blocks).
+    AssistantTurn { step: usize, blocks: Vec<ContentBlock> },
+    /// Matching `ToolResult` blocks for the `ToolUse`s in the preceding turn,
+    /// keyed by `tool_use_id`. Present even when a tool call failed.
+    ToolResults { step: usize, blocks: Vec<ContentBlock> },
+    /// The assistant returned no more `ToolUse` blocks; the loop is done.
+    Done { steps: usize },
+}
+
+/// Drives the repeated "assistant emits `ToolUse` → execute → feed `ToolResult`
+/// back → assistant continues" exchange across multiple rounds, stopping when a
+/// turn comes back with no `ToolUse` blocks, the step cap is hit
+/// ([`AgentError::MaxIterationsReached`]), or the wall-time budget runs out.
+///
+/// Unlike [`crate::executor::Executor`], this operates directly on a
+/// [`CompletionRequest`]'s message list rather than a [`crate::session::Session`],
+/// and surfaces every intermediate turn via [`ToolLoopEvent`] instead of only the
+/// final answer.
+pub struct ToolLoopDriver {
+    llm: Arc<dyn LlmProvider>,
+    tools: Arc<ToolExecutor>,
+    config: ToolLoopConfig,
+}
+
+impl ToolLoopDriver {
+    pub fn new(llm: Arc<dyn LlmProvider>, tools: Arc<ToolExecutor>, config: ToolLoopConfig) -> Self {
+        Self { llm, tools, config }
+    }
+
+    /// Run the loop to completion, returning the final conversation (the
+    /// original messages plus every assistant/tool-result turn appended),
+    /// the response that ended it, and the token usage summed across steps.
+    pub async fn run(&self, request: CompletionRequest) -> Result<ToolLoopOutcome> {
+        let (tx, mut rx) = mpsc::channel(32);
+        let driver = self.clone_handles();
+        let handle = tokio::spawn(async move { driver.run_stream(request, tx).await });
+        while rx.recv().await.is_some() {}
+        handle.await.map_err(|e| AgentError::internal(e.to_string()))?
+    }
+
+    /// Same as [`ToolLoopDriver::run`], but sends a [`ToolLoopEvent`] for every
+    /// intermediate turn on `tx` as the loop progresses.
+    pub async fn run_stream(
+        &self,
+        mut request: CompletionRequest,
+        tx: mpsc::Sender<ToolLoopEvent>,
+    ) -> Result<ToolLoopOutcome> {
+        let start = Instant::now();
+        let mut step = 0usize;
+        let mut usage = TokenUsage::default();
+
+        loop {
+            step += 1;
+            if step > self.config.max_steps {
+                return Err(AgentError::MaxIterationsReached(self.config.max_steps));
+            }
+            if let Some(budget) = self.config.wall_time_budget {
+                if start.elapsed() > budget {
+                    return Err(AgentError::budget_exceeded(budget));
+                }
+            }
+
+            debug!(step, "tool loop: requesting next assistant turn");
+            let response = self.llm.complete(request.clone()).await?;
+            usage.accumulate(&response.usage);
+            let tool_uses = response.message.tool_uses();
+
+            let mut turn_blocks = Vec::new();
+            let text = response.text_content();
+            if !text.is_empty() {
+                turn_blocks.push(ContentBlock::text(text));
+            }
+            for tu in &tool_uses {
+                turn_blocks.push(ContentBlock::tool_use(ToolUse::new(&tu.id, &tu.name, tu.input.clone())));
+            }
+
+            let _ = tx
+                .send(ToolLoopEvent::AssistantTurn {
+                    step,
+                    blocks: turn_blocks.clone(),
+                })
+                .await;
+            request.messages.push(Message::new(Role::Assistant, turn_blocks));
+
+            if tool_uses.is_empty() {
+                let _ = tx.send(ToolLoopEvent::Done { steps: step }).await;
+                return Ok(ToolLoopOutcome {
+                    messages: request.messages,
+                    final_response: response,
+                    usage,
+                });
+            }
+
+            let calls: Vec<hehe_core::ToolCall> = tool_uses
+                .iter()
+                .map(|tu| hehe_core::ToolCall::new(&tu.name, tu.input.clone()))
+                .collect();
+
+            let ctx = Context::new();
+            // `execute_many` preserves input order, so zipping back against
+            // `tool_uses` is enough to recover each call's `tool_use_id`.
+            let outcomes = self.tools.execute_many(&ctx, calls, self.config.concurrency).await;
+
+            let mut result_blocks = Vec::with_capacity(outcomes.len());
+            for (tu, (_, result)) in tool_uses.iter().zip(outcomes) {
+                let block = match result {
+                    Ok(output) if !output.is_error => ContentBlock::tool_result(ToolResult::success(&tu.id, output.content)),
+                    Ok(output) => ContentBlock::tool_result(ToolResult::error(&tu.id, output.content)),
+                    Err(e) => ContentBlock::tool_result(ToolResult::error(&tu.id, e.to_string())),
+                };
+                result_blocks.push(block);
+            }
+
+            info!(step, tool_calls = result_blocks.len(), "tool loop: dispatched tool calls");
+            let _ = tx
+                .send(ToolLoopEvent::ToolResults {
+                    step,
+                    blocks: result_blocks.clone(),
+                })
+                .await;
+            request.messages.push(Message::tool(result_blocks));
+        }
+    }
+
+    fn clone_handles(&self) -> Self {
+        Self {
+            llm: Arc::clone(&self.llm),
+            tools: Arc::clone(&self.tools),
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use hehe_core::capability::Capabilities;
+    use hehe_core::stream::StreamChunk;
+    use hehe_llm::{BoxStream, CompletionResponse, LlmError, ModelInfo};
+    use hehe_tools::traits::{Tool, ToolOutput};
+    use hehe_tools::ToolRegistry;
+
+    struct MockLlm {
+        responses: std::sync::Mutex<Vec<CompletionResponse>>,
+    }
+
+    impl MockLlm {
+        fn new(responses: Vec<CompletionResponse>) -> Self {
+            Self {
+                responses: std::sync::Mutex::new(responses),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for MockLlm {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            let mut responses = self.responses.lock().unwrap();
+            if responses.is_empty() {
+                Ok(CompletionResponse::new("id", "mock", Message::assistant("done")))
+            } else {
+                Ok(responses.remove(0))
+            }
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+            Ok(Box::pin(stream::empty()))
+        }
+
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "mock"
+        }
+    }
+
+    struct EchoTool {
+        def: hehe_core::ToolDefinition,
+    }
+
+    impl EchoTool {
+        fn new() -> Self {
+            Self {
+                def: hehe_core::ToolDefinition::new("echo", "Echoes input"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn definition(&self) -> &hehe_core::ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(&self, _ctx: &Context, input: serde_json::Value) -> hehe_tools::Result<ToolOutput> {
+            Ok(ToolOutput::text(input.to_string()))
+        }
+    }
+
+    fn executor() -> Arc<ToolExecutor> {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(EchoTool::new())).unwrap();
+        Arc::new(ToolExecutor::new(Arc::new(registry)))
+    }
+
+    #[tokio::test]
+    async fn test_loop_stops_when_no_more_tool_uses() {
+        let llm = Arc::new(MockLlm::new(vec![CompletionResponse::new(
+            "r1",
+            "mock",
+            Message::assistant("Hello!"),
+        )
+        .with_usage(TokenUsage::new(10, 4))]));
+
+        let driver = ToolLoopDriver::new(llm, executor(), ToolLoopConfig::new());
+        let request = CompletionRequest::new("mock", vec![Message::user("hi")]);
+
+        let outcome = driver.run(request).await.unwrap();
+        assert_eq!(outcome.messages.last().unwrap().text_content(), "Hello!");
+        assert_eq!(outcome.final_response.id, "r1");
+        assert_eq!(outcome.usage.total(), 14);
+    }
+
+    #[tokio::test]
+    async fn test_loop_feeds_tool_results_back_and_continues() {
+        let tool_turn = Message::new(
+            Role::Assistant,
+            vec![ContentBlock::tool_use(ToolUse::new("call_1", "echo", serde_json::json!({"a": 1})))],
+        );
+
+        let llm = Arc::new(MockLlm::new(vec![
+            CompletionResponse::new("r1", "mock", tool_turn).with_usage(TokenUsage::new(10, 4)),
+            CompletionResponse::new("r2", "mock", Message::assistant("All done")).with_usage(TokenUsage::new(6, 2)),
+        ]));
+
+        let driver = ToolLoopDriver::new(llm, executor(), ToolLoopConfig::new());
+        let request = CompletionRequest::new("mock", vec![Message::user("hi")]);
+
+        let outcome = driver.run(request).await.unwrap();
+        assert!(outcome.messages.iter().any(|m| m.has_tool_result()));
+        assert_eq!(outcome.messages.last().unwrap().text_content(), "All done");
+        assert_eq!(outcome.final_response.id, "r2");
+        assert_eq!(outcome.usage.total(), 22);
+    }
+
+    #[tokio::test]
+    async fn test_loop_errored_tool_still_feeds_back_rather_than_aborting() {
+        let tool_turn = Message::new(
+            Role::Assistant,
+            vec![ContentBlock::tool_use(ToolUse::new("call_1", "missing_tool", serde_json::json!({})))],
+        );
+
+        let llm = Arc::new(MockLlm::new(vec![
+            CompletionResponse::new("r1", "mock", tool_turn),
+            CompletionResponse::new("r2", "mock", Message::assistant("Recovered")),
+        ]));
+
+        let driver = ToolLoopDriver::new(llm, executor(), ToolLoopConfig::new());
+        let request = CompletionRequest::new("mock", vec![Message::user("hi")]);
+
+        let outcome = driver.run(request).await.unwrap();
+        let tool_result_msg = outcome.messages.iter().find(|m| m.has_tool_result()).unwrap();
+        assert!(tool_result_msg.content.iter().any(|b| matches!(b, ContentBlock::ToolResult(r) if r.is_error)));
+        assert_eq!(outcome.messages.last().unwrap().text_content(), "Recovered");
+    }
+
+    #[tokio::test]
+    async fn test_loop_respects_max_steps() {
+        let tool_turn = Message::new(
+            Role::Assistant,
+            vec![ContentBlock::tool_use(ToolUse::new("call_1", "echo", serde_json::json!({})))],
+        );
+
+        let llm = Arc::new(MockLlm::new(vec![
+            CompletionResponse::new("r1", "mock", tool_turn.clone()),
+            CompletionResponse::new("r2", "mock", tool_turn.clone()),
+            CompletionResponse::new("r3", "mock", tool_turn),
+        ]));
+
+        let driver = ToolLoopDriver::new(llm, executor(), ToolLoopConfig::new().with_max_steps(2));
+        let request = CompletionRequest::new("mock", vec![Message::user("hi")]);
+
+        let result = driver.run(request).await;
+        assert!(matches!(result, Err(AgentError::MaxIterationsReached(2))));
+    }
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use hehe_agent::{Decision, ToolApprover};
+use hehe_core::message::ToolUse;
+use hehe_core::ToolDefinition;
+use rustyline::DefaultEditor;
+use std::sync::Mutex;
+
+/// Prompts on stdin/stdout before a `dangerous` tool call runs, so an
+/// interactive REPL user sees the tool name and proposed arguments and can
+/// refuse before, say, a shell command or file write executes. Declining
+/// always maps to [`Decision::Deny`] rather than [`Decision::AllowForSession`]
+/// — this is a plain y/N gate, with no "always allow" shortcut.
+pub struct RustylineApprover {
+    editor: Mutex<DefaultEditor>,
+}
+
+impl RustylineApprover {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { editor: Mutex::new(DefaultEditor::new()?) })
+    }
+}
+
+#[async_trait]
+impl ToolApprover for RustylineApprover {
+    async fn approve(&self, call: &ToolUse, def: &ToolDefinition) -> Decision {
+        println!(
+            "\n{} {} {}",
+            "!".red().bold(),
+            "Dangerous tool call:".red().bold(),
+            def.name.yellow()
+        );
+        println!(
+            "  {} {}",
+            "arguments:".dimmed(),
+            serde_json::to_string(&call.input).unwrap_or_else(|_| call.input.to_string())
+        );
+
+        let prompt = format!("{} ", "Allow this call? [y/N]".cyan().bold());
+        let answer = {
+            let mut editor = self.editor.lock().unwrap();
+            editor.readline(&prompt)
+        };
+
+        match answer {
+            Ok(line) if matches!(line.trim().to_lowercase().as_str(), "y" | "yes") => Decision::Allow,
+            _ => Decision::Deny,
+        }
+    }
+}
@@ -1,11 +1,24 @@
 use colored::Colorize;
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
+use std::sync::Arc;
 
 use super::create_agent;
+use crate::approval::RustylineApprover;
 
-pub async fn run(api_key: Option<String>, model: &str, system_prompt: &str) -> anyhow::Result<()> {
-    let agent = create_agent(api_key, model, system_prompt)?;
+pub async fn run(
+    api_key: Option<String>,
+    model: &str,
+    system_prompt: &str,
+    dangerously_skip_confirmations: bool,
+) -> anyhow::Result<()> {
+    let tool_approver = if dangerously_skip_confirmations {
+        None
+    } else {
+        Some(Arc::new(RustylineApprover::new()?) as Arc<dyn hehe_agent::ToolApprover>)
+    };
+
+    let agent = create_agent(api_key, model, system_prompt, tool_approver)?;
     let session = agent.create_session();
 
     println!("{}", "hehe AI Agent".green().bold());
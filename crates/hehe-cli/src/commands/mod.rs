@@ -1,8 +1,9 @@
 pub mod chat;
+pub mod output;
 pub mod run;
 pub mod serve;
 
-use hehe_agent::Agent;
+use hehe_agent::{Agent, ToolApprover};
 use hehe_llm::OpenAiProvider;
 use hehe_tools::create_default_registry;
 use std::sync::Arc;
@@ -11,6 +12,7 @@ pub fn create_agent(
     api_key: Option<String>,
     model: &str,
     system_prompt: &str,
+    tool_approver: Option<Arc<dyn ToolApprover>>,
 ) -> anyhow::Result<Agent> {
     let api_key = api_key.ok_or_else(|| anyhow::anyhow!(
         "API key required. Set OPENAI_API_KEY env var or use --api-key"
@@ -19,11 +21,17 @@ pub fn create_agent(
     let llm = Arc::new(OpenAiProvider::new(api_key));
     let registry = Arc::new(create_default_registry());
 
-    let agent = Agent::builder()
+    let mut builder = Agent::builder()
         .system_prompt(system_prompt)
         .model(model)
         .llm(llm)
-        .tool_registry(registry)
+        .tool_registry(registry);
+
+    if let Some(approver) = tool_approver {
+        builder = builder.tool_approver(approver);
+    }
+
+    let agent = builder
         .build()
         .map_err(|e| anyhow::anyhow!("Failed to create agent: {}", e))?;
 
@@ -0,0 +1,24 @@
+use colored::Colorize;
+
+/// Renders a turn's final text and failures for a human at a terminal.
+/// Kept separate from [`super::run::run`]'s logging so embedders can swap in
+/// their own presentation (e.g. plain text for a non-tty, JSON for a UI)
+/// without touching the structured `tracing` records the turn also emits.
+pub trait OutputFormatter: Send + Sync {
+    fn format_response(&self, text: &str) -> String;
+    fn format_error(&self, err: &anyhow::Error) -> String;
+}
+
+/// The CLI's default formatter: ANSI colors via `colored`, matching the
+/// look of [`super::chat::run`]'s interactive prompt.
+pub struct ColoredFormatter;
+
+impl OutputFormatter for ColoredFormatter {
+    fn format_response(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn format_error(&self, err: &anyhow::Error) -> String {
+        format!("{} {}", "Error:".red().bold(), err)
+    }
+}
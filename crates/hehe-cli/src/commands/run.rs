@@ -1,6 +1,7 @@
-use colored::Colorize;
+use tracing::{debug, error, info};
 
 use super::create_agent;
+use super::output::{ColoredFormatter, OutputFormatter};
 
 pub async fn run(
     api_key: Option<String>,
@@ -8,18 +9,40 @@ pub async fn run(
     system_prompt: &str,
     message: &str,
 ) -> anyhow::Result<()> {
-    let agent = create_agent(api_key, model, system_prompt)?;
+    run_with_formatter(api_key, model, system_prompt, message, &ColoredFormatter).await
+}
+
+/// Like [`run`], but lets the caller supply its own [`OutputFormatter`]
+/// instead of the CLI's colored default.
+pub async fn run_with_formatter(
+    api_key: Option<String>,
+    model: &str,
+    system_prompt: &str,
+    message: &str,
+    formatter: &dyn OutputFormatter,
+) -> anyhow::Result<()> {
+    let agent = create_agent(api_key, model, system_prompt, None)?;
     let session = agent.create_session();
 
-    match agent.chat(&session, message).await {
+    match agent.process(&session, message).await {
         Ok(response) => {
-            println!("{}", response);
+            for call in &response.tool_calls {
+                debug!(
+                    tool = %call.name,
+                    duration_ms = call.duration_ms,
+                    is_error = call.is_error,
+                    "resolved tool call"
+                );
+            }
+            info!(iterations = response.iterations, tool_calls = response.tool_call_count(), "turn completed");
+            println!("{}", formatter.format_response(&response.text));
+            Ok(())
         }
         Err(e) => {
-            eprintln!("{} {}", "Error:".red().bold(), e);
-            std::process::exit(1);
+            let err = anyhow::Error::from(e);
+            error!(error = %err, "turn failed");
+            eprintln!("{}", formatter.format_error(&err));
+            Err(err)
         }
     }
-
-    Ok(())
 }
@@ -10,7 +10,7 @@ pub async fn run(
     host: &str,
     port: u16,
 ) -> anyhow::Result<()> {
-    let agent = create_agent(api_key, model, system_prompt)?;
+    let agent = create_agent(api_key, model, system_prompt, None)?;
 
     let config = ServerConfig::new()
         .with_host(host)
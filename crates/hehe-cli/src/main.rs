@@ -1,5 +1,6 @@
 use clap::{Parser, Subcommand};
 
+mod approval;
 mod commands;
 
 #[derive(Parser)]
@@ -26,6 +27,9 @@ enum Commands {
         /// System prompt for the agent
         #[arg(short, long, default_value = "You are a helpful assistant.")]
         system: String,
+        /// Run dangerous tool calls without prompting for confirmation
+        #[arg(long = "dangerously-skip-confirmations", alias = "yes")]
+        dangerously_skip_confirmations: bool,
     },
     /// Run a single message and exit
     Run {
@@ -73,8 +77,8 @@ async fn main() -> anyhow::Result<()> {
     let api_key = cli.api_key.or_else(|| std::env::var("OPENAI_API_KEY").ok());
 
     match cli.command {
-        Commands::Chat { system } => {
-            commands::chat::run(api_key, &cli.model, &system).await?;
+        Commands::Chat { system, dangerously_skip_confirmations } => {
+            commands::chat::run(api_key, &cli.model, &system, dangerously_skip_confirmations).await?;
         }
         Commands::Run { message, system } => {
             commands::run::run(api_key, &cli.model, &system, &message).await?;
@@ -0,0 +1,10 @@
+pub mod loader;
+pub mod types;
+pub mod watcher;
+
+pub use types::{
+    Config, GeneralConfig, LlmConfig, LogLevel, McpServerConfig, ProviderConfig, RoutingConfig,
+    RoutingStrategy, SecurityConfig, SecurityMode, StorageBackendConfig, StorageConfig,
+    TelemetryConfig, ToolsConfig,
+};
+pub use watcher::ConfigWatcher;
@@ -2,7 +2,7 @@ use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub general: GeneralConfig,
@@ -31,7 +31,7 @@ impl Default for Config {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GeneralConfig {
     #[serde(default = "default_data_dir")]
     pub data_dir: Utf8PathBuf,
@@ -70,7 +70,7 @@ pub enum LogLevel {
     Error,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct LlmConfig {
     #[serde(default)]
     pub default_provider: Option<String>,
@@ -80,7 +80,7 @@ pub struct LlmConfig {
     pub routing: RoutingConfig,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProviderConfig {
     pub provider_type: String,
     #[serde(default)]
@@ -93,11 +93,31 @@ pub struct ProviderConfig {
     pub max_retries: Option<u32>,
     #[serde(default)]
     pub timeout_secs: Option<u64>,
+    /// How long to wait for the initial TCP/TLS handshake, separate from
+    /// `timeout_secs` which bounds the whole request. Unset leaves the HTTP
+    /// client's own default in place.
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// Forwarded to the provider's HTTP client as an outbound proxy, e.g.
+    /// `http://proxy.internal:8080`.
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// Extra headers sent with every request to this provider, e.g. an
+    /// `Azure`-style `api-key` header or a gateway's auth token.
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+    /// USD per 1K input (prompt) tokens, used by `RoutingStrategy::CostOptimized`
+    /// to estimate request cost.
+    #[serde(default)]
+    pub input_price_per_1k: Option<f64>,
+    /// USD per 1K output (completion) tokens, used by `RoutingStrategy::CostOptimized`.
+    #[serde(default)]
+    pub output_price_per_1k: Option<f64>,
     #[serde(default)]
     pub extra: HashMap<String, serde_json::Value>,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct RoutingConfig {
     #[serde(default)]
     pub strategy: RoutingStrategy,
@@ -116,7 +136,7 @@ pub enum RoutingStrategy {
     Fallback,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct StorageConfig {
     #[serde(default)]
     pub relational: StorageBackendConfig,
@@ -130,7 +150,7 @@ pub struct StorageConfig {
     pub search: StorageBackendConfig,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StorageBackendConfig {
     #[serde(default = "default_backend")]
     pub backend: String,
@@ -154,7 +174,7 @@ impl Default for StorageBackendConfig {
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ToolsConfig {
     #[serde(default = "default_true")]
     pub builtin_enabled: bool,
@@ -170,7 +190,7 @@ fn default_true() -> bool {
     true
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct McpServerConfig {
     pub name: String,
     pub command: String,
@@ -182,7 +202,7 @@ pub struct McpServerConfig {
     pub timeout_secs: Option<u64>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SecurityConfig {
     #[serde(default)]
     pub mode: SecurityMode,
@@ -214,7 +234,7 @@ pub enum SecurityMode {
     Autonomous,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct TelemetryConfig {
     #[serde(default)]
     pub enabled: bool,
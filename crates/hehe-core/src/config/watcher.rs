@@ -0,0 +1,230 @@
+use super::types::Config;
+use crate::error::{Error, Result};
+use crate::event::{Event, EventEmitter};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// How long [`ConfigWatcher`] waits after the last filesystem event on the
+/// watched file before re-reading it, so a save that touches the file more
+/// than once in quick succession (editors commonly write-then-rename)
+/// triggers a single reload instead of several.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches a config file on disk and keeps a [`tokio::sync::watch`] channel of
+/// the most recently loaded [`Config`] up to date, so other parts of the
+/// process can observe changes without polling [`Config::load_from_file`]
+/// themselves.
+///
+/// A file that fails to parse is reported through `emitter` as an
+/// [`Event::error`] and otherwise ignored: subscribers keep seeing the last
+/// config that loaded successfully. A file that parses cleanly is reported as
+/// an [`Event::config_reloaded`] naming the top-level sections that changed.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+impl ConfigWatcher {
+    /// Loads `path` once to seed the channel, then spawns a background watch
+    /// with the default debounce window. Fails only if that initial load or
+    /// the OS watch setup fails; once running, later parse failures are
+    /// reported through `emitter` rather than propagated.
+    pub fn start(
+        path: impl Into<PathBuf>,
+        emitter: Arc<dyn EventEmitter>,
+    ) -> Result<(Self, watch::Receiver<Arc<Config>>)> {
+        Self::start_with_debounce(path, emitter, DEFAULT_DEBOUNCE)
+    }
+
+    pub fn start_with_debounce(
+        path: impl Into<PathBuf>,
+        emitter: Arc<dyn EventEmitter>,
+        debounce: Duration,
+    ) -> Result<(Self, watch::Receiver<Arc<Config>>)> {
+        let path = path.into();
+        let initial = Config::load_from_file(&path)?.merge_env();
+        let (tx, rx) = watch::channel(Arc::new(initial));
+
+        let (notify_tx, notify_rx) = std::sync::mpsc::channel();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(notify_tx)
+            .map_err(|e| Error::Config(format!("failed to start config watcher: {e}")))?;
+        watcher
+            .watch(&path, RecursiveMode::NonRecursive)
+            .map_err(|e| Error::Config(format!("failed to watch {}: {e}", path.display())))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let runtime = tokio::runtime::Handle::current();
+
+        std::thread::spawn(move || {
+            let mut pending = false;
+
+            loop {
+                if thread_stop.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                match notify_rx.recv_timeout(debounce) {
+                    Ok(Ok(event)) => {
+                        if event.kind.is_modify() || event.kind.is_create() {
+                            pending = true;
+                        }
+                    }
+                    Ok(Err(_)) => continue,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                        if pending {
+                            pending = false;
+                            reload_and_publish(&path, &tx, &emitter, &runtime);
+                        }
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        });
+
+        Ok((Self { _watcher: watcher, stop }, rx))
+    }
+}
+
+fn reload_and_publish(
+    path: &std::path::Path,
+    tx: &watch::Sender<Arc<Config>>,
+    emitter: &Arc<dyn EventEmitter>,
+    runtime: &tokio::runtime::Handle,
+) {
+    let path_str = path.to_string_lossy().into_owned();
+
+    match Config::load_from_file(path).map(Config::merge_env) {
+        Ok(new_config) => {
+            let changed = diff_sections(&tx.borrow(), &new_config);
+            let _ = tx.send(Arc::new(new_config));
+
+            if !changed.is_empty() {
+                let emitter = emitter.clone();
+                runtime.spawn(async move {
+                    emitter.emit(Event::config_reloaded(path_str, changed)).await;
+                });
+            }
+        }
+        Err(e) => {
+            let emitter = emitter.clone();
+            runtime.spawn(async move {
+                emitter
+                    .emit(Event::error(
+                        "config_reload_failed",
+                        format!("rejected invalid config reload for {path_str}: {e}"),
+                    ))
+                    .await;
+            });
+        }
+    }
+}
+
+fn diff_sections(old: &Config, new: &Config) -> Vec<String> {
+    let mut changed = Vec::new();
+
+    macro_rules! check {
+        ($field:ident) => {
+            if old.$field != new.$field {
+                changed.push(stringify!($field).to_string());
+            }
+        };
+    }
+
+    check!(general);
+    check!(llm);
+    check!(storage);
+    check!(tools);
+    check!(security);
+    check!(telemetry);
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventKind;
+    use std::sync::Mutex;
+
+    struct RecordingEmitter {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl RecordingEmitter {
+        fn new() -> Self {
+            Self { events: Mutex::new(Vec::new()) }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl EventEmitter for RecordingEmitter {
+        async fn emit(&self, event: Event) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    fn temp_config_path() -> PathBuf {
+        std::env::temp_dir().join(format!("hehe-core-config-watch-test-{}.toml", crate::Id::new()))
+    }
+
+    #[tokio::test]
+    async fn test_watch_reloads_on_change_and_reports_changed_sections() {
+        let path = temp_config_path();
+        tokio::fs::write(&path, "[general]\nlog_level = \"debug\"\n").await.unwrap();
+
+        let emitter = Arc::new(RecordingEmitter::new());
+        let (_watcher, mut rx) = ConfigWatcher::start_with_debounce(
+            &path,
+            emitter.clone(),
+            Duration::from_millis(20),
+        )
+        .unwrap();
+        assert_eq!(rx.borrow().general.log_level, super::super::types::LogLevel::Debug);
+
+        tokio::fs::write(&path, "[general]\nlog_level = \"warn\"\n").await.unwrap();
+        rx.changed().await.unwrap();
+
+        assert_eq!(rx.borrow().general.log_level, super::super::types::LogLevel::Warn);
+
+        let events = emitter.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::ConfigReloaded);
+
+        tokio::fs::remove_file(&path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_watch_keeps_last_good_config_on_invalid_reload() {
+        let path = temp_config_path();
+        tokio::fs::write(&path, "[general]\nlog_level = \"debug\"\n").await.unwrap();
+
+        let emitter = Arc::new(RecordingEmitter::new());
+        let (_watcher, rx) = ConfigWatcher::start_with_debounce(
+            &path,
+            emitter.clone(),
+            Duration::from_millis(20),
+        )
+        .unwrap();
+
+        tokio::fs::write(&path, "not valid toml [[[").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(rx.borrow().general.log_level, super::super::types::LogLevel::Debug);
+        let events = emitter.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::AgentError);
+
+        tokio::fs::remove_file(&path).await.ok();
+    }
+}
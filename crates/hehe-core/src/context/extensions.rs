@@ -0,0 +1,75 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A type-erased, `TypeId`-keyed map for attaching arbitrary per-request data
+/// (auth identity, cost ledger, feature flags, tenant config, ...) to a
+/// [`Context`](super::Context), modeled after `http::Extensions`.
+///
+/// Cloning an `Extensions` is cheap (it just bumps the `Arc`'s refcount); the
+/// backing map is only actually copied on the next [`Self::insert`], so
+/// sibling contexts created by `Context::child` keep sharing the same data
+/// until one of them writes.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: Arc<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        let mut map = (*self.map).clone();
+        map.insert(TypeId::of::<T>(), Arc::new(value));
+        self.map = Arc::new(map);
+    }
+
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get_roundtrip() {
+        let mut ext = Extensions::new();
+        ext.insert(42u32);
+        ext.insert("tenant-a".to_string());
+
+        assert_eq!(ext.get::<u32>(), Some(&42));
+        assert_eq!(ext.get::<String>(), Some(&"tenant-a".to_string()));
+        assert_eq!(ext.get::<bool>(), None);
+    }
+
+    #[test]
+    fn test_clone_shares_until_write() {
+        let mut a = Extensions::new();
+        a.insert(1u32);
+
+        let mut b = a.clone();
+        assert_eq!(b.get::<u32>(), Some(&1));
+
+        b.insert(2u32);
+        assert_eq!(a.get::<u32>(), Some(&1));
+        assert_eq!(b.get::<u32>(), Some(&2));
+    }
+}
@@ -1,6 +1,9 @@
+mod extensions;
+
+pub use extensions::Extensions;
+
 use crate::types::{AgentId, RequestId, SessionId, Timestamp};
 use std::collections::HashMap;
-use std::sync::Arc;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
@@ -16,7 +19,7 @@ pub struct Context {
     pub started_at: Timestamp,
     pub deadline: Option<Timestamp>,
     cancellation: CancellationToken,
-    extensions: Arc<HashMap<String, String>>,
+    extensions: Extensions,
 }
 
 impl Context {
@@ -30,7 +33,7 @@ impl Context {
             started_at: Timestamp::now(),
             deadline: None,
             cancellation: CancellationToken::new(),
-            extensions: Arc::new(HashMap::new()),
+            extensions: Extensions::new(),
         }
     }
 
@@ -65,6 +68,34 @@ impl Context {
         self
     }
 
+    /// Attaches a typed value to this context, replacing any previous value
+    /// of the same type. Cheap to call on an otherwise-shared `Extensions`
+    /// map: only this context's copy is affected (copy-on-write), so sibling
+    /// contexts produced by `child()` before this call are untouched.
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Reads a typed value previously attached with [`Self::with_extension`].
+    pub fn get_extension_typed<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
+
+    /// Back-compat shim over the old string-only extensions API: stores the
+    /// key/value pair in a `HashMap<String, String>` kept as a single typed
+    /// entry in the new extension map.
+    pub fn with_string_extension(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let mut strings = self
+            .extensions
+            .get::<HashMap<String, String>>()
+            .cloned()
+            .unwrap_or_default();
+        strings.insert(key.into(), value.into());
+        self.extensions.insert(strings);
+        self
+    }
+
     pub fn child(&self) -> Self {
         Self {
             request_id: RequestId::new(),
@@ -75,7 +106,7 @@ impl Context {
             started_at: Timestamp::now(),
             deadline: self.deadline,
             cancellation: self.cancellation.child_token(),
-            extensions: Arc::clone(&self.extensions),
+            extensions: self.extensions.clone(),
         }
     }
 
@@ -120,7 +151,7 @@ impl Context {
     }
 
     pub fn get_extension(&self, key: &str) -> Option<&String> {
-        self.extensions.get(key)
+        self.extensions.get::<HashMap<String, String>>()?.get(key)
     }
 }
 
@@ -143,3 +174,47 @@ impl std::fmt::Debug for Context {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct TenantConfig {
+        name: String,
+    }
+
+    #[test]
+    fn test_typed_extension_roundtrip() {
+        let ctx = Context::new().with_extension(TenantConfig {
+            name: "acme".to_string(),
+        });
+
+        assert_eq!(
+            ctx.get_extension_typed::<TenantConfig>(),
+            Some(&TenantConfig {
+                name: "acme".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_string_extension_shim() {
+        let ctx = Context::new().with_string_extension("trace-source", "sdk");
+
+        assert_eq!(ctx.get_extension("trace-source"), Some(&"sdk".to_string()));
+        assert_eq!(ctx.get_extension("missing"), None);
+    }
+
+    #[test]
+    fn test_child_keeps_sharing_extensions_until_written() {
+        let parent = Context::new().with_extension(42u32);
+        let child = parent.child();
+
+        assert_eq!(child.get_extension_typed::<u32>(), Some(&42));
+
+        let child = child.with_extension(7u32);
+        assert_eq!(child.get_extension_typed::<u32>(), Some(&7));
+        assert_eq!(parent.get_extension_typed::<u32>(), Some(&42));
+    }
+}
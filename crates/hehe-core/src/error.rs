@@ -21,6 +21,7 @@ pub mod codes {
     pub const STORAGE_CONNECTION: &str = "E7001";
     pub const STORAGE_QUERY: &str = "E7002";
     pub const STORAGE_WRITE: &str = "E7003";
+    pub const CHECKSUM_MISMATCH: &str = "E7004";
     pub const INTERNAL: &str = "E9001";
     pub const NOT_IMPLEMENTED: &str = "E9002";
 }
@@ -72,6 +73,9 @@ pub enum Error {
     #[error("Storage error: {backend} - {message}")]
     Storage { backend: String, message: String },
 
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
     #[error("Not implemented: {0}")]
     NotImplemented(String),
 
@@ -100,6 +104,7 @@ impl Error {
             Error::Llm { .. } => codes::LLM_REQUEST_FAILED,
             Error::Tool { .. } => codes::TOOL_EXECUTION_FAILED,
             Error::Storage { .. } => codes::STORAGE_CONNECTION,
+            Error::ChecksumMismatch { .. } => codes::CHECKSUM_MISMATCH,
             Error::NotImplemented(_) => codes::NOT_IMPLEMENTED,
             Error::Internal(_) => codes::INTERNAL,
             Error::Other(_) => codes::INTERNAL,
@@ -147,6 +152,13 @@ impl Error {
             message: message.into(),
         }
     }
+
+    pub fn checksum_mismatch(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self::ChecksumMismatch {
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -28,9 +28,20 @@ pub enum EventKind {
     ConfigReloaded,
     PluginLoaded,
     PluginUnloaded,
+    FileChanged,
     Custom(String),
 }
 
+/// What happened to a watched path. Mirrors the coarse categories OS file-watching
+/// APIs agree on; watchers that can't tell create from modify should use `Modified`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
     pub input_tokens: u32,
@@ -54,6 +65,46 @@ impl TokenUsage {
     pub fn total(&self) -> u32 {
         self.input_tokens + self.output_tokens
     }
+
+    /// Folds `other` into `self`, e.g. to tally usage across the steps of a
+    /// multi-turn tool-calling loop.
+    pub fn accumulate(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens = match (self.cache_read_tokens, other.cache_read_tokens) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+        self.cache_write_tokens = match (self.cache_write_tokens, other.cache_write_tokens) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod usage_tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulate_sums_tokens_across_steps() {
+        let mut total = TokenUsage::new(10, 5);
+        total.accumulate(&TokenUsage::new(3, 7));
+
+        assert_eq!(total.input_tokens, 13);
+        assert_eq!(total.output_tokens, 12);
+        assert_eq!(total.cache_read_tokens, None);
+    }
+
+    #[test]
+    fn test_accumulate_sums_cache_tokens_once_either_side_reports_them() {
+        let mut total = TokenUsage::new(1, 1);
+        let mut other = TokenUsage::new(1, 1);
+        other.cache_read_tokens = Some(4);
+
+        total.accumulate(&other);
+        assert_eq!(total.cache_read_tokens, Some(4));
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -85,6 +136,14 @@ pub enum EventPayload {
         code: String,
         message: String,
     },
+    FileChange {
+        path: String,
+        kind: FileChangeKind,
+    },
+    ConfigReload {
+        path: String,
+        changed_sections: Vec<String>,
+    },
     Custom(serde_json::Value),
 }
 
@@ -173,6 +232,20 @@ impl Event {
             message: message.into(),
         })
     }
+
+    pub fn file_changed(path: impl Into<String>, kind: FileChangeKind) -> Self {
+        Self::new(EventKind::FileChanged).with_payload(EventPayload::FileChange {
+            path: path.into(),
+            kind,
+        })
+    }
+
+    pub fn config_reloaded(path: impl Into<String>, changed_sections: Vec<String>) -> Self {
+        Self::new(EventKind::ConfigReloaded).with_payload(EventPayload::ConfigReload {
+            path: path.into(),
+            changed_sections,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -182,6 +255,177 @@ pub trait EventEmitter: Send + Sync {
 
 #[async_trait::async_trait]
 pub trait EventSubscriber: Send + Sync {
+    /// Event kinds this subscriber wants delivered. An empty list means "all kinds".
     fn event_kinds(&self) -> Vec<EventKind>;
-    async fn on_event(&self, event: &Event);
+
+    async fn on_event(&self, event: &Event) -> crate::error::Result<()>;
+}
+
+/// An in-process [`EventEmitter`] that fans each emitted event out to every
+/// interested [`EventSubscriber`], retrying failed deliveries with exponential
+/// backoff before giving up on that subscriber for that event.
+pub struct EventBus {
+    subscribers: std::sync::RwLock<Vec<std::sync::Arc<dyn EventSubscriber>>>,
+    max_retries: usize,
+    base_backoff: std::time::Duration,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            subscribers: std::sync::RwLock::new(Vec::new()),
+            max_retries: 3,
+            base_backoff: std::time::Duration::from_millis(50),
+        }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_base_backoff(mut self, backoff: std::time::Duration) -> Self {
+        self.base_backoff = backoff;
+        self
+    }
+
+    pub fn subscribe(&self, subscriber: std::sync::Arc<dyn EventSubscriber>) {
+        self.subscribers.write().unwrap().push(subscriber);
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().unwrap().len()
+    }
+
+    fn interested(subscriber: &dyn EventSubscriber, kind: &EventKind) -> bool {
+        let kinds = subscriber.event_kinds();
+        kinds.is_empty() || kinds.contains(kind)
+    }
+
+    async fn deliver(&self, subscriber: &dyn EventSubscriber, event: &Event) {
+        let mut attempt = 0;
+        loop {
+            match subscriber.on_event(event).await {
+                Ok(()) => return,
+                Err(_) if attempt < self.max_retries => {
+                    attempt += 1;
+                    let backoff = self.base_backoff * 2u32.saturating_pow(attempt as u32 - 1);
+                    tokio::time::sleep(backoff).await;
+                }
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl EventEmitter for EventBus {
+    async fn emit(&self, event: Event) {
+        let subscribers = self.subscribers.read().unwrap().clone();
+        for subscriber in &subscribers {
+            if Self::interested(subscriber.as_ref(), &event.kind) {
+                self.deliver(subscriber.as_ref(), &event).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod bus_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingSubscriber {
+        kinds: Vec<EventKind>,
+        seen: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl EventSubscriber for CountingSubscriber {
+        fn event_kinds(&self) -> Vec<EventKind> {
+            self.kinds.clone()
+        }
+
+        async fn on_event(&self, _event: &Event) -> crate::error::Result<()> {
+            self.seen.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    struct FlakySubscriber {
+        failures_left: std::sync::atomic::AtomicUsize,
+        succeeded: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl EventSubscriber for FlakySubscriber {
+        fn event_kinds(&self) -> Vec<EventKind> {
+            vec![]
+        }
+
+        async fn on_event(&self, _event: &Event) -> crate::error::Result<()> {
+            if self.failures_left.fetch_sub(1, Ordering::SeqCst) > 0 {
+                return Err(crate::error::Error::Internal("flaky".into()));
+            }
+            self.succeeded.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_filters_by_kind() {
+        let bus = EventBus::new();
+        let interested = Arc::new(CountingSubscriber {
+            kinds: vec![EventKind::AgentStarted],
+            seen: AtomicUsize::new(0),
+        });
+        let uninterested = Arc::new(CountingSubscriber {
+            kinds: vec![EventKind::SessionEnded],
+            seen: AtomicUsize::new(0),
+        });
+        bus.subscribe(interested.clone());
+        bus.subscribe(uninterested.clone());
+
+        bus.emit(Event::new(EventKind::AgentStarted)).await;
+
+        assert_eq!(interested.seen.load(Ordering::SeqCst), 1);
+        assert_eq!(uninterested.seen.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_bus_retries_failed_delivery() {
+        let bus = EventBus::new().with_base_backoff(std::time::Duration::from_millis(1));
+        let flaky = Arc::new(FlakySubscriber {
+            failures_left: std::sync::atomic::AtomicUsize::new(2),
+            succeeded: AtomicUsize::new(0),
+        });
+        bus.subscribe(flaky.clone());
+
+        bus.emit(Event::new(EventKind::AgentStarted)).await;
+
+        assert_eq!(flaky.succeeded.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bus_gives_up_after_max_retries() {
+        let bus = EventBus::new()
+            .with_max_retries(1)
+            .with_base_backoff(std::time::Duration::from_millis(1));
+        let flaky = Arc::new(FlakySubscriber {
+            failures_left: std::sync::atomic::AtomicUsize::new(10),
+            succeeded: AtomicUsize::new(0),
+        });
+        bus.subscribe(flaky.clone());
+
+        bus.emit(Event::new(EventKind::AgentStarted)).await;
+
+        assert_eq!(flaky.succeeded.load(Ordering::SeqCst), 0);
+    }
 }
@@ -12,24 +12,29 @@ pub mod types;
 pub mod utils;
 pub mod version;
 
-pub use config::Config;
+pub use config::{Config, ConfigWatcher};
 pub use context::Context;
 pub use error::{Error, Result};
 pub use message::{ContentBlock, Message, MessageBuilder, Role};
-pub use tool::{ToolCall, ToolCallStatus, ToolDefinition, ToolParameter};
+pub use tool::{ToolCall, ToolCallStatus, ToolDefinition, ToolParameter, ValidationError};
 pub use types::{AgentId, Id, MessageId, Metadata, SessionId, Timestamp, ToolCallId};
 pub use version::VersionInfo;
 
 pub mod prelude {
     pub use crate::capability::{Capabilities, Capability, CapabilityProvider};
-    pub use crate::config::Config;
+    pub use crate::config::{Config, ConfigWatcher};
     pub use crate::context::Context;
     pub use crate::error::{Error, Result, ResultExt};
-    pub use crate::event::{Event, EventEmitter, EventKind, EventPayload, EventSubscriber};
+    pub use crate::event::{
+        Event, EventBus, EventEmitter, EventKind, EventPayload, EventSubscriber, FileChangeKind,
+    };
     pub use crate::message::{ContentBlock, Message, MessageBuilder, Role};
-    pub use crate::resource::{Resource, ResourceRef, ResourceResolver, ResourceStore};
-    pub use crate::stream::{StopReason, StreamAggregator, StreamChunk};
-    pub use crate::tool::{ToolCall, ToolCallStatus, ToolDefinition, ToolParameter};
+    pub use crate::resource::{
+        ChecksumAlgo, ChunkedStore, Resource, ResourceRef, ResourceResolver, ResourceStore,
+        ResourceStream, VerifiedResolver, VerifiedStore,
+    };
+    pub use crate::stream::{SseDecoder, StopReason, StreamAggregator, StreamChunk, StreamToolCall};
+    pub use crate::tool::{ToolCall, ToolCallStatus, ToolDefinition, ToolParameter, ValidationError};
     pub use crate::traits::{Identifiable, Lifecycle, Named, Validatable};
     pub use crate::types::{AgentId, Id, MessageId, Metadata, SessionId, Timestamp, ToolCallId};
 }
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+
+/// A content-hashing algorithm, encoded in checksums as a canonical
+/// `<algo>:<hex digest>` string (e.g. `sha256:9f86d0...`) so a single opaque
+/// string is enough to know how to re-verify it later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumAlgo {
+    Sha256,
+    Blake3,
+}
+
+impl Default for ChecksumAlgo {
+    fn default() -> Self {
+        Self::Sha256
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push_str(&format!("{b:02x}"));
+    }
+    out
+}
+
+impl ChecksumAlgo {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        }
+    }
+
+    /// Digest `data`, returning the canonical `algo:hex` checksum string.
+    pub fn digest(&self, data: &[u8]) -> String {
+        let hex = match self {
+            Self::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                to_hex(&hasher.finalize())
+            }
+            Self::Blake3 => blake3::hash(data).to_hex().to_string(),
+        };
+        format!("{}:{hex}", self.name())
+    }
+
+    /// Split a canonical `algo:hex` checksum string into its algorithm and hex digest.
+    pub fn parse(checksum: &str) -> Result<(Self, &str)> {
+        let (algo, hex) = checksum
+            .split_once(':')
+            .ok_or_else(|| Error::invalid_input("checksum", format!("'{checksum}' is not in 'algo:hex' form")))?;
+
+        let algo = match algo {
+            "sha256" => Self::Sha256,
+            "blake3" => Self::Blake3,
+            other => {
+                return Err(Error::invalid_input(
+                    "checksum",
+                    format!("unknown checksum algorithm '{other}'"),
+                ))
+            }
+        };
+        Ok((algo, hex))
+    }
+
+    /// Verify that `data` matches a canonical `algo:hex` checksum string,
+    /// returning [`Error::ChecksumMismatch`] on a mismatch.
+    pub fn verify(checksum: &str, data: &[u8]) -> Result<()> {
+        let (algo, _) = Self::parse(checksum)?;
+        let actual = algo.digest(data);
+        if actual == checksum {
+            Ok(())
+        } else {
+            Err(Error::checksum_mismatch(checksum, actual))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_round_trips_through_verify() {
+        let digest = ChecksumAlgo::Sha256.digest(b"hello world");
+        assert!(digest.starts_with("sha256:"));
+        assert!(ChecksumAlgo::verify(&digest, b"hello world").is_ok());
+    }
+
+    #[test]
+    fn test_verify_detects_corruption() {
+        let digest = ChecksumAlgo::Blake3.digest(b"hello world");
+        let err = ChecksumAlgo::verify(&digest, b"goodbye world").unwrap_err();
+        assert!(matches!(err, Error::ChecksumMismatch { .. }));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_checksum() {
+        assert!(ChecksumAlgo::parse("not-a-checksum").is_err());
+        assert!(ChecksumAlgo::parse("md5:deadbeef").is_err());
+    }
+}
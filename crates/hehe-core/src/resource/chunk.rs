@@ -0,0 +1,305 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use std::sync::{Arc, OnceLock};
+
+use crate::error::{Error, Result};
+
+use super::{ChecksumAlgo, ResourceMeta, ResourceRef, ResourceStore};
+
+const MIN_CHUNK_SIZE: usize = 2 * 1024;
+const NORMAL_CHUNK_SIZE: usize = 8 * 1024;
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Mask bits tuned so an unbiased byte stream cuts every ~8 KiB on average.
+/// `MASK_SMALL` has one extra bit (stricter, less likely to match) to discourage
+/// cutting before `NORMAL_CHUNK_SIZE`; `MASK_LARGE` has one fewer bit (looser, more
+/// likely to match) to encourage cutting before `MAX_CHUNK_SIZE`.
+const NORMALIZED_BITS: u32 = 13; // log2(8 KiB)
+const MASK_SMALL: u64 = (1u64 << (NORMALIZED_BITS + 1)) - 1;
+const MASK_LARGE: u64 = (1u64 << (NORMALIZED_BITS - 1)) - 1;
+
+/// FastCDC's 256-entry "gear" table: a fixed set of 64-bit values, one per byte
+/// value, used to roll a fingerprint across the input. Generated once from a fixed
+/// seed (not OS randomness) so cut points are reproducible across processes and
+/// machines — storing the same bytes twice must always dedupe to the same chunks.
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        std::array::from_fn(|_| {
+            state = state.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        })
+    })
+}
+
+/// Find FastCDC content-defined cut points in `data`, returning `(start, end)`
+/// byte ranges. Chunks are normally-distributed around [`NORMAL_CHUNK_SIZE`],
+/// never smaller than [`MIN_CHUNK_SIZE`] (except a final short tail) and never
+/// larger than [`MAX_CHUNK_SIZE`].
+fn cut_points(data: &[u8]) -> Vec<(usize, usize)> {
+    let gear = gear_table();
+    let len = data.len();
+    let mut points = Vec::new();
+    let mut start = 0usize;
+
+    while start < len {
+        let remaining = len - start;
+        if remaining <= MIN_CHUNK_SIZE {
+            points.push((start, len));
+            break;
+        }
+
+        let max_len = remaining.min(MAX_CHUNK_SIZE);
+        let normal_len = remaining.min(NORMAL_CHUNK_SIZE);
+
+        let mut fp: u64 = 0;
+        let mut i = MIN_CHUNK_SIZE;
+        let mut cut = max_len;
+
+        while i < max_len {
+            fp = (fp << 1).wrapping_add(gear[data[start + i] as usize]);
+            let mask = if i < normal_len { MASK_SMALL } else { MASK_LARGE };
+            if fp & mask == 0 {
+                cut = i;
+                break;
+            }
+            i += 1;
+        }
+
+        points.push((start, start + cut));
+        start += cut;
+    }
+
+    points
+}
+
+/// Wraps a [`ResourceStore`], splitting large blobs into content-defined chunks
+/// before storing them so that re-storing a near-identical blob only writes the
+/// bytes that actually changed. The returned content address is a manifest
+/// ([`ResourceRef::Manifest`]) listing the ordered chunk hashes and total size;
+/// [`Self::get_chunked`] fetches each chunk in order and reassembles the original
+/// bytes. Chunk and manifest hashes use the same [`ChecksumAlgo`] as
+/// [`super::VerifiedStore`], so content addresses are consistent across both.
+pub struct ChunkedStore {
+    inner: Arc<dyn ResourceStore>,
+    algo: ChecksumAlgo,
+}
+
+impl ChunkedStore {
+    pub fn new(inner: Arc<dyn ResourceStore>) -> Self {
+        Self {
+            inner,
+            algo: ChecksumAlgo::default(),
+        }
+    }
+
+    pub fn with_algo(mut self, algo: ChecksumAlgo) -> Self {
+        self.algo = algo;
+        self
+    }
+
+    pub async fn store_chunked(&self, data: Bytes, meta: ResourceMeta) -> Result<String> {
+        let mut chunks = Vec::new();
+        for (start, end) in cut_points(&data) {
+            let chunk = data.slice(start..end);
+            let hash = self.algo.digest(&chunk);
+            if !self.inner.exists(&hash).await? {
+                self.inner
+                    .store(chunk, ResourceMeta::new().with_checksum(hash.clone()))
+                    .await?;
+            }
+            chunks.push(hash);
+        }
+
+        let manifest = ResourceRef::Manifest {
+            chunks,
+            size: data.len() as u64,
+        };
+        let manifest_bytes = Bytes::from(serde_json::to_vec(&manifest)?);
+        let manifest_hash = self.algo.digest(&manifest_bytes);
+        self.inner
+            .store(manifest_bytes, meta.with_checksum(manifest_hash.clone()))
+            .await?;
+        Ok(manifest_hash)
+    }
+
+    pub async fn get_chunked(&self, manifest_address: &str) -> Result<Option<Bytes>> {
+        let Some(manifest_bytes) = self.inner.get(manifest_address).await? else {
+            return Ok(None);
+        };
+
+        let manifest: ResourceRef = serde_json::from_slice(&manifest_bytes)?;
+        let ResourceRef::Manifest { chunks, size } = manifest else {
+            return Err(Error::Storage {
+                backend: "chunked_store".into(),
+                message: format!("{manifest_address} is not a chunk manifest"),
+            });
+        };
+
+        let mut buf = Vec::with_capacity(size as usize);
+        for hash in &chunks {
+            let chunk = self.inner.get(hash).await?.ok_or_else(|| Error::Storage {
+                backend: "chunked_store".into(),
+                message: format!("missing chunk {hash} referenced by manifest {manifest_address}"),
+            })?;
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(Some(Bytes::from(buf)))
+    }
+
+    /// Like [`Self::get_chunked`], but fetches each chunk lazily as the
+    /// returned stream is polled instead of buffering the whole blob up front.
+    pub async fn get_chunked_stream(&self, manifest_address: &str) -> Result<Option<super::ResourceStream>> {
+        let Some(manifest_bytes) = self.inner.get(manifest_address).await? else {
+            return Ok(None);
+        };
+
+        let manifest: ResourceRef = serde_json::from_slice(&manifest_bytes)?;
+        let ResourceRef::Manifest { chunks, .. } = manifest else {
+            return Err(Error::Storage {
+                backend: "chunked_store".into(),
+                message: format!("{manifest_address} is not a chunk manifest"),
+            });
+        };
+
+        let inner = Arc::clone(&self.inner);
+        let manifest_address = manifest_address.to_string();
+        let stream = futures::stream::iter(chunks).then(move |hash| {
+            let inner = Arc::clone(&inner);
+            let manifest_address = manifest_address.clone();
+            async move {
+                inner.get(&hash).await?.ok_or_else(|| Error::Storage {
+                    backend: "chunked_store".into(),
+                    message: format!("missing chunk {hash} referenced by manifest {manifest_address}"),
+                })
+            }
+        });
+        Ok(Some(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+
+    #[derive(Default)]
+    struct InMemoryStore {
+        blobs: RwLock<HashMap<String, Bytes>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ResourceStore for InMemoryStore {
+        async fn store(&self, data: Bytes, meta: ResourceMeta) -> Result<String> {
+            let hash = meta.checksum.unwrap_or_else(|| ChecksumAlgo::default().digest(&data));
+            self.blobs.write().await.insert(hash.clone(), data);
+            Ok(hash)
+        }
+
+        async fn get(&self, content_address: &str) -> Result<Option<Bytes>> {
+            Ok(self.blobs.read().await.get(content_address).cloned())
+        }
+
+        async fn exists(&self, content_address: &str) -> Result<bool> {
+            Ok(self.blobs.read().await.contains_key(content_address))
+        }
+
+        async fn delete(&self, content_address: &str) -> Result<bool> {
+            Ok(self.blobs.write().await.remove(content_address).is_some())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_small_blob() {
+        let store = ChunkedStore::new(Arc::new(InMemoryStore::default()));
+        let data = Bytes::from_static(b"hello world, this is a small resource");
+
+        let address = store.store_chunked(data.clone(), ResourceMeta::new()).await.unwrap();
+        let restored = store.get_chunked(&address).await.unwrap().unwrap();
+
+        assert_eq!(restored, data);
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_large_blob_has_multiple_chunks() {
+        let inner = Arc::new(InMemoryStore::default());
+        let store = ChunkedStore::new(inner.clone());
+
+        let mut data = Vec::with_capacity(300 * 1024);
+        for i in 0..300 * 1024 {
+            data.push((i % 251) as u8);
+        }
+        let data = Bytes::from(data);
+
+        let address = store.store_chunked(data.clone(), ResourceMeta::new()).await.unwrap();
+        let restored = store.get_chunked(&address).await.unwrap().unwrap();
+
+        assert_eq!(restored, data);
+        // The manifest itself plus at least one content chunk should be present.
+        assert!(inner.blobs.read().await.len() > 1);
+    }
+
+    #[tokio::test]
+    async fn test_restoring_identical_blob_skips_known_chunks() {
+        let inner = Arc::new(InMemoryStore::default());
+        let store = ChunkedStore::new(inner.clone());
+
+        let data = Bytes::from(vec![42u8; 100 * 1024]);
+        store.store_chunked(data.clone(), ResourceMeta::new()).await.unwrap();
+        let stored_after_first = inner.blobs.read().await.len();
+
+        // Storing the same bytes again must not add any new chunks, only (at most)
+        // overwrite the manifest entry for the unchanged content.
+        store.store_chunked(data, ResourceMeta::new()).await.unwrap();
+        let stored_after_second = inner.blobs.read().await.len();
+
+        assert_eq!(stored_after_first, stored_after_second);
+    }
+
+    #[tokio::test]
+    async fn test_get_chunked_stream_reassembles_same_bytes_as_get_chunked() {
+        let store = ChunkedStore::new(Arc::new(InMemoryStore::default()));
+
+        let mut data = Vec::with_capacity(200 * 1024);
+        for i in 0..200 * 1024 {
+            data.push((i % 199) as u8);
+        }
+        let data = Bytes::from(data);
+
+        let address = store.store_chunked(data.clone(), ResourceMeta::new()).await.unwrap();
+
+        let mut stream = store.get_chunked_stream(&address).await.unwrap().unwrap();
+        let mut restored = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            restored.extend_from_slice(&chunk.unwrap());
+        }
+
+        assert_eq!(Bytes::from(restored), data);
+    }
+
+    #[tokio::test]
+    async fn test_get_chunked_stream_missing_manifest_returns_none() {
+        let store = ChunkedStore::new(Arc::new(InMemoryStore::default()));
+        assert!(store.get_chunked_stream("sha256:does-not-exist").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cut_points_cover_whole_input_without_gaps() {
+        let data = vec![7u8; 50 * 1024];
+        let points = cut_points(&data);
+
+        assert_eq!(points.first().unwrap().0, 0);
+        assert_eq!(points.last().unwrap().1, data.len());
+        for window in points.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+        for (start, end) in &points {
+            assert!(end - start <= MAX_CHUNK_SIZE);
+        }
+    }
+}
@@ -5,6 +5,13 @@ use url::Url;
 
 use crate::error::Result;
 
+pub mod checksum;
+pub mod chunk;
+pub mod verify;
+pub use checksum::ChecksumAlgo;
+pub use chunk::ChunkedStore;
+pub use verify::{VerifiedResolver, VerifiedStore};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ResourceRef {
@@ -14,6 +21,7 @@ pub enum ResourceRef {
     File { path: Utf8PathBuf },
     Url { url: Url },
     ContentAddress { hash: String },
+    Manifest { chunks: Vec<String>, size: u64 },
 }
 
 impl ResourceRef {
@@ -37,6 +45,10 @@ impl ResourceRef {
         Self::ContentAddress { hash: hash.into() }
     }
 
+    pub fn manifest(chunks: Vec<String>, size: u64) -> Self {
+        Self::Manifest { chunks, size }
+    }
+
     pub fn is_inline(&self) -> bool {
         matches!(self, Self::Inline(_) | Self::Base64 { .. })
     }
@@ -88,6 +100,15 @@ impl ResourceMeta {
         self.checksum = Some(checksum.into());
         self
     }
+
+    /// Verify `data` against [`ResourceMeta::checksum`], if one is set. A meta
+    /// with no checksum verifies trivially — there's nothing to check against.
+    pub fn verify(&self, data: &bytes::Bytes) -> Result<()> {
+        match &self.checksum {
+            Some(checksum) => ChecksumAlgo::verify(checksum, data),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -126,11 +147,46 @@ impl Resource {
     }
 }
 
+/// A boxed, owned stream of resource bytes, mirroring `hehe_llm::BoxStream`'s
+/// shape for the same reason: a trait method needs one concrete return type
+/// regardless of which resolver produced it.
+pub type ResourceStream = std::pin::Pin<Box<dyn futures::Stream<Item = Result<Bytes>> + Send>>;
+
 #[async_trait::async_trait]
 pub trait ResourceResolver: Send + Sync {
     async fn resolve(&self, resource: &ResourceRef) -> Result<Bytes>;
     async fn resolve_base64(&self, resource: &ResourceRef) -> Result<String>;
     async fn metadata(&self, resource: &ResourceRef) -> Result<ResourceMeta>;
+
+    /// Stream `resource`'s bytes chunk-by-chunk instead of buffering the whole
+    /// payload at once. The default wraps [`ResourceResolver::resolve`] as a
+    /// single-item stream; resolvers backed by a file or socket should override
+    /// this to yield chunks as they arrive.
+    async fn resolve_stream(&self, resource: &ResourceRef) -> Result<ResourceStream> {
+        let data = self.resolve(resource).await?;
+        Ok(Box::pin(futures::stream::once(async move { Ok(data) })))
+    }
+
+    /// Pipe `resource`'s bytes into `writer` via [`ResourceResolver::resolve_stream`]
+    /// without ever holding the whole payload in memory, returning the total
+    /// number of bytes written.
+    async fn copy_to(
+        &self,
+        resource: &ResourceRef,
+        writer: &mut (dyn tokio::io::AsyncWrite + Unpin + Send),
+    ) -> Result<u64> {
+        use futures::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let mut stream = self.resolve_stream(resource).await?;
+        let mut total = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            total += chunk.len() as u64;
+        }
+        Ok(total)
+    }
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,171 @@
+use bytes::Bytes;
+use std::sync::Arc;
+
+use super::{ChecksumAlgo, ResourceMeta, ResourceRef, ResourceResolver, ResourceStore};
+use crate::error::Result;
+
+/// Wraps a [`ResourceStore`], computing a [`ChecksumAlgo`] digest of every blob
+/// before it's stored and using that digest as the content address — so the
+/// address a caller gets back is always derived from the bytes, never whatever
+/// the backing store happens to assign.
+pub struct VerifiedStore {
+    inner: Arc<dyn ResourceStore>,
+    algo: ChecksumAlgo,
+}
+
+impl VerifiedStore {
+    pub fn new(inner: Arc<dyn ResourceStore>) -> Self {
+        Self {
+            inner,
+            algo: ChecksumAlgo::default(),
+        }
+    }
+
+    pub fn with_algo(mut self, algo: ChecksumAlgo) -> Self {
+        self.algo = algo;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceStore for VerifiedStore {
+    async fn store(&self, data: Bytes, meta: ResourceMeta) -> Result<String> {
+        let digest = self.algo.digest(&data);
+        self.inner.store(data, meta.with_checksum(digest.clone())).await?;
+        Ok(digest)
+    }
+
+    async fn get(&self, content_address: &str) -> Result<Option<Bytes>> {
+        let Some(data) = self.inner.get(content_address).await? else {
+            return Ok(None);
+        };
+        ChecksumAlgo::verify(content_address, &data)?;
+        Ok(Some(data))
+    }
+
+    async fn exists(&self, content_address: &str) -> Result<bool> {
+        self.inner.exists(content_address).await
+    }
+
+    async fn delete(&self, content_address: &str) -> Result<bool> {
+        self.inner.delete(content_address).await
+    }
+}
+
+/// Wraps a [`ResourceResolver`], verifying the resolved bytes against whatever
+/// checksum is available — a [`ResourceRef::ContentAddress`] hash, or failing
+/// that, [`ResourceMeta::checksum`] from [`ResourceResolver::metadata`] — before
+/// returning them. A resolution with no checksum to check against still
+/// succeeds; this only catches corruption it has something to compare against.
+pub struct VerifiedResolver {
+    inner: Arc<dyn ResourceResolver>,
+}
+
+impl VerifiedResolver {
+    pub fn new(inner: Arc<dyn ResourceResolver>) -> Self {
+        Self { inner }
+    }
+
+    async fn expected_checksum(&self, resource: &ResourceRef) -> Result<Option<String>> {
+        if let ResourceRef::ContentAddress { hash } = resource {
+            return Ok(Some(hash.clone()));
+        }
+        Ok(self.inner.metadata(resource).await?.checksum)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceResolver for VerifiedResolver {
+    async fn resolve(&self, resource: &ResourceRef) -> Result<Bytes> {
+        let data = self.inner.resolve(resource).await?;
+        if let Some(checksum) = self.expected_checksum(resource).await? {
+            ChecksumAlgo::verify(&checksum, &data)?;
+        }
+        Ok(data)
+    }
+
+    async fn resolve_base64(&self, resource: &ResourceRef) -> Result<String> {
+        let data = self.resolve(resource).await?;
+        Ok(crate::utils::encoding::encode_base64(&data))
+    }
+
+    async fn metadata(&self, resource: &ResourceRef) -> Result<ResourceMeta> {
+        self.inner.metadata(resource).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+
+    #[derive(Default)]
+    struct InMemoryStore {
+        blobs: RwLock<HashMap<String, Bytes>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ResourceStore for InMemoryStore {
+        async fn store(&self, data: Bytes, meta: ResourceMeta) -> Result<String> {
+            let key = meta.checksum.clone().unwrap_or_else(|| "no-checksum".to_string());
+            self.blobs.write().await.insert(key.clone(), data);
+            Ok(key)
+        }
+
+        async fn get(&self, content_address: &str) -> Result<Option<Bytes>> {
+            Ok(self.blobs.read().await.get(content_address).cloned())
+        }
+
+        async fn exists(&self, content_address: &str) -> Result<bool> {
+            Ok(self.blobs.read().await.contains_key(content_address))
+        }
+
+        async fn delete(&self, content_address: &str) -> Result<bool> {
+            Ok(self.blobs.write().await.remove(content_address).is_some())
+        }
+    }
+
+    struct TamperingResolver;
+
+    #[async_trait::async_trait]
+    impl ResourceResolver for TamperingResolver {
+        async fn resolve(&self, _resource: &ResourceRef) -> Result<Bytes> {
+            Ok(Bytes::from_static(b"tampered bytes"))
+        }
+
+        async fn resolve_base64(&self, resource: &ResourceRef) -> Result<String> {
+            let data = self.resolve(resource).await?;
+            Ok(crate::utils::encoding::encode_base64(&data))
+        }
+
+        async fn metadata(&self, _resource: &ResourceRef) -> Result<ResourceMeta> {
+            Ok(ResourceMeta::new().with_checksum(ChecksumAlgo::Sha256.digest(b"original bytes")))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verified_store_round_trips() {
+        let store = VerifiedStore::new(Arc::new(InMemoryStore::default()));
+        let address = store.store(Bytes::from_static(b"hello"), ResourceMeta::new()).await.unwrap();
+
+        assert!(address.starts_with("sha256:"));
+        let fetched = store.get(&address).await.unwrap().unwrap();
+        assert_eq!(fetched, Bytes::from_static(b"hello"));
+    }
+
+    #[tokio::test]
+    async fn test_verified_resolver_rejects_tampered_bytes() {
+        let resolver = VerifiedResolver::new(Arc::new(TamperingResolver));
+        let err = resolver.resolve(&ResourceRef::url("https://example.com/x".parse().unwrap())).await.unwrap_err();
+        assert!(matches!(err, crate::error::Error::ChecksumMismatch { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_verified_resolver_uses_content_address_directly() {
+        let resolver = VerifiedResolver::new(Arc::new(TamperingResolver));
+        let digest = ChecksumAlgo::Sha256.digest(b"tampered bytes");
+        let result = resolver.resolve(&ResourceRef::content_address(digest)).await;
+        assert!(result.is_ok());
+    }
+}
@@ -1,5 +1,10 @@
+use crate::error::{Error, Result};
 use crate::types::MessageId;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub mod sse;
+pub use sse::SseDecoder;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -50,11 +55,26 @@ pub enum StopReason {
     ToolUse,
 }
 
-#[derive(Default)]
-struct ToolUseBuilder {
-    id: String,
-    name: String,
-    input_json: String,
+#[derive(Default, Clone, Debug)]
+pub struct ToolUseBuilder {
+    pub id: String,
+    pub name: String,
+    pub input_json: String,
+    /// Set once a `ToolUseEnd` for this id arrives, distinguishing a
+    /// completed tool use (safe to parse and execute) from one still
+    /// accumulating `ToolUseDelta` fragments.
+    pub finalized: bool,
+}
+
+/// A tool invocation parsed out of a completed [`StreamAggregator`] run, via
+/// [`StreamAggregator::tool_calls`]. Distinct from [`crate::tool::ToolCall`]:
+/// this is the raw, just-parsed shape a provider handed back over the wire,
+/// before it's adopted into the richer lifecycle type tool execution tracks.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StreamToolCall {
+    pub id: String,
+    pub name: String,
+    pub input: Value,
 }
 
 #[derive(Default)]
@@ -86,6 +106,7 @@ impl StreamAggregator {
                     id,
                     name,
                     input_json: String::new(),
+                    finalized: false,
                 });
             }
             StreamChunk::ToolUseDelta { id, input_delta } => {
@@ -93,6 +114,11 @@ impl StreamAggregator {
                     tu.input_json.push_str(&input_delta);
                 }
             }
+            StreamChunk::ToolUseEnd { id } => {
+                if let Some(tu) = self.tool_uses.iter_mut().find(|t| t.id == id) {
+                    tu.finalized = true;
+                }
+            }
             StreamChunk::MessageEnd { stop_reason } => {
                 self.stop_reason = stop_reason;
             }
@@ -150,11 +176,104 @@ impl StreamAggregator {
         self.tool_uses.len()
     }
 
+    pub fn tool_uses(&self) -> &[ToolUseBuilder] {
+        &self.tool_uses
+    }
+
     pub fn has_tool_use(&self) -> bool {
         !self.tool_uses.is_empty()
     }
 
+    /// Whether at least one tool use has received its `ToolUseEnd`.
+    pub fn has_finalized_tool_use(&self) -> bool {
+        self.tool_uses.iter().any(|tu| tu.finalized)
+    }
+
+    /// Parses every finalized tool use's accumulated `input_json` into a
+    /// [`StreamToolCall`], skipping tool uses still in flight (no
+    /// `ToolUseEnd` seen yet). Errors, identifying the offending tool's id
+    /// and name, if a finalized fragment's `input_json` isn't valid JSON.
+    pub fn tool_calls(&self) -> Result<Vec<StreamToolCall>> {
+        self.tool_uses
+            .iter()
+            .filter(|tu| tu.finalized)
+            .map(|tu| {
+                let input: Value = serde_json::from_str(&tu.input_json).map_err(|e| {
+                    Error::tool(
+                        tu.name.clone(),
+                        format!("tool call {} has invalid JSON input: {e}", tu.id),
+                    )
+                })?;
+                Ok(StreamToolCall {
+                    id: tu.id.clone(),
+                    name: tu.name.clone(),
+                    input,
+                })
+            })
+            .collect()
+    }
+
     pub fn clear(&mut self) {
         *self = Self::default();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_calls_skips_unfinalized_tool_use() {
+        let mut aggregator = StreamAggregator::new();
+        aggregator.push(StreamChunk::ToolUseStart {
+            id: "t1".to_string(),
+            name: "search".to_string(),
+        });
+        aggregator.push(StreamChunk::ToolUseDelta {
+            id: "t1".to_string(),
+            input_delta: "{\"query\":\"rust\"}".to_string(),
+        });
+
+        assert!(!aggregator.has_finalized_tool_use());
+        assert_eq!(aggregator.tool_calls().unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_tool_calls_parses_finalized_tool_use() {
+        let mut aggregator = StreamAggregator::new();
+        aggregator.push(StreamChunk::ToolUseStart {
+            id: "t1".to_string(),
+            name: "search".to_string(),
+        });
+        aggregator.push(StreamChunk::ToolUseDelta {
+            id: "t1".to_string(),
+            input_delta: "{\"query\":\"rust\"}".to_string(),
+        });
+        aggregator.push(StreamChunk::ToolUseEnd { id: "t1".to_string() });
+
+        assert!(aggregator.has_finalized_tool_use());
+        let calls = aggregator.tool_calls().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "t1");
+        assert_eq!(calls[0].name, "search");
+        assert_eq!(calls[0].input, serde_json::json!({"query": "rust"}));
+    }
+
+    #[test]
+    fn test_tool_calls_errors_on_malformed_json_and_names_the_tool() {
+        let mut aggregator = StreamAggregator::new();
+        aggregator.push(StreamChunk::ToolUseStart {
+            id: "t1".to_string(),
+            name: "search".to_string(),
+        });
+        aggregator.push(StreamChunk::ToolUseDelta {
+            id: "t1".to_string(),
+            input_delta: "{not json".to_string(),
+        });
+        aggregator.push(StreamChunk::ToolUseEnd { id: "t1".to_string() });
+
+        let err = aggregator.tool_calls().unwrap_err().to_string();
+        assert!(err.contains("search"));
+        assert!(err.contains("t1"));
+    }
+}
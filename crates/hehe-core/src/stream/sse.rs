@@ -0,0 +1,198 @@
+//! Server-Sent-Events codec for [`StreamChunk`], so a streamed completion can
+//! cross an HTTP boundary instead of staying in-process: [`StreamChunk::to_sse_event`]
+//! serializes one chunk as an SSE event, and [`SseDecoder`] reassembles a raw
+//! SSE byte stream back into `StreamChunk`s, feeding them into a
+//! [`StreamAggregator`] as they complete.
+
+use super::{StreamAggregator, StreamChunk};
+use crate::error::{Error, Result};
+
+/// Event names this decoder knows how to turn back into a `StreamChunk`,
+/// matching the `#[serde(tag = "type", rename_all = "snake_case")]` variants
+/// on [`StreamChunk`] (`Ping` excepted — see [`StreamChunk::to_sse_event`]).
+const KNOWN_EVENT_TAGS: &[&str] = &[
+    "message_start",
+    "text_delta",
+    "tool_use_start",
+    "tool_use_delta",
+    "tool_use_end",
+    "content_block_start",
+    "content_block_end",
+    "message_end",
+    "usage",
+    "error",
+];
+
+impl StreamChunk {
+    /// Serializes this chunk as a single SSE event: `event: <tag>\ndata: <json>\n\n`.
+    ///
+    /// `Ping` carries no data worth sending, so it's written as a bare
+    /// `: ping\n\n` comment line instead — the standard SSE keep-alive idiom,
+    /// which conforming parsers (including [`SseDecoder`]) simply skip.
+    pub fn to_sse_event(&self) -> Result<String> {
+        if matches!(self, StreamChunk::Ping) {
+            return Ok(": ping\n\n".to_string());
+        }
+
+        let json = serde_json::to_value(self)?;
+        let tag = json
+            .get("type")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Internal("StreamChunk serialized without a type tag".to_string()))?;
+
+        Ok(format!("event: {tag}\ndata: {json}\n\n"))
+    }
+}
+
+/// Reassembles a raw SSE byte stream into [`StreamChunk`]s.
+///
+/// Bytes can arrive split at any point — mid-line, mid-field, or mid-event —
+/// so [`SseDecoder::push`] buffers everything it's given until it can see a
+/// full event (a blank line terminating a `data:`/`event:` block) before
+/// trying to parse it.
+#[derive(Default)]
+pub struct SseDecoder {
+    buffer: String,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds newly-received bytes into the decoder, returning every
+    /// `StreamChunk` that completed as a result. Bytes that don't yet form a
+    /// full event are buffered for the next call. Unknown event types and
+    /// bare comment lines (e.g. our own `: ping`) are skipped rather than
+    /// erroring, but a known event whose `data:` isn't valid JSON for that
+    /// variant is reported.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Vec<StreamChunk>> {
+        self.buffer.push_str(&String::from_utf8_lossy(bytes).replace("\r\n", "\n"));
+
+        let mut chunks = Vec::new();
+        while let Some(idx) = self.buffer.find("\n\n") {
+            let raw = self.buffer[..idx].to_string();
+            self.buffer.drain(..idx + 2);
+            if let Some(chunk) = parse_event(&raw)? {
+                chunks.push(chunk);
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    /// Like [`SseDecoder::push`], but applies every completed chunk to
+    /// `aggregator` directly and returns how many were applied.
+    pub fn push_into(&mut self, bytes: &[u8], aggregator: &mut StreamAggregator) -> Result<usize> {
+        let chunks = self.push(bytes)?;
+        let applied = chunks.len();
+        for chunk in chunks {
+            aggregator.push(chunk);
+        }
+        Ok(applied)
+    }
+}
+
+/// Parses one complete SSE event block (no trailing blank line) into a
+/// `StreamChunk`, or `None` if it's a comment or an event type this decoder
+/// doesn't recognize.
+fn parse_event(raw: &str) -> Result<Option<StreamChunk>> {
+    let mut event_type: Option<&str> = None;
+    let mut data_lines: Vec<&str> = Vec::new();
+
+    for line in raw.lines() {
+        if line.starts_with(':') {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("event:") {
+            event_type = Some(rest.trim());
+        } else if let Some(rest) = line.strip_prefix("data:") {
+            data_lines.push(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+
+    let Some(event_type) = event_type else {
+        return Ok(None);
+    };
+    if !KNOWN_EVENT_TAGS.contains(&event_type) {
+        return Ok(None);
+    }
+    if data_lines.is_empty() {
+        return Ok(None);
+    }
+
+    let mut value: serde_json::Value = serde_json::from_str(&data_lines.join("\n"))?;
+    if let serde_json::Value::Object(map) = &mut value {
+        map.insert("type".to_string(), serde_json::Value::String(event_type.to_string()));
+    }
+
+    Ok(Some(serde_json::from_value(value)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MessageId;
+
+    #[test]
+    fn test_text_delta_round_trips_through_sse() {
+        let chunk = StreamChunk::TextDelta {
+            text: "hello".to_string(),
+        };
+        let event = chunk.to_sse_event().unwrap();
+        assert_eq!(event, "event: text_delta\ndata: {\"type\":\"text_delta\",\"text\":\"hello\"}\n\n");
+
+        let mut decoder = SseDecoder::new();
+        let chunks = decoder.push(event.as_bytes()).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(matches!(&chunks[0], StreamChunk::TextDelta { text } if text == "hello"));
+    }
+
+    #[test]
+    fn test_ping_encodes_as_comment_and_is_skipped_on_decode() {
+        let event = StreamChunk::Ping.to_sse_event().unwrap();
+        assert_eq!(event, ": ping\n\n");
+
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(event.as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_event_split_across_multiple_pushes_is_buffered() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(b"event: text_delta\ndata: {\"typ").unwrap().is_empty());
+        let chunks = decoder.push(b"e\":\"text_delta\",\"text\":\"ok\"}\n\n").unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(matches!(&chunks[0], StreamChunk::TextDelta { text } if text == "ok"));
+    }
+
+    #[test]
+    fn test_unknown_event_type_is_ignored() {
+        let mut decoder = SseDecoder::new();
+        let chunks = decoder
+            .push(b"event: some_future_event\ndata: {\"foo\":1}\n\n")
+            .unwrap();
+        assert!(chunks.is_empty());
+    }
+
+    #[test]
+    fn test_decoder_feeds_completed_chunks_into_aggregator() {
+        let mut decoder = SseDecoder::new();
+        let mut aggregator = StreamAggregator::new();
+
+        let message_id = MessageId::new();
+        let start = StreamChunk::MessageStart { message_id }.to_sse_event().unwrap();
+        let delta = StreamChunk::TextDelta {
+            text: "hi".to_string(),
+        }
+        .to_sse_event()
+        .unwrap();
+
+        let applied = decoder
+            .push_into(format!("{start}{delta}").as_bytes(), &mut aggregator)
+            .unwrap();
+        assert_eq!(applied, 2);
+        assert_eq!(aggregator.message_id(), Some(message_id));
+        assert_eq!(aggregator.text(), "hi");
+    }
+}
@@ -7,6 +7,8 @@ use serde_json::Value;
 pub enum ToolCallStatus {
     Pending,
     Running,
+    Suspended,
+    Resumed,
     Completed,
     Failed,
     Cancelled,
@@ -20,6 +22,10 @@ impl ToolCallStatus {
         )
     }
 
+    pub fn is_suspended(&self) -> bool {
+        matches!(self, ToolCallStatus::Suspended)
+    }
+
     pub fn is_success(&self) -> bool {
         matches!(self, ToolCallStatus::Completed)
     }
@@ -91,12 +97,31 @@ impl ToolCall {
         self.completed_at = Some(Timestamp::now());
     }
 
+    /// Park a running or pending call so it can be picked back up later with
+    /// [`ToolCall::resume`]. Unlike [`ToolCall::cancel`], this is not terminal.
+    pub fn suspend(&mut self) {
+        self.status = ToolCallStatus::Suspended;
+    }
+
+    /// Bring a suspended call back into the running state, e.g. after a batch
+    /// is reloaded from a checkpoint and re-dispatched.
+    pub fn resume(&mut self) {
+        self.status = ToolCallStatus::Resumed;
+        if self.started_at.is_none() {
+            self.started_at = Some(Timestamp::now());
+        }
+    }
+
     pub fn is_pending(&self) -> bool {
         self.status == ToolCallStatus::Pending
     }
 
     pub fn is_running(&self) -> bool {
-        self.status == ToolCallStatus::Running
+        matches!(self.status, ToolCallStatus::Running | ToolCallStatus::Resumed)
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.status.is_suspended()
     }
 
     pub fn is_completed(&self) -> bool {
@@ -159,4 +184,20 @@ mod tests {
         assert!(call.is_terminal());
         assert_eq!(call.error, Some("Something went wrong".to_string()));
     }
+
+    #[test]
+    fn test_tool_call_suspend_and_resume() {
+        let mut call = ToolCall::new("long_running_tool", serde_json::json!({}));
+
+        call.start();
+        call.suspend();
+        assert!(call.is_suspended());
+        assert!(!call.is_terminal());
+        assert!(!call.is_running());
+
+        call.resume();
+        assert!(call.is_running());
+        assert!(!call.is_suspended());
+        assert!(!call.is_terminal());
+    }
 }
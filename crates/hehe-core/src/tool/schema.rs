@@ -32,6 +32,42 @@ pub struct ToolParameter {
     pub required: Option<Vec<String>>,
 }
 
+/// One thing wrong with a value checked against a [`ToolParameter`] schema:
+/// `path` locates it (e.g. `"files[2].encoding"`, empty for the root value)
+/// and `message` describes the mismatch. [`ToolParameter::validate`] returns
+/// every error it finds rather than stopping at the first one, so a model
+/// that hallucinated several bad arguments can fix them all in one retry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Joins a batch of [`ValidationError`]s into one human-readable line, for
+/// surfacing to a model as a tool error it can act on.
+pub fn describe_validation_errors(errors: &[ValidationError]) -> String {
+    errors.iter().map(ValidationError::to_string).collect::<Vec<_>>().join("; ")
+}
+
 impl ToolParameter {
     pub fn string() -> Self {
         Self {
@@ -111,6 +147,147 @@ impl ToolParameter {
         }
         self
     }
+
+    /// Checks `value` against this schema, recursing into `properties` and
+    /// array `items`. On success, returns a normalized copy of `value` with
+    /// `default` filled in for every absent optional object field. On
+    /// failure, returns every mismatch found rather than just the first, so
+    /// a model that hallucinated several bad arguments can fix them all at
+    /// once.
+    pub fn validate(&self, value: &Value) -> std::result::Result<Value, Vec<ValidationError>> {
+        self.validate_at("", value)
+    }
+
+    fn validate_at(&self, path: &str, value: &Value) -> std::result::Result<Value, Vec<ValidationError>> {
+        if !Self::matches_type(&self.schema_type, value) {
+            return Err(vec![ValidationError::new(
+                path,
+                format!(
+                    "expected {:?}, got {}",
+                    self.schema_type,
+                    Self::type_name(value)
+                ),
+            )]);
+        }
+
+        if let Some(enum_values) = &self.enum_values {
+            if !enum_values.contains(value) {
+                return Err(vec![ValidationError::new(
+                    path,
+                    format!("{value} is not one of the allowed values"),
+                )]);
+            }
+        }
+
+        match value {
+            Value::Object(map) => self.validate_object(path, map),
+            Value::Array(items) => self.validate_array(path, items),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn validate_object(
+        &self,
+        path: &str,
+        map: &serde_json::Map<String, Value>,
+    ) -> std::result::Result<Value, Vec<ValidationError>> {
+        let Some(properties) = &self.properties else {
+            return Ok(Value::Object(map.clone()));
+        };
+
+        let required = self.required.as_deref().unwrap_or(&[]);
+        let mut errors = Vec::new();
+        let mut result = serde_json::Map::new();
+
+        for (name, param) in properties {
+            let child_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{path}.{name}")
+            };
+
+            match map.get(name) {
+                Some(v) => match param.validate_at(&child_path, v) {
+                    Ok(normalized) => {
+                        result.insert(name.clone(), normalized);
+                    }
+                    Err(mut field_errors) => errors.append(&mut field_errors),
+                },
+                None if required.contains(name) => {
+                    errors.push(ValidationError::new(child_path, "missing required field"));
+                }
+                None => {
+                    if let Some(default) = &param.default {
+                        result.insert(name.clone(), default.clone());
+                    }
+                }
+            }
+        }
+
+        for (key, v) in map {
+            if !properties.contains_key(key) {
+                result.insert(key.clone(), v.clone());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Value::Object(result))
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_array(
+        &self,
+        path: &str,
+        items: &[Value],
+    ) -> std::result::Result<Value, Vec<ValidationError>> {
+        let Some(item_schema) = &self.items else {
+            return Ok(Value::Array(items.to_vec()));
+        };
+
+        let mut errors = Vec::new();
+        let mut result = Vec::with_capacity(items.len());
+
+        for (i, item) in items.iter().enumerate() {
+            match item_schema.validate_at(&format!("{path}[{i}]"), item) {
+                Ok(normalized) => result.push(normalized),
+                Err(mut item_errors) => errors.append(&mut item_errors),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Value::Array(result))
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn matches_type(schema_type: &JsonSchemaType, value: &Value) -> bool {
+        matches!(
+            (schema_type, value),
+            (JsonSchemaType::String, Value::String(_))
+                | (JsonSchemaType::Number, Value::Number(_))
+                | (JsonSchemaType::Boolean, Value::Bool(_))
+                | (JsonSchemaType::Array, Value::Array(_))
+                | (JsonSchemaType::Object, Value::Object(_))
+                | (JsonSchemaType::Null, Value::Null)
+        ) || matches!(
+            (schema_type, value),
+            (JsonSchemaType::Integer, Value::Number(n)) if n.is_i64() || n.is_u64()
+        )
+    }
+
+    fn type_name(value: &Value) -> &'static str {
+        match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -181,6 +358,7 @@ impl ToolDefinition {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_tool_definition() {
@@ -208,4 +386,77 @@ mod tests {
         let required = tool.parameters.required.as_ref().unwrap();
         assert!(required.contains(&"path".to_string()));
     }
+
+    #[test]
+    fn test_validate_fills_in_defaults_for_absent_optional_fields() {
+        let schema = ToolParameter::object()
+            .with_property(
+                "encoding",
+                ToolParameter::string().with_default(Value::String("utf-8".into())),
+            )
+            .with_property("path", ToolParameter::string())
+            .with_required("path");
+
+        let normalized = schema.validate(&json!({"path": "a.txt"})).unwrap();
+
+        assert_eq!(normalized, json!({"path": "a.txt", "encoding": "utf-8"}));
+    }
+
+    #[test]
+    fn test_validate_reports_missing_required_and_wrong_type() {
+        let schema = ToolParameter::object()
+            .with_property("path", ToolParameter::string())
+            .with_property("retries", ToolParameter::integer())
+            .with_required("path");
+
+        let errors = schema.validate(&json!({"retries": "three"})).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.path == "path" && e.message.contains("missing")));
+        assert!(errors.iter().any(|e| e.path == "retries" && e.message.contains("expected")));
+    }
+
+    #[test]
+    fn test_validate_rejects_value_outside_enum() {
+        let schema = ToolParameter::string().with_enum(vec![json!("low"), json!("high")]);
+
+        let errors = schema.validate(&json!("medium")).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("not one of the allowed values"));
+    }
+
+    #[test]
+    fn test_validate_recurses_into_array_items_and_nested_objects() {
+        let schema = ToolParameter::object()
+            .with_property(
+                "files",
+                ToolParameter::array(
+                    ToolParameter::object()
+                        .with_property("name", ToolParameter::string())
+                        .with_required("name"),
+                ),
+            )
+            .with_required("files");
+
+        let errors = schema
+            .validate(&json!({"files": [{"name": "a"}, {}]}))
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "files[1].name");
+    }
+
+    #[test]
+    fn test_describe_validation_errors_joins_with_paths() {
+        let errors = vec![
+            ValidationError::new("path", "missing required field"),
+            ValidationError::new("retries", "expected integer, got string"),
+        ];
+
+        assert_eq!(
+            describe_validation_errors(&errors),
+            "path: missing required field; retries: expected integer, got string"
+        );
+    }
 }
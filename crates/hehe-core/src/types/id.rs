@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Compares by the wrapped UUID's raw bytes. For the UUIDv7s [`Id::new`]
+/// generates, that byte order matches creation order, so `Id` sorts and
+/// compares as a time-ordered cursor (e.g. `hehe_store`'s sync journal).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Id(Uuid);
 
@@ -1,3 +1,4 @@
+use serde_json::Value;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -16,3 +17,55 @@ pub fn hash_bytes(data: &[u8]) -> u64 {
 pub fn short_hash(s: &str) -> String {
     format!("{:016x}", hash_string(s))
 }
+
+/// Serializes `value` with object keys sorted recursively, so two JSON
+/// values that differ only in key order produce identical output. Meant to
+/// be fed into [`hash_string`] to build a stable cache key from arbitrary
+/// JSON, e.g. a tool call's arguments.
+pub fn canonical_json(value: &Value) -> String {
+    serde_json::to_string(&sort_keys(value)).unwrap_or_default()
+}
+
+fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(sort_keys).collect()),
+        Value::Object(map) => {
+            let mut sorted = serde_json::Map::new();
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for key in keys {
+                sorted.insert(key.clone(), sort_keys(&map[key]));
+            }
+            Value::Object(sorted)
+        }
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json_ignores_key_order() {
+        let a = json!({"b": 1, "a": 2});
+        let b = json!({"a": 2, "b": 1});
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_nested_objects() {
+        let value = json!({"outer": {"z": 1, "y": 2}, "a": [3, {"d": 4, "c": 5}]});
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":[3,{"c":5,"d":4}],"outer":{"y":2,"z":1}}"#
+        );
+    }
+
+    #[test]
+    fn test_hash_string_is_deterministic() {
+        assert_eq!(hash_string("tool:args"), hash_string("tool:args"));
+        assert_ne!(hash_string("tool:args"), hash_string("tool:other"));
+    }
+}
@@ -88,6 +88,14 @@ impl LlmError {
         Self::Stream(msg.into())
     }
 
+    pub fn tool(msg: impl Into<String>) -> Self {
+        Self::Tool(msg.into())
+    }
+
+    pub fn config(msg: impl Into<String>) -> Self {
+        Self::Config(msg.into())
+    }
+
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
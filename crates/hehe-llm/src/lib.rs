@@ -1,19 +1,32 @@
 pub mod error;
 pub mod providers;
+pub mod retry;
+pub mod router;
+pub mod tool_loop;
 pub mod traits;
 pub mod types;
 
 pub use error::{LlmError, Result};
+pub use retry::RetryingProvider;
+pub use router::{ProviderHealth, ProviderRouter, RouterHealth};
+pub use tool_loop::{complete_with_tools, ToolExecutorFn};
 pub use traits::{BoxStream, EmbeddingProvider, LlmProvider};
-pub use types::{CompletionRequest, CompletionResponse, ModelInfo, ToolChoice};
+pub use types::{
+    CompletionRequest, CompletionResponse, ModelInfo, ReasoningEffort, ResponseFormat, ToolChoice,
+};
 
 #[cfg(feature = "openai")]
 pub use providers::OpenAiProvider;
 
 pub mod prelude {
     pub use crate::error::{LlmError, Result};
+    pub use crate::retry::RetryingProvider;
+    pub use crate::router::{ProviderHealth, ProviderRouter, RouterHealth};
+    pub use crate::tool_loop::{complete_with_tools, ToolExecutorFn};
     pub use crate::traits::{BoxStream, EmbeddingProvider, LlmProvider};
-    pub use crate::types::{CompletionRequest, CompletionResponse, ModelInfo, ToolChoice};
+    pub use crate::types::{
+        CompletionRequest, CompletionResponse, ModelInfo, ReasoningEffort, ResponseFormat, ToolChoice,
+    };
 
     #[cfg(feature = "openai")]
     pub use crate::providers::OpenAiProvider;
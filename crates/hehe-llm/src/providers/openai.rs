@@ -1,14 +1,16 @@
 use crate::error::{LlmError, Result};
 use crate::traits::{BoxStream, LlmProvider};
-use crate::types::{CompletionRequest, CompletionResponse, ModelInfo, ToolChoice};
+use crate::types::{CompletionRequest, CompletionResponse, ModelInfo, ResponseFormat, ToolChoice};
 use async_stream::try_stream;
 use async_trait::async_trait;
 use futures::StreamExt;
 use hehe_core::capability::{Capabilities, Capability};
+use hehe_core::config::ProviderConfig;
 use hehe_core::event::TokenUsage;
 use hehe_core::message::{ContentBlock, ToolResult, ToolUse};
 use hehe_core::stream::{StopReason, StreamChunk};
 use hehe_core::{Message, MessageId, Role};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -47,12 +49,72 @@ impl OpenAiProvider {
         self
     }
 
-    fn convert_messages(&self, messages: &[Message], system: Option<&str>) -> Vec<OpenAiMessage> {
+    /// Builds a client from a declared [`ProviderConfig`] instead of
+    /// [`OpenAiProvider::new`]'s fixed 300s timeout and no-proxy defaults,
+    /// so OpenAI-compatible backends (Azure, Together, a local gateway, ...)
+    /// can each bring their own base URL, timeouts, proxy, and auth headers.
+    pub fn from_config(config: &ProviderConfig) -> Result<Self> {
+        let api_key = config
+            .api_key
+            .clone()
+            .ok_or_else(|| LlmError::config("provider config is missing an api_key"))?;
+
+        let mut builder =
+            Client::builder().timeout(Duration::from_secs(config.timeout_secs.unwrap_or(300)));
+
+        if let Some(connect_timeout_secs) = config.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(connect_timeout_secs));
+        }
+
+        if let Some(proxy_url) = &config.proxy_url {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .map_err(|e| LlmError::config(format!("invalid proxy_url '{proxy_url}': {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if !config.extra_headers.is_empty() {
+            let mut headers = HeaderMap::new();
+            for (name, value) in &config.extra_headers {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| LlmError::config(format!("invalid header name '{name}': {e}")))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|e| LlmError::config(format!("invalid header value for '{name:?}': {e}")))?;
+                headers.insert(name, value);
+            }
+            builder = builder.default_headers(headers);
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| LlmError::config(format!("failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            client,
+            api_key,
+            base_url: config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
+            default_model: config.model.clone().unwrap_or_else(|| "gpt-4o".to_string()),
+            capabilities: Capabilities::full_agent(),
+        })
+    }
+
+    fn convert_messages(
+        &self,
+        messages: &[Message],
+        system: Option<&str>,
+        use_developer_role: bool,
+    ) -> Vec<OpenAiMessage> {
         let mut result = Vec::new();
 
         if let Some(sys) = system {
             result.push(OpenAiMessage {
-                role: "system".to_string(),
+                role: if use_developer_role {
+                    "developer".to_string()
+                } else {
+                    "system".to_string()
+                },
                 content: Some(OpenAiContent::Text(sys.to_string())),
                 tool_calls: None,
                 tool_call_id: None,
@@ -208,7 +270,30 @@ impl OpenAiProvider {
         }
     }
 
-    fn parse_response(&self, response: OpenAiResponse) -> Result<CompletionResponse> {
+    fn convert_response_format(&self, format: &ResponseFormat) -> Value {
+        match format {
+            ResponseFormat::Text => serde_json::json!({ "type": "text" }),
+            ResponseFormat::JsonObject => serde_json::json!({ "type": "json_object" }),
+            ResponseFormat::JsonSchema {
+                name,
+                schema,
+                strict,
+            } => serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": name,
+                    "schema": schema,
+                    "strict": strict,
+                }
+            }),
+        }
+    }
+
+    fn parse_response(
+        &self,
+        response: OpenAiResponse,
+        response_format: Option<&ResponseFormat>,
+    ) -> Result<CompletionResponse> {
         let choice = response
             .choices
             .into_iter()
@@ -218,6 +303,13 @@ impl OpenAiProvider {
         let mut content_blocks = Vec::new();
 
         if let Some(text) = choice.message.content {
+            if matches!(response_format, Some(ResponseFormat::JsonSchema { .. })) {
+                serde_json::from_str::<Value>(&text).map_err(|e| {
+                    LlmError::invalid_response(format!(
+                        "structured output did not parse as valid JSON: {e}"
+                    ))
+                })?;
+            }
             content_blocks.push(ContentBlock::text(text));
         }
 
@@ -253,6 +345,60 @@ impl OpenAiProvider {
     }
 }
 
+/// Declares a table of OpenAI-compatible backends and generates one
+/// constructor function per entry, each building an [`OpenAiProvider`]
+/// pointed at that backend's `base_url` with its API key read from
+/// `env_var`. Lets a binary declare many such clients in one place instead
+/// of hand-rolling a `with_base_url`/`with_model` call per backend.
+///
+/// ```ignore
+/// hehe_llm::register_providers! {
+///     azure { base_url: "https://my-resource.openai.azure.com", env_var: "AZURE_OPENAI_API_KEY", default_model: "gpt-4o" },
+///     together { base_url: "https://api.together.xyz/v1", env_var: "TOGETHER_API_KEY", default_model: "meta-llama/Llama-3-70b-chat-hf" },
+/// }
+///
+/// let azure_client = azure()?;
+/// ```
+#[macro_export]
+macro_rules! register_providers {
+    ($($name:ident { base_url: $base_url:expr, env_var: $env_var:expr, default_model: $default_model:expr $(,)? }),+ $(,)?) => {
+        $(
+            pub fn $name() -> $crate::Result<$crate::providers::OpenAiProvider> {
+                let api_key = ::std::env::var($env_var).map_err(|_| {
+                    $crate::LlmError::config(::std::format!(
+                        "missing environment variable {}",
+                        $env_var
+                    ))
+                })?;
+                ::std::result::Result::Ok(
+                    $crate::providers::OpenAiProvider::with_base_url(api_key, $base_url)
+                        .with_model($default_model),
+                )
+            }
+        )+
+    };
+}
+
+/// Whether `model` is one of OpenAI's o1/o3 reasoning models, which reject
+/// `max_tokens`/`temperature`/`top_p`/a `system` role in favor of
+/// `max_completion_tokens`, a `developer` role, and (optionally)
+/// `reasoning_effort`.
+fn is_reasoning_model(model: &str) -> bool {
+    model.starts_with("o1") || model.starts_with("o3")
+}
+
+/// Merges `extra` into `body`'s top-level object, overwriting any key the
+/// typed fields above already set. This is the deliberate override order:
+/// `extra_body` is the escape hatch for knobs this crate doesn't model, so
+/// it should win over whatever a typed field happened to produce.
+fn merge_extra_body(body: &mut Value, extra: &serde_json::Map<String, Value>) {
+    if let Value::Object(map) = body {
+        for (key, value) in extra {
+            map.insert(key.clone(), value.clone());
+        }
+    }
+}
+
 #[async_trait]
 impl LlmProvider for OpenAiProvider {
     fn name(&self) -> &str {
@@ -264,7 +410,9 @@ impl LlmProvider for OpenAiProvider {
     }
 
     async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
-        let messages = self.convert_messages(&request.messages, request.system.as_deref());
+        let reasoning_model = is_reasoning_model(&request.model);
+        let messages =
+            self.convert_messages(&request.messages, request.system.as_deref(), reasoning_model);
 
         let mut body = serde_json::json!({
             "model": request.model,
@@ -272,13 +420,24 @@ impl LlmProvider for OpenAiProvider {
         });
 
         if let Some(max_tokens) = request.max_tokens {
-            body["max_tokens"] = max_tokens.into();
+            if reasoning_model {
+                body["max_completion_tokens"] = max_tokens.into();
+            } else {
+                body["max_tokens"] = max_tokens.into();
+            }
         }
-        if let Some(temp) = request.temperature {
-            body["temperature"] = temp.into();
+        if !reasoning_model {
+            if let Some(temp) = request.temperature {
+                body["temperature"] = temp.into();
+            }
+            if let Some(top_p) = request.top_p {
+                body["top_p"] = top_p.into();
+            }
         }
-        if let Some(top_p) = request.top_p {
-            body["top_p"] = top_p.into();
+        if reasoning_model {
+            if let Some(effort) = request.reasoning_effort {
+                body["reasoning_effort"] = serde_json::to_value(effort)?;
+            }
         }
         if let Some(stop) = &request.stop {
             body["stop"] = stop.clone().into();
@@ -289,6 +448,12 @@ impl LlmProvider for OpenAiProvider {
         if let Some(choice) = &request.tool_choice {
             body["tool_choice"] = self.convert_tool_choice(choice);
         }
+        if let Some(format) = &request.response_format {
+            body["response_format"] = self.convert_response_format(format);
+        }
+        if let Some(extra) = &request.extra_body {
+            merge_extra_body(&mut body, extra);
+        }
 
         let response = self
             .client
@@ -302,36 +467,53 @@ impl LlmProvider for OpenAiProvider {
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            
+
             if status.as_u16() == 429 {
                 return Err(LlmError::rate_limited("openai", None));
             }
-            
+
             return Err(LlmError::api("openai", format!("{}: {}", status, text)));
         }
 
         let openai_response: OpenAiResponse = response.json().await?;
-        self.parse_response(openai_response)
+        self.parse_response(openai_response, request.response_format.as_ref())
     }
 
     async fn complete_stream(&self, request: CompletionRequest) -> Result<BoxStream<StreamChunk>> {
-        let messages = self.convert_messages(&request.messages, request.system.as_deref());
+        let reasoning_model = is_reasoning_model(&request.model);
+        let messages =
+            self.convert_messages(&request.messages, request.system.as_deref(), reasoning_model);
 
         let mut body = serde_json::json!({
             "model": request.model,
             "messages": messages,
             "stream": true,
+            "stream_options": { "include_usage": true },
         });
 
         if let Some(max_tokens) = request.max_tokens {
-            body["max_tokens"] = max_tokens.into();
+            if reasoning_model {
+                body["max_completion_tokens"] = max_tokens.into();
+            } else {
+                body["max_tokens"] = max_tokens.into();
+            }
+        }
+        if !reasoning_model {
+            if let Some(temp) = request.temperature {
+                body["temperature"] = temp.into();
+            }
         }
-        if let Some(temp) = request.temperature {
-            body["temperature"] = temp.into();
+        if reasoning_model {
+            if let Some(effort) = request.reasoning_effort {
+                body["reasoning_effort"] = serde_json::to_value(effort)?;
+            }
         }
         if let Some(tools) = &request.tools {
             body["tools"] = serde_json::to_value(self.convert_tools(tools))?;
         }
+        if let Some(extra) = &request.extra_body {
+            merge_extra_body(&mut body, extra);
+        }
 
         let response = self
             .client
@@ -352,6 +534,10 @@ impl LlmProvider for OpenAiProvider {
             let mut bytes_stream = response.bytes_stream();
             let mut buffer = String::new();
             let message_id = MessageId::new();
+            // OpenAI sends `id`/`name` only on a tool call's first delta, then
+            // streams further `arguments` fragments keyed solely by `index` —
+            // this tracks which open call each later fragment belongs to.
+            let mut open_tool_calls: std::collections::HashMap<u32, (String, String)> = std::collections::HashMap::new();
 
             yield StreamChunk::MessageStart { message_id };
 
@@ -371,6 +557,13 @@ impl LlmProvider for OpenAiProvider {
                         }
 
                         if let Ok(event) = serde_json::from_str::<OpenAiStreamEvent>(data) {
+                            if let Some(usage) = &event.usage {
+                                yield StreamChunk::Usage {
+                                    input_tokens: usage.prompt_tokens,
+                                    output_tokens: usage.completion_tokens,
+                                };
+                            }
+
                             if let Some(choice) = event.choices.first() {
                                 if let Some(content) = &choice.delta.content {
                                     yield StreamChunk::TextDelta { text: content.clone() };
@@ -378,16 +571,25 @@ impl LlmProvider for OpenAiProvider {
 
                                 if let Some(tool_calls) = &choice.delta.tool_calls {
                                     for tc in tool_calls {
+                                        let index = tc.index.unwrap_or(0);
+
                                         if let Some(ref func) = tc.function {
                                             if let Some(ref name) = func.name {
+                                                let id = tc.id.clone().unwrap_or_default();
+                                                open_tool_calls.insert(index, (id.clone(), name.clone()));
                                                 yield StreamChunk::ToolUseStart {
-                                                    id: tc.id.clone().unwrap_or_default(),
+                                                    id,
                                                     name: name.clone(),
                                                 };
                                             }
                                             if let Some(ref args) = func.arguments {
+                                                let id = open_tool_calls
+                                                    .get(&index)
+                                                    .map(|(id, _)| id.clone())
+                                                    .or_else(|| tc.id.clone())
+                                                    .unwrap_or_default();
                                                 yield StreamChunk::ToolUseDelta {
-                                                    id: tc.id.clone().unwrap_or_default(),
+                                                    id,
                                                     input_delta: args.clone(),
                                                 };
                                             }
@@ -537,6 +739,7 @@ struct OpenAiUsage {
 #[derive(Debug, Deserialize)]
 struct OpenAiStreamEvent {
     choices: Vec<OpenAiStreamChoice>,
+    usage: Option<OpenAiUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -553,6 +756,7 @@ struct OpenAiStreamDelta {
 
 #[derive(Debug, Deserialize)]
 struct OpenAiStreamToolCall {
+    index: Option<u32>,
     id: Option<String>,
     function: Option<OpenAiStreamFunction>,
 }
@@ -576,6 +780,81 @@ mod tests {
         assert!(provider.capabilities().has(&Capability::ToolUse));
     }
 
+    fn base_provider_config() -> ProviderConfig {
+        ProviderConfig {
+            provider_type: "openai".into(),
+            api_key: Some("test-key".into()),
+            base_url: None,
+            model: None,
+            max_retries: None,
+            timeout_secs: None,
+            connect_timeout_secs: None,
+            proxy_url: None,
+            extra_headers: std::collections::HashMap::new(),
+            input_price_per_1k: None,
+            output_price_per_1k: None,
+            extra: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_from_config_requires_api_key() {
+        let mut config = base_provider_config();
+        config.api_key = None;
+
+        let result = OpenAiProvider::from_config(&config);
+
+        assert!(matches!(result, Err(LlmError::Config(_))));
+    }
+
+    #[test]
+    fn test_from_config_applies_base_url_and_model() {
+        let mut config = base_provider_config();
+        config.base_url = Some("https://my-gateway.internal/v1".into());
+        config.model = Some("gpt-4o-custom".into());
+
+        let provider = OpenAiProvider::from_config(&config).unwrap();
+
+        assert_eq!(provider.base_url, "https://my-gateway.internal/v1");
+        assert_eq!(provider.default_model(), "gpt-4o-custom");
+    }
+
+    #[test]
+    fn test_from_config_rejects_invalid_extra_header_name() {
+        let mut config = base_provider_config();
+        config
+            .extra_headers
+            .insert("not a header name".into(), "value".into());
+
+        let result = OpenAiProvider::from_config(&config);
+
+        assert!(matches!(result, Err(LlmError::Config(_))));
+    }
+
+    mod registered {
+        use super::super::*;
+
+        crate::register_providers! {
+            test_backend {
+                base_url: "https://example.test/v1",
+                env_var: "HEHE_TEST_BACKEND_API_KEY",
+                default_model: "test-model",
+            },
+        }
+    }
+
+    #[test]
+    fn test_register_providers_macro_generates_constructor() {
+        std::env::set_var("HEHE_TEST_BACKEND_API_KEY", "from-env");
+
+        let provider = registered::test_backend().unwrap();
+
+        assert_eq!(provider.base_url, "https://example.test/v1");
+        assert_eq!(provider.default_model(), "test-model");
+
+        std::env::remove_var("HEHE_TEST_BACKEND_API_KEY");
+    }
+
     #[test]
     fn test_message_conversion() {
         let provider = OpenAiProvider::new("test-key");
@@ -585,7 +864,7 @@ mod tests {
             Message::assistant("Hi there!"),
         ];
 
-        let converted = provider.convert_messages(&messages, Some("You are helpful"));
+        let converted = provider.convert_messages(&messages, Some("You are helpful"), false);
 
         assert_eq!(converted.len(), 3);
         assert_eq!(converted[0].role, "system");
@@ -593,6 +872,24 @@ mod tests {
         assert_eq!(converted[2].role, "assistant");
     }
 
+    #[test]
+    fn test_convert_messages_uses_developer_role_for_reasoning_models() {
+        let provider = OpenAiProvider::new("test-key");
+        let messages = vec![Message::user("Hello")];
+
+        let converted = provider.convert_messages(&messages, Some("You are helpful"), true);
+
+        assert_eq!(converted[0].role, "developer");
+    }
+
+    #[test]
+    fn test_is_reasoning_model() {
+        assert!(is_reasoning_model("o1"));
+        assert!(is_reasoning_model("o1-mini"));
+        assert!(is_reasoning_model("o3-mini"));
+        assert!(!is_reasoning_model("gpt-4o"));
+    }
+
     #[tokio::test]
     async fn test_list_models() {
         let provider = OpenAiProvider::new("test-key");
@@ -601,4 +898,56 @@ mod tests {
         assert!(!models.is_empty());
         assert!(models.iter().any(|m| m.id == "gpt-4o"));
     }
+
+    #[test]
+    fn test_convert_response_format_json_schema() {
+        let provider = OpenAiProvider::new("test-key");
+        let format = ResponseFormat::json_schema(
+            "answer",
+            serde_json::json!({"type": "object"}),
+            true,
+        );
+
+        let value = provider.convert_response_format(&format);
+
+        assert_eq!(value["type"], "json_schema");
+        assert_eq!(value["json_schema"]["name"], "answer");
+        assert_eq!(value["json_schema"]["strict"], true);
+    }
+
+    #[test]
+    fn test_parse_response_rejects_invalid_json_when_schema_requested() {
+        let provider = OpenAiProvider::new("test-key");
+        let response = OpenAiResponse {
+            id: "resp".to_string(),
+            model: "gpt-4o".to_string(),
+            choices: vec![OpenAiChoice {
+                message: OpenAiResponseMessage {
+                    content: Some("not json".to_string()),
+                    tool_calls: None,
+                },
+                finish_reason: Some("stop".to_string()),
+            }],
+            usage: None,
+        };
+        let format = ResponseFormat::json_schema("answer", serde_json::json!({}), true);
+
+        let result = provider.parse_response(response, Some(&format));
+
+        assert!(matches!(result, Err(LlmError::InvalidResponse(_))));
+    }
+
+    #[test]
+    fn test_merge_extra_body_overrides_existing_keys() {
+        let mut body = serde_json::json!({ "model": "gpt-4o", "temperature": 0.7 });
+        let mut extra = serde_json::Map::new();
+        extra.insert("temperature".to_string(), serde_json::json!(0.2));
+        extra.insert("seed".to_string(), serde_json::json!(42));
+
+        merge_extra_body(&mut body, &extra);
+
+        assert_eq!(body["temperature"], 0.2);
+        assert_eq!(body["seed"], 42);
+        assert_eq!(body["model"], "gpt-4o");
+    }
 }
@@ -0,0 +1,255 @@
+use crate::error::{LlmError, Result};
+use crate::traits::{BoxStream, LlmProvider};
+use crate::types::{CompletionRequest, CompletionResponse, ModelInfo};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use hehe_core::capability::Capabilities;
+use hehe_core::event::{Event, EventEmitter, EventKind, EventPayload};
+use hehe_core::stream::StreamChunk;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const DEFAULT_MAX_ATTEMPTS: usize = 3;
+pub const DEFAULT_BASE_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Wraps an [`LlmProvider`], retrying retryable [`LlmError`]s (rate limits,
+/// timeouts, network errors — see [`LlmError::is_retryable`]) with exponential
+/// backoff and jitter before giving up. Non-retryable errors (auth, invalid
+/// request, ...) fail on the first attempt. For `complete_stream`, retries only
+/// happen before the first chunk is yielded, so a caller never sees duplicated
+/// output from a stream that failed partway through.
+pub struct RetryingProvider<P> {
+    inner: P,
+    max_attempts: usize,
+    base_backoff: Duration,
+    emitter: Option<Arc<dyn EventEmitter>>,
+}
+
+impl<P: LlmProvider> RetryingProvider<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_backoff: DEFAULT_BASE_BACKOFF,
+            emitter: None,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_base_backoff(mut self, backoff: Duration) -> Self {
+        self.base_backoff = backoff;
+        self
+    }
+
+    pub fn with_emitter(mut self, emitter: Arc<dyn EventEmitter>) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    async fn emit(&self, event: Event) {
+        if let Some(emitter) = &self.emitter {
+            emitter.emit(event).await;
+        }
+    }
+
+    async fn emit_started(&self, model: &str) {
+        self.emit(Event::new(EventKind::LlmRequestStarted).with_payload(EventPayload::Llm {
+            provider: self.inner.name().to_string(),
+            model: model.to_string(),
+            usage: None,
+        }))
+        .await;
+    }
+
+    async fn emit_failed(&self, error: &LlmError) {
+        self.emit(
+            Event::new(EventKind::LlmRequestFailed).with_payload(EventPayload::Error {
+                code: self.inner.name().to_string(),
+                message: error.to_string(),
+            }),
+        )
+        .await;
+    }
+
+    /// Exponential backoff from `base_backoff`, jittered by up to +/-25% so that
+    /// concurrent callers retrying the same failure don't all wake up in lockstep.
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let backoff = self.base_backoff * 2u32.saturating_pow(attempt.saturating_sub(1));
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let jitter = 0.75 + (nanos % 500) as f64 / 1000.0;
+        backoff.mul_f64(jitter)
+    }
+
+    fn should_retry(&self, error: &LlmError, attempt: usize) -> bool {
+        error.is_retryable() && attempt < self.max_attempts
+    }
+}
+
+#[async_trait]
+impl<P: LlmProvider> LlmProvider for RetryingProvider<P> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> &Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.emit_started(&request.model).await;
+
+            match self.inner.complete(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    self.emit_failed(&error).await;
+                    if !self.should_retry(&error, attempt) {
+                        return Err(error);
+                    }
+                    tokio::time::sleep(self.backoff_for(attempt as u32)).await;
+                }
+            }
+        }
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<BoxStream<StreamChunk>> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.emit_started(&request.model).await;
+
+            let mut inner_stream = match self.inner.complete_stream(request.clone()).await {
+                Ok(stream) => stream,
+                Err(error) => {
+                    self.emit_failed(&error).await;
+                    if !self.should_retry(&error, attempt) {
+                        return Err(error);
+                    }
+                    tokio::time::sleep(self.backoff_for(attempt as u32)).await;
+                    continue;
+                }
+            };
+
+            match inner_stream.next().await {
+                None => return Ok(Box::pin(stream::empty())),
+                Some(Ok(first)) => {
+                    return Ok(Box::pin(stream::once(async move { Ok(first) }).chain(inner_stream)));
+                }
+                Some(Err(error)) => {
+                    self.emit_failed(&error).await;
+                    if !self.should_retry(&error, attempt) {
+                        return Err(error);
+                    }
+                    tokio::time::sleep(self.backoff_for(attempt as u32)).await;
+                }
+            }
+        }
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        self.inner.list_models().await
+    }
+
+    fn default_model(&self) -> &str {
+        self.inner.default_model()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hehe_core::Message;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FlakyProvider {
+        failures_left: AtomicUsize,
+        error: fn() -> LlmError,
+    }
+
+    #[async_trait]
+    impl LlmProvider for FlakyProvider {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            if self.failures_left.fetch_sub(1, Ordering::SeqCst) > 0 {
+                return Err((self.error)());
+            }
+            Ok(CompletionResponse::new(
+                "id",
+                "flaky-model",
+                Message::assistant("recovered"),
+            ))
+        }
+
+        async fn complete_stream(&self, _request: CompletionRequest) -> Result<BoxStream<StreamChunk>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "flaky-model"
+        }
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new("flaky-model", vec![Message::user("hi")])
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_error_until_success() {
+        let provider = RetryingProvider::new(FlakyProvider {
+            failures_left: AtomicUsize::new(2),
+            error: || LlmError::Timeout(1000),
+        })
+        .with_max_attempts(5)
+        .with_base_backoff(Duration::from_millis(1));
+
+        let response = provider.complete(request()).await.unwrap();
+        assert_eq!(response.text_content(), "recovered");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let provider = RetryingProvider::new(FlakyProvider {
+            failures_left: AtomicUsize::new(10),
+            error: || LlmError::Network("connection reset".into()),
+        })
+        .with_max_attempts(2)
+        .with_base_backoff(Duration::from_millis(1));
+
+        let err = provider.complete(request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::Network(_)));
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_fails_fast() {
+        let provider = RetryingProvider::new(FlakyProvider {
+            failures_left: AtomicUsize::new(10),
+            error: || LlmError::AuthenticationFailed("bad key".into()),
+        })
+        .with_max_attempts(5)
+        .with_base_backoff(Duration::from_millis(1));
+
+        let err = provider.complete(request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::AuthenticationFailed(_)));
+    }
+}
@@ -0,0 +1,563 @@
+use crate::error::{LlmError, Result};
+use crate::traits::LlmProvider;
+use crate::types::{CompletionRequest, CompletionResponse};
+use hehe_core::capability::Capabilities;
+use hehe_core::config::{ProviderConfig, RoutingConfig, RoutingStrategy};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Smoothing factor for the latency EWMA: `ewma = alpha*sample_ms + (1-alpha)*ewma`.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// How many consecutive failures a provider must accrue before it's marked
+/// unhealthy and skipped for [`COOLDOWN`].
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a provider stays unhealthy after hitting [`FAILURE_THRESHOLD`]
+/// consecutive failures, before it's given another chance.
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Very rough chars-per-token heuristic used by `RoutingStrategy::CostOptimized`
+/// when no real tokenizer is available at routing time.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Per-provider runtime state tracked by [`ProviderRouter`]: rolling latency,
+/// and enough failure history to decide whether it's in its cooldown window.
+#[derive(Debug, Default, Clone)]
+struct ProviderState {
+    latency_ewma_ms: Option<f64>,
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+impl ProviderState {
+    fn is_healthy(&self) -> bool {
+        match self.unhealthy_until {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    fn record_success(&mut self, latency_ms: u64) {
+        self.consecutive_failures = 0;
+        self.unhealthy_until = None;
+        self.latency_ewma_ms = Some(match self.latency_ewma_ms {
+            Some(ewma) => LATENCY_EWMA_ALPHA * latency_ms as f64 + (1.0 - LATENCY_EWMA_ALPHA) * ewma,
+            None => latency_ms as f64,
+        });
+    }
+
+    fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= FAILURE_THRESHOLD {
+            self.unhealthy_until = Some(Instant::now() + COOLDOWN);
+        }
+    }
+}
+
+/// USD-per-1K-token pricing for a provider, used by `RoutingStrategy::CostOptimized`.
+#[derive(Debug, Clone, Copy)]
+struct ProviderPricing {
+    input_per_1k: f64,
+    output_per_1k: f64,
+}
+
+/// Dispatches [`CompletionRequest`]s across a set of named [`LlmProvider`]s
+/// according to a [`RoutingConfig`], tracking per-provider latency and
+/// health so `RoutingStrategy::LeastLatency` and `RoutingStrategy::Fallback`
+/// have live data to act on. Built once per [`hehe_core::config::LlmConfig`]
+/// and shared behind an `Arc` by callers that need to issue requests.
+pub struct ProviderRouter {
+    providers: HashMap<String, Arc<dyn LlmProvider>>,
+    pricing: HashMap<String, ProviderPricing>,
+    strategy: RoutingStrategy,
+    fallback_chain: Vec<String>,
+    round_robin_cursor: AtomicUsize,
+    state: RwLock<HashMap<String, ProviderState>>,
+}
+
+impl ProviderRouter {
+    /// Builds a router over `providers`, reading pricing out of the matching
+    /// entries of `provider_configs` (providers without a config, or without
+    /// an input price set, are estimated at zero cost and so win a
+    /// `CostOptimized` comparison).
+    pub fn new(
+        providers: HashMap<String, Arc<dyn LlmProvider>>,
+        provider_configs: &HashMap<String, ProviderConfig>,
+        routing: &RoutingConfig,
+    ) -> Self {
+        let pricing = provider_configs
+            .iter()
+            .filter_map(|(name, config)| {
+                Some((
+                    name.clone(),
+                    ProviderPricing {
+                        input_per_1k: config.input_price_per_1k?,
+                        output_per_1k: config.output_price_per_1k.unwrap_or(0.0),
+                    },
+                ))
+            })
+            .collect();
+
+        let state = providers
+            .keys()
+            .map(|name| (name.clone(), ProviderState::default()))
+            .collect();
+
+        Self {
+            providers,
+            pricing,
+            strategy: routing.strategy,
+            fallback_chain: routing.fallback_chain.clone(),
+            round_robin_cursor: AtomicUsize::new(0),
+            state: RwLock::new(state),
+        }
+    }
+
+    /// Routes `request` to a provider chosen by the configured
+    /// [`RoutingStrategy`], retrying on the fallback chain when that
+    /// strategy is [`RoutingStrategy::Fallback`].
+    pub async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        match self.strategy {
+            RoutingStrategy::Single => {
+                let name = self
+                    .healthy_names()
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| LlmError::ProviderNotAvailable("no providers configured".into()))?;
+                self.dispatch(&name, request).await
+            }
+            RoutingStrategy::RoundRobin => self.complete_round_robin(request).await,
+            RoutingStrategy::LeastLatency => self.complete_least_latency(request).await,
+            RoutingStrategy::CostOptimized => self.complete_cost_optimized(request).await,
+            RoutingStrategy::Fallback => self.complete_fallback(request).await,
+        }
+    }
+
+    async fn complete_round_robin(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let candidates = self.healthy_names();
+        if candidates.is_empty() {
+            return Err(LlmError::ProviderNotAvailable(
+                "no healthy providers available".into(),
+            ));
+        }
+
+        let index = self.round_robin_cursor.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        self.dispatch(&candidates[index], request).await
+    }
+
+    async fn complete_least_latency(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let candidates = self.healthy_names();
+        let name = {
+            let state = self.state.read().expect("provider state lock poisoned");
+            candidates
+                .into_iter()
+                .min_by(|a, b| {
+                    let latency = |name: &str| {
+                        state
+                            .get(name)
+                            .and_then(|s| s.latency_ewma_ms)
+                            .unwrap_or(0.0)
+                    };
+                    latency(a)
+                        .partial_cmp(&latency(b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .ok_or_else(|| LlmError::ProviderNotAvailable("no healthy providers available".into()))?
+        };
+        self.dispatch(&name, request).await
+    }
+
+    async fn complete_cost_optimized(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let required = required_capabilities(&request);
+        let name = self
+            .healthy_names()
+            .into_iter()
+            .filter(|name| {
+                self.providers
+                    .get(name)
+                    .map(|p| p.capabilities().has_all(&required))
+                    .unwrap_or(false)
+            })
+            .min_by(|a, b| {
+                self.estimated_cost(a, &request)
+                    .partial_cmp(&self.estimated_cost(b, &request))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .ok_or_else(|| {
+                LlmError::ProviderNotAvailable(
+                    "no healthy provider satisfies the requested capabilities".into(),
+                )
+            })?;
+        self.dispatch(&name, request).await
+    }
+
+    async fn complete_fallback(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let chain: Vec<&String> = if self.fallback_chain.is_empty() {
+            self.providers.keys().collect()
+        } else {
+            self.fallback_chain.iter().collect()
+        };
+
+        let mut last_error = None;
+        for name in chain {
+            if !self.is_healthy(name) {
+                continue;
+            }
+            match self.dispatch(name, request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            LlmError::ProviderNotAvailable("every provider in the fallback chain is unhealthy".into())
+        }))
+    }
+
+    /// Estimates USD cost of `request` against `name`'s pricing, assuming a
+    /// response of `request.max_tokens` output tokens (256 if unset) and
+    /// counting input tokens as roughly one per [`CHARS_PER_TOKEN`] characters
+    /// of prompt text. Providers without pricing configured cost `0.0`, so
+    /// they sort first.
+    fn estimated_cost(&self, name: &str, request: &CompletionRequest) -> f64 {
+        let Some(pricing) = self.pricing.get(name) else {
+            return 0.0;
+        };
+
+        let input_chars: usize = request
+            .system
+            .as_deref()
+            .map(str::len)
+            .unwrap_or(0)
+            + request
+                .messages
+                .iter()
+                .map(|m| m.text_content().len())
+                .sum::<usize>();
+        let input_tokens = (input_chars / CHARS_PER_TOKEN).max(1) as f64;
+        let output_tokens = request.max_tokens.unwrap_or(256) as f64;
+
+        (input_tokens / 1000.0) * pricing.input_per_1k + (output_tokens / 1000.0) * pricing.output_per_1k
+    }
+
+    async fn dispatch(&self, name: &str, request: CompletionRequest) -> Result<CompletionResponse> {
+        let provider = self
+            .providers
+            .get(name)
+            .ok_or_else(|| LlmError::ProviderNotAvailable(name.to_string()))?;
+
+        let started = Instant::now();
+        match provider.complete(request).await {
+            Ok(response) => {
+                self.record_success(name, started.elapsed());
+                Ok(response)
+            }
+            Err(error) => {
+                self.record_failure(name);
+                Err(error)
+            }
+        }
+    }
+
+    fn record_success(&self, name: &str, elapsed: Duration) {
+        let mut state = self.state.write().expect("provider state lock poisoned");
+        state
+            .entry(name.to_string())
+            .or_default()
+            .record_success(elapsed.as_millis() as u64);
+    }
+
+    fn record_failure(&self, name: &str) {
+        let mut state = self.state.write().expect("provider state lock poisoned");
+        state.entry(name.to_string()).or_default().record_failure();
+    }
+
+    fn is_healthy(&self, name: &str) -> bool {
+        self.state
+            .read()
+            .expect("provider state lock poisoned")
+            .get(name)
+            .map(ProviderState::is_healthy)
+            .unwrap_or(true)
+    }
+
+    fn healthy_names(&self) -> Vec<String> {
+        let state = self.state.read().expect("provider state lock poisoned");
+        let mut names: Vec<String> = self
+            .providers
+            .keys()
+            .filter(|name| state.get(*name).map(ProviderState::is_healthy).unwrap_or(true))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Reports per-provider health from the latency and failure state recorded
+    /// by past dispatches, without sending any request to a provider; the
+    /// same shape as [`hehe_store::StoreHealth`] but for LLM providers.
+    pub fn health(&self) -> RouterHealth {
+        let state = self.state.read().expect("provider state lock poisoned");
+        let providers = self
+            .providers
+            .keys()
+            .map(|name| {
+                let entry = state.get(name).cloned().unwrap_or_default();
+                (
+                    name.clone(),
+                    ProviderHealth {
+                        healthy: entry.is_healthy(),
+                        latency_ewma_ms: entry.latency_ewma_ms,
+                        consecutive_failures: entry.consecutive_failures,
+                    },
+                )
+            })
+            .collect();
+
+        RouterHealth { providers }
+    }
+}
+
+fn required_capabilities(request: &CompletionRequest) -> Vec<hehe_core::capability::Capability> {
+    use hehe_core::capability::Capability;
+
+    let mut required = vec![Capability::TextInput, Capability::TextOutput];
+    if request.tools.is_some() {
+        required.push(Capability::ToolUse);
+    }
+    if request.stream {
+        required.push(Capability::Streaming);
+    }
+    required
+}
+
+/// Snapshot of a single provider's health as tracked by [`ProviderRouter`].
+#[derive(Debug, Clone)]
+pub struct ProviderHealth {
+    pub healthy: bool,
+    pub latency_ewma_ms: Option<f64>,
+    pub consecutive_failures: u32,
+}
+
+/// The result of [`ProviderRouter::health`]: per-provider health keyed by
+/// provider name.
+#[derive(Debug, Clone)]
+pub struct RouterHealth {
+    pub providers: HashMap<String, ProviderHealth>,
+}
+
+impl RouterHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.providers.values().all(|h| h.healthy)
+    }
+
+    pub fn unhealthy_providers(&self) -> Vec<&str> {
+        self.providers
+            .iter()
+            .filter(|(_, h)| !h.healthy)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::BoxStream;
+    use crate::types::ModelInfo;
+    use async_trait::async_trait;
+    use hehe_core::stream::StreamChunk;
+    use hehe_core::Message;
+    use std::sync::atomic::AtomicU32;
+
+    struct StubProvider {
+        name: &'static str,
+        delay_ms: u64,
+        fail_calls: AtomicU32,
+        capabilities: Capabilities,
+    }
+
+    impl StubProvider {
+        fn new(name: &'static str, delay_ms: u64) -> Self {
+            Self {
+                name,
+                delay_ms,
+                fail_calls: AtomicU32::new(0),
+                capabilities: Capabilities::tool_capable(),
+            }
+        }
+
+        fn failing(name: &'static str, times: u32) -> Self {
+            Self {
+                name,
+                delay_ms: 0,
+                fail_calls: AtomicU32::new(times),
+                capabilities: Capabilities::tool_capable(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for StubProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            &self.capabilities
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            if self.fail_calls.load(Ordering::SeqCst) > 0 {
+                self.fail_calls.fetch_sub(1, Ordering::SeqCst);
+                return Err(LlmError::network("stub failure"));
+            }
+            if self.delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+            }
+            Ok(CompletionResponse::new(
+                "id",
+                self.name,
+                Message::assistant(self.name),
+            ))
+        }
+
+        async fn complete_stream(&self, _request: CompletionRequest) -> Result<BoxStream<StreamChunk>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new("model", vec![Message::user("hi")])
+    }
+
+    fn provider_map(providers: Vec<Arc<dyn LlmProvider>>) -> HashMap<String, Arc<dyn LlmProvider>> {
+        providers.into_iter().map(|p| (p.name().to_string(), p)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_cycles_through_providers() {
+        let providers = provider_map(vec![
+            Arc::new(StubProvider::new("a", 0)),
+            Arc::new(StubProvider::new("b", 0)),
+        ]);
+        let routing = RoutingConfig {
+            strategy: RoutingStrategy::RoundRobin,
+            fallback_chain: vec![],
+        };
+        let router = ProviderRouter::new(providers, &HashMap::new(), &routing);
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            let response = router.complete(request()).await.unwrap();
+            seen.push(response.model);
+        }
+
+        assert_eq!(seen, vec!["a", "b", "a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn test_least_latency_prefers_faster_provider_after_warmup() {
+        let providers = provider_map(vec![
+            Arc::new(StubProvider::new("slow", 40)),
+            Arc::new(StubProvider::new("fast", 0)),
+        ]);
+        let routing = RoutingConfig {
+            strategy: RoutingStrategy::LeastLatency,
+            fallback_chain: vec![],
+        };
+        let router = ProviderRouter::new(providers, &HashMap::new(), &routing);
+
+        for _ in 0..2 {
+            router.complete(request()).await.unwrap();
+        }
+
+        let response = router.complete(request()).await.unwrap();
+        assert_eq!(response.model, "fast");
+    }
+
+    #[tokio::test]
+    async fn test_cost_optimized_picks_cheaper_provider() {
+        let providers = provider_map(vec![
+            Arc::new(StubProvider::new("expensive", 0)),
+            Arc::new(StubProvider::new("cheap", 0)),
+        ]);
+        let mut configs = HashMap::new();
+        configs.insert(
+            "expensive".to_string(),
+            ProviderConfig {
+                provider_type: "stub".into(),
+                api_key: None,
+                base_url: None,
+                model: None,
+                max_retries: None,
+                timeout_secs: None,
+                connect_timeout_secs: None,
+                proxy_url: None,
+                extra_headers: HashMap::new(),
+                input_price_per_1k: Some(10.0),
+                output_price_per_1k: Some(10.0),
+                extra: HashMap::new(),
+            },
+        );
+        configs.insert(
+            "cheap".to_string(),
+            ProviderConfig {
+                provider_type: "stub".into(),
+                api_key: None,
+                base_url: None,
+                model: None,
+                max_retries: None,
+                timeout_secs: None,
+                connect_timeout_secs: None,
+                proxy_url: None,
+                extra_headers: HashMap::new(),
+                input_price_per_1k: Some(0.01),
+                output_price_per_1k: Some(0.01),
+                extra: HashMap::new(),
+            },
+        );
+        let routing = RoutingConfig {
+            strategy: RoutingStrategy::CostOptimized,
+            fallback_chain: vec![],
+        };
+        let router = ProviderRouter::new(providers, &configs, &routing);
+
+        let response = router.complete(request()).await.unwrap();
+        assert_eq!(response.model, "cheap");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_skips_provider_after_repeated_failures() {
+        let providers = provider_map(vec![
+            Arc::new(StubProvider::failing("flaky", 100)),
+            Arc::new(StubProvider::new("backup", 0)),
+        ]);
+        let routing = RoutingConfig {
+            strategy: RoutingStrategy::Fallback,
+            fallback_chain: vec!["flaky".to_string(), "backup".to_string()],
+        };
+        let router = ProviderRouter::new(providers, &HashMap::new(), &routing);
+
+        for _ in 0..FAILURE_THRESHOLD {
+            let response = router.complete(request()).await.unwrap();
+            assert_eq!(response.model, "backup");
+        }
+
+        let health = router.health();
+        assert!(!health.providers["flaky"].healthy);
+        assert!(health.providers["backup"].healthy);
+        assert_eq!(health.unhealthy_providers(), vec!["flaky"]);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::error::{LlmError, Result};
+use crate::traits::LlmProvider;
+use crate::types::{CompletionRequest, CompletionResponse};
+use futures::future::BoxFuture;
+use hehe_core::message::{ContentBlock, ToolResult};
+use hehe_core::{Message, Role};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// An async tool executor passed to [`complete_with_tools`]: takes a `ToolUse`'s
+/// JSON `input` and resolves to the text that becomes its `ToolResult` content.
+pub type ToolExecutorFn = Arc<dyn Fn(serde_json::Value) -> BoxFuture<'static, String> + Send + Sync>;
+
+/// Drives `request` against `llm` through as many rounds of tool calling as it
+/// takes to reach a final answer: call the model, and if its turn contains
+/// `ToolUse` blocks, run each one through the matching entry in `tools`
+/// (keyed by tool name), append a `Role::Tool` message with the `ToolResult`s
+/// (keyed by `tool_use_id`), and re-issue the request. Stops as soon as a turn
+/// comes back with no `ToolUse` blocks, returning that response. A call naming
+/// a tool missing from `tools` resolves to an error `ToolResult` rather than
+/// aborting the loop; exceeding `max_steps` rounds does abort it, with
+/// `LlmError::Tool`, to avoid spinning forever on a model that never stops
+/// calling tools. Reuses the provider's own `complete` (and so its
+/// `convert_messages`/`parse_response`) unchanged.
+pub async fn complete_with_tools(
+    llm: &dyn LlmProvider,
+    mut request: CompletionRequest,
+    tools: &HashMap<String, ToolExecutorFn>,
+    max_steps: usize,
+) -> Result<CompletionResponse> {
+    for _ in 0..max_steps {
+        let response = llm.complete(request.clone()).await?;
+        let tool_uses = response.message.tool_uses();
+
+        if tool_uses.is_empty() {
+            return Ok(response);
+        }
+
+        request.messages.push(response.message.clone());
+
+        let tool_result_content: Vec<ContentBlock> = {
+            let mut blocks = Vec::with_capacity(tool_uses.len());
+            for tu in &tool_uses {
+                let result = match tools.get(&tu.name) {
+                    Some(executor) => ToolResult::success(&tu.id, executor(tu.input.clone()).await),
+                    None => ToolResult::error(&tu.id, format!("Tool '{}' is not available", tu.name)),
+                };
+                blocks.push(ContentBlock::tool_result(result));
+            }
+            blocks
+        };
+
+        request.messages.push(Message::tool(tool_result_content));
+    }
+
+    Err(LlmError::tool(format!(
+        "exceeded max_steps ({max_steps}) without the model completing its turn"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use hehe_core::capability::Capabilities;
+    use hehe_core::message::ToolUse;
+    use hehe_core::stream::StreamChunk;
+    use crate::traits::BoxStream;
+    use crate::types::ModelInfo;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct ScriptedLlm {
+        responses: std::sync::Mutex<Vec<CompletionResponse>>,
+    }
+
+    impl ScriptedLlm {
+        fn new(responses: Vec<CompletionResponse>) -> Self {
+            Self {
+                responses: std::sync::Mutex::new(responses),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmProvider for ScriptedLlm {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+
+        async fn complete(&self, _request: CompletionRequest) -> Result<CompletionResponse> {
+            let mut responses = self.responses.lock().unwrap();
+            Ok(responses.remove(0))
+        }
+
+        async fn complete_stream(&self, _request: CompletionRequest) -> Result<BoxStream<StreamChunk>> {
+            use futures::stream;
+            Ok(Box::pin(stream::empty()))
+        }
+
+        async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+            Ok(vec![])
+        }
+
+        fn default_model(&self) -> &str {
+            "scripted"
+        }
+    }
+
+    fn tool_use_response(id: &str, name: &str, input: serde_json::Value) -> CompletionResponse {
+        CompletionResponse::new(
+            "resp",
+            "scripted",
+            Message::new(Role::Assistant, vec![ContentBlock::tool_use(ToolUse::new(id, name, input))]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_complete_with_tools_runs_until_final_answer() {
+        let llm = ScriptedLlm::new(vec![
+            tool_use_response("call_1", "add", serde_json::json!({"a": 1, "b": 2})),
+            CompletionResponse::new("resp", "scripted", Message::assistant("The answer is 3")),
+        ]);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = Arc::clone(&calls);
+        let mut tools: HashMap<String, ToolExecutorFn> = HashMap::new();
+        tools.insert(
+            "add".to_string(),
+            Arc::new(move |input: serde_json::Value| {
+                calls_clone.fetch_add(1, Ordering::SeqCst);
+                let sum = input["a"].as_i64().unwrap_or(0) + input["b"].as_i64().unwrap_or(0);
+                Box::pin(async move { sum.to_string() }) as BoxFuture<'static, String>
+            }),
+        );
+
+        let request = CompletionRequest::new("scripted", vec![Message::user("what is 1+2?")]);
+        let response = complete_with_tools(&llm, request, &tools, 5).await.unwrap();
+
+        assert_eq!(response.text_content(), "The answer is 3");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_complete_with_tools_reports_missing_tool_without_aborting() {
+        let llm = ScriptedLlm::new(vec![
+            tool_use_response("call_1", "unknown_tool", serde_json::json!({})),
+            CompletionResponse::new("resp", "scripted", Message::assistant("done")),
+        ]);
+
+        let tools: HashMap<String, ToolExecutorFn> = HashMap::new();
+        let request = CompletionRequest::new("scripted", vec![Message::user("hi")]);
+
+        let response = complete_with_tools(&llm, request, &tools, 5).await.unwrap();
+
+        assert_eq!(response.text_content(), "done");
+    }
+
+    #[tokio::test]
+    async fn test_complete_with_tools_errors_past_max_steps() {
+        let responses = (0..3)
+            .map(|i| tool_use_response(&format!("call_{i}"), "noop", serde_json::json!({})))
+            .collect();
+        let llm = ScriptedLlm::new(responses);
+
+        let mut tools: HashMap<String, ToolExecutorFn> = HashMap::new();
+        tools.insert(
+            "noop".to_string(),
+            Arc::new(|_| Box::pin(async { "ok".to_string() }) as BoxFuture<'static, String>),
+        );
+
+        let request = CompletionRequest::new("scripted", vec![Message::user("loop forever")]);
+
+        let result = complete_with_tools(&llm, request, &tools, 2).await;
+
+        assert!(matches!(result, Err(LlmError::Tool(_))));
+    }
+}
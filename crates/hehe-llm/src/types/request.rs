@@ -1,5 +1,6 @@
 use hehe_core::{Message, Metadata, ToolDefinition};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompletionRequest {
@@ -19,10 +20,22 @@ pub struct CompletionRequest {
     pub top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    /// Only honored by reasoning models (OpenAI's o1/o3 family); ignored by
+    /// providers/models that don't support it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
     #[serde(default)]
     pub stream: bool,
     #[serde(default, skip_serializing_if = "Metadata::is_empty")]
     pub metadata: Metadata,
+    /// Raw JSON merged into the provider's request body after every typed
+    /// field above, letting callers reach knobs (`seed`, `logit_bias`, ...)
+    /// this struct doesn't model. Because it's
+    /// merged last, a key here overrides the same key set by a typed field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_body: Option<serde_json::Map<String, Value>>,
 }
 
 impl CompletionRequest {
@@ -37,8 +50,11 @@ impl CompletionRequest {
             temperature: None,
             top_p: None,
             stop: None,
+            response_format: None,
+            reasoning_effort: None,
             stream: false,
             metadata: Metadata::new(),
+            extra_body: None,
         }
     }
 
@@ -77,6 +93,21 @@ impl CompletionRequest {
         self
     }
 
+    pub fn with_response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.response_format = Some(response_format);
+        self
+    }
+
+    pub fn with_extra_body(mut self, extra_body: serde_json::Map<String, Value>) -> Self {
+        self.extra_body = Some(extra_body);
+        self
+    }
+
+    pub fn with_reasoning_effort(mut self, reasoning_effort: ReasoningEffort) -> Self {
+        self.reasoning_effort = Some(reasoning_effort);
+        self
+    }
+
     pub fn streaming(mut self) -> Self {
         self.stream = true;
         self
@@ -98,6 +129,40 @@ impl Default for ToolChoice {
     }
 }
 
+/// Requests constrained output from the model. Providers that don't support
+/// a given variant are free to ignore it; [`crate::providers::OpenAiProvider`]
+/// serializes this into the `response_format` field OpenAI's chat completions
+/// API expects.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    Text,
+    JsonObject,
+    JsonSchema {
+        name: String,
+        schema: Value,
+        strict: bool,
+    },
+}
+
+impl ResponseFormat {
+    pub fn json_schema(name: impl Into<String>, schema: Value, strict: bool) -> Self {
+        Self::JsonSchema {
+            name: name.into(),
+            schema,
+            strict,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +192,35 @@ mod tests {
         .unwrap();
         assert!(tool.contains("search"));
     }
+
+    #[test]
+    fn test_with_response_format_sets_json_schema() {
+        let req = CompletionRequest::new("gpt-4", vec![Message::user("Hello")]).with_response_format(
+            ResponseFormat::json_schema("answer", serde_json::json!({"type": "object"}), true),
+        );
+
+        assert!(matches!(req.response_format, Some(ResponseFormat::JsonSchema { .. })));
+    }
+
+    #[test]
+    fn test_with_extra_body_sets_raw_fields() {
+        let mut extra = serde_json::Map::new();
+        extra.insert("seed".to_string(), serde_json::json!(42));
+
+        let req = CompletionRequest::new("gpt-4", vec![Message::user("Hello")]).with_extra_body(extra);
+
+        assert_eq!(req.extra_body.unwrap()["seed"], 42);
+    }
+
+    #[test]
+    fn test_with_reasoning_effort_serializes_snake_case() {
+        let req = CompletionRequest::new("o1", vec![Message::user("Hello")])
+            .with_reasoning_effort(ReasoningEffort::High);
+
+        assert_eq!(req.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            serde_json::to_string(&ReasoningEffort::High).unwrap(),
+            "\"high\""
+        );
+    }
 }
@@ -0,0 +1,302 @@
+//! Bearer-token authentication backed by Argon2id-hashed API keys.
+//!
+//! A validated request gets an [`Identity`] attached to its extensions,
+//! which routes can forward onto a [`hehe_core::Context`] via
+//! `Context::with_extension` — the same `Context` that already reaches
+//! tool execution, so a [`hehe_tools::Sandbox`] can read it back out with
+//! `Context::get_extension_typed::<Identity>` to make per-caller
+//! authorization decisions.
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::{Result, ServerError};
+
+/// One accepted API key. Presented credentials are `id.secret`, so a
+/// request only needs one Argon2id verification (against the entry named
+/// by `id`) instead of hashing the secret against every configured key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiKeyEntry {
+    pub id: String,
+    pub argon2_phc_hash: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl ApiKeyEntry {
+    pub fn new(id: impl Into<String>, argon2_phc_hash: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            argon2_phc_hash: argon2_phc_hash.into(),
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+}
+
+/// Argon2id cost parameters for hashing a *new* key with [`hash_api_key`].
+/// Verifying an existing hash reads its own cost parameters back out of
+/// the PHC string, so these don't need to match across key rotations.
+#[derive(Clone, Copy, Debug)]
+pub struct Argon2CostParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2CostParams {
+    fn default() -> Self {
+        // OWASP's minimum recommendation for Argon2id.
+        Self {
+            memory_kib: 19_456,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl Argon2CostParams {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        Self {
+            memory_kib,
+            iterations,
+            parallelism,
+        }
+    }
+
+    fn build(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+            .map_err(|e| ServerError::internal(format!("invalid Argon2 cost parameters: {e}")))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// Hashes `raw_key` into a salted Argon2id PHC string suitable for
+/// [`ApiKeyEntry::argon2_phc_hash`].
+pub fn hash_api_key(raw_key: &str, cost: Argon2CostParams) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    let argon2 = cost.build()?;
+    let hash = argon2
+        .hash_password(raw_key.as_bytes(), &salt)
+        .map_err(|e| ServerError::internal(format!("failed to hash API key: {e}")))?;
+    Ok(hash.to_string())
+}
+
+/// Verifies `raw_key` against a stored PHC hash. Comparison happens
+/// inside `password-hash`'s constant-time verifier, and the cost
+/// parameters are read from the hash itself, so this works regardless of
+/// which [`Argon2CostParams`] produced it.
+pub fn verify_api_key(raw_key: &str, phc_hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(phc_hash) else {
+        return false;
+    };
+    Argon2::default().verify_password(raw_key.as_bytes(), &parsed).is_ok()
+}
+
+/// The caller identity attached to an authenticated request's
+/// extensions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Identity {
+    pub id: String,
+    pub scopes: Vec<String>,
+}
+
+impl Identity {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// State the [`require_api_key`] middleware checks requests against.
+/// Kept independent of [`crate::state::AppState`] so auth can be
+/// configured without coupling it to agent/session state.
+#[derive(Clone)]
+pub struct AuthState {
+    keys: Arc<Vec<ApiKeyEntry>>,
+    allow_unauthenticated: bool,
+}
+
+impl AuthState {
+    pub fn new(keys: Vec<ApiKeyEntry>, allow_unauthenticated: bool) -> Self {
+        Self {
+            keys: Arc::new(keys),
+            allow_unauthenticated,
+        }
+    }
+
+    fn find(&self, id: &str) -> Option<&ApiKeyEntry> {
+        self.keys.iter().find(|k| k.id == id)
+    }
+}
+
+/// Validates an `Authorization: Bearer <id>.<secret>` header against
+/// [`AuthState`], attaching the resolved [`Identity`] to the request's
+/// extensions on success. A request with no `Authorization` header
+/// passes through unauthenticated when `allow_unauthenticated` is set
+/// (local development); otherwise every request must present a valid
+/// key.
+pub async fn require_api_key(State(auth): State<AuthState>, mut req: Request, next: Next) -> Result<Response> {
+    let header = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    let Some(header) = header else {
+        if auth.allow_unauthenticated {
+            return Ok(next.run(req).await);
+        }
+        return Err(ServerError::unauthorized("missing Authorization header"));
+    };
+
+    let presented = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| ServerError::unauthorized("Authorization header must use the Bearer scheme"))?;
+
+    let (id, secret) = presented
+        .split_once('.')
+        .ok_or_else(|| ServerError::unauthorized("API key must be in \"id.secret\" form"))?;
+
+    let entry = auth.find(id).ok_or_else(|| ServerError::unauthorized("unknown API key id"))?;
+
+    if !verify_api_key(secret, &entry.argon2_phc_hash) {
+        return Err(ServerError::unauthorized("invalid API key"));
+    }
+
+    req.extensions_mut().insert(Identity {
+        id: entry.id.clone(),
+        scopes: entry.scopes.clone(),
+    });
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Request as HttpRequest, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn test_entry() -> (ApiKeyEntry, &'static str) {
+        let secret = "s3cret";
+        let hash = hash_api_key(secret, Argon2CostParams::default()).unwrap();
+        (ApiKeyEntry::new("alice", hash).with_scope("chat"), secret)
+    }
+
+    #[test]
+    fn test_hash_and_verify_roundtrip() {
+        let hash = hash_api_key("hunter2", Argon2CostParams::default()).unwrap();
+        assert!(verify_api_key("hunter2", &hash));
+        assert!(!verify_api_key("wrong", &hash));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_hash() {
+        assert!(!verify_api_key("hunter2", "not a phc string"));
+    }
+
+    async fn protected() -> &'static str {
+        "ok"
+    }
+
+    fn test_router(auth: AuthState) -> Router {
+        Router::new()
+            .route("/protected", get(protected))
+            .layer(axum::middleware::from_fn_with_state(auth, require_api_key))
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_credentials_by_default() {
+        let (entry, _secret) = test_entry();
+        let router = test_router(AuthState::new(vec![entry], false));
+
+        let response = router
+            .oneshot(HttpRequest::builder().uri("/protected").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_allows_missing_credentials_when_configured() {
+        let (entry, _secret) = test_entry();
+        let router = test_router(AuthState::new(vec![entry], true));
+
+        let response = router
+            .oneshot(HttpRequest::builder().uri("/protected").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_valid_key() {
+        let (entry, secret) = test_entry();
+        let router = test_router(AuthState::new(vec![entry], false));
+
+        let response = router
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/protected")
+                    .header(header::AUTHORIZATION, format!("Bearer alice.{secret}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_wrong_secret() {
+        let (entry, _secret) = test_entry();
+        let router = test_router(AuthState::new(vec![entry], false));
+
+        let response = router
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/protected")
+                    .header(header::AUTHORIZATION, "Bearer alice.wrong")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_key_id() {
+        let (entry, secret) = test_entry();
+        let router = test_router(AuthState::new(vec![entry], false));
+
+        let response = router
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/protected")
+                    .header(header::AUTHORIZATION, format!("Bearer bob.{secret}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use hehe_agent::{Agent, AgentConfig};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::error::{Result, ServerError};
+
+/// Which top-level [`AgentConfig`] fields differed between the
+/// last-applied config and a freshly reloaded one.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReloadOutcome {
+    pub changed_fields: Vec<String>,
+}
+
+/// Hot-reloads an [`Agent`]'s [`AgentConfig`] from a file on disk, either
+/// on demand via [`Self::reload`] or continuously via [`Self::watch`]. A
+/// file that fails to parse is logged and rejected; the agent keeps
+/// running with its last-good config.
+pub struct ConfigReloader {
+    path: PathBuf,
+    agent: Arc<Agent>,
+}
+
+impl ConfigReloader {
+    pub fn new(path: impl Into<PathBuf>, agent: Arc<Agent>) -> Self {
+        Self { path: path.into(), agent }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads and parses [`Self::path`] as TOML, diffs it against the
+    /// agent's current config, and swaps it in. Returns the names of the
+    /// fields that changed (empty if the file was unchanged). The agent's
+    /// config is left untouched if the file can't be read or parsed.
+    pub async fn reload(&self) -> Result<ReloadOutcome> {
+        let contents = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            ServerError::internal(format!("failed to read config file {}: {e}", self.path.display()))
+        })?;
+
+        let new_config: AgentConfig = toml::from_str(&contents).map_err(|e| {
+            ServerError::internal(format!("invalid config file {}: {e}", self.path.display()))
+        })?;
+
+        let changed_fields = diff_fields(&self.agent.config(), &new_config);
+        self.agent.update_config(new_config);
+
+        if !changed_fields.is_empty() {
+            info!(path = %self.path.display(), changed = ?changed_fields, "reloaded agent config");
+        }
+
+        Ok(ReloadOutcome { changed_fields })
+    }
+
+    /// Spawns a background task that watches [`Self::path`] and calls
+    /// [`Self::reload`] whenever it changes. Reload failures are logged,
+    /// not propagated, so a single bad edit doesn't kill the watcher.
+    pub fn watch(self: Arc<Self>) -> Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let mut watcher: RecommendedWatcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    let _ = tx.send(event);
+                }
+            })
+            .map_err(|e| ServerError::internal(format!("failed to start config watcher: {e}")))?;
+
+        watcher
+            .watch(&self.path, RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                ServerError::internal(format!("failed to watch {}: {e}", self.path.display()))
+            })?;
+
+        tokio::spawn(async move {
+            // Held for the task's lifetime so the OS watch isn't torn down early.
+            let _watcher = watcher;
+
+            while let Some(event) = rx.recv().await {
+                if !event.kind.is_modify() && !event.kind.is_create() {
+                    continue;
+                }
+
+                match self.reload().await {
+                    Ok(_) => {}
+                    Err(e) => warn!(path = %self.path.display(), error = %e, "rejected invalid config reload"),
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+fn diff_fields(old: &AgentConfig, new: &AgentConfig) -> Vec<String> {
+    let mut changed = Vec::new();
+
+    macro_rules! check {
+        ($field:ident) => {
+            if old.$field != new.$field {
+                changed.push(stringify!($field).to_string());
+            }
+        };
+    }
+
+    check!(name);
+    check!(system_prompt);
+    check!(model);
+    check!(temperature);
+    check!(max_tokens);
+    check!(max_iterations);
+    check!(max_context_messages);
+    check!(tool_timeout_secs);
+    check!(tools_enabled);
+    check!(max_parallel_tools);
+    check!(llm_timeout_secs);
+    check!(max_llm_retries);
+    check!(llm_retry_base_backoff_ms);
+    check!(llm_retry_max_backoff_ms);
+    check!(context_token_budget);
+    check!(keep_recent_messages);
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use hehe_core::capability::Capabilities;
+    use hehe_core::stream::StreamChunk;
+    use hehe_core::Message;
+    use hehe_llm::{BoxStream, CompletionRequest, CompletionResponse, LlmError, LlmProvider, ModelInfo};
+
+    struct MockLlm;
+
+    #[async_trait]
+    impl LlmProvider for MockLlm {
+        fn name(&self) -> &str { "mock" }
+        fn capabilities(&self) -> &Capabilities {
+            static CAPS: std::sync::OnceLock<Capabilities> = std::sync::OnceLock::new();
+            CAPS.get_or_init(Capabilities::text_basic)
+        }
+        async fn complete(&self, _: CompletionRequest) -> std::result::Result<CompletionResponse, LlmError> {
+            Ok(CompletionResponse::new("id", "mock", Message::assistant("Hi")))
+        }
+        async fn complete_stream(&self, _: CompletionRequest) -> std::result::Result<BoxStream<StreamChunk>, LlmError> {
+            use futures::stream;
+            Ok(Box::pin(stream::empty()))
+        }
+        async fn list_models(&self) -> std::result::Result<Vec<ModelInfo>, LlmError> { Ok(vec![]) }
+        fn default_model(&self) -> &str { "mock" }
+    }
+
+    fn test_agent() -> Arc<Agent> {
+        Arc::new(
+            Agent::builder()
+                .system_prompt("Original prompt")
+                .model("mock")
+                .llm(Arc::new(MockLlm))
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn temp_config_path() -> PathBuf {
+        std::env::temp_dir().join(format!("hehe-server-config-reload-test-{}.toml", hehe_core::Id::new()))
+    }
+
+    #[tokio::test]
+    async fn test_reload_applies_new_config_and_reports_changed_fields() {
+        let agent = test_agent();
+        let path = temp_config_path();
+        tokio::fs::write(
+            &path,
+            r#"
+            system_prompt = "Updated prompt"
+            model = "mock"
+            temperature = 0.9
+            "#,
+        )
+        .await
+        .unwrap();
+
+        let reloader = ConfigReloader::new(&path, agent.clone());
+        let outcome = reloader.reload().await.unwrap();
+
+        assert!(outcome.changed_fields.contains(&"system_prompt".to_string()));
+        assert!(outcome.changed_fields.contains(&"temperature".to_string()));
+        assert_eq!(agent.config().system_prompt, "Updated prompt");
+        assert_eq!(agent.config().temperature, 0.9);
+    }
+
+    #[tokio::test]
+    async fn test_reload_reports_no_changes_for_identical_config() {
+        let agent = test_agent();
+        let path = temp_config_path();
+        let current = agent.config();
+        tokio::fs::write(&path, toml::to_string(&current).unwrap()).await.unwrap();
+
+        let reloader = ConfigReloader::new(&path, agent);
+        let outcome = reloader.reload().await.unwrap();
+
+        assert!(outcome.changed_fields.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reload_rejects_invalid_file_and_keeps_last_good_config() {
+        let agent = test_agent();
+        let path = temp_config_path();
+        tokio::fs::write(&path, "not valid toml {{{").await.unwrap();
+
+        let reloader = ConfigReloader::new(&path, agent.clone());
+        let result = reloader.reload().await;
+
+        assert!(result.is_err());
+        assert_eq!(agent.config().system_prompt, "Original prompt");
+    }
+}
@@ -0,0 +1,184 @@
+//! CORS enforcement driven by [`crate::config::ServerConfig`]'s
+//! `cors_origins`/`allowed_methods`/`allowed_headers`/`allow_credentials`/
+//! `cors_max_age_secs` fields.
+//!
+//! Unlike a blanket `Access-Control-Allow-Origin: *`, this matches the
+//! incoming `Origin` against the configured allow-list and echoes back that
+//! single origin rather than the whole list or a wildcard once credentials
+//! are involved (browsers reject `*` alongside
+//! `Access-Control-Allow-Credentials: true`). Origins that don't match get
+//! no CORS headers at all, so the browser enforces same-origin as usual.
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub allow_credentials: bool,
+    pub max_age: Duration,
+}
+
+impl CorsConfig {
+    fn origin_allowed(&self, origin: &str) -> bool {
+        self.allowed_origins.iter().any(|allowed| allowed == "*" || allowed == origin)
+    }
+
+    fn has_wildcard(&self) -> bool {
+        self.allowed_origins.iter().any(|allowed| allowed == "*")
+    }
+
+    /// The value to send back in `Access-Control-Allow-Origin`, or `None` if
+    /// `origin` isn't on the allow-list at all.
+    fn allow_origin_value(&self, origin: &str) -> Option<String> {
+        if !self.origin_allowed(origin) {
+            return None;
+        }
+
+        if self.has_wildcard() && !self.allow_credentials {
+            Some("*".to_string())
+        } else {
+            Some(origin.to_string())
+        }
+    }
+}
+
+fn origin_header(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::ORIGIN)?.to_str().ok()
+}
+
+fn is_preflight(req: &Request) -> bool {
+    req.method() == Method::OPTIONS && req.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+fn set_header(headers: &mut HeaderMap, name: header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+/// Builds the full set of CORS response headers for a matched origin,
+/// shared between the preflight short-circuit and the post-response pass
+/// over an actual request.
+fn cors_headers(config: &CorsConfig, origin: &str, for_preflight: bool) -> Option<HeaderMap> {
+    let allow_origin = config.allow_origin_value(origin)?;
+
+    let mut headers = HeaderMap::new();
+    set_header(&mut headers, header::ACCESS_CONTROL_ALLOW_ORIGIN, &allow_origin);
+    headers.insert(header::VARY, HeaderValue::from_static("origin"));
+
+    if config.allow_credentials {
+        set_header(&mut headers, header::ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+
+    if for_preflight {
+        set_header(
+            &mut headers,
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            &config.allowed_methods.join(", "),
+        );
+        set_header(
+            &mut headers,
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            &config.allowed_headers.join(", "),
+        );
+        set_header(
+            &mut headers,
+            header::ACCESS_CONTROL_MAX_AGE,
+            &config.max_age.as_secs().to_string(),
+        );
+    }
+
+    Some(headers)
+}
+
+/// Answers CORS preflight (`OPTIONS`) requests directly and attaches
+/// `Access-Control-*` response headers to every other request whose
+/// `Origin` matches `config`. Requests with no `Origin` header pass straight
+/// through. A preflight from an unmatched origin gets a bare `204` with no
+/// CORS headers; any other request from one passes through with none added.
+pub async fn enforce_cors(State(config): State<CorsConfig>, req: Request, next: Next) -> Response {
+    let origin = origin_header(req.headers()).map(str::to_string);
+
+    let Some(origin) = origin else {
+        return next.run(req).await;
+    };
+
+    if is_preflight(&req) {
+        return match cors_headers(&config, &origin, true) {
+            Some(headers) => (StatusCode::NO_CONTENT, headers).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        };
+    }
+
+    let mut response = next.run(req).await;
+    if let Some(headers) = cors_headers(&config, &origin, false) {
+        response.headers_mut().extend(headers);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(origins: &[&str], allow_credentials: bool) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: origins.iter().map(|s| s.to_string()).collect(),
+            allowed_methods: vec!["GET".to_string(), "POST".to_string()],
+            allowed_headers: vec!["content-type".to_string()],
+            allow_credentials,
+            max_age: Duration::from_secs(600),
+        }
+    }
+
+    #[test]
+    fn test_exact_origin_is_echoed_back() {
+        let config = config(&["https://example.com"], false);
+        assert_eq!(
+            config.allow_origin_value("https://example.com"),
+            Some("https://example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unmatched_origin_gets_no_header() {
+        let config = config(&["https://example.com"], false);
+        assert_eq!(config.allow_origin_value("https://evil.example"), None);
+    }
+
+    #[test]
+    fn test_wildcard_without_credentials_sends_wildcard() {
+        let config = config(&["*"], false);
+        assert_eq!(config.allow_origin_value("https://anything.example"), Some("*".to_string()));
+    }
+
+    #[test]
+    fn test_wildcard_with_credentials_echoes_origin_instead() {
+        let config = config(&["*"], true);
+        assert_eq!(
+            config.allow_origin_value("https://anything.example"),
+            Some("https://anything.example".to_string())
+        );
+    }
+
+    #[test]
+    fn test_preflight_headers_include_methods_and_max_age() {
+        let config = config(&["https://example.com"], false);
+        let headers = cors_headers(&config, "https://example.com", true).unwrap();
+        assert_eq!(headers.get(header::ACCESS_CONTROL_ALLOW_METHODS).unwrap(), "GET, POST");
+        assert_eq!(headers.get(header::ACCESS_CONTROL_MAX_AGE).unwrap(), "600");
+    }
+
+    #[test]
+    fn test_non_preflight_headers_omit_methods() {
+        let config = config(&["https://example.com"], false);
+        let headers = cors_headers(&config, "https://example.com", false).unwrap();
+        assert!(headers.get(header::ACCESS_CONTROL_ALLOW_METHODS).is_none());
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+/// A protocol + address [`crate::Server`] is (or will be) listening on.
+/// Lets callers (e.g. a `--help`/startup banner) report every listener
+/// without caring whether HTTP/3 is compiled in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    Tcp(SocketAddr),
+    #[cfg(feature = "http3")]
+    Quic(SocketAddr),
+}
+
+impl Endpoint {
+    pub fn addr(&self) -> SocketAddr {
+        match self {
+            Endpoint::Tcp(addr) => *addr,
+            #[cfg(feature = "http3")]
+            Endpoint::Quic(addr) => *addr,
+        }
+    }
+
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            Endpoint::Tcp(_) => "http/1.1+2",
+            #[cfg(feature = "http3")]
+            Endpoint::Quic(_) => "http/3",
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.addr(), self.protocol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_endpoint_display() {
+        let endpoint = Endpoint::Tcp("127.0.0.1:3000".parse().unwrap());
+        assert_eq!(endpoint.protocol(), "http/1.1+2");
+        assert_eq!(endpoint.to_string(), "127.0.0.1:3000 (http/1.1+2)");
+    }
+}
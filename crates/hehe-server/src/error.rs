@@ -21,6 +21,14 @@ pub enum ServerError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[cfg(feature = "event-log")]
+    #[error("Store error: {0}")]
+    Store(#[from] hehe_store::StoreError),
+
+    #[cfg(feature = "auth")]
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
 }
 
 pub type Result<T> = std::result::Result<T, ServerError>;
@@ -39,6 +47,10 @@ impl IntoResponse for ServerError {
             ServerError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             ServerError::Agent(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
             ServerError::Serialization(e) => (StatusCode::BAD_REQUEST, e.to_string()),
+            #[cfg(feature = "event-log")]
+            ServerError::Store(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            #[cfg(feature = "auth")]
+            ServerError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
         };
 
         let body = Json(ErrorResponse {
@@ -62,4 +74,9 @@ impl ServerError {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    #[cfg(feature = "auth")]
+    pub fn unauthorized(msg: impl Into<String>) -> Self {
+        Self::Unauthorized(msg.into())
+    }
 }
@@ -0,0 +1,222 @@
+//! Persists [`AgentEvent`]s emitted by `/api/v1/chat/stream` so a client that
+//! disconnects mid-turn can reconnect and replay everything it missed
+//! instead of starting the conversation over.
+
+use hehe_agent::AgentEvent;
+use hehe_core::{Id, Timestamp};
+use hehe_store::{Migration, RelationalStore, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::error::{Result, ServerError};
+
+/// One persisted [`AgentEvent`], tagged with the monotonically increasing
+/// sequence number it was appended at within its session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredEvent {
+    pub seq: u64,
+    pub session_id: Id,
+    pub event: AgentEvent,
+    pub recorded_at: Timestamp,
+}
+
+fn migrations() -> Vec<Migration> {
+    vec![Migration::new(
+        1,
+        "create_agent_events",
+        "CREATE TABLE IF NOT EXISTS agent_events (
+            session_id TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            event_json TEXT NOT NULL,
+            recorded_at_ms INTEGER NOT NULL,
+            PRIMARY KEY (session_id, seq)
+        )",
+    )]
+}
+
+/// Appends, replays, and prunes [`AgentEvent`]s for a [`RelationalStore`].
+/// Sequence numbers are per-session and start at 1.
+pub struct EventLog {
+    store: Arc<dyn RelationalStore>,
+}
+
+impl EventLog {
+    pub async fn new(store: Arc<dyn RelationalStore>) -> Result<Self> {
+        store.migrate(&migrations()).await?;
+        Ok(Self { store })
+    }
+
+    /// Appends `event` for `session_id`, returning the sequence number it
+    /// was stored at.
+    pub async fn append(&self, session_id: Id, event: &AgentEvent) -> Result<u64> {
+        let seq = self.next_seq(session_id).await?;
+        let event_json = serde_json::to_string(event)?;
+        let recorded_at_ms = Timestamp::now().unix_millis();
+
+        self.store
+            .execute(
+                "INSERT INTO agent_events (session_id, seq, event_json, recorded_at_ms) VALUES (?, ?, ?, ?)",
+                &[
+                    Value::String(session_id.to_string()),
+                    Value::from(seq),
+                    Value::String(event_json),
+                    Value::from(recorded_at_ms),
+                ],
+            )
+            .await?;
+
+        Ok(seq)
+    }
+
+    async fn next_seq(&self, session_id: Id) -> Result<u64> {
+        let row = self
+            .store
+            .query_one(
+                "SELECT MAX(seq) as max_seq FROM agent_events WHERE session_id = ?",
+                &[Value::String(session_id.to_string())],
+            )
+            .await?;
+
+        Ok(row.and_then(|r| r.get_i64("max_seq")).unwrap_or(0) as u64 + 1)
+    }
+
+    /// Events for `session_id` with `seq > after_seq`, oldest first, capped
+    /// at `limit` — enough for a reconnecting client to replay
+    /// `TextDelta`/`ToolUseStart`/`ToolUseEnd` and rebuild the transcript.
+    pub async fn history(&self, session_id: Id, after_seq: u64, limit: usize) -> Result<Vec<StoredEvent>> {
+        let rows = self
+            .store
+            .query(
+                "SELECT seq, event_json, recorded_at_ms FROM agent_events
+                 WHERE session_id = ? AND seq > ?
+                 ORDER BY seq ASC
+                 LIMIT ?",
+                &[
+                    Value::String(session_id.to_string()),
+                    Value::from(after_seq),
+                    Value::from(limit as i64),
+                ],
+            )
+            .await?;
+
+        rows.iter().map(|row| row_to_stored_event(session_id, row)).collect()
+    }
+
+    /// Deletes everything but the most recent `keep` events for
+    /// `session_id` — the retention policy, keyed by the caller off
+    /// [`hehe_agent::AgentConfig::max_context_messages`].
+    pub async fn prune(&self, session_id: Id, keep: usize) -> Result<u64> {
+        self.store
+            .execute(
+                "DELETE FROM agent_events
+                 WHERE session_id = ?
+                 AND seq <= (
+                     SELECT COALESCE(MAX(seq), 0) - ? FROM agent_events WHERE session_id = ?
+                 )",
+                &[
+                    Value::String(session_id.to_string()),
+                    Value::from(keep as i64),
+                    Value::String(session_id.to_string()),
+                ],
+            )
+            .await
+            .map_err(ServerError::from)
+    }
+}
+
+fn row_to_stored_event(session_id: Id, row: &Row) -> Result<StoredEvent> {
+    let seq = row
+        .get_i64("seq")
+        .ok_or_else(|| ServerError::internal("agent_events row missing seq"))? as u64;
+    let event_json = row
+        .get_str("event_json")
+        .ok_or_else(|| ServerError::internal("agent_events row missing event_json"))?;
+    let event: AgentEvent = serde_json::from_str(event_json)?;
+    let recorded_at_ms = row
+        .get_i64("recorded_at_ms")
+        .ok_or_else(|| ServerError::internal("agent_events row missing recorded_at_ms"))?;
+    let recorded_at = Timestamp::from_unix_millis(recorded_at_ms)
+        .ok_or_else(|| ServerError::internal("invalid recorded_at_ms in agent_events row"))?;
+
+    Ok(StoredEvent {
+        seq,
+        session_id,
+        event,
+        recorded_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hehe_store::SqliteStore;
+
+    async fn test_log() -> EventLog {
+        let store: Arc<dyn RelationalStore> = Arc::new(SqliteStore::memory().unwrap());
+        EventLog::new(store).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_append_assigns_increasing_seq_per_session() {
+        let log = test_log().await;
+        let session_id = Id::new();
+
+        let first = log.append(session_id, &AgentEvent::text_delta("Hel")).await.unwrap();
+        let second = log.append(session_id, &AgentEvent::text_delta("lo")).await.unwrap();
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 2);
+    }
+
+    #[tokio::test]
+    async fn test_history_returns_events_after_seq_in_order() {
+        let log = test_log().await;
+        let session_id = Id::new();
+
+        log.append(session_id, &AgentEvent::text_delta("a")).await.unwrap();
+        log.append(session_id, &AgentEvent::text_delta("b")).await.unwrap();
+        log.append(session_id, &AgentEvent::text_delta("c")).await.unwrap();
+
+        let events = log.history(session_id, 1, 10).await.unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].seq, 2);
+        assert_eq!(events[1].seq, 3);
+    }
+
+    #[tokio::test]
+    async fn test_history_is_scoped_to_session() {
+        let log = test_log().await;
+        let a = Id::new();
+        let b = Id::new();
+
+        log.append(a, &AgentEvent::text_delta("for a")).await.unwrap();
+        log.append(b, &AgentEvent::text_delta("for b")).await.unwrap();
+
+        let events = log.history(a, 0, 10).await.unwrap();
+        assert_eq!(events.len(), 1);
+        if let AgentEvent::TextDelta { delta } = &events[0].event {
+            assert_eq!(delta, "for a");
+        } else {
+            panic!("expected a TextDelta event");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_only_the_most_recent_events() {
+        let log = test_log().await;
+        let session_id = Id::new();
+
+        for i in 0..5 {
+            log.append(session_id, &AgentEvent::text_delta(i.to_string())).await.unwrap();
+        }
+
+        log.prune(session_id, 2).await.unwrap();
+
+        let events = log.history(session_id, 0, 10).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].seq, 4);
+        assert_eq!(events[1].seq, 5);
+    }
+}
@@ -0,0 +1,152 @@
+//! Experimental HTTP/3 (QUIC) listener, served alongside the TCP HTTP/1-2
+//! listener when the `http3` feature is on and TLS is configured (see
+//! [`crate::config::ServerConfig::quic_socket_addr`]).
+//!
+//! `h3`/`quinn` speak QUIC streams, not `tower`/`axum::Router`, so each
+//! accepted stream is turned into an `http::Request`, dispatched through the
+//! router with [`tower::ServiceExt::oneshot`], and its response streamed back
+//! frame by frame. That's enough to give `/api/v1/chat/stream`'s token
+//! deltas HTTP/3's head-of-line-blocking-free multiplexing across sessions.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::Router;
+use bytes::{Bytes, BytesMut};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::error::{Result, ServerError};
+
+pub(crate) async fn serve_quic(addr: SocketAddr, cert_path: &Path, key_path: &Path, router: Router) -> Result<()> {
+    let tls_config = load_tls_config(cert_path, key_path)?;
+    let server_config = quinn::ServerConfig::with_crypto(Arc::new(
+        quinn::crypto::rustls::QuicServerConfig::try_from(tls_config)
+            .map_err(|e| ServerError::internal(format!("invalid HTTP/3 TLS config: {e}")))?,
+    ));
+    let endpoint = quinn::Endpoint::server(server_config, addr)
+        .map_err(|e| ServerError::internal(format!("failed to bind QUIC endpoint {addr}: {e}")))?;
+
+    info!("HTTP/3 (QUIC) listening on {addr}");
+
+    while let Some(connecting) = endpoint.accept().await {
+        let router = router.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(connecting, router).await {
+                warn!(error = %e, "HTTP/3 connection ended with an error");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle_connection(connecting: quinn::Connecting, router: Router) -> Result<()> {
+    let connection = connecting
+        .await
+        .map_err(|e| ServerError::internal(format!("QUIC handshake failed: {e}")))?;
+
+    let mut h3_conn = h3::server::Connection::new(h3_quinn::Connection::new(connection))
+        .await
+        .map_err(|e| ServerError::internal(format!("HTTP/3 handshake failed: {e}")))?;
+
+    while let Some((request, stream)) = h3_conn
+        .accept()
+        .await
+        .map_err(|e| ServerError::internal(format!("HTTP/3 stream accept failed: {e}")))?
+    {
+        let router = router.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_request(request, stream, router).await {
+                warn!(error = %e, "HTTP/3 request failed");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle_request<S>(
+    request: http::Request<()>,
+    mut stream: h3::server::RequestStream<S, Bytes>,
+    router: Router,
+) -> Result<()>
+where
+    S: h3::quic::BidiStream<Bytes>,
+{
+    let mut body = BytesMut::new();
+    while let Some(chunk) = stream
+        .recv_data()
+        .await
+        .map_err(|e| ServerError::internal(format!("failed to read HTTP/3 request body: {e}")))?
+    {
+        body.extend_from_slice(chunk.chunk());
+    }
+
+    let request = request.map(|_| axum::body::Body::from(body.freeze()));
+
+    let response = router
+        .oneshot(request)
+        .await
+        .map_err(|e: std::convert::Infallible| ServerError::internal(e.to_string()))?;
+
+    let (parts, mut body) = response.into_parts();
+    stream
+        .send_response(http::Response::from_parts(parts, ()))
+        .await
+        .map_err(|e| ServerError::internal(format!("failed to send HTTP/3 response headers: {e}")))?;
+
+    while let Some(frame) = body
+        .frame()
+        .await
+        .transpose()
+        .map_err(|e| ServerError::internal(format!("failed to read response body: {e}")))?
+    {
+        if let Some(data) = frame.data_ref() {
+            stream
+                .send_data(data.clone())
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to send HTTP/3 response body: {e}")))?;
+        }
+    }
+
+    stream
+        .finish()
+        .await
+        .map_err(|e| ServerError::internal(format!("failed to finish HTTP/3 stream: {e}")))?;
+
+    Ok(())
+}
+
+fn load_tls_config(cert_path: &Path, key_path: &Path) -> Result<rustls::ServerConfig> {
+    let certs = load_certs(cert_path)?;
+    let key = load_private_key(key_path)?;
+
+    let mut config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| ServerError::internal(format!("invalid TLS cert/key: {e}")))?;
+    config.alpn_protocols = vec![b"h3".to_vec()];
+
+    Ok(config)
+}
+
+fn load_certs(path: &Path) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| ServerError::internal(format!("failed to read TLS cert {}: {e}", path.display())))?;
+    let mut reader = std::io::BufReader::new(file);
+    rustls_pemfile::certs(&mut reader)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| ServerError::internal(format!("invalid TLS cert {}: {e}", path.display())))
+}
+
+fn load_private_key(path: &Path) -> Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| ServerError::internal(format!("failed to read TLS key {}: {e}", path.display())))?;
+    let mut reader = std::io::BufReader::new(file);
+    rustls_pemfile::private_key(&mut reader)
+        .map_err(|e| ServerError::internal(format!("invalid TLS key {}: {e}", path.display())))?
+        .ok_or_else(|| ServerError::internal(format!("no private key found in {}", path.display())))
+}
@@ -1,17 +1,65 @@
+#[cfg(feature = "auth")]
+pub mod auth;
 pub mod config;
+#[cfg(feature = "config-reload")]
+pub mod config_reload;
+pub mod cors;
+pub mod endpoint;
 pub mod error;
+#[cfg(feature = "event-log")]
+pub mod event_log;
+#[cfg(feature = "http3")]
+mod http3;
 pub mod routes;
 pub mod server;
 pub mod state;
+pub mod store;
+pub mod timeouts;
+#[cfg(feature = "auth")]
+pub mod token;
 
+#[cfg(feature = "auth")]
+pub use auth::{ApiKeyEntry, Argon2CostParams, AuthState, Identity};
 pub use config::ServerConfig;
+#[cfg(feature = "config-reload")]
+pub use config_reload::{ConfigReloader, ReloadOutcome};
+pub use cors::CorsConfig;
+pub use endpoint::Endpoint;
 pub use error::{Result, ServerError};
+#[cfg(feature = "event-log")]
+pub use event_log::{EventLog, StoredEvent};
 pub use server::{shutdown_signal, Server};
 pub use state::AppState;
+pub use store::{InMemorySessionStore, SessionStore};
+pub use timeouts::Timeouts;
+#[cfg(feature = "auth")]
+pub use token::{TokenAuthState, TokenSigner, TokenVerifier};
+
+#[cfg(feature = "file-store")]
+pub use store::FileSessionStore;
+#[cfg(feature = "sqlite-store")]
+pub use store::SqliteSessionStore;
 
 pub mod prelude {
+    #[cfg(feature = "auth")]
+    pub use crate::auth::{ApiKeyEntry, Argon2CostParams, AuthState, Identity};
     pub use crate::config::ServerConfig;
+    #[cfg(feature = "config-reload")]
+    pub use crate::config_reload::{ConfigReloader, ReloadOutcome};
+    pub use crate::cors::CorsConfig;
+    pub use crate::endpoint::Endpoint;
     pub use crate::error::{Result, ServerError};
+    #[cfg(feature = "event-log")]
+    pub use crate::event_log::{EventLog, StoredEvent};
     pub use crate::server::{shutdown_signal, Server};
     pub use crate::state::AppState;
+    pub use crate::store::{InMemorySessionStore, SessionStore};
+    pub use crate::timeouts::Timeouts;
+    #[cfg(feature = "auth")]
+    pub use crate::token::{TokenAuthState, TokenSigner, TokenVerifier};
+
+    #[cfg(feature = "file-store")]
+    pub use crate::store::FileSessionStore;
+    #[cfg(feature = "sqlite-store")]
+    pub use crate::store::SqliteSessionStore;
 }
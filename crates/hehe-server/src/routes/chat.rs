@@ -1,19 +1,39 @@
 use axum::{
     extract::State,
+    http::HeaderMap,
     response::sse::{Event, KeepAlive, Sse},
     Json,
 };
 use futures::stream::Stream;
 use hehe_agent::AgentEvent;
-use hehe_core::Id;
+use hehe_core::{Context, Id};
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
+use std::pin::Pin;
 use std::str::FromStr;
 use tokio_stream::StreamExt;
 
 use crate::error::{Result, ServerError};
+use crate::routes::version::check_protocol_header;
 use crate::state::AppState;
 
+#[cfg(feature = "auth")]
+use crate::auth::Identity;
+#[cfg(feature = "auth")]
+use axum::extract::Extension;
+
+/// Builds the `Context` passed into the agent, carrying the caller's
+/// [`Identity`] (if any) so anything downstream that reads a `Context` —
+/// including a `hehe_tools::Sandbox` — can make per-caller authorization
+/// decisions.
+#[cfg(feature = "auth")]
+fn context_for(identity: Option<Extension<Identity>>) -> Context {
+    match identity {
+        Some(Extension(identity)) => Context::new().with_extension(identity),
+        None => Context::new(),
+    }
+}
+
 #[derive(Deserialize)]
 pub struct ChatRequest {
     pub session_id: Option<String>,
@@ -40,17 +60,28 @@ pub struct ToolCallInfo {
 
 pub async fn chat(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    #[cfg(feature = "auth")] identity: Option<Extension<Identity>>,
     Json(request): Json<ChatRequest>,
 ) -> Result<Json<ChatResponse>> {
+    check_protocol_header(&headers)?;
+
     let session_id = request.session_id.and_then(|s| Id::from_str(&s).ok());
-    let session = state.get_or_create_session(session_id).await;
+    let session = state.get_or_create_session(session_id).await?;
+
+    #[cfg(feature = "auth")]
+    let ctx = context_for(identity);
+    #[cfg(not(feature = "auth"))]
+    let ctx = Context::new();
 
     let response = state
         .agent
-        .process(&session, &request.message)
+        .process_with_context(&ctx, &session, &request.message)
         .await
         .map_err(ServerError::from)?;
 
+    state.save_session(&session).await?;
+
     Ok(Json(ChatResponse {
         session_id: session.id().to_string(),
         response: response.text,
@@ -68,17 +99,75 @@ pub async fn chat(
     }))
 }
 
+type SseStream = Pin<Box<dyn Stream<Item = std::result::Result<Event, Infallible>> + Send>>;
+
+/// A one-shot SSE stream carrying a single `error` event, for failures
+/// discovered before the real event stream can be built (e.g. a rejected
+/// session lookup or protocol mismatch).
+fn error_stream(message: String) -> Sse<SseStream> {
+    let data = serde_json::json!({
+        "type": "error",
+        "message": message,
+    });
+    let stream = futures::stream::once(async move { Ok(Event::default().data(data.to_string())) });
+    Sse::new(Box::pin(stream)).keep_alive(KeepAlive::default())
+}
+
 pub async fn chat_stream(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    #[cfg(feature = "auth")] identity: Option<Extension<Identity>>,
     Json(request): Json<ChatRequest>,
-) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+) -> Sse<SseStream> {
+    if let Err(e) = check_protocol_header(&headers) {
+        return error_stream(e.to_string());
+    }
+
     let session_id = request.session_id.and_then(|s| Id::from_str(&s).ok());
-    let session = state.get_or_create_session(session_id).await;
+    let session = match state.get_or_create_session(session_id).await {
+        Ok(session) => session,
+        Err(e) => return error_stream(e.to_string()),
+    };
     let message = request.message;
+    #[cfg(feature = "event-log")]
+    let session_id = *session.id();
+
+    #[cfg(feature = "auth")]
+    let ctx = context_for(identity);
+    #[cfg(not(feature = "auth"))]
+    let ctx = Context::new();
+
+    let event_stream = state.agent.chat_stream_with_context(ctx, &session, &message);
+
+    #[cfg(feature = "event-log")]
+    let log_state = state.clone();
+    let persist_state = state.clone();
+    let persist_session = session.clone();
 
-    let event_stream = state.agent.chat_stream(&session, &message);
+    let sse_stream = event_stream.map(move |event| {
+        #[cfg(feature = "event-log")]
+        {
+            let log_state = log_state.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                if let Err(e) = log_state.record_event(session_id, &event).await {
+                    tracing::warn!(error = %e, session_id = %session_id, "failed to persist agent event");
+                }
+            });
+        }
+
+        // A turn ends once, at `MessageEnd`/`Error` — persisting there (rather
+        // than after every event) keeps the store from being hammered mid-turn.
+        if event.is_end() {
+            let persist_state = persist_state.clone();
+            let persist_session = persist_session.clone();
+            tokio::spawn(async move {
+                if let Err(e) = persist_state.save_session(&persist_session).await {
+                    tracing::warn!(error = %e, "failed to persist session after turn");
+                }
+            });
+        }
 
-    let sse_stream = event_stream.map(|event| {
         let data = match &event {
             AgentEvent::MessageStart { session_id } => {
                 serde_json::json!({
@@ -86,6 +175,12 @@ pub async fn chat_stream(
                     "session_id": session_id.to_string()
                 })
             }
+            AgentEvent::IterationStart { iteration } => {
+                serde_json::json!({
+                    "type": "iteration_start",
+                    "iteration": iteration
+                })
+            }
             AgentEvent::TextDelta { delta } => {
                 serde_json::json!({
                     "type": "text_delta",
@@ -106,12 +201,21 @@ pub async fn chat_stream(
                     "input": input
                 })
             }
-            AgentEvent::ToolUseEnd { id, output, is_error } => {
+            AgentEvent::ConfirmationRequired { id, name, input } => {
+                serde_json::json!({
+                    "type": "confirmation_required",
+                    "id": id,
+                    "name": name,
+                    "input": input
+                })
+            }
+            AgentEvent::ToolUseEnd { id, output, is_error, duration_ms } => {
                 serde_json::json!({
                     "type": "tool_use_end",
                     "id": id,
                     "output": output,
-                    "is_error": is_error
+                    "is_error": is_error,
+                    "duration_ms": duration_ms
                 })
             }
             AgentEvent::Thinking { content } => {
@@ -126,6 +230,13 @@ pub async fn chat_stream(
                     "session_id": session_id.to_string()
                 })
             }
+            AgentEvent::Retry { attempt, delay_ms } => {
+                serde_json::json!({
+                    "type": "retry",
+                    "attempt": attempt,
+                    "delay_ms": delay_ms
+                })
+            }
             AgentEvent::Error { message } => {
                 serde_json::json!({
                     "type": "error",
@@ -137,5 +248,5 @@ pub async fn chat_stream(
         Ok(Event::default().data(data.to_string()))
     });
 
-    Sse::new(sse_stream).keep_alive(KeepAlive::default())
+    Sse::new(Box::pin(sse_stream)).keep_alive(KeepAlive::default())
 }
@@ -1,6 +1,7 @@
 use axum::{extract::State, Json};
 use serde::Serialize;
 
+use crate::error::Result;
 use crate::state::AppState;
 
 #[derive(Serialize)]
@@ -22,11 +23,11 @@ pub async fn health() -> Json<HealthResponse> {
     })
 }
 
-pub async fn ready(State(state): State<AppState>) -> Json<ReadyResponse> {
-    Json(ReadyResponse {
+pub async fn ready(State(state): State<AppState>) -> Result<Json<ReadyResponse>> {
+    Ok(Json(ReadyResponse {
         status: "ready".to_string(),
-        sessions: state.session_count().await,
-    })
+        sessions: state.session_count().await?,
+    }))
 }
 
 #[cfg(test)]
@@ -0,0 +1,47 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use hehe_core::Id;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::{Result, ServerError};
+use crate::event_log::StoredEvent;
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct HistoryQuery {
+    pub session_id: String,
+    #[serde(default)]
+    pub after_seq: u64,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    100
+}
+
+#[derive(Serialize)]
+pub struct HistoryResponse {
+    pub session_id: String,
+    pub events: Vec<StoredEvent>,
+}
+
+/// `GET /api/v1/chat/history?session_id=...&after_seq=...&limit=...` —
+/// past `AgentEvent`s for a session, oldest first, so a reconnecting client
+/// can replay `TextDelta`/`ToolUseStart`/`ToolUseEnd` and rebuild the
+/// transcript instead of starting the conversation over.
+pub async fn history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<HistoryResponse>> {
+    let session_id = Id::from_str(&query.session_id)
+        .map_err(|_| ServerError::bad_request(format!("invalid session_id: {}", query.session_id)))?;
+
+    let events = state.event_history(session_id, query.after_seq, query.limit).await?;
+
+    Ok(Json(HistoryResponse {
+        session_id: query.session_id,
+        events,
+    }))
+}
@@ -1,5 +1,19 @@
 pub mod chat;
 pub mod health;
+#[cfg(feature = "event-log")]
+pub mod history;
+pub mod openai;
+#[cfg(feature = "config-reload")]
+pub mod reload;
+pub mod session_history;
+pub mod version;
 
 pub use chat::{chat, chat_stream, ChatRequest, ChatResponse};
 pub use health::{health, ready, HealthResponse, ReadyResponse};
+#[cfg(feature = "event-log")]
+pub use history::{history, HistoryQuery, HistoryResponse};
+pub use openai::chat_completions;
+#[cfg(feature = "config-reload")]
+pub use reload::reload;
+pub use session_history::{session_history, SessionHistoryQuery, SessionHistoryResponse};
+pub use version::{version, ProtocolVersion, ServerCapabilities, VersionResponse};
@@ -0,0 +1,455 @@
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::stream::{Stream, StreamExt};
+use hehe_core::message::{ContentBlock, ToolResult};
+use hehe_core::stream::{StopReason, StreamChunk};
+use hehe_core::tool::JsonSchemaType;
+use hehe_core::{Id, Message, Role, ToolDefinition, ToolParameter};
+use hehe_llm::{CompletionRequest, CompletionResponse, ToolChoice};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::pin::Pin;
+
+use crate::error::{Result, ServerError};
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct ChatCompletionsRequest {
+    pub model: String,
+    pub messages: Vec<OpenAiRequestMessage>,
+    #[serde(default)]
+    pub tools: Option<Vec<OpenAiToolSpec>>,
+    #[serde(default)]
+    pub tool_choice: Option<Value>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub stream: bool,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAiRequestMessage {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Option<Vec<OpenAiRequestToolCall>>,
+    #[serde(default)]
+    pub tool_call_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAiRequestToolCall {
+    pub id: String,
+    pub function: OpenAiRequestFunctionCall,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAiRequestFunctionCall {
+    pub name: String,
+    pub arguments: String,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAiToolSpec {
+    pub function: OpenAiFunctionSpec,
+}
+
+#[derive(Deserialize)]
+pub struct OpenAiFunctionSpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Value,
+}
+
+/// Translates an OpenAI JSON Schema `parameters` object into this crate's
+/// [`ToolParameter`] tree, recursing through `items`/`properties` so nested
+/// object and array schemas round-trip.
+fn tool_parameter_from_json_schema(schema: &Value) -> ToolParameter {
+    let schema_type = match schema.get("type").and_then(Value::as_str) {
+        Some("string") => JsonSchemaType::String,
+        Some("number") => JsonSchemaType::Number,
+        Some("integer") => JsonSchemaType::Integer,
+        Some("boolean") => JsonSchemaType::Boolean,
+        Some("array") => JsonSchemaType::Array,
+        Some("null") => JsonSchemaType::Null,
+        _ => JsonSchemaType::Object,
+    };
+
+    ToolParameter {
+        schema_type,
+        description: schema.get("description").and_then(Value::as_str).map(String::from),
+        default: schema.get("default").cloned(),
+        enum_values: schema.get("enum").and_then(Value::as_array).cloned(),
+        items: schema.get("items").map(|items| Box::new(tool_parameter_from_json_schema(items))),
+        properties: schema.get("properties").and_then(Value::as_object).map(|props| {
+            props
+                .iter()
+                .map(|(name, prop)| (name.clone(), tool_parameter_from_json_schema(prop)))
+                .collect()
+        }),
+        required: schema.get("required").and_then(Value::as_array).map(|names| {
+            names.iter().filter_map(Value::as_str).map(String::from).collect()
+        }),
+    }
+}
+
+fn tool_definition_from_spec(spec: &OpenAiToolSpec) -> ToolDefinition {
+    ToolDefinition::new(&spec.function.name, spec.function.description.clone().unwrap_or_default())
+        .with_parameters(tool_parameter_from_json_schema(&spec.function.parameters))
+}
+
+fn tool_choice_from_value(value: &Value) -> Option<ToolChoice> {
+    match value {
+        Value::String(s) if s == "auto" => Some(ToolChoice::Auto),
+        Value::String(s) if s == "none" => Some(ToolChoice::None),
+        Value::String(s) if s == "required" => Some(ToolChoice::Required),
+        Value::Object(_) => value
+            .get("function")
+            .and_then(|f| f.get("name"))
+            .and_then(Value::as_str)
+            .map(|name| ToolChoice::Tool { name: name.to_string() }),
+        _ => None,
+    }
+}
+
+/// Converts one incoming OpenAI-shaped message into this crate's `Message`.
+/// Assistant messages with `tool_calls` carry their `function.arguments` as a
+/// JSON-encoded string; a malformed one is rejected here with a clear error
+/// rather than forwarded raw into a `ToolUse.input` that nothing downstream
+/// could parse either.
+fn message_from_openai(msg: &OpenAiRequestMessage) -> Result<Message> {
+    let role = match msg.role.as_str() {
+        "system" => Role::System,
+        "user" => Role::User,
+        "assistant" => Role::Assistant,
+        "tool" => Role::Tool,
+        other => return Err(ServerError::bad_request(format!("unsupported message role: {other}"))),
+    };
+
+    if role == Role::Tool {
+        let tool_use_id = msg
+            .tool_call_id
+            .clone()
+            .ok_or_else(|| ServerError::bad_request("tool message is missing tool_call_id"))?;
+        let content = msg.content.clone().unwrap_or_default();
+        return Ok(Message::tool(vec![ContentBlock::ToolResult(ToolResult::success(
+            tool_use_id,
+            content,
+        ))]));
+    }
+
+    let mut content = Vec::new();
+    if let Some(text) = &msg.content {
+        if !text.is_empty() {
+            content.push(ContentBlock::text(text));
+        }
+    }
+
+    for tool_call in msg.tool_calls.iter().flatten() {
+        let input = serde_json::from_str::<Value>(&tool_call.function.arguments).map_err(|e| {
+            ServerError::bad_request(format!(
+                "malformed tool_calls arguments for '{}': {e}",
+                tool_call.function.name
+            ))
+        })?;
+        content.push(ContentBlock::tool_use(hehe_core::message::ToolUse::new(
+            tool_call.id.clone(),
+            tool_call.function.name.clone(),
+            input,
+        )));
+    }
+
+    if content.is_empty() {
+        content.push(ContentBlock::text(""));
+    }
+
+    Ok(Message::new(role, content))
+}
+
+fn build_completion_request(request: ChatCompletionsRequest) -> Result<CompletionRequest> {
+    let mut messages = Vec::with_capacity(request.messages.len());
+    for msg in &request.messages {
+        messages.push(message_from_openai(msg)?);
+    }
+
+    let mut completion_request = CompletionRequest::new(request.model, messages);
+
+    if let Some(tools) = &request.tools {
+        let definitions = tools.iter().map(tool_definition_from_spec).collect();
+        completion_request = completion_request.with_tools(definitions);
+    }
+
+    if let Some(choice) = request.tool_choice.as_ref().and_then(tool_choice_from_value) {
+        completion_request = completion_request.with_tool_choice(choice);
+    }
+
+    if let Some(temperature) = request.temperature {
+        completion_request = completion_request.with_temperature(temperature);
+    }
+
+    if let Some(max_tokens) = request.max_tokens {
+        completion_request = completion_request.with_max_tokens(max_tokens);
+    }
+
+    Ok(completion_request)
+}
+
+fn finish_reason_for(stop_reason: Option<&StopReason>) -> Option<&'static str> {
+    match stop_reason {
+        Some(StopReason::EndTurn) => Some("stop"),
+        Some(StopReason::StopSequence) => Some("stop"),
+        Some(StopReason::MaxTokens) => Some("length"),
+        Some(StopReason::ToolUse) => Some("tool_calls"),
+        None => None,
+    }
+}
+
+#[derive(Serialize)]
+struct ChatCompletionObject {
+    id: String,
+    object: &'static str,
+    model: String,
+    choices: Vec<ChatCompletionChoice>,
+    usage: ChatCompletionUsage,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionChoice {
+    index: u32,
+    message: ChatCompletionResponseMessage,
+    finish_reason: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionResponseMessage {
+    role: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<ChatCompletionResponseToolCall>>,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionResponseToolCall {
+    id: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: ChatCompletionResponseFunctionCall,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionResponseFunctionCall {
+    name: String,
+    arguments: String,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionUsage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+    total_tokens: u32,
+}
+
+fn chat_completion_object_from(response: CompletionResponse) -> ChatCompletionObject {
+    let tool_uses = response.message.tool_uses();
+    let tool_calls = if tool_uses.is_empty() {
+        None
+    } else {
+        Some(
+            tool_uses
+                .into_iter()
+                .map(|tu| ChatCompletionResponseToolCall {
+                    id: tu.id.clone(),
+                    kind: "function",
+                    function: ChatCompletionResponseFunctionCall {
+                        name: tu.name.clone(),
+                        arguments: tu.input.to_string(),
+                    },
+                })
+                .collect(),
+        )
+    };
+
+    let text = response.text_content();
+    let content = if text.is_empty() && tool_calls.is_some() { None } else { Some(text) };
+
+    ChatCompletionObject {
+        id: response.id,
+        object: "chat.completion",
+        model: response.model,
+        choices: vec![ChatCompletionChoice {
+            index: 0,
+            message: ChatCompletionResponseMessage {
+                role: "assistant",
+                content,
+                tool_calls,
+            },
+            finish_reason: finish_reason_for(response.stop_reason.as_ref()),
+        }],
+        usage: ChatCompletionUsage {
+            prompt_tokens: response.usage.input_tokens,
+            completion_tokens: response.usage.output_tokens,
+            total_tokens: response.usage.total(),
+        },
+    }
+}
+
+async fn chat_completions_json(state: AppState, request: ChatCompletionsRequest) -> Result<Json<ChatCompletionObject>> {
+    let completion_request = build_completion_request(request)?;
+
+    let response = state
+        .agent
+        .llm()
+        .complete(completion_request)
+        .await
+        .map_err(|e| ServerError::internal(e.to_string()))?;
+
+    Ok(Json(chat_completion_object_from(response)))
+}
+
+type SseStream = Pin<Box<dyn Stream<Item = std::result::Result<Event, Infallible>> + Send>>;
+
+fn chunk_event(id: &str, model: &str, delta: Value, finish_reason: Option<&str>) -> Event {
+    let data = serde_json::json!({
+        "id": id,
+        "object": "chat.completion.chunk",
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "delta": delta,
+            "finish_reason": finish_reason,
+        }],
+    });
+    Event::default().data(data.to_string())
+}
+
+fn error_event(message: String) -> Event {
+    let data = serde_json::json!({
+        "error": { "message": message, "type": "internal_error" },
+    });
+    Event::default().data(data.to_string())
+}
+
+/// A one-shot SSE stream carrying a single OpenAI-shaped error event, for
+/// failures discovered before the real event stream can be built (e.g. a
+/// malformed incoming message).
+fn error_stream(message: String) -> Sse<SseStream> {
+    let stream = futures::stream::once(async move { Ok(error_event(message)) });
+    Sse::new(Box::pin(stream)).keep_alive(KeepAlive::default())
+}
+
+async fn chat_completions_sse(state: AppState, request: ChatCompletionsRequest) -> Sse<SseStream> {
+    let completion_request = match build_completion_request(request) {
+        Ok(req) => req,
+        Err(e) => return error_stream(e.to_string()),
+    };
+    let model = completion_request.model.clone();
+    let response_id = format!("chatcmpl-{}", Id::new());
+
+    let llm = state.agent.llm().clone();
+    let upstream = match llm.complete_stream(completion_request).await {
+        Ok(stream) => stream,
+        Err(e) => return error_stream(e.to_string()),
+    };
+
+    let mut tool_call_indices: HashMap<String, usize> = HashMap::new();
+
+    // Only chunks with something a client needs to react to become a
+    // `chat.completion.chunk`; bookkeeping events with no OpenAI analogue
+    // (`MessageStart`, `ToolUseEnd`, `Ping`, ...) are dropped rather than
+    // forwarded as empty-delta noise.
+    let sse_stream = upstream.filter_map(move |chunk| {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => return std::future::ready(Some(Ok(error_event(e.to_string())))),
+        };
+
+        let event = match chunk {
+            StreamChunk::TextDelta { text } => Some(chunk_event(
+                &response_id,
+                &model,
+                serde_json::json!({ "content": text }),
+                None,
+            )),
+            StreamChunk::ToolUseStart { id, name } => {
+                let index = tool_call_indices.len();
+                tool_call_indices.insert(id.clone(), index);
+                Some(chunk_event(
+                    &response_id,
+                    &model,
+                    serde_json::json!({
+                        "tool_calls": [{
+                            "index": index,
+                            "id": id,
+                            "type": "function",
+                            "function": { "name": name, "arguments": "" },
+                        }],
+                    }),
+                    None,
+                ))
+            }
+            StreamChunk::ToolUseDelta { id, input_delta } => {
+                let index = tool_call_indices.get(&id).copied().unwrap_or(0);
+                Some(chunk_event(
+                    &response_id,
+                    &model,
+                    serde_json::json!({
+                        "tool_calls": [{
+                            "index": index,
+                            "function": { "arguments": input_delta },
+                        }],
+                    }),
+                    None,
+                ))
+            }
+            StreamChunk::MessageEnd { stop_reason } => Some(chunk_event(
+                &response_id,
+                &model,
+                serde_json::json!({}),
+                finish_reason_for(stop_reason.as_ref()),
+            )),
+            StreamChunk::Error { message, .. } => Some(error_event(message)),
+            StreamChunk::MessageStart { .. }
+            | StreamChunk::ToolUseEnd { .. }
+            | StreamChunk::ContentBlockStart { .. }
+            | StreamChunk::ContentBlockEnd { .. }
+            | StreamChunk::Usage { .. }
+            | StreamChunk::Ping => None,
+        };
+
+        std::future::ready(event.map(Ok))
+    });
+
+    let done = futures::stream::once(async { Ok(Event::default().data("[DONE]")) });
+
+    Sse::new(Box::pin(sse_stream.chain(done))).keep_alive(KeepAlive::default())
+}
+
+/// OpenAI-compatible `POST /v1/chat/completions`. Both streaming and
+/// non-streaming replies live behind this one route, same as the real API —
+/// the `stream` field on the request body picks the response shape, not the
+/// URL.
+pub async fn chat_completions(
+    State(state): State<AppState>,
+    Json(request): Json<ChatCompletionsRequest>,
+) -> Response {
+    if request.stream {
+        chat_completions_sse(state, request).await.into_response()
+    } else {
+        match chat_completions_json(state, request).await {
+            Ok(json) => json.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
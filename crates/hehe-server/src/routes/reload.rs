@@ -0,0 +1,13 @@
+use axum::{extract::State, Json};
+
+use crate::config_reload::ReloadOutcome;
+use crate::error::Result;
+use crate::state::AppState;
+
+/// `POST /api/v1/reload` — re-reads the watched `AgentConfig` file on
+/// demand and applies it immediately, without waiting for the filesystem
+/// watcher to notice the change.
+pub async fn reload(State(state): State<AppState>) -> Result<Json<ReloadOutcome>> {
+    let outcome = state.reload_config().await?;
+    Ok(Json(outcome))
+}
@@ -0,0 +1,66 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use hehe_core::{Id, Message, Timestamp};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::{Result, ServerError};
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct SessionHistoryQuery {
+    pub before: Option<i64>,
+    pub after: Option<i64>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    100
+}
+
+#[derive(Serialize)]
+pub struct SessionHistoryResponse {
+    pub session_id: String,
+    pub messages: Vec<Message>,
+}
+
+/// `GET /sessions/:id/history?before=...&after=...&limit=...` — a page of a
+/// session's messages, so a UI can lazily scroll back through a long
+/// conversation or a reconnecting client can fetch only what's new.
+/// `before`/`after` are Unix millisecond timestamps selecting messages
+/// strictly on that side of the cutoff; passing neither returns the most
+/// recent `limit` messages, and passing both is a bad request since they
+/// select opposite directions through the transcript.
+pub async fn session_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<SessionHistoryQuery>,
+) -> Result<Json<SessionHistoryResponse>> {
+    let session_id =
+        Id::from_str(&id).map_err(|_| ServerError::bad_request(format!("invalid session id: {id}")))?;
+
+    let session = state
+        .get_session(&session_id)
+        .await?
+        .ok_or_else(|| ServerError::not_found(format!("no session with id {id}")))?;
+
+    let messages = match (query.before, query.after) {
+        (Some(_), Some(_)) => {
+            return Err(ServerError::bad_request("specify at most one of `before` or `after`"));
+        }
+        (Some(before), None) => {
+            let timestamp = Timestamp::from_unix_millis(before)
+                .ok_or_else(|| ServerError::bad_request(format!("invalid `before` timestamp: {before}")))?;
+            session.messages_before(timestamp, query.limit)
+        }
+        (None, Some(after)) => {
+            let timestamp = Timestamp::from_unix_millis(after)
+                .ok_or_else(|| ServerError::bad_request(format!("invalid `after` timestamp: {after}")))?;
+            session.messages_after(timestamp, query.limit)
+        }
+        (None, None) => session.last_messages(query.limit),
+    };
+
+    Ok(Json(SessionHistoryResponse { session_id: id, messages }))
+}
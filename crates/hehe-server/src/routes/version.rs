@@ -0,0 +1,165 @@
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::Json;
+use hehe_agent::AgentEvent;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, ServerError};
+use crate::state::AppState;
+
+/// Header a client sends to declare the protocol it speaks, e.g. `1.0`.
+/// Absent entirely, a request is assumed compatible (pre-negotiation
+/// clients keep working).
+pub const PROTOCOL_HEADER: &str = "x-hehe-protocol";
+
+/// The wire protocol version this build of the server speaks. Bump
+/// `major` for breaking changes to `chat`/`chat_stream` (new required
+/// fields, removed `AgentEvent` variants); bump `minor` for additive,
+/// ignorable changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ProtocolVersion {
+    pub const CURRENT: Self = Self { major: 1, minor: 0 };
+
+    /// Parses a `"major.minor"` string, e.g. from [`PROTOCOL_HEADER`].
+    pub fn parse(s: &str) -> Option<Self> {
+        let (major, minor) = s.split_once('.')?;
+        Some(Self {
+            major: major.trim().parse().ok()?,
+            minor: minor.trim().parse().ok()?,
+        })
+    }
+
+    /// Two versions can talk to each other iff their major version
+    /// matches; a differing minor just means some newer, additive fields
+    /// are missing or ignored.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.major == other.major
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// What this running server can do, so a client can feature-gate before
+/// calling `chat`/`chat_stream` instead of discovering it the hard way.
+#[derive(Serialize)]
+pub struct ServerCapabilities {
+    pub streaming: bool,
+    pub tools_enabled: bool,
+    pub max_context_messages: usize,
+    pub model: String,
+    pub event_types: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+pub struct VersionResponse {
+    pub protocol: ProtocolVersion,
+    pub capabilities: ServerCapabilities,
+}
+
+/// `GET /api/v1/version` — the protocol version and capabilities a client
+/// should negotiate against before it starts sending chat requests.
+pub async fn version(State(state): State<AppState>) -> Json<VersionResponse> {
+    let config = state.agent.config();
+
+    Json(VersionResponse {
+        protocol: ProtocolVersion::CURRENT,
+        capabilities: ServerCapabilities {
+            streaming: true,
+            tools_enabled: config.tools_enabled,
+            max_context_messages: config.max_context_messages,
+            model: config.model,
+            event_types: AgentEvent::EVENT_TYPES.to_vec(),
+        },
+    })
+}
+
+/// Rejects a request whose [`PROTOCOL_HEADER`] names a major version this
+/// server can't satisfy. A missing header or a differing minor version is
+/// accepted — `chat`/`chat_stream` degrade gracefully in that case.
+pub fn check_protocol_header(headers: &HeaderMap) -> Result<()> {
+    let Some(value) = headers.get(PROTOCOL_HEADER) else {
+        return Ok(());
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| ServerError::bad_request(format!("{PROTOCOL_HEADER} header is not valid UTF-8")))?;
+
+    let requested = ProtocolVersion::parse(value).ok_or_else(|| {
+        ServerError::bad_request(format!(
+            "{PROTOCOL_HEADER} header '{value}' is not a valid protocol version (expected \"major.minor\")"
+        ))
+    })?;
+
+    if !ProtocolVersion::CURRENT.is_compatible(&requested) {
+        return Err(ServerError::bad_request(format!(
+            "protocol mismatch: server speaks {}, client requested {} which is not compatible",
+            ProtocolVersion::CURRENT,
+            requested
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_parse_roundtrips_with_display() {
+        let version = ProtocolVersion::parse("1.2").unwrap();
+        assert_eq!(version, ProtocolVersion { major: 1, minor: 2 });
+        assert_eq!(version.to_string(), "1.2");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_input() {
+        assert!(ProtocolVersion::parse("1").is_none());
+        assert!(ProtocolVersion::parse("a.b").is_none());
+    }
+
+    #[test]
+    fn test_is_compatible_ignores_minor_mismatch() {
+        let server = ProtocolVersion { major: 1, minor: 5 };
+        let client = ProtocolVersion { major: 1, minor: 0 };
+        assert!(server.is_compatible(&client));
+    }
+
+    #[test]
+    fn test_is_compatible_rejects_major_mismatch() {
+        let server = ProtocolVersion { major: 1, minor: 0 };
+        let client = ProtocolVersion { major: 2, minor: 0 };
+        assert!(!server.is_compatible(&client));
+    }
+
+    #[test]
+    fn test_check_protocol_header_accepts_missing_header() {
+        let headers = HeaderMap::new();
+        assert!(check_protocol_header(&headers).is_ok());
+    }
+
+    #[test]
+    fn test_check_protocol_header_rejects_major_mismatch() {
+        let mut headers = HeaderMap::new();
+        headers.insert(PROTOCOL_HEADER, HeaderValue::from_static("99.0"));
+        assert!(check_protocol_header(&headers).is_err());
+    }
+
+    #[test]
+    fn test_check_protocol_header_rejects_malformed_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert(PROTOCOL_HEADER, HeaderValue::from_static("not-a-version"));
+        assert!(check_protocol_header(&headers).is_err());
+    }
+}
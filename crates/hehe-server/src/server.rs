@@ -3,15 +3,20 @@ use axum::{
     Router,
 };
 use hehe_agent::Agent;
-use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::info;
 
 use crate::config::ServerConfig;
+use crate::endpoint::Endpoint;
 use crate::error::Result;
 use crate::routes;
 use crate::state::AppState;
 
+#[cfg(feature = "config-reload")]
+use crate::config_reload::ConfigReloader;
+#[cfg(feature = "config-reload")]
+use std::sync::Arc;
+
 pub struct Server {
     config: ServerConfig,
     state: AppState,
@@ -19,41 +24,111 @@ pub struct Server {
 
 impl Server {
     pub fn new(config: ServerConfig, agent: Agent) -> Self {
-        Self {
-            config,
-            state: AppState::new(agent),
+        let state = AppState::new(agent);
+
+        #[cfg(feature = "config-reload")]
+        let state = Self::attach_config_reloader(&config, state);
+
+        Self { config, state }
+    }
+
+    /// If `config.config_reload_path` is set, builds a [`ConfigReloader`]
+    /// for the new state's agent, starts its filesystem watcher, and
+    /// attaches it so `/api/v1/reload` and the watcher share the same
+    /// reloader.
+    #[cfg(feature = "config-reload")]
+    fn attach_config_reloader(config: &ServerConfig, state: AppState) -> AppState {
+        let Some(path) = &config.config_reload_path else {
+            return state;
+        };
+
+        let reloader = Arc::new(ConfigReloader::new(path.clone(), state.agent.clone()));
+        if let Err(e) = reloader.clone().watch() {
+            tracing::warn!(error = %e, path = %path.display(), "failed to start config file watcher");
+            return state;
         }
+
+        state.with_config_reloader(reloader)
     }
 
     pub fn router(&self) -> Router {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any);
-
-        Router::new()
+        let router = Router::new()
             .route("/health", get(routes::health))
             .route("/ready", get(routes::ready))
+            .route("/api/v1/version", get(routes::version))
             .route("/api/v1/chat", post(routes::chat))
             .route("/api/v1/chat/stream", post(routes::chat_stream))
-            .layer(cors)
+            .route("/sessions/:id/history", get(routes::session_history))
+            .route("/v1/chat/completions", post(routes::chat_completions));
+
+        #[cfg(feature = "config-reload")]
+        let router = router.route("/api/v1/reload", post(routes::reload));
+
+        #[cfg(feature = "event-log")]
+        let router = router.route("/api/v1/chat/history", get(routes::history));
+
+        // Innermost of the request-wide layers so a rejected CORS preflight
+        // (handled by `cors` below, which wraps this) never has to carry
+        // credentials at all. A configured `token_verifying_key` switches the
+        // whole server over to signed capability tokens instead of `api_keys`
+        // — the two schemes parse the `Authorization` header differently, so
+        // only one can be active at a time.
+        #[cfg(feature = "auth")]
+        let router = match self.config.token_auth_state() {
+            Some(token_auth) => router.layer(axum::middleware::from_fn_with_state(
+                token_auth,
+                crate::token::require_capability_token,
+            )),
+            None => router.layer(axum::middleware::from_fn_with_state(
+                self.config.auth_state(),
+                crate::auth::require_api_key,
+            )),
+        };
+
+        let router = router
+            .layer(axum::middleware::from_fn_with_state(
+                self.config.cors_config(),
+                crate::cors::enforce_cors,
+            ))
             .layer(TraceLayer::new_for_http())
-            .with_state(self.state.clone())
+            // Outermost: bounds total handling time for every request,
+            // including the layers above, so a stuck client can't hold a
+            // connection slot open indefinitely.
+            .layer(axum::middleware::from_fn_with_state(
+                self.config.timeouts(),
+                crate::timeouts::enforce_slow_request_timeout,
+            ));
+
+        // Advertises the QUIC listener so HTTP/2 clients can upgrade on their
+        // next request; harmless (and omitted) when HTTP/3 isn't enabled.
+        #[cfg(feature = "http3")]
+        let router = match self.config.quic_socket_addr() {
+            Some(quic_addr) => router.layer(tower_http::set_header::SetResponseHeaderLayer::overriding(
+                axum::http::header::HeaderName::from_static("alt-svc"),
+                axum::http::HeaderValue::from_str(&format!("h3=\":{}\"; ma=3600", quic_addr.port()))
+                    .expect("alt-svc header value is always valid ASCII"),
+            )),
+            None => router,
+        };
+
+        router.with_state(self.state.clone())
     }
 
-    pub async fn run(self) -> Result<()> {
-        let addr = self.config.socket_addr();
-        let listener = tokio::net::TcpListener::bind(addr).await.map_err(|e| {
-            crate::error::ServerError::internal(format!("Failed to bind to {}: {}", addr, e))
-        })?;
+    /// Every listener this server binds when run: the TCP HTTP/1-2 listener,
+    /// plus the QUIC HTTP/3 listener if `http3` is enabled and TLS is configured.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        let mut endpoints = vec![Endpoint::Tcp(self.config.socket_addr())];
 
-        info!("Server listening on {}", addr);
+        #[cfg(feature = "http3")]
+        if let Some(quic_addr) = self.config.quic_socket_addr() {
+            endpoints.push(Endpoint::Quic(quic_addr));
+        }
 
-        axum::serve(listener, self.router())
-            .await
-            .map_err(|e| crate::error::ServerError::internal(e.to_string()))?;
+        endpoints
+    }
 
-        Ok(())
+    pub async fn run(self) -> Result<()> {
+        self.run_with_shutdown(std::future::pending()).await
     }
 
     pub async fn run_with_shutdown<F>(self, shutdown: F) -> Result<()>
@@ -67,14 +142,70 @@ impl Server {
 
         info!("Server listening on {}", addr);
 
-        axum::serve(listener, self.router())
-            .with_graceful_shutdown(shutdown)
-            .await
-            .map_err(|e| crate::error::ServerError::internal(e.to_string()))?;
+        let timeouts = self.config.timeouts();
+        let router = self.router();
+
+        #[cfg(feature = "http3")]
+        let quic_task = self.spawn_quic_listener(router.clone());
+
+        // Lets the forced-exit timer below start counting only once shutdown
+        // has actually been requested, rather than from server start.
+        let (shutdown_requested_tx, mut shutdown_requested_rx) = tokio::sync::watch::channel(false);
+        let graceful_shutdown = async move {
+            shutdown.await;
+            let _ = shutdown_requested_tx.send(true);
+        };
+
+        let tcp_serve = axum::serve(listener, router).with_graceful_shutdown(graceful_shutdown);
+
+        let forced_exit_after_grace_period = async {
+            let _ = shutdown_requested_rx.changed().await;
+            tokio::time::sleep(timeouts.shutdown_timeout).await;
+        };
+
+        #[cfg(feature = "http3")]
+        if let Some(quic_task) = quic_task {
+            tokio::select! {
+                res = tcp_serve => return res.map_err(|e| crate::error::ServerError::internal(e.to_string())),
+                res = quic_task => {
+                    match res {
+                        Ok(Err(e)) => tracing::warn!(error = %e, "HTTP/3 listener exited"),
+                        Err(e) => tracing::warn!(error = %e, "HTTP/3 listener task panicked"),
+                        Ok(Ok(())) => {}
+                    }
+                    return Ok(());
+                }
+                _ = forced_exit_after_grace_period => {
+                    tracing::warn!(timeout = ?timeouts.shutdown_timeout, "graceful shutdown grace period elapsed; forcing close");
+                    return Ok(());
+                }
+            }
+        }
+
+        tokio::select! {
+            res = tcp_serve => res.map_err(|e| crate::error::ServerError::internal(e.to_string()))?,
+            _ = forced_exit_after_grace_period => {
+                tracing::warn!(timeout = ?timeouts.shutdown_timeout, "graceful shutdown grace period elapsed; forcing close");
+            }
+        }
 
         Ok(())
     }
 
+    /// Spawns the QUIC listener as a background task when `config.quic_socket_addr()`
+    /// resolves (i.e. TLS is configured). Returns `None` (HTTP/3 simply stays off)
+    /// otherwise.
+    #[cfg(feature = "http3")]
+    fn spawn_quic_listener(&self, router: Router) -> Option<tokio::task::JoinHandle<Result<()>>> {
+        let addr = self.config.quic_socket_addr()?;
+        let cert_path = self.config.tls_cert_path.clone()?;
+        let key_path = self.config.tls_key_path.clone()?;
+
+        Some(tokio::spawn(async move {
+            crate::http3::serve_quic(addr, &cert_path, &key_path, router).await
+        }))
+    }
+
     pub fn config(&self) -> &ServerConfig {
         &self.config
     }
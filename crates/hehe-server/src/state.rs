@@ -1,57 +1,146 @@
-use hehe_agent::{Agent, Session};
+use hehe_agent::{Agent, AgentEvent, Session};
 use hehe_core::Id;
-use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+
+use crate::error::Result;
+use crate::store::{InMemorySessionStore, SessionStore};
+
+#[cfg(feature = "config-reload")]
+use crate::config_reload::{ConfigReloader, ReloadOutcome};
+
+#[cfg(feature = "event-log")]
+use crate::error::ServerError;
+#[cfg(feature = "event-log")]
+use crate::event_log::{EventLog, StoredEvent};
 
 #[derive(Clone)]
 pub struct AppState {
     pub agent: Arc<Agent>,
-    sessions: Arc<RwLock<HashMap<Id, Session>>>,
+    sessions: Arc<dyn SessionStore>,
+    #[cfg(feature = "config-reload")]
+    config_reloader: Option<Arc<ConfigReloader>>,
+    #[cfg(feature = "event-log")]
+    event_log: Option<Arc<EventLog>>,
 }
 
 impl AppState {
     pub fn new(agent: Agent) -> Self {
+        Self::with_session_store(agent, Arc::new(InMemorySessionStore::new()))
+    }
+
+    pub fn with_session_store(agent: Agent, sessions: Arc<dyn SessionStore>) -> Self {
         Self {
             agent: Arc::new(agent),
-            sessions: Arc::new(RwLock::new(HashMap::new())),
+            sessions,
+            #[cfg(feature = "config-reload")]
+            config_reloader: None,
+            #[cfg(feature = "event-log")]
+            event_log: None,
         }
     }
 
-    pub async fn get_or_create_session(&self, session_id: Option<Id>) -> Session {
+    /// Attaches a [`ConfigReloader`] so [`Self::reload_config`] (and the
+    /// `/api/v1/reload` route) can hot-reload this state's agent config.
+    #[cfg(feature = "config-reload")]
+    pub fn with_config_reloader(mut self, reloader: Arc<ConfigReloader>) -> Self {
+        self.config_reloader = Some(reloader);
+        self
+    }
+
+    /// Re-reads and applies the watched config file on demand, returning
+    /// which fields changed. Errors if no [`ConfigReloader`] was attached.
+    #[cfg(feature = "config-reload")]
+    pub async fn reload_config(&self) -> Result<ReloadOutcome> {
+        match &self.config_reloader {
+            Some(reloader) => reloader.reload().await,
+            None => Err(crate::error::ServerError::bad_request(
+                "config reload is not configured for this server",
+            )),
+        }
+    }
+
+    /// Attaches an [`EventLog`] so [`Self::record_event`]/[`Self::event_history`]
+    /// (and the `/api/v1/chat/history` route) can persist and replay
+    /// `/api/v1/chat/stream` events.
+    #[cfg(feature = "event-log")]
+    pub fn with_event_log(mut self, event_log: Arc<EventLog>) -> Self {
+        self.event_log = Some(event_log);
+        self
+    }
+
+    /// Persists `event` for `session_id`, returning its sequence number.
+    /// When the event ends a turn ([`AgentEvent::is_end`]), also prunes the
+    /// session's log down to the agent's `max_context_messages`. Errors if
+    /// no [`EventLog`] was attached.
+    #[cfg(feature = "event-log")]
+    pub async fn record_event(&self, session_id: Id, event: &AgentEvent) -> Result<u64> {
+        let log = self
+            .event_log
+            .as_ref()
+            .ok_or_else(|| ServerError::bad_request("event log is not configured for this server"))?;
+
+        let seq = log.append(session_id, event).await?;
+
+        if event.is_end() {
+            let keep = self.agent.config().max_context_messages;
+            log.prune(session_id, keep).await?;
+        }
+
+        Ok(seq)
+    }
+
+    /// Past events for `session_id` after `after_seq`, oldest first, so a
+    /// reconnecting client can replay what it missed. Errors if no
+    /// [`EventLog`] was attached.
+    #[cfg(feature = "event-log")]
+    pub async fn event_history(&self, session_id: Id, after_seq: u64, limit: usize) -> Result<Vec<StoredEvent>> {
+        let log = self
+            .event_log
+            .as_ref()
+            .ok_or_else(|| ServerError::bad_request("event log is not configured for this server"))?;
+
+        log.history(session_id, after_seq, limit).await
+    }
+
+    pub async fn get_or_create_session(&self, session_id: Option<Id>) -> Result<Session> {
         match session_id {
             Some(id) => {
-                let sessions = self.sessions.read().await;
-                if let Some(session) = sessions.get(&id) {
-                    return session.clone();
+                if let Some(session) = self.sessions.get(&id).await? {
+                    return Ok(session);
                 }
-                drop(sessions);
 
-                let session = Session::with_id(id.clone());
-                self.sessions.write().await.insert(id, session.clone());
-                session
+                let session = Session::with_id(id);
+                self.sessions.put(session.clone()).await?;
+                Ok(session)
             }
             None => {
                 let session = self.agent.create_session();
-                self.sessions
-                    .write()
-                    .await
-                    .insert(session.id().clone(), session.clone());
-                session
+                self.sessions.put(session.clone()).await?;
+                Ok(session)
             }
         }
     }
 
-    pub async fn get_session(&self, session_id: &Id) -> Option<Session> {
-        self.sessions.read().await.get(session_id).cloned()
+    pub async fn get_session(&self, session_id: &Id) -> Result<Option<Session>> {
+        self.sessions.get(session_id).await
+    }
+
+    /// Writes `session`'s current messages/stats back to the store, so a
+    /// restart (or a `SessionStore` shared across workers) sees whatever
+    /// happened in the turn that just completed. Callers persist after a
+    /// turn ends rather than on every `add_message`, since `Session`'s
+    /// interior mutability means the in-memory handle is already up to
+    /// date for anyone still holding it.
+    pub async fn save_session(&self, session: &Session) -> Result<()> {
+        self.sessions.put(session.clone()).await
     }
 
-    pub async fn remove_session(&self, session_id: &Id) -> Option<Session> {
-        self.sessions.write().await.remove(session_id)
+    pub async fn remove_session(&self, session_id: &Id) -> Result<Option<Session>> {
+        self.sessions.remove(session_id).await
     }
 
-    pub async fn session_count(&self) -> usize {
-        self.sessions.read().await.len()
+    pub async fn session_count(&self) -> Result<usize> {
+        self.sessions.count().await
     }
 }
 
@@ -96,21 +185,24 @@ mod tests {
     #[tokio::test]
     async fn test_app_state_create_session() {
         let state = AppState::new(create_test_agent());
-        
-        let session = state.get_or_create_session(None).await;
-        assert_eq!(state.session_count().await, 1);
 
-        let session2 = state.get_or_create_session(Some(session.id().clone())).await;
+        let session = state.get_or_create_session(None).await.unwrap();
+        assert_eq!(state.session_count().await.unwrap(), 1);
+
+        let session2 = state
+            .get_or_create_session(Some(*session.id()))
+            .await
+            .unwrap();
         assert_eq!(session.id(), session2.id());
-        assert_eq!(state.session_count().await, 1);
+        assert_eq!(state.session_count().await.unwrap(), 1);
     }
 
     #[tokio::test]
     async fn test_app_state_remove_session() {
         let state = AppState::new(create_test_agent());
-        let session = state.get_or_create_session(None).await;
-        
-        assert!(state.remove_session(session.id()).await.is_some());
-        assert_eq!(state.session_count().await, 0);
+        let session = state.get_or_create_session(None).await.unwrap();
+
+        assert!(state.remove_session(session.id()).await.unwrap().is_some());
+        assert_eq!(state.session_count().await.unwrap(), 0);
     }
 }
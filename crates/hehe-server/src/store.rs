@@ -0,0 +1,351 @@
+use async_trait::async_trait;
+use hehe_agent::Session;
+use hehe_core::Id;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::error::Result;
+
+/// Persists and retrieves [`Session`]s for [`crate::state::AppState`]. The default
+/// [`InMemorySessionStore`] keeps everything in a process-local map, so sessions are
+/// lost on restart; swap in a different implementation — the file-backed one behind
+/// `file-store`, or the SQLite-backed one behind `sqlite-store` — to survive restarts
+/// or share sessions across workers.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    async fn get(&self, id: &Id) -> Result<Option<Session>>;
+    async fn put(&self, session: Session) -> Result<()>;
+    async fn remove(&self, id: &Id) -> Result<Option<Session>>;
+    async fn count(&self) -> Result<usize>;
+    async fn list(&self) -> Result<Vec<Id>>;
+}
+
+#[derive(Default)]
+pub struct InMemorySessionStore {
+    sessions: RwLock<HashMap<Id, Session>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn get(&self, id: &Id) -> Result<Option<Session>> {
+        Ok(self.sessions.read().await.get(id).cloned())
+    }
+
+    async fn put(&self, session: Session) -> Result<()> {
+        self.sessions.write().await.insert(*session.id(), session);
+        Ok(())
+    }
+
+    async fn remove(&self, id: &Id) -> Result<Option<Session>> {
+        Ok(self.sessions.write().await.remove(id))
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.sessions.read().await.len())
+    }
+
+    async fn list(&self) -> Result<Vec<Id>> {
+        Ok(self.sessions.read().await.keys().copied().collect())
+    }
+}
+
+#[cfg(feature = "file-store")]
+mod file {
+    use super::*;
+    use crate::error::ServerError;
+    use std::path::PathBuf;
+    use tokio::fs;
+
+    /// Persists each session as one JSON file under a root directory, so
+    /// conversations survive a server restart. Every call goes straight to disk; an
+    /// in-process write lock keeps concurrent writes from tearing, but there is no
+    /// cross-process locking, so only one server instance should point at a given
+    /// root at a time.
+    pub struct FileSessionStore {
+        root: PathBuf,
+        write_lock: tokio::sync::Mutex<()>,
+    }
+
+    impl FileSessionStore {
+        pub fn new(root: impl Into<PathBuf>) -> Self {
+            Self {
+                root: root.into(),
+                write_lock: tokio::sync::Mutex::new(()),
+            }
+        }
+
+        fn path_for(&self, id: &Id) -> PathBuf {
+            self.root.join(format!("{id}.json"))
+        }
+
+        async fn ensure_root(&self) -> Result<()> {
+            fs::create_dir_all(&self.root)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to create session store directory: {e}")))
+        }
+    }
+
+    #[async_trait]
+    impl SessionStore for FileSessionStore {
+        async fn get(&self, id: &Id) -> Result<Option<Session>> {
+            match fs::read(self.path_for(id)).await {
+                Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(ServerError::internal(format!("failed to read session: {e}"))),
+            }
+        }
+
+        async fn put(&self, session: Session) -> Result<()> {
+            self.ensure_root().await?;
+            let bytes = serde_json::to_vec(&session)?;
+            let _guard = self.write_lock.lock().await;
+            fs::write(self.path_for(session.id()), bytes)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to write session: {e}")))
+        }
+
+        async fn remove(&self, id: &Id) -> Result<Option<Session>> {
+            let existing = self.get(id).await?;
+            if existing.is_some() {
+                let _guard = self.write_lock.lock().await;
+                fs::remove_file(self.path_for(id))
+                    .await
+                    .map_err(|e| ServerError::internal(format!("failed to remove session: {e}")))?;
+            }
+            Ok(existing)
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.list().await?.len())
+        }
+
+        async fn list(&self) -> Result<Vec<Id>> {
+            self.ensure_root().await?;
+            let mut entries = fs::read_dir(&self.root)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to list session store directory: {e}")))?;
+
+            let mut ids = Vec::new();
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to list session store directory: {e}")))?
+            {
+                if let Some(id) = entry
+                    .path()
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.parse::<Id>().ok())
+                {
+                    ids.push(id);
+                }
+            }
+            Ok(ids)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_file_store_round_trips_session() {
+            let dir = std::env::temp_dir().join(format!("hehe-session-store-test-{}", Id::new()));
+            let store = FileSessionStore::new(&dir);
+
+            let session = Session::new();
+            session.add_message(hehe_core::Message::user("hello"));
+            store.put(session.clone()).await.unwrap();
+
+            let loaded = store.get(session.id()).await.unwrap().unwrap();
+            assert_eq!(loaded.id(), session.id());
+            assert_eq!(loaded.message_count(), 1);
+
+            store.remove(session.id()).await.unwrap();
+            assert!(store.get(session.id()).await.unwrap().is_none());
+
+            let _ = std::fs::remove_dir_all(&dir);
+        }
+    }
+}
+
+#[cfg(feature = "file-store")]
+pub use file::FileSessionStore;
+
+#[cfg(feature = "sqlite-store")]
+mod sqlite {
+    use super::*;
+    use crate::error::ServerError;
+    use hehe_store::SqliteKvStore;
+    use std::str::FromStr;
+
+    /// `list`/`count` scan the whole key space in one call rather than
+    /// paging, so they cap at this many sessions instead of risking an
+    /// unbounded result set.
+    const MAX_SESSIONS_PER_SCAN: usize = 100_000;
+
+    /// Persists each session as one entry in a [`SqliteKvStore`], keyed by
+    /// the session id's string form, so conversations survive a server
+    /// restart without `FileSessionStore`'s one-file-per-session layout.
+    pub struct SqliteSessionStore {
+        kv: SqliteKvStore,
+    }
+
+    impl SqliteSessionStore {
+        pub async fn from_path(path: &str) -> Result<Self> {
+            let kv = SqliteKvStore::from_path(path)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to open session store: {e}")))?;
+            Ok(Self { kv })
+        }
+
+        pub async fn memory() -> Result<Self> {
+            let kv = SqliteKvStore::memory()
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to open session store: {e}")))?;
+            Ok(Self { kv })
+        }
+
+        fn key_for(id: &Id) -> Vec<u8> {
+            id.to_string().into_bytes()
+        }
+    }
+
+    #[async_trait]
+    impl SessionStore for SqliteSessionStore {
+        async fn get(&self, id: &Id) -> Result<Option<Session>> {
+            let entry = self
+                .kv
+                .get(&Self::key_for(id))
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to read session: {e}")))?;
+
+            entry
+                .map(|entry| serde_json::from_slice(&entry.value).map_err(ServerError::from))
+                .transpose()
+        }
+
+        async fn put(&self, session: Session) -> Result<()> {
+            let bytes = serde_json::to_vec(&session)?;
+            self.kv
+                .set(Self::key_for(session.id()), bytes)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to write session: {e}")))?;
+            Ok(())
+        }
+
+        async fn remove(&self, id: &Id) -> Result<Option<Session>> {
+            let existing = self.get(id).await?;
+            if existing.is_some() {
+                self.kv
+                    .delete(Self::key_for(id))
+                    .await
+                    .map_err(|e| ServerError::internal(format!("failed to remove session: {e}")))?;
+            }
+            Ok(existing)
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.list().await?.len())
+        }
+
+        async fn list(&self) -> Result<Vec<Id>> {
+            // Every key is a 36-character UUID string; an all-0xff key one
+            // byte longer than that sorts after any real key regardless of
+            // its content, so it works as an exclusive upper bound.
+            let start = vec![0u8; 36];
+            let mut end = vec![0xffu8; 36];
+            end.push(0x00);
+
+            let entries = self
+                .kv
+                .scan(&start, &end, MAX_SESSIONS_PER_SCAN)
+                .await
+                .map_err(|e| ServerError::internal(format!("failed to list session store: {e}")))?;
+
+            entries
+                .into_iter()
+                .filter_map(|entry| String::from_utf8(entry.key).ok().and_then(|s| Id::from_str(&s).ok()))
+                .map(Ok)
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_sqlite_store_round_trips_session() {
+            let store = SqliteSessionStore::memory().await.unwrap();
+
+            let session = Session::new();
+            session.add_message(hehe_core::Message::user("hello"));
+            store.put(session.clone()).await.unwrap();
+
+            let loaded = store.get(session.id()).await.unwrap().unwrap();
+            assert_eq!(loaded.id(), session.id());
+            assert_eq!(loaded.message_count(), 1);
+
+            store.remove(session.id()).await.unwrap();
+            assert!(store.get(session.id()).await.unwrap().is_none());
+        }
+
+        #[tokio::test]
+        async fn test_sqlite_store_list_and_count() {
+            let store = SqliteSessionStore::memory().await.unwrap();
+            let a = Session::new();
+            let b = Session::new();
+            store.put(a.clone()).await.unwrap();
+            store.put(b.clone()).await.unwrap();
+
+            assert_eq!(store.count().await.unwrap(), 2);
+            let ids = store.list().await.unwrap();
+            assert!(ids.contains(a.id()));
+            assert!(ids.contains(b.id()));
+        }
+    }
+}
+
+#[cfg(feature = "sqlite-store")]
+pub use sqlite::SqliteSessionStore;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_store_put_get_remove() {
+        let store = InMemorySessionStore::new();
+        let session = Session::new();
+
+        store.put(session.clone()).await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 1);
+
+        let loaded = store.get(session.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.id(), session.id());
+
+        assert!(store.remove(session.id()).await.unwrap().is_some());
+        assert_eq!(store.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_list() {
+        let store = InMemorySessionStore::new();
+        let a = Session::new();
+        let b = Session::new();
+        store.put(a.clone()).await.unwrap();
+        store.put(b.clone()).await.unwrap();
+
+        let ids = store.list().await.unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(a.id()));
+        assert!(ids.contains(b.id()));
+    }
+}
@@ -0,0 +1,62 @@
+//! Connection-lifecycle timeouts, built from [`crate::config::ServerConfig`]'s
+//! `keep_alive_secs`/`slow_request_timeout_ms`/`shutdown_timeout_secs` fields
+//! so the serving layer reads them from one place instead of each call site
+//! picking its own number.
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Timeouts {
+    /// Idle keep-alive duration taken from the config. [`crate::server::Server`]
+    /// does not apply it: idle connections are not dropped on this timer.
+    pub keep_alive: Duration,
+    /// A request whose handling (including reading its body) hasn't finished
+    /// within this long is answered with `408 Request Timeout`. The timer
+    /// starts once the request head has been parsed, and the connection is
+    /// not closed.
+    pub slow_request_timeout: Duration,
+    /// How long graceful shutdown waits for in-flight requests to finish,
+    /// once triggered, before the listener is forced closed.
+    pub shutdown_timeout: Duration,
+}
+
+impl Timeouts {
+    pub fn new(keep_alive: Duration, slow_request_timeout: Duration, shutdown_timeout: Duration) -> Self {
+        Self {
+            keep_alive,
+            slow_request_timeout,
+            shutdown_timeout,
+        }
+    }
+}
+
+/// Bounds total request handling time at `timeouts.slow_request_timeout`,
+/// responding `408 Request Timeout` instead of leaving the client (and the
+/// connection slot it holds) hanging indefinitely.
+pub async fn enforce_slow_request_timeout(State(timeouts): State<Timeouts>, req: Request, next: Next) -> Response {
+    match tokio::time::timeout(timeouts.slow_request_timeout, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => StatusCode::REQUEST_TIMEOUT.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeouts_new_stores_each_duration() {
+        let timeouts = Timeouts::new(
+            Duration::from_secs(5),
+            Duration::from_millis(10_000),
+            Duration::from_secs(30),
+        );
+        assert_eq!(timeouts.keep_alive, Duration::from_secs(5));
+        assert_eq!(timeouts.slow_request_timeout, Duration::from_millis(10_000));
+        assert_eq!(timeouts.shutdown_timeout, Duration::from_secs(30));
+    }
+}
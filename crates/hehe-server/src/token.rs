@@ -0,0 +1,275 @@
+//! Signed capability-token authentication, as an alternative to [`crate::auth`]'s
+//! fixed list of Argon2-hashed API keys: a token's payload carries its own
+//! expiry and scopes, chosen by whoever minted it with a [`TokenSigner`],
+//! rather than indexing into a server-side list. Useful for short-lived,
+//! narrowly-scoped credentials (e.g. "read-only access to this chat session
+//! for the next 10 minutes") that never need to be provisioned ahead of time.
+//!
+//! A token is `base64(payload_json).base64(signature)`, where `payload_json`
+//! is `{ sub, exp_unix_millis, scopes }` and `signature` is a detached
+//! Ed25519 signature over the *undecoded* payload bytes. [`TokenVerifier`]
+//! checks the signature and `exp_unix_millis` before attaching an
+//! [`Identity`] to the request, same as [`crate::auth::require_api_key`] does.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hehe_core::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth::Identity;
+use crate::error::{Result, ServerError};
+
+fn b64_encode(bytes: impl AsRef<[u8]>) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn b64_decode(s: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(s)
+        .map_err(|e| ServerError::unauthorized(format!("invalid base64 in token: {e}")))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TokenPayload {
+    sub: String,
+    exp_unix_millis: i64,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+/// Mints signed capability tokens. Holds the Ed25519 private key, so this
+/// belongs wherever tokens are issued (a CLI command, an admin endpoint) —
+/// not inside the running server, which only ever needs [`TokenVerifier`].
+pub struct TokenSigner {
+    signing_key: SigningKey,
+}
+
+impl TokenSigner {
+    pub fn new(signing_key: SigningKey) -> Self {
+        Self { signing_key }
+    }
+
+    /// Generates a fresh signing key. Callers that need the key to survive
+    /// past this process should persist `signing_key.to_bytes()` themselves.
+    pub fn generate() -> Self {
+        Self::new(SigningKey::generate(&mut rand::rngs::OsRng))
+    }
+
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    /// The [`VerifyingKey`] a [`TokenVerifier`] needs to check tokens this
+    /// signer mints.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Mints a token for `sub`, valid for `ttl` from now, carrying `scopes`.
+    pub fn mint(&self, sub: impl Into<String>, ttl: Duration, scopes: Vec<String>) -> String {
+        let payload = TokenPayload {
+            sub: sub.into(),
+            exp_unix_millis: Timestamp::now().unix_millis() + ttl.as_millis() as i64,
+            scopes,
+        };
+
+        let payload_bytes = serde_json::to_vec(&payload).expect("TokenPayload always serializes");
+        let signature = self.signing_key.sign(&payload_bytes);
+
+        format!("{}.{}", b64_encode(&payload_bytes), b64_encode(signature.to_bytes()))
+    }
+}
+
+/// Verifies tokens minted by the matching [`TokenSigner`]. This is all the
+/// running server needs — it never sees the private key.
+pub struct TokenVerifier {
+    verifying_key: VerifyingKey,
+}
+
+impl TokenVerifier {
+    pub fn new(verifying_key: VerifyingKey) -> Self {
+        Self { verifying_key }
+    }
+
+    /// Checks `token`'s signature and expiry, returning the [`Identity`] it
+    /// grants on success.
+    pub fn verify(&self, token: &str) -> Result<Identity> {
+        let (payload_b64, sig_b64) = token
+            .split_once('.')
+            .ok_or_else(|| ServerError::unauthorized("token must be in \"payload.signature\" form"))?;
+
+        let payload_bytes = b64_decode(payload_b64)?;
+        let sig_bytes = b64_decode(sig_b64)?;
+        let sig_bytes: [u8; 64] = sig_bytes
+            .try_into()
+            .map_err(|_| ServerError::unauthorized("token signature must be 64 bytes"))?;
+        let signature = Signature::from_bytes(&sig_bytes);
+
+        self.verifying_key
+            .verify(&payload_bytes, &signature)
+            .map_err(|_| ServerError::unauthorized("invalid token signature"))?;
+
+        let payload: TokenPayload = serde_json::from_slice(&payload_bytes)
+            .map_err(|e| ServerError::unauthorized(format!("malformed token payload: {e}")))?;
+
+        if payload.exp_unix_millis < Timestamp::now().unix_millis() {
+            return Err(ServerError::unauthorized("token has expired"));
+        }
+
+        Ok(Identity {
+            id: payload.sub,
+            scopes: payload.scopes,
+        })
+    }
+}
+
+/// State the [`require_capability_token`] middleware checks requests
+/// against. Mirrors [`crate::auth::AuthState`]'s shape so the two auth
+/// modes can be wired up the same way from [`crate::config::ServerConfig`].
+#[derive(Clone)]
+pub struct TokenAuthState {
+    verifier: Arc<TokenVerifier>,
+    allow_unauthenticated: bool,
+}
+
+impl TokenAuthState {
+    pub fn new(verifier: TokenVerifier, allow_unauthenticated: bool) -> Self {
+        Self {
+            verifier: Arc::new(verifier),
+            allow_unauthenticated,
+        }
+    }
+}
+
+/// Validates an `Authorization: Bearer <payload>.<signature>` header against
+/// [`TokenAuthState`], attaching the resolved [`Identity`] (with the
+/// token's `scopes`) to the request's extensions on success. This middleware
+/// checks only the signature and expiry; no scope is checked here or by the
+/// routes, and [`Identity::has_scope`] is there for code that wants to.
+pub async fn require_capability_token(State(auth): State<TokenAuthState>, mut req: Request, next: Next) -> Result<Response> {
+    let header = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+
+    let Some(header) = header else {
+        if auth.allow_unauthenticated {
+            return Ok(next.run(req).await);
+        }
+        return Err(ServerError::unauthorized("missing Authorization header"));
+    };
+
+    let presented = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| ServerError::unauthorized("Authorization header must use the Bearer scheme"))?;
+
+    let identity = auth.verifier.verify(presented)?;
+    req.extensions_mut().insert(identity);
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Request as HttpRequest, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn protected() -> &'static str {
+        "ok"
+    }
+
+    fn test_router(auth: TokenAuthState) -> Router {
+        Router::new()
+            .route("/protected", get(protected))
+            .layer(axum::middleware::from_fn_with_state(auth, require_capability_token))
+    }
+
+    #[test]
+    fn test_mint_and_verify_roundtrip() {
+        let signer = TokenSigner::generate();
+        let verifier = TokenVerifier::new(signer.verifying_key());
+
+        let token = signer.mint("alice", Duration::from_secs(60), vec!["chat".to_string()]);
+        let identity = verifier.verify(&token).unwrap();
+
+        assert_eq!(identity.id, "alice");
+        assert!(identity.has_scope("chat"));
+    }
+
+    #[test]
+    fn test_verify_rejects_expired_token() {
+        let signer = TokenSigner::generate();
+        let verifier = TokenVerifier::new(signer.verifying_key());
+
+        let token = signer.mint("alice", Duration::from_millis(0), vec![]);
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert!(verifier.verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_key() {
+        let signer = TokenSigner::generate();
+        let other_signer = TokenSigner::generate();
+        let verifier = TokenVerifier::new(other_signer.verifying_key());
+
+        let token = signer.mint("alice", Duration::from_secs(60), vec![]);
+        assert!(verifier.verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload() {
+        let signer = TokenSigner::generate();
+        let verifier = TokenVerifier::new(signer.verifying_key());
+
+        let token = signer.mint("alice", Duration::from_secs(60), vec![]);
+        let (payload_b64, sig_b64) = token.split_once('.').unwrap();
+        let mut payload_bytes = b64_decode(payload_b64).unwrap();
+        payload_bytes[0] ^= 0xFF;
+        let tampered = format!("{}.{}", b64_encode(&payload_bytes), sig_b64);
+
+        assert!(verifier.verify(&tampered).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_credentials_by_default() {
+        let signer = TokenSigner::generate();
+        let router = test_router(TokenAuthState::new(TokenVerifier::new(signer.verifying_key()), false));
+
+        let response = router
+            .oneshot(HttpRequest::builder().uri("/protected").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_valid_token() {
+        let signer = TokenSigner::generate();
+        let token = signer.mint("alice", Duration::from_secs(60), vec!["chat".to_string()]);
+        let router = test_router(TokenAuthState::new(TokenVerifier::new(signer.verifying_key()), false));
+
+        let response = router
+            .oneshot(
+                HttpRequest::builder()
+                    .uri("/protected")
+                    .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
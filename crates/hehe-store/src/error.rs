@@ -1,6 +1,48 @@
 use hehe_core::error::Error as CoreError;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+/// Stable, machine-readable slugs returned by [`StoreError::error_code`], so
+/// API consumers can branch on a fixed string instead of parsing `message`.
+pub mod codes {
+    pub const INDEX_NOT_FOUND: &str = "index_not_found";
+    pub const INDEX_ALREADY_EXISTS: &str = "index_already_exists";
+    pub const INVALID_INDEX_UID: &str = "invalid_index_uid";
+    pub const INVALID_INPUT: &str = "invalid_input";
+    pub const QUERY_ERROR: &str = "query_error";
+    pub const CONNECTION_ERROR: &str = "connection_error";
+    pub const TRANSACTION_ERROR: &str = "transaction_error";
+    pub const MIGRATION_ERROR: &str = "migration_error";
+    pub const SERIALIZATION_ERROR: &str = "serialization_error";
+    pub const BACKEND_NOT_AVAILABLE: &str = "backend_not_available";
+    pub const POOL_EXHAUSTED: &str = "pool_exhausted";
+    pub const TIMEOUT: &str = "timeout";
+    pub const INTERNAL_ERROR: &str = "internal_error";
+}
+
+/// Coarse classification of a [`StoreError`], mirroring MeiliSearch's
+/// `ErrorType`: whether the caller's request was at fault (fix the input and
+/// retry), or something failed on the store's side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorType {
+    InvalidRequest,
+    Internal,
+}
+
+/// A structured, API-facing view of a [`StoreError`], mirroring MeiliSearch's
+/// `ResponseError`: a human-readable `message`, a stable `error_code` to
+/// branch on, a coarse `error_type`, an HTTP-style `status`, and a
+/// documentation `error_link`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub message: String,
+    pub error_code: &'static str,
+    pub error_type: ErrorType,
+    pub status: u16,
+    pub error_link: String,
+}
+
 #[derive(Error, Debug)]
 pub enum StoreError {
     #[error("Connection error: {0}")]
@@ -18,6 +60,9 @@ pub enum StoreError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Invalid index name '{0}': must match [A-Za-z0-9_-]+")]
+    InvalidIndexUid(String),
+
     #[error("Transaction error: {0}")]
     Transaction(String),
 
@@ -70,6 +115,10 @@ impl StoreError {
         Self::InvalidInput(msg.into())
     }
 
+    pub fn invalid_index_uid(uid: impl Into<String>) -> Self {
+        Self::InvalidIndexUid(uid.into())
+    }
+
     pub fn transaction(msg: impl Into<String>) -> Self {
         Self::Transaction(msg.into())
     }
@@ -81,4 +130,96 @@ impl StoreError {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    /// Stable machine-readable slug for this error, e.g. `"index_not_found"`.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::Connection(_) => codes::CONNECTION_ERROR,
+            Self::Query(_) => codes::QUERY_ERROR,
+            Self::NotFound(_) => codes::INDEX_NOT_FOUND,
+            Self::AlreadyExists(_) => codes::INDEX_ALREADY_EXISTS,
+            Self::InvalidInput(_) => codes::INVALID_INPUT,
+            Self::InvalidIndexUid(_) => codes::INVALID_INDEX_UID,
+            Self::Transaction(_) => codes::TRANSACTION_ERROR,
+            Self::Migration(_) => codes::MIGRATION_ERROR,
+            Self::Serialization(_) => codes::SERIALIZATION_ERROR,
+            Self::BackendNotAvailable(_) => codes::BACKEND_NOT_AVAILABLE,
+            Self::PoolExhausted => codes::POOL_EXHAUSTED,
+            Self::Timeout => codes::TIMEOUT,
+            Self::Internal(_) => codes::INTERNAL_ERROR,
+            Self::Core(_) => codes::INTERNAL_ERROR,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(_) => codes::INTERNAL_ERROR,
+            #[cfg(feature = "duckdb")]
+            Self::DuckDb(_) => codes::INTERNAL_ERROR,
+        }
+    }
+
+    /// Whether this is the caller's fault (bad input, fixable by retrying
+    /// differently) or the store's (an operational failure).
+    pub fn error_type(&self) -> ErrorType {
+        match self {
+            Self::NotFound(_) | Self::AlreadyExists(_) | Self::InvalidInput(_) | Self::InvalidIndexUid(_) => {
+                ErrorType::InvalidRequest
+            }
+            _ => ErrorType::Internal,
+        }
+    }
+
+    /// HTTP-style status code a server surfacing this error over an API
+    /// should respond with.
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::AlreadyExists(_) => 409,
+            Self::InvalidInput(_) | Self::InvalidIndexUid(_) => 400,
+            Self::PoolExhausted | Self::Timeout => 503,
+            _ => 500,
+        }
+    }
+
+    /// Render this error as a JSON-serializable [`ErrorResponse`], mirroring
+    /// MeiliSearch's `ResponseError` shape for API consumers.
+    pub fn to_response(&self) -> ErrorResponse {
+        ErrorResponse {
+            message: self.to_string(),
+            error_code: self.error_code(),
+            error_type: self.error_type(),
+            status: self.status(),
+            error_link: format!("https://docs.hehe.dev/errors#{}", self.error_code()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_error_code_and_status() {
+        let err = StoreError::not_found("Index 'articles'");
+        assert_eq!(err.error_code(), codes::INDEX_NOT_FOUND);
+        assert_eq!(err.error_type(), ErrorType::InvalidRequest);
+        assert_eq!(err.status(), 404);
+    }
+
+    #[test]
+    fn test_invalid_index_uid_error_response_is_serializable() {
+        let err = StoreError::invalid_index_uid("bad name!");
+        let response = err.to_response();
+
+        assert_eq!(response.error_code, codes::INVALID_INDEX_UID);
+        assert_eq!(response.status, 400);
+        assert!(response.error_link.contains(codes::INVALID_INDEX_UID));
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"invalid_index_uid\""));
+    }
+
+    #[test]
+    fn test_internal_error_is_classified_internal() {
+        let err = StoreError::internal("disk full");
+        assert_eq!(err.error_type(), ErrorType::Internal);
+        assert_eq!(err.status(), 500);
+    }
 }
@@ -1,35 +1,66 @@
 pub mod error;
 pub mod local;
+pub mod migrator;
+pub mod pool;
+pub mod sync;
 pub mod traits;
 pub mod router;
 
-pub use error::{Result, StoreError};
-pub use router::{StoreHealth, StoreRouter};
+pub use error::{ErrorResponse, ErrorType, Result, StoreError};
+pub use migrator::{AppliedMigration, Migrator};
+pub use pool::{Pool, PoolConfig, PoolManager, PooledConnection};
+pub use router::{BackendHealth, StoreHealth, StoreRouter};
+pub use sync::{ApplyReport, RelationalSyncable, SyncEngine, SyncRecord, SyncReport, SyncState, Syncable};
 
 pub use traits::{
-    CacheStore, 
-    Document, IndexSchema, SearchFilter, SearchHit, SearchStore,
-    Migration, RelationalStore, Row, Transaction,
-    CollectionInfo, SearchResult, VectorFilter, VectorRecord, VectorStore,
+    CacheStore,
+    Document, FacetSearchResult, HighlightOptions, IndexSchema, SearchFilter, SearchHit, SearchStore,
+    FromColumn, FromRow, Migration, RelationalStore, RelationalStoreExt, Row, Transaction,
+    BoxStream, CollectionInfo, DistanceMetric, Embedder, FilterExpr, SearchResult, VectorFilter,
+    VectorRecord, VectorStore,
+    decode_order_key, encode_order_key,
 };
 
 #[cfg(feature = "sqlite")]
 pub use local::SqliteStore;
 
+#[cfg(feature = "sqlcipher")]
+pub use local::CipherConfig;
+
 #[cfg(feature = "memory-cache")]
 pub use local::MemoryCache;
 
+#[cfg(feature = "redis")]
+pub use local::RedisCache;
+
+pub use local::MemorySearchStore;
 pub use local::MemoryVectorStore;
 
+pub use local::HnswConfig;
+pub use local::HnswStore;
+pub use local::{LshConfig, LshStore};
+
 #[cfg(feature = "sqlite")]
 pub use local::SqliteFtsStore;
 
+#[cfg(feature = "sqlite")]
+pub use local::TypoToleranceConfig;
+
+#[cfg(feature = "sqlite")]
+pub use local::{CsvImportConfig, ImportFormat, JsonlImportConfig};
+
+#[cfg(feature = "sqlite")]
+pub use local::{AtomicWrite, CommitOutcome, KvEntry, SqliteKvStore, VersionStamp};
+
 pub mod prelude {
     pub use crate::error::{Result, StoreError};
-    pub use crate::router::{StoreHealth, StoreRouter};
+    pub use crate::migrator::{AppliedMigration, Migrator};
+    pub use crate::pool::{Pool, PoolConfig, PoolManager, PooledConnection};
+    pub use crate::router::{BackendHealth, StoreHealth, StoreRouter};
+    pub use crate::sync::{ApplyReport, RelationalSyncable, SyncEngine, SyncRecord, SyncReport, SyncState, Syncable};
     pub use crate::traits::{
-        CacheStore, RelationalStore, SearchStore, VectorStore,
-        Migration, Row,
+        CacheStore, RelationalStore, RelationalStoreExt, SearchStore, VectorStore,
+        FromRow, Migration, Row,
         VectorRecord, SearchResult, VectorFilter,
         Document, SearchHit, IndexSchema,
     };
@@ -1,50 +1,783 @@
+use super::import::{csv_row_to_document, jsonl_line_to_document, parse_csv_line};
+use super::typo::levenshtein;
 use crate::error::{Result, StoreError};
-use crate::local::SqliteStore;
-use crate::traits::{Document, IndexSchema, RelationalStore, SearchFilter, SearchHit, SearchStore};
+use crate::local::{CsvImportConfig, ImportFormat, JsonlImportConfig, SqliteStore, TypoToleranceConfig};
+use crate::traits::{
+    Document, FacetSearchResult, HighlightOptions, IndexField, IndexFieldType, IndexSchema,
+    RelationalStore, SearchCondition, SearchFilter, SearchHit, SearchStore,
+};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Name of the companion metadata table that persists each index's
+/// [`IndexSchema`] (column order, type and weight) so it survives reopening
+/// the database, rather than living only in the caller's in-memory `IndexSchema`.
+const SCHEMA_TABLE: &str = "_fts_schema";
+
+/// One line of an [`SqliteFtsStore::export_dump`]/[`SqliteFtsStore::import_dump`]
+/// archive: a `Header` starts a new index's section (recreated from its
+/// embedded schema on import), followed by that index's `Document` records.
+/// Dumping every index just writes multiple `Header`/`Document*` sections
+/// one after another into the same stream.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DumpRecord {
+    Header { index: String, schema: IndexSchema },
+    Document { id: String, content: String, fields: HashMap<String, Value> },
+}
+
+/// Default number of documents upserted per `index_documents` transaction;
+/// see [`SqliteFtsStore::with_batch_size`].
+const DEFAULT_INDEX_BATCH_SIZE: usize = 500;
 
 pub struct SqliteFtsStore {
     db: Arc<SqliteStore>,
+    batch_size: usize,
 }
 
 impl SqliteFtsStore {
     pub fn new(db: Arc<SqliteStore>) -> Self {
-        Self { db }
+        Self {
+            db,
+            batch_size: DEFAULT_INDEX_BATCH_SIZE,
+        }
     }
 
     pub async fn from_path(path: &str) -> Result<Self> {
         let db = SqliteStore::open(path)?;
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            batch_size: DEFAULT_INDEX_BATCH_SIZE,
+        })
     }
 
     pub async fn memory() -> Result<Self> {
         let db = SqliteStore::memory()?;
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self {
+            db: Arc::new(db),
+            batch_size: DEFAULT_INDEX_BATCH_SIZE,
+        })
+    }
+
+    /// Set how many documents `index_documents` upserts per transaction.
+    /// Larger batches mean fewer round trips but a longer-held transaction;
+    /// defaults to [`DEFAULT_INDEX_BATCH_SIZE`].
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
     }
 
     fn table_name(index: &str) -> String {
         format!("fts_{}", index.replace('-', "_"))
     }
+
+    /// Reject index names that aren't `[A-Za-z0-9_-]+`, since `table_name`
+    /// interpolates them directly into SQL (`CREATE VIRTUAL TABLE ...`).
+    fn validate_index_uid(name: &str) -> Result<()> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        if valid {
+            Ok(())
+        } else {
+            Err(StoreError::invalid_index_uid(name))
+        }
+    }
+
+    fn field_type_name(field_type: &IndexFieldType) -> &'static str {
+        match field_type {
+            IndexFieldType::Text => "text",
+            IndexFieldType::Keyword => "keyword",
+            IndexFieldType::Integer => "integer",
+            IndexFieldType::Float => "float",
+            IndexFieldType::Boolean => "boolean",
+            IndexFieldType::Date => "date",
+        }
+    }
+
+    fn parse_field_type(name: &str) -> IndexFieldType {
+        match name {
+            "keyword" => IndexFieldType::Keyword,
+            "integer" => IndexFieldType::Integer,
+            "float" => IndexFieldType::Float,
+            "boolean" => IndexFieldType::Boolean,
+            "date" => IndexFieldType::Date,
+            _ => IndexFieldType::Text,
+        }
+    }
+
+    /// Create the companion metadata table that persists each index's schema,
+    /// if it doesn't already exist.
+    async fn ensure_schema_table(&self) -> Result<()> {
+        self.db
+            .execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {SCHEMA_TABLE} (
+                        index_name TEXT NOT NULL,
+                        position INTEGER NOT NULL,
+                        name TEXT NOT NULL,
+                        field_type TEXT NOT NULL,
+                        weight REAL NOT NULL,
+                        indexed INTEGER NOT NULL,
+                        stored INTEGER NOT NULL,
+                        PRIMARY KEY (index_name, name)
+                    )"
+                ),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Persist `schema`'s fields for `index`, replacing whatever was stored
+    /// for it before (e.g. from a prior `create_index` call).
+    async fn save_schema(&self, index: &str, schema: &IndexSchema) -> Result<()> {
+        self.ensure_schema_table().await?;
+        self.db
+            .execute(
+                &format!("DELETE FROM {SCHEMA_TABLE} WHERE index_name = ?1"),
+                &[Value::String(index.to_string())],
+            )
+            .await?;
+
+        for (position, field) in schema.fields.iter().enumerate() {
+            self.db
+                .execute(
+                    &format!(
+                        "INSERT INTO {SCHEMA_TABLE}
+                         (index_name, position, name, field_type, weight, indexed, stored)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+                    ),
+                    &[
+                        Value::String(index.to_string()),
+                        Value::Number((position as i64).into()),
+                        Value::String(field.name.clone()),
+                        Value::String(Self::field_type_name(&field.field_type).to_string()),
+                        serde_json::json!(field.weight),
+                        Value::Bool(field.indexed),
+                        Value::Bool(field.stored),
+                    ],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Load the persisted schema fields for `index`, in column order. Indexes
+    /// created before this table existed (or with an empty schema) simply have
+    /// no rows, so callers fall back to the original `(id, content, fields)`
+    /// layout.
+    async fn load_schema(&self, index: &str) -> Result<Vec<IndexField>> {
+        self.ensure_schema_table().await?;
+        let rows = self
+            .db
+            .query(
+                &format!(
+                    "SELECT name, field_type, weight, indexed, stored FROM {SCHEMA_TABLE}
+                     WHERE index_name = ?1 ORDER BY position"
+                ),
+                &[Value::String(index.to_string())],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| IndexField {
+                name: row.get_str("name").unwrap_or_default().to_string(),
+                field_type: Self::parse_field_type(row.get_str("field_type").unwrap_or("text")),
+                stored: row.get_i64("stored").map(|v| v != 0).unwrap_or(true),
+                indexed: row.get_i64("indexed").map(|v| v != 0).unwrap_or(true),
+                weight: row.get_f64("weight").unwrap_or(1.0) as f32,
+            })
+            .collect())
+    }
+
+    /// Render a document field's JSON value as the plain text FTS5 stores and
+    /// tokenizes columns as.
+    fn field_value_to_text(value: Option<&Value>) -> String {
+        match value {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        }
+    }
+
+    /// Render a facet group's `json_extract` result as the string key used in
+    /// [`SearchStore::facet_search`]'s distribution map. Documents missing the
+    /// facet field extract to SQL `NULL`, which isn't a countable value.
+    fn facet_value_to_key(value: &Value) -> Option<String> {
+        match value {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Translate one [`SearchCondition`] into a `json_extract(fields, '$.<key>')`
+    /// predicate, returning the SQL fragment and the values it binds. Placeholder
+    /// numbers are assigned starting from `next_param`, which is bumped past each
+    /// one consumed so callers can chain conditions onto a single positional
+    /// parameter list.
+    fn condition_clause(condition: &SearchCondition, next_param: &mut usize) -> (String, Vec<Value>) {
+        let mut placeholder = || {
+            let idx = *next_param;
+            *next_param += 1;
+            format!("?{idx}")
+        };
+
+        match condition {
+            SearchCondition::Eq(key, value) => (
+                format!("json_extract(fields, '$.{key}') = {}", placeholder()),
+                vec![value.clone()],
+            ),
+            SearchCondition::Range(key, min, max) => {
+                let mut clauses = Vec::new();
+                let mut values = Vec::new();
+                if let Some(min) = min {
+                    clauses.push(format!("json_extract(fields, '$.{key}') >= {}", placeholder()));
+                    values.push(min.clone());
+                }
+                if let Some(max) = max {
+                    clauses.push(format!("json_extract(fields, '$.{key}') <= {}", placeholder()));
+                    values.push(max.clone());
+                }
+                if clauses.is_empty() {
+                    ("1".to_string(), vec![])
+                } else {
+                    (clauses.join(" AND "), values)
+                }
+            }
+            SearchCondition::In(key, values) => {
+                if values.is_empty() {
+                    return ("0".to_string(), vec![]);
+                }
+                let placeholders: Vec<String> = values.iter().map(|_| placeholder()).collect();
+                (
+                    format!("json_extract(fields, '$.{key}') IN ({})", placeholders.join(", ")),
+                    values.clone(),
+                )
+            }
+        }
+    }
+
+    /// Translate every condition in `filter` into a single `AND`-joined SQL
+    /// fragment (each condition is itself already AND/OR-shaped internally, e.g.
+    /// a range's lower and upper bound), plus the values it binds in order.
+    /// Returns an empty fragment and no values when `filter` has no conditions.
+    fn filter_clause(filter: &SearchFilter, next_param: &mut usize) -> (String, Vec<Value>) {
+        let mut sql = String::new();
+        let mut values = Vec::new();
+        for condition in &filter.conditions {
+            let (clause, clause_values) = Self::condition_clause(condition, next_param);
+            sql.push_str(" AND ");
+            sql.push_str(&clause);
+            values.extend(clause_values);
+        }
+        (sql, values)
+    }
+
+    /// Build the highlight/snippet SELECT expression for `content` (FTS5 column
+    /// index 1), binding `opts`'s tags (and token window, for a cropped snippet)
+    /// as parameters. With no options, selects a constant empty string so the
+    /// `highlight_snippet` column is always present in the result set.
+    fn highlight_select(
+        table: &str,
+        opts: Option<&HighlightOptions>,
+        params: &mut Vec<Value>,
+        next_param: &mut usize,
+    ) -> String {
+        let Some(opts) = opts else {
+            return ", '' as highlight_snippet".to_string();
+        };
+
+        let open_idx = *next_param;
+        *next_param += 1;
+        params.push(Value::String(opts.open_tag.clone()));
+
+        let close_idx = *next_param;
+        *next_param += 1;
+        params.push(Value::String(opts.close_tag.clone()));
+
+        if opts.crop_to_snippet {
+            let tokens_idx = *next_param;
+            *next_param += 1;
+            params.push(Value::Number(opts.snippet_tokens.into()));
+            format!(
+                ", snippet({table}, 1, ?{open_idx}, ?{close_idx}, '…', ?{tokens_idx}) as highlight_snippet"
+            )
+        } else {
+            format!(", highlight({table}, 1, ?{open_idx}, ?{close_idx}) as highlight_snippet")
+        }
+    }
+
+    /// Like [`SearchStore::search`], but tolerant of typos: any query term with
+    /// no exact hit in `index`'s vocabulary is OR-expanded with terms within
+    /// [`TypoToleranceConfig::min_len_for_distance_1`]/`_2`'s edit distance, so
+    /// e.g. `"technlogy"` still matches documents containing `"technology"`.
+    /// Disabled by default (plain [`SearchStore::search`] stays exact-match
+    /// only) — callers opt in per query by calling this instead.
+    pub async fn search_typo_tolerant(
+        &self,
+        index: &str,
+        query: &str,
+        config: &TypoToleranceConfig,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let rewritten = self.expand_typos(index, query, config).await?;
+        self.search(index, &rewritten, limit).await
+    }
+
+    /// Load `index`'s distinct token vocabulary via FTS5's `fts5vocab` module,
+    /// creating the (persistent, reusable) shadow vocabulary table on demand.
+    async fn load_vocabulary(&self, index: &str) -> Result<HashSet<String>> {
+        let table = Self::table_name(index);
+        let vocab_table = format!("{table}_vocab");
+
+        self.db
+            .execute(
+                &format!(
+                    "CREATE VIRTUAL TABLE IF NOT EXISTS {vocab_table} USING fts5vocab('{table}', 'row')"
+                ),
+                &[],
+            )
+            .await?;
+
+        let rows = self
+            .db
+            .query(&format!("SELECT term FROM {vocab_table}"), &[])
+            .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| row.get_str("term"))
+            .map(|s| s.to_string())
+            .collect())
+    }
+
+    /// Rewrite `query` so that any term absent from `index`'s vocabulary is
+    /// OR-expanded with nearby (edit-distance) vocabulary terms, per `config`.
+    /// Terms already in the vocabulary, and terms too short for either
+    /// distance tier, pass through unchanged.
+    async fn expand_typos(&self, index: &str, query: &str, config: &TypoToleranceConfig) -> Result<String> {
+        let vocabulary = self.load_vocabulary(index).await?;
+
+        let expanded: Vec<String> = query
+            .split_whitespace()
+            .map(|term| {
+                let term = term.to_lowercase();
+                if vocabulary.contains(&term) {
+                    return term;
+                }
+
+                let candidates = Self::typo_candidates(&term, &vocabulary, config);
+                if candidates.is_empty() {
+                    term
+                } else {
+                    let mut group = vec![term];
+                    group.extend(candidates);
+                    format!("({})", group.join(" OR "))
+                }
+            })
+            .collect();
+
+        Ok(expanded.join(" "))
+    }
+
+    /// Find up to `config.max_candidates` vocabulary terms within edit
+    /// distance of `term`, ordered by distance then alphabetically. Returns
+    /// no candidates for terms shorter than `min_len_for_distance_1`. Before
+    /// computing full edit distance, drops vocabulary terms whose length
+    /// differs by more than the allowed distance and terms that start with a
+    /// different letter, so a typo in the first letter finds no candidates.
+    fn typo_candidates(term: &str, vocabulary: &HashSet<String>, config: &TypoToleranceConfig) -> Vec<String> {
+        let term_len = term.chars().count();
+        let max_distance = if term_len >= config.min_len_for_distance_2 {
+            2
+        } else if term_len >= config.min_len_for_distance_1 {
+            1
+        } else {
+            return vec![];
+        };
+
+        let first_char = term.chars().next();
+
+        let mut candidates: Vec<(usize, &str)> = vocabulary
+            .iter()
+            .filter(|candidate| candidate.as_str() != term)
+            .filter(|candidate| candidate.chars().next() == first_char)
+            .filter(|candidate| {
+                let len_diff = (candidate.chars().count() as i64 - term_len as i64).unsigned_abs() as usize;
+                len_diff <= max_distance
+            })
+            .filter_map(|candidate| {
+                let distance = levenshtein(term, candidate);
+                (distance <= max_distance).then_some((distance, candidate.as_str()))
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+        candidates
+            .into_iter()
+            .take(config.max_candidates)
+            .map(|(_, candidate)| candidate.to_string())
+            .collect()
+    }
+
+    /// Bulk-index a CSV or JSON-Lines stream into `index`, parsing it lazily
+    /// (one line at a time, never buffering the whole input) and batching
+    /// rows into `index_documents` calls per `format`'s configured batch
+    /// size. Returns the total number of documents indexed.
+    pub async fn index_from_reader(
+        &self,
+        index: &str,
+        format: ImportFormat,
+        reader: impl AsyncRead + Unpin + Send,
+    ) -> Result<usize> {
+        match format {
+            ImportFormat::Csv(config) => self.index_from_csv(index, reader, &config).await,
+            ImportFormat::JsonLines(config) => self.index_from_jsonl(index, reader, &config).await,
+        }
+    }
+
+    async fn index_from_csv(
+        &self,
+        index: &str,
+        reader: impl AsyncRead + Unpin + Send,
+        config: &CsvImportConfig,
+    ) -> Result<usize> {
+        let mut lines = BufReader::new(reader).lines();
+        let header = match lines
+            .next_line()
+            .await
+            .map_err(|e| StoreError::invalid_input(e.to_string()))?
+        {
+            Some(line) => parse_csv_line(&line),
+            None => return Ok(0),
+        };
+
+        let mut total = 0;
+        let mut batch = Vec::with_capacity(config.batch_size);
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| StoreError::invalid_input(e.to_string()))?
+        {
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(doc) = csv_row_to_document(&header, &parse_csv_line(&line), config) {
+                batch.push(doc);
+            }
+            if batch.len() >= config.batch_size {
+                total += self.index_documents(index, &batch).await?;
+                batch.clear();
+            }
+        }
+        if !batch.is_empty() {
+            total += self.index_documents(index, &batch).await?;
+        }
+        Ok(total)
+    }
+
+    async fn index_from_jsonl(
+        &self,
+        index: &str,
+        reader: impl AsyncRead + Unpin + Send,
+        config: &JsonlImportConfig,
+    ) -> Result<usize> {
+        let mut lines = BufReader::new(reader).lines();
+
+        let mut total = 0;
+        let mut batch = Vec::with_capacity(config.batch_size);
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| StoreError::invalid_input(e.to_string()))?
+        {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(doc) = jsonl_line_to_document(&line, config) {
+                batch.push(doc);
+            }
+            if batch.len() >= config.batch_size {
+                total += self.index_documents(index, &batch).await?;
+                batch.clear();
+            }
+        }
+        if !batch.is_empty() {
+            total += self.index_documents(index, &batch).await?;
+        }
+        Ok(total)
+    }
+
+    /// Upsert one chunk of documents as a single transaction: a single
+    /// multi-row `DELETE ... WHERE id IN (...)` clears any existing rows
+    /// sharing an id, then a single multi-row `INSERT` adds them back, so a
+    /// chunk of N documents costs one round trip each way instead of two per
+    /// document. Rolls back and propagates the error on any failure, leaving
+    /// the table unchanged.
+    async fn upsert_document_chunk(
+        &self,
+        table: &str,
+        schema_fields: &[IndexField],
+        chunk: &[Document],
+    ) -> Result<usize> {
+        if chunk.is_empty() {
+            return Ok(0);
+        }
+
+        self.db.execute("BEGIN", &[]).await?;
+
+        match self.upsert_document_chunk_inner(table, schema_fields, chunk).await {
+            Ok(count) => {
+                self.db.execute("COMMIT", &[]).await?;
+                Ok(count)
+            }
+            Err(e) => {
+                let _ = self.db.execute("ROLLBACK", &[]).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn upsert_document_chunk_inner(
+        &self,
+        table: &str,
+        schema_fields: &[IndexField],
+        chunk: &[Document],
+    ) -> Result<usize> {
+        let delete_placeholders: Vec<String> = (1..=chunk.len()).map(|i| format!("?{i}")).collect();
+        let delete_values: Vec<Value> = chunk.iter().map(|doc| Value::String(doc.id.clone())).collect();
+        self.db
+            .execute(
+                &format!("DELETE FROM {table} WHERE id IN ({})", delete_placeholders.join(", ")),
+                &delete_values,
+            )
+            .await?;
+
+        let mut columns = vec!["id".to_string(), "content".to_string()];
+        for field in schema_fields {
+            columns.push(field.name.clone());
+        }
+        columns.push("fields".to_string());
+
+        let mut values = Vec::with_capacity(chunk.len() * columns.len());
+        let mut row_placeholders = Vec::with_capacity(chunk.len());
+        let mut next_param = 1;
+        for doc in chunk {
+            let fields_json = serde_json::to_string(&doc.fields)
+                .map_err(|e| StoreError::Serialization(e.to_string()))?;
+
+            let mut placeholders = Vec::with_capacity(columns.len());
+            let mut push = |value: Value, placeholders: &mut Vec<String>| {
+                placeholders.push(format!("?{next_param}"));
+                next_param += 1;
+                values.push(value);
+            };
+            push(Value::String(doc.id.clone()), &mut placeholders);
+            push(Value::String(doc.content.clone()), &mut placeholders);
+            for field in schema_fields {
+                push(
+                    Value::String(Self::field_value_to_text(doc.fields.get(&field.name))),
+                    &mut placeholders,
+                );
+            }
+            push(Value::String(fields_json), &mut placeholders);
+
+            row_placeholders.push(format!("({})", placeholders.join(", ")));
+        }
+
+        self.db
+            .execute(
+                &format!(
+                    "INSERT INTO {table} ({}) VALUES {}",
+                    columns.join(", "),
+                    row_placeholders.join(", ")
+                ),
+                &values,
+            )
+            .await?;
+
+        Ok(chunk.len())
+    }
+
+    /// Dump `index` to `writer` as a line-delimited JSON archive: a `Header`
+    /// record with its persisted schema, followed by one `Document` record
+    /// per row. Since FTS5 virtual tables can't be copied with a plain file
+    /// snapshot, this content-level dump is how an index is backed up,
+    /// moved between databases, or migrated across schema/tokenizer changes.
+    /// Returns the number of documents written.
+    pub async fn export_dump(&self, index: &str, mut writer: impl AsyncWrite + Unpin + Send) -> Result<usize> {
+        if !self.index_exists(index).await? {
+            return Err(StoreError::not_found(format!("Index '{}'", index)));
+        }
+
+        let schema = IndexSchema {
+            fields: self.load_schema(index).await?,
+        };
+        Self::write_dump_line(
+            &mut writer,
+            &DumpRecord::Header {
+                index: index.to_string(),
+                schema,
+            },
+        )
+        .await?;
+
+        let table = Self::table_name(index);
+        let rows = self
+            .db
+            .query(&format!("SELECT id, content, fields FROM {table}"), &[])
+            .await?;
+
+        let mut count = 0;
+        for row in rows {
+            let id = row.get_str("id").unwrap_or_default().to_string();
+            let content = row.get_str("content").unwrap_or_default().to_string();
+            let fields: HashMap<String, Value> = row
+                .get_str("fields")
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default();
+
+            Self::write_dump_line(&mut writer, &DumpRecord::Document { id, content, fields }).await?;
+            count += 1;
+        }
+
+        writer.flush().await.map_err(|e| StoreError::internal(e.to_string()))?;
+        Ok(count)
+    }
+
+    /// Dump every index in the store to `writer`, one `Header`/`Document*`
+    /// section after another in the same stream. Returns the total number of
+    /// documents written across all indexes.
+    pub async fn export_all_dumps(&self, mut writer: impl AsyncWrite + Unpin + Send) -> Result<usize> {
+        // `list_indexes` also matches FTS5's own shadow tables (`fts_<name>_data`,
+        // `fts_<name>_idx`, ...) and `fts5vocab` tables; only tables declared
+        // `USING fts5(` are indexes.
+        let rows = self
+            .db
+            .query(
+                "SELECT name FROM sqlite_master WHERE type='table' AND name LIKE 'fts_%' AND sql LIKE '%USING fts5(%'",
+                &[],
+            )
+            .await?;
+
+        let mut total = 0;
+        for name in rows.iter().filter_map(|r| r.get_str("name")) {
+            let index = name.strip_prefix("fts_").unwrap_or(name).replace('_', "-");
+            total += self.export_dump(&index, &mut writer).await?;
+        }
+        Ok(total)
+    }
+
+    /// Restore an archive written by [`Self::export_dump`]/[`Self::export_all_dumps`]:
+    /// each `Header` recreates its index from the embedded schema (if it
+    /// doesn't already exist), and the `Document` records that follow it are
+    /// streamed back through the batched [`SearchStore::index_documents`]
+    /// path. Returns the total number of documents indexed.
+    pub async fn import_dump(&self, reader: impl AsyncRead + Unpin + Send) -> Result<usize> {
+        let mut lines = BufReader::new(reader).lines();
+
+        let mut current_index: Option<String> = None;
+        let mut batch: Vec<Document> = Vec::with_capacity(self.batch_size);
+        let mut total = 0;
+
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| StoreError::invalid_input(e.to_string()))?
+        {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: DumpRecord =
+                serde_json::from_str(&line).map_err(|e| StoreError::Serialization(e.to_string()))?;
+
+            match record {
+                DumpRecord::Header { index, schema } => {
+                    if let Some(previous) = current_index.take() {
+                        if !batch.is_empty() {
+                            total += self.index_documents(&previous, &batch).await?;
+                            batch.clear();
+                        }
+                    }
+                    if !self.index_exists(&index).await? {
+                        self.create_index(&index, &schema).await?;
+                    }
+                    current_index = Some(index);
+                }
+                DumpRecord::Document { id, content, fields } => {
+                    let Some(index) = current_index.as_ref() else {
+                        return Err(StoreError::invalid_input(
+                            "dump document record appears before any header record",
+                        ));
+                    };
+
+                    let mut doc = Document::new(id, content);
+                    doc.fields = fields;
+                    batch.push(doc);
+
+                    if batch.len() >= self.batch_size {
+                        total += self.index_documents(index, &batch).await?;
+                        batch.clear();
+                    }
+                }
+            }
+        }
+
+        if let Some(index) = current_index {
+            if !batch.is_empty() {
+                total += self.index_documents(&index, &batch).await?;
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn write_dump_line(writer: &mut (impl AsyncWrite + Unpin + Send), record: &DumpRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record).map_err(|e| StoreError::Serialization(e.to_string()))?;
+        line.push('\n');
+        writer
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| StoreError::internal(e.to_string()))
+    }
 }
 
 #[async_trait]
 impl SearchStore for SqliteFtsStore {
-    async fn create_index(&self, name: &str, _schema: &IndexSchema) -> Result<()> {
+    async fn create_index(&self, name: &str, schema: &IndexSchema) -> Result<()> {
+        Self::validate_index_uid(name)?;
         let table = Self::table_name(name);
 
+        let mut columns = vec!["id".to_string(), "content".to_string()];
+        for field in &schema.fields {
+            columns.push(if field.indexed {
+                field.name.clone()
+            } else {
+                format!("{} UNINDEXED", field.name)
+            });
+        }
+        columns.push("fields UNINDEXED".to_string());
+
         let sql = format!(
-            "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING fts5(
-                id,
-                content,
-                fields,
+            "CREATE VIRTUAL TABLE IF NOT EXISTS {table} USING fts5(
+                {},
                 tokenize='porter unicode61'
             )",
-            table
+            columns.join(",\n                ")
         );
 
         self.db.execute(&sql, &[]).await?;
+        self.save_schema(name, schema).await?;
         Ok(())
     }
 
@@ -52,6 +785,14 @@ impl SearchStore for SqliteFtsStore {
         let table = Self::table_name(name);
         let sql = format!("DROP TABLE IF EXISTS {}", table);
         self.db.execute(&sql, &[]).await?;
+
+        self.ensure_schema_table().await?;
+        self.db
+            .execute(
+                &format!("DELETE FROM {SCHEMA_TABLE} WHERE index_name = ?1"),
+                &[Value::String(name.to_string())],
+            )
+            .await?;
         Ok(())
     }
 
@@ -77,42 +818,21 @@ impl SearchStore for SqliteFtsStore {
     }
 
     async fn index_documents(&self, index: &str, docs: &[Document]) -> Result<usize> {
+        Self::validate_index_uid(index)?;
         let table = Self::table_name(index);
 
         if !self.index_exists(index).await? {
             return Err(StoreError::not_found(format!("Index '{}'", index)));
         }
 
-        let mut count = 0;
-        for doc in docs {
-            let fields_json = serde_json::to_string(&doc.fields)
-                .map_err(|e| StoreError::Serialization(e.to_string()))?;
-
-            self.db
-                .execute(
-                    &format!("DELETE FROM {} WHERE id = ?1", table),
-                    &[Value::String(doc.id.clone())],
-                )
-                .await?;
-
-            self.db
-                .execute(
-                    &format!(
-                        "INSERT INTO {} (id, content, fields) VALUES (?1, ?2, ?3)",
-                        table
-                    ),
-                    &[
-                        Value::String(doc.id.clone()),
-                        Value::String(doc.content.clone()),
-                        Value::String(fields_json),
-                    ],
-                )
-                .await?;
+        let schema_fields = self.load_schema(index).await?;
 
-            count += 1;
+        let mut total = 0;
+        for chunk in docs.chunks(self.batch_size) {
+            total += self.upsert_document_chunk(&table, &schema_fields, chunk).await?;
         }
 
-        Ok(count)
+        Ok(total)
     }
 
     async fn delete_documents(&self, index: &str, ids: &[String]) -> Result<usize> {
@@ -138,7 +858,7 @@ impl SearchStore for SqliteFtsStore {
     }
 
     async fn search(&self, index: &str, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
-        self.search_with_filter(index, query, &SearchFilter::default(), limit)
+        self.search_with_filter(index, query, &SearchFilter::default(), None, limit)
             .await
     }
 
@@ -146,7 +866,8 @@ impl SearchStore for SqliteFtsStore {
         &self,
         index: &str,
         query: &str,
-        _filter: &SearchFilter,
+        filter: &SearchFilter,
+        highlight: Option<&HighlightOptions>,
         limit: usize,
     ) -> Result<Vec<SearchHit>> {
         let table = Self::table_name(index);
@@ -155,27 +876,38 @@ impl SearchStore for SqliteFtsStore {
             return Err(StoreError::not_found(format!("Index '{}'", index)));
         }
 
+        let schema_fields = self.load_schema(index).await?;
+
+        // bm25() takes one weight per column, in table declaration order:
+        // id, content, each schema field, then the fields blob. `id` and
+        // `fields` never rank, so they're weighted out; `content` always
+        // ranks at the base weight, with schema fields scaled relative to it.
+        let mut weights = vec!["0".to_string(), "1".to_string()];
+        weights.extend(schema_fields.iter().map(|f| f.weight.to_string()));
+        weights.push("0".to_string());
+        let bm25_expr = format!("bm25({table}, {})", weights.join(", "));
+
         let escaped_query = query.replace('"', "\"\"");
 
+        let mut params = vec![Value::String(escaped_query)];
+        let mut next_param = 2;
+        let (filter_sql, filter_values) = Self::filter_clause(filter, &mut next_param);
+        params.extend(filter_values);
+
+        let highlight_sql = Self::highlight_select(&table, highlight, &mut params, &mut next_param);
+
+        let limit_placeholder = format!("?{next_param}");
+        params.push(Value::Number(limit.into()));
+
         let sql = format!(
-            "SELECT id, content, fields, bm25({}) as score
-             FROM {} 
-             WHERE {} MATCH ?1
+            "SELECT id, content, fields, {bm25_expr} as score{highlight_sql}
+             FROM {table}
+             WHERE {table} MATCH ?1{filter_sql}
              ORDER BY score
-             LIMIT ?2",
-            table, table, table
+             LIMIT {limit_placeholder}"
         );
 
-        let rows = self
-            .db
-            .query(
-                &sql,
-                &[
-                    Value::String(escaped_query),
-                    Value::Number(limit.into()),
-                ],
-            )
-            .await?;
+        let rows = self.db.query(&sql, &params).await?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -185,12 +917,16 @@ impl SearchStore for SqliteFtsStore {
             let fields_str = row.get_str("fields").unwrap_or("{}");
             let fields: std::collections::HashMap<String, Value> =
                 serde_json::from_str(fields_str).unwrap_or_default();
+            let highlights = match row.get_str("highlight_snippet") {
+                Some(snippet) if !snippet.is_empty() => vec![snippet.to_string()],
+                _ => vec![],
+            };
 
             results.push(SearchHit {
                 id,
                 score: -score,
                 content,
-                highlights: vec![],
+                highlights,
                 fields,
             });
         }
@@ -198,6 +934,54 @@ impl SearchStore for SqliteFtsStore {
         Ok(results)
     }
 
+    async fn facet_search(
+        &self,
+        index: &str,
+        query: &str,
+        filter: &SearchFilter,
+        facets: &[String],
+        limit: usize,
+    ) -> Result<FacetSearchResult> {
+        let hits = self.search_with_filter(index, query, filter, None, limit).await?;
+
+        let table = Self::table_name(index);
+        let escaped_query = query.replace('"', "\"\"");
+
+        let mut facet_distribution = std::collections::HashMap::new();
+        for facet in facets {
+            let mut params = vec![Value::String(escaped_query.clone())];
+            let mut next_param = 2;
+            let (filter_sql, filter_values) = Self::filter_clause(filter, &mut next_param);
+            params.extend(filter_values);
+
+            // `fields` is always present (even empty `{}`), so json_extract
+            // is always valid SQL here; it just yields NULL for documents
+            // missing `facet`, which we drop below rather than count as a value.
+            let sql = format!(
+                "SELECT json_extract(fields, '$.{facet}') as facet_value, COUNT(*) as facet_count
+                 FROM {table}
+                 WHERE {table} MATCH ?1{filter_sql}
+                 GROUP BY facet_value"
+            );
+
+            let rows = self.db.query(&sql, &params).await?;
+            let mut counts = std::collections::HashMap::new();
+            for row in rows {
+                let Some(value) = row.get("facet_value").and_then(Self::facet_value_to_key) else {
+                    continue;
+                };
+                let count = row.get_i64("facet_count").unwrap_or(0) as usize;
+                counts.insert(value, count);
+            }
+            facet_distribution.insert(facet.clone(), counts);
+        }
+
+        Ok(FacetSearchResult {
+            hits,
+            facet_distribution,
+        })
+    }
+
     async fn count(&self, index: &str) -> Result<usize> {
         let table = Self::table_name(index);
 
@@ -335,6 +1119,541 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_fts_search_with_filter_matches_field_equality() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("art1", "An interesting article about technology")
+                .with_field("author", "Alice")
+                .with_field("category", "tech"),
+            Document::new("art2", "Another interesting article about technology")
+                .with_field("author", "Bob")
+                .with_field("category", "tech"),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let filter = SearchFilter::new().eq("author", "Alice");
+        let results = store
+            .search_with_filter("articles", "interesting", &filter, None, 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_with_filter_combines_eq_and_in() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("art1", "interesting technology article")
+                .with_field("category", "tech")
+                .with_field("year", 2023),
+            Document::new("art2", "interesting technology article")
+                .with_field("category", "tech")
+                .with_field("year", 2020),
+            Document::new("art3", "interesting technology article")
+                .with_field("category", "sports")
+                .with_field("year", 2023),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let filter = SearchFilter::new()
+            .eq("category", "tech")
+            .in_list("year", vec![serde_json::json!(2023)]);
+        let results = store
+            .search_with_filter("articles", "interesting", &filter, None, 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_with_highlight_wraps_matched_terms() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("docs", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        store
+            .index_documents(
+                "docs",
+                &[Document::new("doc1", "The quick brown fox jumps over the lazy dog")],
+            )
+            .await
+            .unwrap();
+
+        let highlight = HighlightOptions::new().with_tags("[", "]");
+        let results = store
+            .search_with_filter("docs", "quick", &SearchFilter::default(), Some(&highlight), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].highlights.len(), 1);
+        assert!(results[0].highlights[0].contains("[quick]"));
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_without_highlight_options_has_no_highlights() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("docs", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        store
+            .index_documents("docs", &[Document::new("doc1", "a quick brown fox")])
+            .await
+            .unwrap();
+
+        let results = store.search("docs", "quick", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].highlights.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fts_facet_search_counts_distinct_values_among_matches() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("art1", "interesting technology article").with_field("category", "tech"),
+            Document::new("art2", "interesting technology article").with_field("category", "tech"),
+            Document::new("art3", "interesting technology article").with_field("category", "sports"),
+            Document::new("art4", "not matching at all"),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let result = store
+            .facet_search(
+                "articles",
+                "interesting",
+                &SearchFilter::default(),
+                &["category".to_string()],
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.hits.len(), 3);
+        let category_counts = result.facet_distribution.get("category").unwrap();
+        assert_eq!(category_counts.get("tech"), Some(&2));
+        assert_eq!(category_counts.get("sports"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_fts_facet_search_combines_with_filter() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("art1", "interesting technology article")
+                .with_field("category", "tech")
+                .with_field("author", "Alice"),
+            Document::new("art2", "interesting technology article")
+                .with_field("category", "tech")
+                .with_field("author", "Bob"),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let filter = SearchFilter::new().eq("author", "Alice");
+        let result = store
+            .facet_search("articles", "interesting", &filter, &["category".to_string()], 10)
+            .await
+            .unwrap();
+
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(
+            result.facet_distribution.get("category").unwrap().get("tech"),
+            Some(&1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_weights_rank_matching_field_higher() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index(
+                "articles",
+                &IndexSchema::new()
+                    .add_text("title")
+                    .weight(5.0)
+                    .add_text("body"),
+            )
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("doc_title", "placeholder")
+                .with_field("title", "unique term here")
+                .with_field("body", "nothing relevant"),
+            Document::new("doc_body", "placeholder")
+                .with_field("title", "nothing relevant")
+                .with_field("body", "unique term here"),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let results = store.search("articles", "unique", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "doc_title");
+    }
+
+    #[tokio::test]
+    async fn test_fts_schema_persists_across_reopen() {
+        let path = std::env::temp_dir().join(format!("hehe-fts-schema-test-{}.sqlite3", hehe_core::Id::new()));
+        let path = path.to_str().unwrap().to_string();
+
+        {
+            let store = SqliteFtsStore::from_path(&path).await.unwrap();
+            store
+                .create_index(
+                    "articles",
+                    &IndexSchema::new().add_text("title").weight(4.0).add_text("body"),
+                )
+                .await
+                .unwrap();
+            store
+                .index_documents(
+                    "articles",
+                    &[
+                        Document::new("doc_title", "placeholder")
+                            .with_field("title", "unique term here")
+                            .with_field("body", "nothing relevant"),
+                        Document::new("doc_body", "placeholder")
+                            .with_field("title", "nothing relevant")
+                            .with_field("body", "unique term here"),
+                    ],
+                )
+                .await
+                .unwrap();
+        }
+
+        // Reopen against the same file to simulate a process restart; the
+        // persisted schema (not an in-memory `IndexSchema`) must still drive
+        // per-column weighting.
+        let reopened = SqliteFtsStore::from_path(&path).await.unwrap();
+        let results = reopened.search("articles", "unique", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "doc_title");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_typo_tolerant_corrects_misspelled_term() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        store
+            .index_documents(
+                "articles",
+                &[Document::new("art1", "An interesting article about technology")],
+            )
+            .await
+            .unwrap();
+
+        assert!(store.search("articles", "technlogy", 10).await.unwrap().is_empty());
+
+        let results = store
+            .search_typo_tolerant("articles", "technlogy", &TypoToleranceConfig::new(), 10)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+    }
+
+    #[tokio::test]
+    async fn test_fts_search_typo_tolerant_requires_opt_in() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        store
+            .index_documents("articles", &[Document::new("art1", "an article about technology")])
+            .await
+            .unwrap();
+
+        let results = store.search("articles", "technlogy", 10).await.unwrap();
+        assert!(results.is_empty(), "plain search must stay exact-match only");
+    }
+
+    #[tokio::test]
+    async fn test_fts_typo_candidates_respects_max_candidates() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs = vec![
+            Document::new("art1", "bat"),
+            Document::new("art2", "bet"),
+            Document::new("art3", "bit"),
+            Document::new("art4", "bot"),
+        ];
+        store.index_documents("articles", &docs).await.unwrap();
+
+        let vocabulary = store.load_vocabulary("articles").await.unwrap();
+        let config = TypoToleranceConfig::new()
+            .with_min_len_for_distance_1(3)
+            .with_max_candidates(2);
+        let candidates = SqliteFtsStore::typo_candidates("but", &vocabulary, &config);
+        assert_eq!(candidates.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_documents_batches_large_inserts_into_multiple_chunks() {
+        let store = SqliteFtsStore::memory().await.unwrap().with_batch_size(2);
+        store
+            .create_index("docs", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let docs: Vec<Document> = (0..5)
+            .map(|i| Document::new(format!("doc{i}"), format!("content number {i}")))
+            .collect();
+
+        let total = store.index_documents("docs", &docs).await.unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(store.count("docs").await.unwrap(), 5);
+
+        let results = store.search("docs", "content", 10).await.unwrap();
+        assert_eq!(results.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_documents_chunked_upsert_replaces_existing_rows() {
+        let store = SqliteFtsStore::memory().await.unwrap().with_batch_size(2);
+        store
+            .create_index("docs", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("doc1", "original one"),
+                    Document::new("doc2", "original two"),
+                    Document::new("doc3", "original three"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(store.count("docs").await.unwrap(), 3);
+
+        store
+            .index_documents(
+                "docs",
+                &[Document::new("doc1", "updated one"), Document::new("doc2", "updated two")],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(store.count("docs").await.unwrap(), 3);
+        let results = store.search("docs", "updated", 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_from_reader_csv_batches_and_maps_fields() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let csv = "id,title,category\nart1,Hello world,tech\nart2,Another piece,sports\n";
+        let config = CsvImportConfig::new("title").with_id_column("id").with_batch_size(1);
+
+        let total = store
+            .index_from_reader("articles", ImportFormat::Csv(config), csv.as_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(total, 2);
+        assert_eq!(store.count("articles").await.unwrap(), 2);
+
+        let results = store.search("articles", "hello", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+        assert_eq!(
+            results[0].fields.get("category"),
+            Some(&Value::String("tech".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_from_reader_jsonl_maps_fields() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let jsonl = "{\"id\":\"art1\",\"body\":\"Hello world\",\"category\":\"tech\"}\n{\"id\":\"art2\",\"body\":\"Another piece\",\"category\":\"sports\"}\n";
+        let config = JsonlImportConfig::new("body").with_id_key("id");
+
+        let total = store
+            .index_from_reader("articles", ImportFormat::JsonLines(config), jsonl.as_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(total, 2);
+        let results = store.search("articles", "hello", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_from_reader_csv_without_id_column_generates_ids() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store
+            .create_index("articles", &IndexSchema::new())
+            .await
+            .unwrap();
+
+        let csv = "title\nHello world\n";
+        let config = CsvImportConfig::new("title");
+
+        let total = store
+            .index_from_reader("articles", ImportFormat::Csv(config), csv.as_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(total, 1);
+        assert_eq!(store.count("articles").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fts_export_then_import_dump_round_trips_schema_and_documents() {
+        let source = SqliteFtsStore::memory().await.unwrap();
+        source
+            .create_index(
+                "articles",
+                &IndexSchema::new().add_text("title").weight(3.0).add_keyword("category"),
+            )
+            .await
+            .unwrap();
+        source
+            .index_documents(
+                "articles",
+                &[
+                    Document::new("art1", "Hello world")
+                        .with_field("title", "Hello world")
+                        .with_field("category", "tech"),
+                    Document::new("art2", "Another article")
+                        .with_field("title", "Another article")
+                        .with_field("category", "sports"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let mut archive = Vec::new();
+        let written = source.export_dump("articles", &mut archive).await.unwrap();
+        assert_eq!(written, 2);
+
+        let dest = SqliteFtsStore::memory().await.unwrap();
+        assert!(!dest.index_exists("articles").await.unwrap());
+
+        let imported = dest.import_dump(archive.as_slice()).await.unwrap();
+        assert_eq!(imported, 2);
+        assert_eq!(dest.count("articles").await.unwrap(), 2);
+
+        let results = dest.search("articles", "hello", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "art1");
+        assert_eq!(
+            results[0].fields.get("category"),
+            Some(&Value::String("tech".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fts_export_all_dumps_covers_every_index() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store.create_index("notes", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("docs", &[Document::new("d1", "doc content")])
+            .await
+            .unwrap();
+        store
+            .index_documents("notes", &[Document::new("n1", "note content")])
+            .await
+            .unwrap();
+
+        let mut archive = Vec::new();
+        let total = store.export_all_dumps(&mut archive).await.unwrap();
+        assert_eq!(total, 2);
+
+        let dest = SqliteFtsStore::memory().await.unwrap();
+        let imported = dest.import_dump(archive.as_slice()).await.unwrap();
+        assert_eq!(imported, 2);
+        assert_eq!(dest.count("docs").await.unwrap(), 1);
+        assert_eq!(dest.count("notes").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fts_create_index_rejects_invalid_uid() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+
+        let err = store
+            .create_index("bad name!", &IndexSchema::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_code(), crate::error::codes::INVALID_INDEX_UID);
+        assert_eq!(err.status(), 400);
+
+        store.create_index("valid_name-1", &IndexSchema::new()).await.unwrap();
+        assert!(store.index_exists("valid_name-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_fts_index_documents_rejects_invalid_uid() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+
+        let err = store
+            .index_documents("bad name!", &[Document::new("id", "content")])
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_code(), crate::error::codes::INVALID_INDEX_UID);
+    }
+
+    #[tokio::test]
+    async fn test_fts_not_found_error_has_index_not_found_code() {
+        let store = SqliteFtsStore::memory().await.unwrap();
+
+        let err = store.search("nonexistent", "query", 10).await.unwrap_err();
+        assert_eq!(err.error_code(), crate::error::codes::INDEX_NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_fts_nonexistent_index() {
         let store = SqliteFtsStore::memory().await.unwrap();
@@ -0,0 +1,396 @@
+//! Incremental HNSW (Hierarchical Navigable Small World) approximate
+//! nearest-neighbor index used by [`super::memory_vector::MemoryVectorStore`]
+//! for collections where an exact linear `cosine_similarity` scan no longer
+//! scales. See Malkov & Yashunin, "Efficient and robust approximate nearest
+//! neighbor search using Hierarchical Navigable Small World graphs".
+
+use crate::traits::cosine_similarity;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Tunable parameters of an [`HnswIndex`]. `m` is the number of bidirectional
+/// links created per node per layer (`2*m` at layer 0), `ef_construction`
+/// is the candidate list size used while building the graph, and
+/// `ef_search` is the candidate list size used at query time (larger is
+/// slower but more accurate).
+#[derive(Clone, Copy, Debug)]
+pub struct HnswConfig {
+    pub m: usize,
+    pub ef_construction: usize,
+    pub ef_search: usize,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 50,
+        }
+    }
+}
+
+impl HnswConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_m(mut self, m: usize) -> Self {
+        self.m = m.max(1);
+        self
+    }
+
+    pub fn with_ef_construction(mut self, ef_construction: usize) -> Self {
+        self.ef_construction = ef_construction.max(1);
+        self
+    }
+
+    pub fn with_ef_search(mut self, ef_search: usize) -> Self {
+        self.ef_search = ef_search.max(1);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Neighbor {
+    dist: f32,
+    idx: usize,
+}
+
+impl Eq for Neighbor {}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.dist.total_cmp(&other.dist)
+    }
+}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct HnswNode {
+    id: String,
+    vector: Vec<f32>,
+    neighbors: Vec<Vec<usize>>,
+    deleted: bool,
+}
+
+/// A tiny xorshift64 PRNG, used only to diversify per-node HNSW levels.
+/// Avoids pulling in an external RNG crate for what is otherwise not
+/// security- or statistics-sensitive randomness.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed | 1)
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        let unit = (x >> 11) as f64 / (1u64 << 53) as f64;
+        unit.clamp(f64::EPSILON, 1.0 - f64::EPSILON) as f32
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - cosine_similarity(a, b)
+}
+
+/// An incrementally-built HNSW graph over a fixed-dimension vector space.
+/// Insertion assigns each node a random top level `l = floor(-ln(u) * mL)`
+/// (`mL = 1/ln(m)`), threads it into the graph from the top layer down, and
+/// connects it to its closest neighbors at every layer `<= l`, pruning
+/// over-full neighbor lists back down to `m` (`2*m` at layer 0). Deletions
+/// are tombstones: the node is skipped by future traversals and results but
+/// its graph slot is not reclaimed.
+pub(crate) struct HnswIndex {
+    config: HnswConfig,
+    ml: f32,
+    nodes: Vec<HnswNode>,
+    id_to_idx: HashMap<String, usize>,
+    entry_point: Option<usize>,
+    rng: Rng,
+}
+
+impl HnswIndex {
+    pub(crate) fn new(config: HnswConfig) -> Self {
+        let ml = 1.0 / (config.m.max(2) as f32).ln();
+        Self {
+            config,
+            ml,
+            nodes: Vec::new(),
+            id_to_idx: HashMap::new(),
+            entry_point: None,
+            rng: Rng::new(0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.id_to_idx.len()
+    }
+
+    fn random_level(&mut self) -> usize {
+        let u = self.rng.next_f32();
+        (-u.ln() * self.ml).floor() as usize
+    }
+
+    /// Inserts or replaces the vector for `id`. A replacement is implemented
+    /// as a tombstone of the old node followed by a fresh insert, which
+    /// keeps the graph-building logic simple at the cost of leaving a dead
+    /// slot behind.
+    pub(crate) fn insert(&mut self, id: &str, vector: Vec<f32>) {
+        if self.id_to_idx.contains_key(id) {
+            self.remove(id);
+        }
+
+        let level = self.random_level();
+        let idx = self.nodes.len();
+        self.nodes.push(HnswNode {
+            id: id.to_string(),
+            vector: vector.clone(),
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.id_to_idx.insert(id.to_string(), idx);
+
+        let entry = match self.entry_point {
+            None => {
+                self.entry_point = Some(idx);
+                return;
+            }
+            Some(entry) => entry,
+        };
+
+        let top_level = self.nodes[entry].neighbors.len() - 1;
+        let mut curr = entry;
+        for layer in (level + 1..=top_level).rev() {
+            curr = self.greedy_closest(curr, &vector, layer);
+        }
+
+        let mut entry_points = vec![curr];
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates = self.search_layer(&vector, &entry_points, self.config.ef_construction, layer);
+            let max_conns = if layer == 0 { self.config.m * 2 } else { self.config.m };
+            let selected: Vec<usize> = candidates.iter().take(max_conns).map(|n| n.idx).collect();
+
+            for &neighbor in &selected {
+                self.nodes[idx].neighbors[layer].push(neighbor);
+                self.nodes[neighbor].neighbors[layer].push(idx);
+                self.prune_neighbors(neighbor, layer);
+            }
+
+            entry_points = candidates.into_iter().map(|n| n.idx).collect();
+        }
+
+        if level > top_level {
+            self.entry_point = Some(idx);
+        }
+    }
+
+    /// Tombstones `id` so it is skipped by future traversals and results.
+    /// Returns `false` if `id` was not present.
+    pub(crate) fn remove(&mut self, id: &str) -> bool {
+        let Some(idx) = self.id_to_idx.remove(id) else {
+            return false;
+        };
+        self.nodes[idx].deleted = true;
+
+        if self.entry_point == Some(idx) {
+            self.entry_point = self
+                .nodes
+                .iter()
+                .position(|node| !node.deleted);
+        }
+
+        true
+    }
+
+    /// Approximate nearest neighbors of `query`, as `(id, cosine_similarity)`
+    /// pairs sorted descending by similarity.
+    pub(crate) fn search(&self, query: &[f32], k: usize) -> Vec<(String, f32)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let top_level = self.nodes[entry].neighbors.len() - 1;
+        let mut curr = entry;
+        for layer in (1..=top_level).rev() {
+            curr = self.greedy_closest(curr, query, layer);
+        }
+
+        let ef = self.config.ef_search.max(k);
+        self.search_layer(query, &[curr], ef, 0)
+            .into_iter()
+            .take(k)
+            .map(|n| (self.nodes[n.idx].id.clone(), 1.0 - n.dist))
+            .collect()
+    }
+
+    fn greedy_closest(&self, entry: usize, query: &[f32], layer: usize) -> usize {
+        let mut curr = entry;
+        let mut curr_dist = distance(query, &self.nodes[curr].vector);
+
+        loop {
+            let mut improved = false;
+            for &neighbor in &self.nodes[curr].neighbors[layer] {
+                if self.nodes[neighbor].deleted {
+                    continue;
+                }
+                let d = distance(query, &self.nodes[neighbor].vector);
+                if d < curr_dist {
+                    curr_dist = d;
+                    curr = neighbor;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return curr;
+            }
+        }
+    }
+
+    /// Beam search at a single layer: returns up to `ef` candidates nearest
+    /// `query`, sorted ascending by distance, reached from `entry_points`.
+    fn search_layer(&self, query: &[f32], entry_points: &[usize], ef: usize, layer: usize) -> Vec<Neighbor> {
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut candidates: BinaryHeap<Reverse<Neighbor>> = BinaryHeap::new();
+        let mut results: BinaryHeap<Neighbor> = BinaryHeap::new();
+
+        for &ep in entry_points {
+            if self.nodes[ep].deleted || visited.contains(&ep) {
+                continue;
+            }
+            visited.insert(ep);
+            let d = distance(query, &self.nodes[ep].vector);
+            candidates.push(Reverse(Neighbor { dist: d, idx: ep }));
+            results.push(Neighbor { dist: d, idx: ep });
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if let Some(furthest) = results.peek() {
+                if current.dist > furthest.dist && results.len() >= ef {
+                    break;
+                }
+            }
+
+            if layer >= self.nodes[current.idx].neighbors.len() {
+                continue;
+            }
+
+            for &neighbor in &self.nodes[current.idx].neighbors[layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                if self.nodes[neighbor].deleted {
+                    continue;
+                }
+
+                let d = distance(query, &self.nodes[neighbor].vector);
+                let worst = results.peek().map(|n| n.dist).unwrap_or(f32::MAX);
+                if results.len() < ef || d < worst {
+                    candidates.push(Reverse(Neighbor { dist: d, idx: neighbor }));
+                    results.push(Neighbor { dist: d, idx: neighbor });
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        let mut out = results.into_vec();
+        out.sort_by(|a, b| a.dist.total_cmp(&b.dist));
+        out
+    }
+
+    fn prune_neighbors(&mut self, idx: usize, layer: usize) {
+        let max_conns = if layer == 0 { self.config.m * 2 } else { self.config.m };
+        if self.nodes[idx].neighbors[layer].len() <= max_conns {
+            return;
+        }
+
+        let vector = self.nodes[idx].vector.clone();
+        let mut scored: Vec<(usize, f32)> = self.nodes[idx].neighbors[layer]
+            .iter()
+            .map(|&n| (n, distance(&vector, &self.nodes[n].vector)))
+            .collect();
+        scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+        scored.truncate(max_conns);
+        self.nodes[idx].neighbors[layer] = scored.into_iter().map(|(n, _)| n).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(angle_deg: f32) -> Vec<f32> {
+        let rad = angle_deg.to_radians();
+        vec![rad.cos(), rad.sin()]
+    }
+
+    #[test]
+    fn test_insert_and_search_returns_closest_vector() {
+        let mut index = HnswIndex::new(HnswConfig::new());
+        for i in 0..50 {
+            index.insert(&format!("v{i}"), unit(i as f32 * 7.0));
+        }
+
+        let results = index.search(&unit(0.0), 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "v0");
+        assert!(results[0].1 > 0.99);
+    }
+
+    #[test]
+    fn test_search_returns_k_results_ranked_descending() {
+        let mut index = HnswIndex::new(HnswConfig::new().with_ef_search(100));
+        for i in 0..200 {
+            index.insert(&format!("v{i}"), unit(i as f32 * 1.5));
+        }
+
+        let results = index.search(&unit(0.0), 5);
+        assert_eq!(results.len(), 5);
+        for pair in results.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+    }
+
+    #[test]
+    fn test_remove_excludes_node_from_future_searches() {
+        let mut index = HnswIndex::new(HnswConfig::new());
+        for i in 0..50 {
+            index.insert(&format!("v{i}"), unit(i as f32 * 7.0));
+        }
+
+        assert!(index.remove("v0"));
+        assert_eq!(index.len(), 49);
+
+        let results = index.search(&unit(0.0), 50);
+        assert!(!results.iter().any(|(id, _)| id == "v0"));
+    }
+
+    #[test]
+    fn test_insert_replaces_existing_id() {
+        let mut index = HnswIndex::new(HnswConfig::new());
+        index.insert("a", unit(0.0));
+        index.insert("b", unit(90.0));
+        index.insert("a", unit(180.0));
+
+        assert_eq!(index.len(), 2);
+        let results = index.search(&unit(180.0), 1);
+        assert_eq!(results[0].0, "a");
+    }
+
+    #[test]
+    fn test_empty_index_search_returns_nothing() {
+        let index = HnswIndex::new(HnswConfig::new());
+        assert!(index.search(&unit(0.0), 5).is_empty());
+    }
+}
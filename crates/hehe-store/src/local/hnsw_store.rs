@@ -0,0 +1,313 @@
+//! A [`VectorStore`] backed purely by an [`HnswIndex`] per collection,
+//! unlike [`super::memory_vector::MemoryVectorStore`] where the index is an
+//! opt-in accelerator behind an exact-scan fallback. Every collection here
+//! always searches approximately, so this backend trades a small amount of
+//! recall for search time that stays roughly flat as collections grow past
+//! what a linear scan can handle.
+
+use super::hnsw::{HnswConfig, HnswIndex};
+use crate::error::{Result, StoreError};
+use crate::traits::{
+    BoxStream, CollectionInfo, DistanceMetric, SearchResult, VectorFilter, VectorRecord,
+    VectorStore,
+};
+use async_trait::async_trait;
+use futures::stream;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+/// How many candidates [`HnswStore::search_with_filter`] pulls from the
+/// index per requested result before filtering, since the graph has no
+/// notion of metadata filters and a selective one can reject most
+/// candidates. A very selective filter can still return fewer than `limit`
+/// hits if the index doesn't surface enough matching candidates.
+const FILTER_OVERSAMPLE_FACTOR: usize = 10;
+
+struct Collection {
+    dimension: usize,
+    index: HnswIndex,
+    records: HashMap<String, VectorRecord>,
+}
+
+/// An in-memory [`VectorStore`] where every collection is always backed by
+/// an [`HnswIndex`], configured once for the whole store via
+/// [`HnswStore::with_config`]. See [`super::memory_vector::MemoryVectorStore`]
+/// for a store that can mix exact and approximate collections.
+pub struct HnswStore {
+    config: HnswConfig,
+    collections: RwLock<HashMap<String, Collection>>,
+}
+
+impl HnswStore {
+    pub fn new() -> Self {
+        Self::with_config(HnswConfig::default())
+    }
+
+    /// Like [`Self::new`], but every collection's [`HnswIndex`] is built
+    /// with `config` instead of [`HnswConfig::default`].
+    pub fn with_config(config: HnswConfig) -> Self {
+        Self {
+            config,
+            collections: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for HnswStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VectorStore for HnswStore {
+    async fn create_collection(&self, name: &str, dimension: usize) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Collection '{}'", name)));
+        }
+        collections.insert(
+            name.to_string(),
+            Collection {
+                dimension,
+                index: HnswIndex::new(self.config),
+                records: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.remove(name).is_none() {
+            return Err(StoreError::not_found(format!("Collection '{}'", name)));
+        }
+        Ok(())
+    }
+
+    async fn list_collections(&self) -> Result<Vec<CollectionInfo>> {
+        let collections = self.collections.read();
+        Ok(collections
+            .iter()
+            .map(|(name, col)| CollectionInfo {
+                name: name.clone(),
+                dimension: col.dimension,
+                count: col.records.len(),
+                metric: DistanceMetric::Cosine,
+            })
+            .collect())
+    }
+
+    async fn collection_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.collections.read().contains_key(name))
+    }
+
+    async fn upsert(&self, collection: &str, records: &[VectorRecord]) -> Result<usize> {
+        let mut collections = self.collections.write();
+        let col = collections
+            .get_mut(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let mut count = 0;
+        for record in records {
+            if record.vector.len() != col.dimension {
+                return Err(StoreError::invalid_input(format!(
+                    "Vector dimension mismatch: expected {}, got {}",
+                    col.dimension,
+                    record.vector.len()
+                )));
+            }
+            col.index.insert(&record.id, record.vector.clone());
+            col.records.insert(record.id.clone(), record.clone());
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    async fn search(&self, collection: &str, query: &[f32], limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_filter(collection, query, &VectorFilter::default(), limit)
+            .await
+    }
+
+    async fn search_with_filter(
+        &self,
+        collection: &str,
+        query: &[f32],
+        filter: &VectorFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        if query.len() != col.dimension {
+            return Err(StoreError::invalid_input(format!(
+                "Query dimension mismatch: expected {}, got {}",
+                col.dimension,
+                query.len()
+            )));
+        }
+
+        let fetch = if filter.is_empty() {
+            limit
+        } else {
+            limit.saturating_mul(FILTER_OVERSAMPLE_FACTOR).max(limit)
+        };
+
+        Ok(col
+            .index
+            .search(query, fetch)
+            .into_iter()
+            .filter_map(|(id, score)| col.records.get(&id).map(|record| (score, record)))
+            .filter(|(_, record)| filter.evaluate(&record.metadata))
+            .take(limit)
+            .map(|(score, record)| SearchResult {
+                id: record.id.clone(),
+                score,
+                metadata: record.metadata.clone(),
+                content: record.content.clone(),
+            })
+            .collect())
+    }
+
+    async fn get(&self, collection: &str, id: &str) -> Result<Option<VectorRecord>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        Ok(col.records.get(id).cloned())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[String]) -> Result<usize> {
+        let mut collections = self.collections.write();
+        let col = collections
+            .get_mut(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let mut count = 0;
+        for id in ids {
+            if col.records.remove(id).is_some() {
+                col.index.remove(id);
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    async fn count(&self, collection: &str) -> Result<usize> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        Ok(col.records.len())
+    }
+
+    async fn export(&self, collection: &str) -> Result<BoxStream<VectorRecord>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let records: Vec<VectorRecord> = col.records.values().cloned().collect();
+        Ok(Box::pin(stream::iter(records.into_iter().map(Ok))))
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "hnsw"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(angle_deg: f32) -> Vec<f32> {
+        let rad = angle_deg.to_radians();
+        vec![rad.cos(), rad.sin()]
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_rejects_duplicate() {
+        let store = HnswStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        assert!(store.create_collection("docs", 2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_search_returns_closest_vector() {
+        let store = HnswStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+
+        let records: Vec<VectorRecord> = (0..50)
+            .map(|i| VectorRecord::new(format!("v{i}"), unit(i as f32 * 7.0)))
+            .collect();
+        store.upsert("docs", &records).await.unwrap();
+
+        let results = store.search("docs", &unit(0.0), 1).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "v0");
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filter_only_returns_matching_metadata() {
+        let store = HnswStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+
+        let records = vec![
+            VectorRecord::new("a", unit(0.0)).with_metadata("kind", "article"),
+            VectorRecord::new("b", unit(1.0)).with_metadata("kind", "note"),
+        ];
+        store.upsert("docs", &records).await.unwrap();
+
+        let filter = VectorFilter::new().eq("kind", "note");
+        let results = store
+            .search_with_filter("docs", &unit(0.0), &filter, 10)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "b");
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_from_index_and_records() {
+        let store = HnswStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        store
+            .upsert("docs", &[VectorRecord::new("a", unit(0.0))])
+            .await
+            .unwrap();
+
+        assert_eq!(store.delete("docs", &["a".to_string()]).await.unwrap(), 1);
+        assert_eq!(store.count("docs").await.unwrap(), 0);
+        assert!(store.get("docs", "a").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_every_record() {
+        use futures::StreamExt;
+
+        let store = HnswStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        store
+            .upsert(
+                "docs",
+                &[VectorRecord::new("a", unit(0.0)), VectorRecord::new("b", unit(90.0))],
+            )
+            .await
+            .unwrap();
+
+        let mut stream = store.export("docs").await.unwrap();
+        let mut ids = Vec::new();
+        while let Some(record) = stream.next().await {
+            ids.push(record.unwrap().id);
+        }
+        ids.sort();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string()]);
+    }
+}
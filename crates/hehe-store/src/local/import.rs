@@ -0,0 +1,207 @@
+use crate::traits::Document;
+use serde_json::Value;
+
+/// Configures [`SqliteFtsStore::index_from_reader`](super::SqliteFtsStore::index_from_reader)'s
+/// CSV ingestion: which header column becomes [`Document::content`] (and,
+/// optionally, [`Document::id`]), plus how many rows to batch per
+/// `index_documents` call.
+#[derive(Clone, Debug)]
+pub struct CsvImportConfig {
+    pub content_column: String,
+    pub id_column: Option<String>,
+    pub batch_size: usize,
+}
+
+impl CsvImportConfig {
+    pub fn new(content_column: impl Into<String>) -> Self {
+        Self {
+            content_column: content_column.into(),
+            id_column: None,
+            batch_size: 500,
+        }
+    }
+
+    pub fn with_id_column(mut self, column: impl Into<String>) -> Self {
+        self.id_column = Some(column.into());
+        self
+    }
+
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+}
+
+/// Configures [`SqliteFtsStore::index_from_reader`](super::SqliteFtsStore::index_from_reader)'s
+/// JSON-Lines ingestion: which object key becomes [`Document::content`] (and,
+/// optionally, [`Document::id`]), plus how many lines to batch per
+/// `index_documents` call.
+#[derive(Clone, Debug)]
+pub struct JsonlImportConfig {
+    pub content_key: String,
+    pub id_key: Option<String>,
+    pub batch_size: usize,
+}
+
+impl JsonlImportConfig {
+    pub fn new(content_key: impl Into<String>) -> Self {
+        Self {
+            content_key: content_key.into(),
+            id_key: None,
+            batch_size: 500,
+        }
+    }
+
+    pub fn with_id_key(mut self, key: impl Into<String>) -> Self {
+        self.id_key = Some(key.into());
+        self
+    }
+
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+}
+
+/// Selects which bulk format [`SqliteFtsStore::index_from_reader`](super::SqliteFtsStore::index_from_reader)
+/// parses a stream as.
+#[derive(Clone, Debug)]
+pub enum ImportFormat {
+    Csv(CsvImportConfig),
+    JsonLines(JsonlImportConfig),
+}
+
+/// Split one CSV line into fields. Supports double-quoted fields containing
+/// commas, with `""` as an escaped quote — RFC 4180's minimal dialect, which
+/// is all a header-driven importer needs.
+pub(crate) fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == ',' {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Build a [`Document`] from one parsed CSV `row`, given its `header` column
+/// names and `config`. Returns `None` if the row has no value in the
+/// configured content column (e.g. a short/malformed row).
+pub(crate) fn csv_row_to_document(header: &[String], row: &[String], config: &CsvImportConfig) -> Option<Document> {
+    let column = |name: &str| header.iter().position(|h| h == name).and_then(|i| row.get(i));
+
+    let content = column(&config.content_column)?.clone();
+    let id = config
+        .id_column
+        .as_deref()
+        .and_then(column)
+        .cloned()
+        .unwrap_or_else(|| hehe_core::Id::new().to_string());
+
+    let mut doc = Document::new(id, content);
+    for (i, name) in header.iter().enumerate() {
+        if name == &config.content_column || config.id_column.as_deref() == Some(name.as_str()) {
+            continue;
+        }
+        if let Some(value) = row.get(i) {
+            doc = doc.with_field(name.clone(), value.clone());
+        }
+    }
+    Some(doc)
+}
+
+/// Build a [`Document`] from one JSON-Lines `line`, given `config`. Returns
+/// `None` if the line isn't a JSON object or has no value under the
+/// configured content key.
+pub(crate) fn jsonl_line_to_document(line: &str, config: &JsonlImportConfig) -> Option<Document> {
+    let Value::Object(mut obj) = serde_json::from_str(line).ok()? else {
+        return None;
+    };
+
+    let content = match obj.remove(&config.content_key)? {
+        Value::String(s) => s,
+        other => other.to_string(),
+    };
+
+    let id = config
+        .id_key
+        .as_ref()
+        .and_then(|key| obj.remove(key))
+        .map(|v| match v {
+            Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .unwrap_or_else(|| hehe_core::Id::new().to_string());
+
+    let mut doc = Document::new(id, content);
+    for (key, value) in obj {
+        doc = doc.with_field(key, value);
+    }
+    Some(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_line_splits_plain_fields() {
+        assert_eq!(parse_csv_line("id,title,body"), vec!["id", "title", "body"]);
+    }
+
+    #[test]
+    fn test_parse_csv_line_handles_quoted_comma_and_escaped_quote() {
+        let fields = parse_csv_line(r#"1,"hello, ""world""",tech"#);
+        assert_eq!(fields, vec!["1", "hello, \"world\"", "tech"]);
+    }
+
+    #[test]
+    fn test_csv_row_to_document_maps_content_id_and_remaining_fields() {
+        let header = vec!["id".to_string(), "title".to_string(), "category".to_string()];
+        let row = vec!["art1".to_string(), "Hello world".to_string(), "tech".to_string()];
+        let config = CsvImportConfig::new("title").with_id_column("id");
+
+        let doc = csv_row_to_document(&header, &row, &config).unwrap();
+        assert_eq!(doc.id, "art1");
+        assert_eq!(doc.content, "Hello world");
+        assert_eq!(doc.fields.get("category"), Some(&Value::String("tech".into())));
+        assert!(!doc.fields.contains_key("title"));
+    }
+
+    #[test]
+    fn test_jsonl_line_to_document_maps_content_id_and_remaining_fields() {
+        let line = r#"{"id":"art1","body":"Hello world","category":"tech"}"#;
+        let config = JsonlImportConfig::new("body").with_id_key("id");
+
+        let doc = jsonl_line_to_document(line, &config).unwrap();
+        assert_eq!(doc.id, "art1");
+        assert_eq!(doc.content, "Hello world");
+        assert_eq!(doc.fields.get("category"), Some(&Value::String("tech".into())));
+    }
+
+    #[test]
+    fn test_jsonl_line_to_document_missing_content_key_returns_none() {
+        let config = JsonlImportConfig::new("body");
+        assert!(jsonl_line_to_document(r#"{"title":"no body here"}"#, &config).is_none());
+    }
+}
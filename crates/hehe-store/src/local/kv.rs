@@ -0,0 +1,437 @@
+use crate::error::{Result, StoreError};
+use crate::local::SqliteStore;
+use crate::traits::{RelationalStore, Transaction};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A monotonically increasing stamp assigned to a key on every successful
+/// write, so callers can detect whether a key changed since they last read
+/// it — the same role Deno KV's `versionstamp` plays. Backed by a
+/// single-row counter table rather than derived from `rowid` or a
+/// timestamp, so it still advances on a `set` that writes back the same
+/// value, and stays correct across process restarts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VersionStamp(pub u64);
+
+/// A key/value pair as read back from [`SqliteKvStore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KvEntry {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    pub version_stamp: VersionStamp,
+}
+
+/// One precondition in an [`AtomicWrite`]: the commit only proceeds if
+/// `key`'s current version stamp equals `expected` (`None` meaning "the key
+/// must not exist yet").
+struct Check {
+    key: Vec<u8>,
+    expected: Option<VersionStamp>,
+}
+
+/// One write in an [`AtomicWrite`], applied only once every [`Check`] has
+/// passed.
+enum Mutation {
+    Set { key: Vec<u8>, value: Vec<u8> },
+    Delete { key: Vec<u8> },
+    /// Adds `delta` to the key's current value, read as an 8-byte
+    /// big-endian `i64` (a missing key reads as `0`), wrapping on overflow.
+    Sum { key: Vec<u8>, delta: i64 },
+}
+
+/// Result of [`AtomicWrite::commit`]. A mismatched [`Check`] aborts the
+/// whole batch — nothing is written and no version stamp is assigned — but
+/// is reported here rather than as an `Err`, since a failed optimistic-
+/// concurrency check is an expected outcome callers branch on, not a fault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitOutcome {
+    Committed(VersionStamp),
+    CheckFailed,
+}
+
+/// Accumulates [`Check`] preconditions and mutations, then applies all of
+/// them inside one SQLite transaction via [`Self::commit`]. Built with
+/// [`SqliteKvStore::atomic`].
+pub struct AtomicWrite<'a> {
+    store: &'a SqliteKvStore,
+    checks: Vec<Check>,
+    mutations: Vec<Mutation>,
+}
+
+impl<'a> AtomicWrite<'a> {
+    /// Requires `key`'s current version stamp to equal `expected_version`
+    /// (or `None` to require the key be absent) for the commit to proceed.
+    pub fn check(mut self, key: impl Into<Vec<u8>>, expected_version: Option<VersionStamp>) -> Self {
+        self.checks.push(Check { key: key.into(), expected: expected_version });
+        self
+    }
+
+    pub fn set(mut self, key: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Self {
+        self.mutations.push(Mutation::Set { key: key.into(), value: value.into() });
+        self
+    }
+
+    pub fn delete(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.mutations.push(Mutation::Delete { key: key.into() });
+        self
+    }
+
+    pub fn sum(mut self, key: impl Into<Vec<u8>>, delta: i64) -> Self {
+        self.mutations.push(Mutation::Sum { key: key.into(), delta });
+        self
+    }
+
+    pub async fn commit(self) -> Result<CommitOutcome> {
+        self.store.apply_atomic(self.checks, self.mutations).await
+    }
+}
+
+/// Encodes arbitrary bytes as lowercase hex, the one encoding shared by
+/// every BLOB-shaped column in this store: unlike base64, equal-length hex
+/// strings compare in the same order as the bytes they came from, which
+/// [`SqliteKvStore::scan`] relies on for its `key >= ? AND key < ?` range.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hex_decode(text: &str) -> Result<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return Err(StoreError::internal("corrupt kv entry: odd-length hex"));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16)
+                .map_err(|e| StoreError::internal(format!("corrupt kv entry: {e}")))
+        })
+        .collect()
+}
+
+/// A Deno-KV-style key/value layer on top of [`SqliteStore`]: plain
+/// `get`/`set`/`delete`/`scan` for the common case, plus [`Self::atomic`]
+/// for optimistic-concurrency batches that check-then-mutate several keys
+/// in one transaction.
+///
+/// Keys and values are arbitrary bytes, stored hex-encoded in `TEXT`
+/// columns rather than the schema's literal `BLOB` — the only way this
+/// store talks to SQLite is through [`RelationalStore`]'s `serde_json::Value`
+/// params, which has no byte-string variant, so a lossless textual encoding
+/// is the closest fit that interface allows.
+pub struct SqliteKvStore {
+    db: Arc<SqliteStore>,
+}
+
+impl SqliteKvStore {
+    pub fn new(db: Arc<SqliteStore>) -> Self {
+        Self { db }
+    }
+
+    pub async fn from_path(path: &str) -> Result<Self> {
+        let store = Self { db: Arc::new(SqliteStore::open(path)?) };
+        store.ensure_tables().await?;
+        Ok(store)
+    }
+
+    pub async fn memory() -> Result<Self> {
+        let store = Self { db: Arc::new(SqliteStore::memory()?) };
+        store.ensure_tables().await?;
+        Ok(store)
+    }
+
+    async fn ensure_tables(&self) -> Result<()> {
+        self.db
+            .execute(
+                "CREATE TABLE IF NOT EXISTS kv_entries (
+                    key TEXT PRIMARY KEY,
+                    value TEXT NOT NULL,
+                    version_stamp INTEGER NOT NULL
+                )",
+                &[],
+            )
+            .await?;
+        self.db
+            .execute(
+                "CREATE TABLE IF NOT EXISTS kv_version_counter (
+                    id INTEGER PRIMARY KEY CHECK (id = 0),
+                    counter INTEGER NOT NULL
+                )",
+                &[],
+            )
+            .await?;
+        self.db
+            .execute("INSERT OR IGNORE INTO kv_version_counter (id, counter) VALUES (0, 0)", &[])
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &[u8]) -> Result<Option<KvEntry>> {
+        let row = self
+            .db
+            .query_one(
+                "SELECT value, version_stamp FROM kv_entries WHERE key = ?1",
+                &[Value::String(hex_encode(key))],
+            )
+            .await?;
+
+        row.map(|row| {
+            let value = hex_decode(row.get_str("value").unwrap_or_default())?;
+            let version_stamp = VersionStamp(row.get_i64("version_stamp").unwrap_or_default() as u64);
+            Ok(KvEntry { key: key.to_vec(), value, version_stamp })
+        })
+        .transpose()
+    }
+
+    /// Shorthand for `atomic().set(key, value).commit()` with no checks, so
+    /// a plain write never needs to look at [`CommitOutcome`].
+    pub async fn set(&self, key: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Result<VersionStamp> {
+        match self.atomic().set(key, value).commit().await? {
+            CommitOutcome::Committed(stamp) => Ok(stamp),
+            CommitOutcome::CheckFailed => unreachable!("a write with no checks can't fail a check"),
+        }
+    }
+
+    /// Shorthand for `atomic().delete(key).commit()`.
+    pub async fn delete(&self, key: impl Into<Vec<u8>>) -> Result<()> {
+        self.atomic().delete(key).commit().await?;
+        Ok(())
+    }
+
+    /// Lists up to `limit` entries with `start <= key < end`, ascending by
+    /// key. `end` is exclusive, matching Deno KV's range semantics — pass a
+    /// key one past the last one you want (e.g. the prefix with its last
+    /// byte incremented) to scan a whole prefix.
+    pub async fn scan(&self, start: &[u8], end: &[u8], limit: usize) -> Result<Vec<KvEntry>> {
+        let rows = self
+            .db
+            .query(
+                "SELECT key, value, version_stamp FROM kv_entries
+                 WHERE key >= ?1 AND key < ?2 ORDER BY key ASC LIMIT ?3",
+                &[
+                    Value::String(hex_encode(start)),
+                    Value::String(hex_encode(end)),
+                    serde_json::json!(limit as i64),
+                ],
+            )
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let key = hex_decode(row.get_str("key").unwrap_or_default())?;
+                let value = hex_decode(row.get_str("value").unwrap_or_default())?;
+                let version_stamp = VersionStamp(row.get_i64("version_stamp").unwrap_or_default() as u64);
+                Ok(KvEntry { key, value, version_stamp })
+            })
+            .collect()
+    }
+
+    /// Starts an [`AtomicWrite`] batch of checks and mutations against this
+    /// store.
+    pub fn atomic(&self) -> AtomicWrite<'_> {
+        AtomicWrite { store: self, checks: Vec::new(), mutations: Vec::new() }
+    }
+
+    async fn apply_atomic(&self, checks: Vec<Check>, mutations: Vec<Mutation>) -> Result<CommitOutcome> {
+        let mut tx = self.db.begin().await?;
+
+        for check in &checks {
+            let row = tx
+                .query_one(
+                    "SELECT version_stamp FROM kv_entries WHERE key = ?1",
+                    &[Value::String(hex_encode(&check.key))],
+                )
+                .await?;
+
+            let actual = row.and_then(|row| row.get_i64("version_stamp")).map(|v| VersionStamp(v as u64));
+            if actual != check.expected {
+                tx.rollback().await?;
+                return Ok(CommitOutcome::CheckFailed);
+            }
+        }
+
+        tx.execute("UPDATE kv_version_counter SET counter = counter + 1 WHERE id = 0", &[]).await?;
+        let counter_row = tx
+            .query_one("SELECT counter FROM kv_version_counter WHERE id = 0", &[])
+            .await?
+            .ok_or_else(|| StoreError::internal("kv version counter row is missing"))?;
+        let version_stamp = VersionStamp(counter_row.get_i64("counter").unwrap_or_default() as u64);
+
+        for mutation in mutations {
+            match mutation {
+                Mutation::Set { key, value } => {
+                    Self::upsert(&mut *tx, &key, &value, version_stamp).await?;
+                }
+                Mutation::Delete { key } => {
+                    tx.execute("DELETE FROM kv_entries WHERE key = ?1", &[Value::String(hex_encode(&key))])
+                        .await?;
+                }
+                Mutation::Sum { key, delta } => {
+                    let key_hex = hex_encode(&key);
+                    let existing = tx
+                        .query_one("SELECT value FROM kv_entries WHERE key = ?1", &[Value::String(key_hex.clone())])
+                        .await?;
+
+                    let current = match existing {
+                        Some(row) => {
+                            let bytes = hex_decode(row.get_str("value").unwrap_or_default())?;
+                            let array: [u8; 8] = bytes.as_slice().try_into().map_err(|_| {
+                                StoreError::invalid_input("sum mutation on a value that isn't an 8-byte integer")
+                            })?;
+                            i64::from_be_bytes(array)
+                        }
+                        None => 0,
+                    };
+
+                    let updated = current.wrapping_add(delta);
+                    Self::upsert(&mut *tx, &key, &updated.to_be_bytes(), version_stamp).await?;
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(CommitOutcome::Committed(version_stamp))
+    }
+
+    async fn upsert(tx: &mut dyn Transaction, key: &[u8], value: &[u8], version_stamp: VersionStamp) -> Result<()> {
+        tx.execute(
+            "INSERT INTO kv_entries (key, value, version_stamp) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, version_stamp = excluded.version_stamp",
+            &[Value::String(hex_encode(key)), Value::String(hex_encode(value)), serde_json::json!(version_stamp.0)],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_kv_set_and_get() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        let stamp = store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        let entry = store.get(b"a").await.unwrap().unwrap();
+        assert_eq!(entry.value, b"1");
+        assert_eq!(entry.version_stamp, stamp);
+
+        assert!(store.get(b"missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_kv_version_stamp_advances_on_every_write() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        let first = store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        let second = store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        assert!(second > first, "writing back the same value should still bump the version stamp");
+    }
+
+    #[tokio::test]
+    async fn test_kv_delete() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        store.delete(b"a".to_vec()).await.unwrap();
+
+        assert!(store.get(b"a").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_kv_atomic_check_passes_and_applies_mutations() {
+        let store = SqliteKvStore::memory().await.unwrap();
+        let stamp = store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+
+        let outcome = store
+            .atomic()
+            .check(b"a".to_vec(), Some(stamp))
+            .set(b"a".to_vec(), b"2".to_vec())
+            .set(b"b".to_vec(), b"new".to_vec())
+            .commit()
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, CommitOutcome::Committed(_)));
+        assert_eq!(store.get(b"a").await.unwrap().unwrap().value, b"2");
+        assert_eq!(store.get(b"b").await.unwrap().unwrap().value, b"new");
+    }
+
+    #[tokio::test]
+    async fn test_kv_atomic_check_mismatch_applies_nothing() {
+        let store = SqliteKvStore::memory().await.unwrap();
+        let stale_stamp = store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        store.set(b"a".to_vec(), b"2".to_vec()).await.unwrap();
+
+        let outcome = store
+            .atomic()
+            .check(b"a".to_vec(), Some(stale_stamp))
+            .set(b"a".to_vec(), b"3".to_vec())
+            .set(b"b".to_vec(), b"new".to_vec())
+            .commit()
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CommitOutcome::CheckFailed);
+        assert_eq!(store.get(b"a").await.unwrap().unwrap().value, b"2", "a mismatched check should roll back the whole batch");
+        assert!(store.get(b"b").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_kv_atomic_check_absent_key() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        let outcome = store.atomic().check(b"a".to_vec(), None).set(b"a".to_vec(), b"1".to_vec()).commit().await.unwrap();
+        assert!(matches!(outcome, CommitOutcome::Committed(_)));
+
+        let outcome = store.atomic().check(b"a".to_vec(), None).set(b"a".to_vec(), b"2".to_vec()).commit().await.unwrap();
+        assert_eq!(outcome, CommitOutcome::CheckFailed, "key now exists, so expecting absence should fail");
+    }
+
+    #[tokio::test]
+    async fn test_kv_sum_wraps_on_overflow() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        store.atomic().sum(b"counter".to_vec(), i64::MAX).commit().await.unwrap();
+        store.atomic().sum(b"counter".to_vec(), 1).commit().await.unwrap();
+
+        let entry = store.get(b"counter").await.unwrap().unwrap();
+        let value = i64::from_be_bytes(entry.value.as_slice().try_into().unwrap());
+        assert_eq!(value, i64::MIN, "sum should wrap rather than panic on overflow");
+    }
+
+    #[tokio::test]
+    async fn test_kv_sum_on_missing_key_starts_from_zero() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        store.atomic().sum(b"counter".to_vec(), 5).commit().await.unwrap();
+
+        let entry = store.get(b"counter").await.unwrap().unwrap();
+        let value = i64::from_be_bytes(entry.value.as_slice().try_into().unwrap());
+        assert_eq!(value, 5);
+    }
+
+    #[tokio::test]
+    async fn test_kv_scan_returns_keys_in_byte_order() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        store.set(b"b".to_vec(), b"2".to_vec()).await.unwrap();
+        store.set(b"a".to_vec(), b"1".to_vec()).await.unwrap();
+        store.set(b"c".to_vec(), b"3".to_vec()).await.unwrap();
+
+        let entries = store.scan(b"a", b"c", 10).await.unwrap();
+        let keys: Vec<Vec<u8>> = entries.into_iter().map(|e| e.key).collect();
+        assert_eq!(keys, vec![b"a".to_vec(), b"b".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn test_kv_scan_respects_limit() {
+        let store = SqliteKvStore::memory().await.unwrap();
+
+        for k in [b"a", b"b", b"c"] {
+            store.set(k.to_vec(), b"v".to_vec()).await.unwrap();
+        }
+
+        let entries = store.scan(b"a", b"z", 2).await.unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+}
@@ -0,0 +1,445 @@
+//! An approximate [`VectorStore`] backend using SimHash random-hyperplane
+//! locality-sensitive hashing for cosine similarity. Unlike
+//! [`super::hnsw_store::HnswStore`]'s graph traversal, candidates are found
+//! by hash-bucket lookup and then exactly reranked, which trades recall for
+//! a simpler, more cache-friendly index.
+
+use crate::error::{Result, StoreError};
+use crate::traits::{
+    cosine_similarity, BoxStream, CollectionInfo, DistanceMetric, SearchResult, VectorFilter,
+    VectorRecord, VectorStore,
+};
+use async_trait::async_trait;
+use futures::stream;
+use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
+
+/// Tunable parameters of an [`LshStore`] collection. `l` is the number of
+/// independent hash tables (more tables raise recall, at the cost of more
+/// buckets to probe) and `k` is the number of random hyperplanes per table
+/// (more hyperplanes shrink buckets, raising precision but lowering
+/// recall). `k` is capped at 64 since a signature is packed into a `u64`.
+#[derive(Clone, Copy, Debug)]
+pub struct LshConfig {
+    pub l: usize,
+    pub k: usize,
+}
+
+impl Default for LshConfig {
+    fn default() -> Self {
+        Self { l: 8, k: 12 }
+    }
+}
+
+impl LshConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_l(mut self, l: usize) -> Self {
+        self.l = l.max(1);
+        self
+    }
+
+    pub fn with_k(mut self, k: usize) -> Self {
+        self.k = k.clamp(1, 64);
+        self
+    }
+}
+
+/// A tiny xorshift64 PRNG, used only to draw the random hyperplanes below.
+/// Avoids pulling in an external RNG/distribution crate for what is
+/// otherwise not security- or statistics-sensitive randomness. Mirrors the
+/// one in `super::hnsw`, kept separate since neither module exposes its RNG.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed | 1)
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        let unit = (x >> 11) as f64 / (1u64 << 53) as f64;
+        unit.clamp(f64::EPSILON, 1.0 - f64::EPSILON) as f32
+    }
+
+    /// A standard-normal sample via the Box-Muller transform.
+    fn next_gaussian(&mut self) -> f32 {
+        let u1 = self.next_f32();
+        let u2 = self.next_f32();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+    }
+}
+
+/// One SimHash table: `k` random hyperplanes and the bucket each signature
+/// packs its members into.
+struct HashTable {
+    hyperplanes: Vec<Vec<f32>>,
+    buckets: HashMap<u64, Vec<String>>,
+}
+
+impl HashTable {
+    fn new(rng: &mut Rng, dimension: usize, k: usize) -> Self {
+        let hyperplanes = (0..k)
+            .map(|_| (0..dimension).map(|_| rng.next_gaussian()).collect())
+            .collect();
+        Self {
+            hyperplanes,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// The `k`-bit signature of `vector`: bit `i` is set if `vector`'s dot
+    /// product with hyperplane `i` is non-negative.
+    fn signature(&self, vector: &[f32]) -> u64 {
+        let mut sig = 0u64;
+        for (i, plane) in self.hyperplanes.iter().enumerate() {
+            let dot: f32 = plane.iter().zip(vector.iter()).map(|(a, b)| a * b).sum();
+            if dot >= 0.0 {
+                sig |= 1u64 << i;
+            }
+        }
+        sig
+    }
+
+    fn insert(&mut self, id: &str, vector: &[f32]) -> u64 {
+        let sig = self.signature(vector);
+        self.buckets.entry(sig).or_default().push(id.to_string());
+        sig
+    }
+
+    fn remove(&mut self, id: &str, sig: u64) {
+        if let Some(bucket) = self.buckets.get_mut(&sig) {
+            bucket.retain(|existing| existing != id);
+            if bucket.is_empty() {
+                self.buckets.remove(&sig);
+            }
+        }
+    }
+}
+
+struct Collection {
+    dimension: usize,
+    tables: Vec<HashTable>,
+    /// Each record's signature per table, so [`HashTable::remove`] can find
+    /// its bucket without recomputing the hash from a (possibly already
+    /// removed) vector.
+    signatures: HashMap<String, Vec<u64>>,
+    records: HashMap<String, VectorRecord>,
+}
+
+/// An approximate-search [`VectorStore`] that buckets vectors by SimHash
+/// signature across `L` independent hash tables (see [`LshConfig`]), then
+/// exactly reranks the union of candidate buckets with
+/// [`crate::traits::cosine_similarity`] at query time.
+pub struct LshStore {
+    config: LshConfig,
+    collections: RwLock<HashMap<String, Collection>>,
+}
+
+impl LshStore {
+    pub fn new() -> Self {
+        Self::with_config(LshConfig::default())
+    }
+
+    /// Like [`Self::new`], but every collection's hash tables are built
+    /// with `config` instead of [`LshConfig::default`].
+    pub fn with_config(config: LshConfig) -> Self {
+        Self {
+            config,
+            collections: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for LshStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VectorStore for LshStore {
+    async fn create_collection(&self, name: &str, dimension: usize) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Collection '{}'", name)));
+        }
+
+        // Seeded from the collection name so two stores given the same
+        // schema end up with the same hyperplanes, which is convenient for
+        // tests and makes search behavior reproducible across restarts of
+        // an otherwise-identical setup.
+        let mut rng = Rng::new(name.bytes().fold(0x9E37_79B9_7F4A_7C15u64, |acc, b| {
+            acc.wrapping_mul(1099511628211).wrapping_add(b as u64)
+        }));
+        let tables = (0..self.config.l)
+            .map(|_| HashTable::new(&mut rng, dimension, self.config.k))
+            .collect();
+
+        collections.insert(
+            name.to_string(),
+            Collection {
+                dimension,
+                tables,
+                signatures: HashMap::new(),
+                records: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.remove(name).is_none() {
+            return Err(StoreError::not_found(format!("Collection '{}'", name)));
+        }
+        Ok(())
+    }
+
+    async fn list_collections(&self) -> Result<Vec<CollectionInfo>> {
+        let collections = self.collections.read();
+        Ok(collections
+            .iter()
+            .map(|(name, col)| CollectionInfo {
+                name: name.clone(),
+                dimension: col.dimension,
+                count: col.records.len(),
+                metric: DistanceMetric::Cosine,
+            })
+            .collect())
+    }
+
+    async fn collection_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.collections.read().contains_key(name))
+    }
+
+    async fn upsert(&self, collection: &str, records: &[VectorRecord]) -> Result<usize> {
+        let mut collections = self.collections.write();
+        let col = collections
+            .get_mut(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let mut count = 0;
+        for record in records {
+            if record.vector.len() != col.dimension {
+                return Err(StoreError::invalid_input(format!(
+                    "Vector dimension mismatch: expected {}, got {}",
+                    col.dimension,
+                    record.vector.len()
+                )));
+            }
+
+            if let Some(old_sigs) = col.signatures.remove(&record.id) {
+                for (table, sig) in col.tables.iter_mut().zip(old_sigs) {
+                    table.remove(&record.id, sig);
+                }
+            }
+
+            let sigs: Vec<u64> = col
+                .tables
+                .iter_mut()
+                .map(|table| table.insert(&record.id, &record.vector))
+                .collect();
+            col.signatures.insert(record.id.clone(), sigs);
+            col.records.insert(record.id.clone(), record.clone());
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    async fn search(&self, collection: &str, query: &[f32], limit: usize) -> Result<Vec<SearchResult>> {
+        self.search_with_filter(collection, query, &VectorFilter::default(), limit)
+            .await
+    }
+
+    async fn search_with_filter(
+        &self,
+        collection: &str,
+        query: &[f32],
+        filter: &VectorFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        if query.len() != col.dimension {
+            return Err(StoreError::invalid_input(format!(
+                "Query dimension mismatch: expected {}, got {}",
+                col.dimension,
+                query.len()
+            )));
+        }
+
+        let mut candidates: HashSet<&String> = HashSet::new();
+        for table in &col.tables {
+            let sig = table.signature(query);
+            if let Some(bucket) = table.buckets.get(&sig) {
+                candidates.extend(bucket.iter());
+            }
+        }
+
+        let mut scored: Vec<SearchResult> = candidates
+            .into_iter()
+            .filter_map(|id| col.records.get(id))
+            .filter(|record| filter.evaluate(&record.metadata))
+            .map(|record| SearchResult {
+                id: record.id.clone(),
+                score: cosine_similarity(query, &record.vector),
+                metadata: record.metadata.clone(),
+                content: record.content.clone(),
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+
+        Ok(scored)
+    }
+
+    async fn get(&self, collection: &str, id: &str) -> Result<Option<VectorRecord>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        Ok(col.records.get(id).cloned())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[String]) -> Result<usize> {
+        let mut collections = self.collections.write();
+        let col = collections
+            .get_mut(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let mut count = 0;
+        for id in ids {
+            if col.records.remove(id).is_some() {
+                if let Some(sigs) = col.signatures.remove(id) {
+                    for (table, sig) in col.tables.iter_mut().zip(sigs) {
+                        table.remove(id, sig);
+                    }
+                }
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    async fn count(&self, collection: &str) -> Result<usize> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        Ok(col.records.len())
+    }
+
+    async fn export(&self, collection: &str) -> Result<BoxStream<VectorRecord>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let records: Vec<VectorRecord> = col.records.values().cloned().collect();
+        Ok(Box::pin(stream::iter(records.into_iter().map(Ok))))
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "lsh"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(angle_deg: f32) -> Vec<f32> {
+        let rad = angle_deg.to_radians();
+        vec![rad.cos(), rad.sin()]
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_search_finds_closest_vector() {
+        let store = LshStore::with_config(LshConfig::new().with_l(16).with_k(4));
+        store.create_collection("docs", 2).await.unwrap();
+
+        let records: Vec<VectorRecord> = (0..100)
+            .map(|i| VectorRecord::new(format!("v{i}"), unit(i as f32 * 3.6)))
+            .collect();
+        store.upsert("docs", &records).await.unwrap();
+
+        let results = store.search("docs", &unit(0.0), 1).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "v0");
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filter_only_returns_matching_metadata() {
+        let store = LshStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+
+        let records = vec![
+            VectorRecord::new("a", unit(0.0)).with_metadata("kind", "article"),
+            VectorRecord::new("b", unit(1.0)).with_metadata("kind", "note"),
+        ];
+        store.upsert("docs", &records).await.unwrap();
+
+        let filter = VectorFilter::new().eq("kind", "note");
+        let results = store
+            .search_with_filter("docs", &unit(0.0), &filter, 10)
+            .await
+            .unwrap();
+
+        assert!(results.iter().all(|r| r.id == "b"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_from_buckets_and_records() {
+        let store = LshStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        store
+            .upsert("docs", &[VectorRecord::new("a", unit(0.0))])
+            .await
+            .unwrap();
+
+        assert_eq!(store.delete("docs", &["a".to_string()]).await.unwrap(), 1);
+        assert_eq!(store.count("docs").await.unwrap(), 0);
+        assert!(store.get("docs", "a").await.unwrap().is_none());
+
+        let results = store.search("docs", &unit(0.0), 10).await.unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_every_record() {
+        use futures::StreamExt;
+
+        let store = LshStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        store
+            .upsert(
+                "docs",
+                &[VectorRecord::new("a", unit(0.0)), VectorRecord::new("b", unit(90.0))],
+            )
+            .await
+            .unwrap();
+
+        let mut stream = store.export("docs").await.unwrap();
+        let mut ids = Vec::new();
+        while let Some(record) = stream.next().await {
+            ids.push(record.unwrap().id);
+        }
+        ids.sort();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string()]);
+    }
+}
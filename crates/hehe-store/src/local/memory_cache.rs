@@ -6,11 +6,15 @@ use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub struct MemoryCache {
     cache: Cache<String, Vec<u8>>,
     counters: Arc<RwLock<HashMap<String, AtomicI64>>>,
+    /// Per-key expiry deadlines set by [`Self::set`]/[`Self::mset`]/[`CacheStore::expire`].
+    /// `cache`'s own `time_to_idle`/`time_to_live` are capacity-wide backstops; this is
+    /// what makes an individual key's TTL actually expire that key.
+    expiries: Arc<RwLock<HashMap<String, Instant>>>,
 }
 
 impl MemoryCache {
@@ -21,6 +25,7 @@ impl MemoryCache {
                 .time_to_idle(Duration::from_secs(3600))
                 .build(),
             counters: Arc::new(RwLock::new(HashMap::new())),
+            expiries: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -31,8 +36,38 @@ impl MemoryCache {
                 .time_to_live(default_ttl)
                 .build(),
             counters: Arc::new(RwLock::new(HashMap::new())),
+            expiries: Arc::new(RwLock::new(HashMap::new())),
         }
     }
+
+    /// Records (or clears, if `ttl` is `None`) the deadline for `key`. Matches Redis's
+    /// `SET` semantics: a plain write with no TTL drops any expiry the key had before.
+    fn apply_ttl(&self, key: &str, ttl: Option<Duration>) {
+        match ttl {
+            Some(ttl) => {
+                self.expiries.write().insert(key.to_string(), Instant::now() + ttl);
+            }
+            None => {
+                self.expiries.write().remove(key);
+            }
+        }
+    }
+
+    /// If `key`'s deadline has passed, evicts it from `cache`, `counters`, and
+    /// `expiries` and returns `true`. Called at the top of every read and of
+    /// `delete`/`incr`/`expire`, so an expired key is treated as absent without a
+    /// background sweep; `set`/`mset` overwrite the key without calling it.
+    async fn evict_if_expired(&self, key: &str) -> bool {
+        let expired = matches!(self.expiries.read().get(key), Some(deadline) if Instant::now() >= *deadline);
+
+        if expired {
+            self.cache.remove(key).await;
+            self.counters.write().remove(key);
+            self.expiries.write().remove(key);
+        }
+
+        expired
+    }
 }
 
 impl Default for MemoryCache {
@@ -44,40 +79,49 @@ impl Default for MemoryCache {
 #[async_trait]
 impl CacheStore for MemoryCache {
     async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.evict_if_expired(key).await;
         Ok(self.cache.get(key).await)
     }
 
-    async fn set(&self, key: &str, value: &[u8], _ttl: Option<Duration>) -> Result<()> {
+    async fn set(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()> {
         self.cache.insert(key.to_string(), value.to_vec()).await;
+        self.apply_ttl(key, ttl);
         Ok(())
     }
 
     async fn delete(&self, key: &str) -> Result<bool> {
+        self.evict_if_expired(key).await;
         let existed = self.cache.contains_key(key);
         self.cache.remove(key).await;
+        self.expiries.write().remove(key);
         Ok(existed)
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
+        self.evict_if_expired(key).await;
         Ok(self.cache.contains_key(key))
     }
 
     async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
         let mut results = Vec::with_capacity(keys.len());
         for key in keys {
+            self.evict_if_expired(key).await;
             results.push(self.cache.get(*key).await);
         }
         Ok(results)
     }
 
-    async fn mset(&self, entries: &[(&str, &[u8])], _ttl: Option<Duration>) -> Result<()> {
+    async fn mset(&self, entries: &[(&str, &[u8])], ttl: Option<Duration>) -> Result<()> {
         for (key, value) in entries {
             self.cache.insert((*key).to_string(), value.to_vec()).await;
+            self.apply_ttl(key, ttl);
         }
         Ok(())
     }
 
     async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
+        self.evict_if_expired(key).await;
+
         let counters = self.counters.read();
         if let Some(counter) = counters.get(key) {
             return Ok(counter.fetch_add(delta, Ordering::SeqCst) + delta);
@@ -91,23 +135,39 @@ impl CacheStore for MemoryCache {
         Ok(counter.fetch_add(delta, Ordering::SeqCst) + delta)
     }
 
-    async fn expire(&self, _key: &str, _ttl: Duration) -> Result<bool> {
-        Ok(true)
+    async fn expire(&self, key: &str, ttl: Duration) -> Result<bool> {
+        self.evict_if_expired(key).await;
+
+        let exists = self.cache.contains_key(key) || self.counters.read().contains_key(key);
+        if exists {
+            self.apply_ttl(key, Some(ttl));
+        }
+        Ok(exists)
     }
 
-    async fn ttl(&self, _key: &str) -> Result<Option<Duration>> {
-        Ok(None)
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>> {
+        if self.evict_if_expired(key).await {
+            return Ok(None);
+        }
+
+        let deadline = self.expiries.read().get(key).copied();
+        Ok(deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())))
     }
 
     async fn keys(&self, pattern: &str) -> Result<Vec<String>> {
         let pattern = pattern.replace('*', "");
-        let mut result = Vec::new();
 
         self.cache.run_pending_tasks().await;
 
-        for (key, _) in self.cache.iter() {
+        let candidates: Vec<String> = self.cache.iter().map(|(key, _)| key.to_string()).collect();
+        let mut result = Vec::new();
+
+        for key in candidates {
+            if self.evict_if_expired(&key).await {
+                continue;
+            }
             if pattern.is_empty() || key.contains(&pattern) {
-                result.push(key.to_string());
+                result.push(key);
             }
         }
 
@@ -118,6 +178,7 @@ impl CacheStore for MemoryCache {
         self.cache.invalidate_all();
         self.cache.run_pending_tasks().await;
         self.counters.write().clear();
+        self.expiries.write().clear();
         Ok(())
     }
 
@@ -225,4 +286,44 @@ mod tests {
         let value = cache.get_string("msg").await.unwrap();
         assert_eq!(value, Some("hello world".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_memory_cache_set_ttl_expires_key() {
+        let cache = MemoryCache::new(100);
+
+        cache.set("key", b"value", Some(Duration::from_millis(20))).await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), Some(b"value".to_vec()));
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(cache.get("key").await.unwrap(), None);
+        assert!(!cache.exists("key").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_expire_and_ttl() {
+        let cache = MemoryCache::new(100);
+
+        assert!(!cache.expire("missing", Duration::from_secs(60)).await.unwrap());
+
+        cache.set("key", b"value", None).await.unwrap();
+        assert_eq!(cache.ttl("key").await.unwrap(), None);
+
+        assert!(cache.expire("key", Duration::from_secs(60)).await.unwrap());
+        let remaining = cache.ttl("key").await.unwrap().unwrap();
+        assert!(remaining <= Duration::from_secs(60) && remaining > Duration::from_secs(50));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_incr_resets_after_expiry() {
+        let cache = MemoryCache::new(100);
+
+        cache.incr("counter", 1).await.unwrap();
+        assert!(cache.expire("counter", Duration::from_millis(20)).await.unwrap());
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        let value = cache.incr("counter", 1).await.unwrap();
+        assert_eq!(value, 1);
+    }
 }
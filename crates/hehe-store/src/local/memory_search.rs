@@ -0,0 +1,705 @@
+use crate::error::{Result, StoreError};
+use crate::traits::{
+    Document, FacetSearchResult, HighlightOptions, IndexFieldType, IndexSchema, SearchCondition,
+    SearchFilter, SearchHit, SearchStore,
+};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// BM25 term-frequency saturation constant (typical range 1.2-2.0): higher
+/// values let repeated terms keep contributing to the score for longer
+/// before saturating.
+const BM25_K1: f32 = 1.2;
+/// BM25 length-normalization constant: `0.0` ignores document length
+/// entirely, `1.0` fully normalizes against the field's average length.
+const BM25_B: f32 = 0.75;
+
+/// The distinguished field name `content` is scored under: every
+/// [`Document::content`] is tokenized into this field regardless of what the
+/// index's [`IndexSchema`] declares, at a fixed weight of `1.0` (schema
+/// fields are scaled relative to it), mirroring [`super::SqliteFtsStore`]'s
+/// `content` column.
+const CONTENT_FIELD: &str = "content";
+
+fn tokenize(text: &str) -> Vec<String> {
+    tokenize_with_spans(text).into_iter().map(|(token, _)| token).collect()
+}
+
+/// Tokenizes `text` into lowercase alphanumeric runs, alongside the byte
+/// range each token occupies in `text`, so [`build_highlight`] can slice and
+/// annotate the original string instead of reassembling it from scratch.
+fn tokenize_with_spans(text: &str) -> Vec<(String, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut last_end = 0;
+
+    for (byte_idx, c) in text.char_indices() {
+        last_end = byte_idx + c.len_utf8();
+        if c.is_alphanumeric() {
+            start.get_or_insert(byte_idx);
+        } else if let Some(s) = start.take() {
+            tokens.push((text[s..byte_idx].to_lowercase(), s..byte_idx));
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((text[s..last_end].to_lowercase(), s..last_end));
+    }
+
+    tokens
+}
+
+/// Renders a document field's JSON value as plain text for tokenizing,
+/// matching [`super::SqliteFtsStore`]'s column rendering so scores are
+/// comparable between the two backends.
+fn field_value_to_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn facet_value_to_key(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn matches_condition(doc: &Document, condition: &SearchCondition) -> bool {
+    match condition {
+        SearchCondition::Eq(key, value) => doc.fields.get(key) == Some(value),
+        SearchCondition::Range(key, min, max) => {
+            let Some(actual) = doc.fields.get(key) else {
+                return false;
+            };
+            if let Some(min) = min {
+                if !matches!(compare_values(actual, min), Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)) {
+                    return false;
+                }
+            }
+            if let Some(max) = max {
+                if !matches!(compare_values(actual, max), Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)) {
+                    return false;
+                }
+            }
+            true
+        }
+        SearchCondition::In(key, values) => doc.fields.get(key).map(|v| values.contains(v)).unwrap_or(false),
+    }
+}
+
+fn matches_filter(doc: &Document, filter: &SearchFilter) -> bool {
+    filter.conditions.iter().all(|condition| matches_condition(doc, condition))
+}
+
+/// Builds the highlighted snippet for `content` around the densest cluster of
+/// `query_tokens` matches, wrapping each match in `opts`'s tags. Returns
+/// `None` when no query token appears in `content` at all.
+fn build_highlight(content: &str, query_tokens: &[String], opts: &HighlightOptions) -> Option<String> {
+    let spans = tokenize_with_spans(content);
+    let query_set: HashSet<&str> = query_tokens.iter().map(String::as_str).collect();
+
+    let match_positions: Vec<usize> = spans
+        .iter()
+        .enumerate()
+        .filter(|(_, (token, _))| query_set.contains(token.as_str()))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if match_positions.is_empty() {
+        return None;
+    }
+
+    let window = (opts.snippet_tokens.max(1) as usize) / 2;
+
+    // Center the snippet on whichever match has the most neighbors within
+    // `window` tokens, i.e. the densest cluster of matches in the document.
+    let center = *match_positions
+        .iter()
+        .max_by_key(|&&candidate| match_positions.iter().filter(|&&m| m.abs_diff(candidate) <= window).count())
+        .expect("match_positions is non-empty");
+
+    let (start_idx, end_idx) = if opts.crop_to_snippet {
+        (center.saturating_sub(window), (center + window).min(spans.len() - 1))
+    } else {
+        (0, spans.len() - 1)
+    };
+
+    let mut out = String::new();
+    if opts.crop_to_snippet && start_idx > 0 {
+        out.push('…');
+    }
+
+    let mut cursor = spans[start_idx].1.start;
+    for (token, range) in &spans[start_idx..=end_idx] {
+        out.push_str(&content[cursor..range.start]);
+        if query_set.contains(token.as_str()) {
+            out.push_str(&opts.open_tag);
+            out.push_str(&content[range.clone()]);
+            out.push_str(&opts.close_tag);
+        } else {
+            out.push_str(&content[range.clone()]);
+        }
+        cursor = range.end;
+    }
+
+    if opts.crop_to_snippet && end_idx < spans.len() - 1 {
+        out.push('…');
+    }
+
+    Some(out)
+}
+
+/// One indexed field's inverted index: which documents contain which tokens,
+/// how many times, and how long each document's tokenization of this field
+/// is, which is everything BM25 needs besides the collection size.
+#[derive(Default)]
+struct FieldIndex {
+    /// token -> doc id -> term frequency within this field for that document.
+    postings: HashMap<String, HashMap<String, u32>>,
+    doc_lengths: HashMap<String, usize>,
+    total_tokens: usize,
+}
+
+impl FieldIndex {
+    fn avg_len(&self) -> f32 {
+        if self.doc_lengths.is_empty() {
+            0.0
+        } else {
+            self.total_tokens as f32 / self.doc_lengths.len() as f32
+        }
+    }
+
+    fn add_doc(&mut self, id: &str, tokens: &[String]) {
+        if tokens.is_empty() {
+            return;
+        }
+        let mut term_freqs: HashMap<&str, u32> = HashMap::new();
+        for token in tokens {
+            *term_freqs.entry(token.as_str()).or_insert(0) += 1;
+        }
+        for (token, freq) in term_freqs {
+            self.postings.entry(token.to_string()).or_default().insert(id.to_string(), freq);
+        }
+        self.doc_lengths.insert(id.to_string(), tokens.len());
+        self.total_tokens += tokens.len();
+    }
+
+    fn remove_doc(&mut self, id: &str) {
+        if let Some(len) = self.doc_lengths.remove(id) {
+            self.total_tokens -= len;
+        }
+        self.postings.retain(|_, docs| {
+            docs.remove(id);
+            !docs.is_empty()
+        });
+    }
+
+    fn doc_freq(&self, token: &str) -> usize {
+        self.postings.get(token).map(HashMap::len).unwrap_or(0)
+    }
+
+    fn term_freq(&self, token: &str, id: &str) -> u32 {
+        self.postings.get(token).and_then(|docs| docs.get(id)).copied().unwrap_or(0)
+    }
+}
+
+struct IndexState {
+    schema: IndexSchema,
+    docs: HashMap<String, Document>,
+    /// [`CONTENT_FIELD`] plus every indexed `Text`/`Keyword` field in `schema`.
+    fields: HashMap<String, FieldIndex>,
+}
+
+impl IndexState {
+    fn new(schema: IndexSchema) -> Self {
+        let mut fields = HashMap::new();
+        fields.insert(CONTENT_FIELD.to_string(), FieldIndex::default());
+        for field in &schema.fields {
+            if field.indexed && matches!(field.field_type, IndexFieldType::Text | IndexFieldType::Keyword) {
+                fields.insert(field.name.clone(), FieldIndex::default());
+            }
+        }
+        Self { schema, docs: HashMap::new(), fields }
+    }
+
+    fn field_weight(&self, name: &str) -> f32 {
+        if name == CONTENT_FIELD {
+            return 1.0;
+        }
+        self.schema.fields.iter().find(|f| f.name == name).map(|f| f.weight).unwrap_or(1.0)
+    }
+
+    fn upsert(&mut self, doc: &Document) {
+        if self.docs.contains_key(&doc.id) {
+            self.remove(&doc.id);
+        }
+
+        if let Some(field) = self.fields.get_mut(CONTENT_FIELD) {
+            field.add_doc(&doc.id, &tokenize(&doc.content));
+        }
+        for schema_field in &self.schema.fields {
+            if let Some(field) = self.fields.get_mut(&schema_field.name) {
+                let text = field_value_to_text(doc.fields.get(&schema_field.name));
+                field.add_doc(&doc.id, &tokenize(&text));
+            }
+        }
+
+        self.docs.insert(doc.id.clone(), doc.clone());
+    }
+
+    fn remove(&mut self, id: &str) {
+        for field in self.fields.values_mut() {
+            field.remove_doc(id);
+        }
+        self.docs.remove(id);
+    }
+
+    /// Document ids whose tokenization of any indexed field contains at
+    /// least one of `query_tokens` — the candidate set BM25 scores, since
+    /// anything outside it scores zero anyway.
+    fn candidates(&self, query_tokens: &[String]) -> HashSet<String> {
+        let mut ids = HashSet::new();
+        for field in self.fields.values() {
+            for token in query_tokens {
+                if let Some(docs) = field.postings.get(token) {
+                    ids.extend(docs.keys().cloned());
+                }
+            }
+        }
+        ids
+    }
+
+    /// `score = Σ_field weight_field · Σ_q IDF_field(q) · (tf·(k1+1)) / (tf + k1·(1 − b + b·|d|/avgdl))`,
+    /// summed across every field the document was indexed under.
+    fn bm25_score(&self, id: &str, query_tokens: &[String]) -> f32 {
+        let n_docs = self.docs.len() as f32;
+        if n_docs == 0.0 {
+            return 0.0;
+        }
+
+        let mut score = 0.0;
+        for (name, field) in &self.fields {
+            let doc_len = *field.doc_lengths.get(id).unwrap_or(&0) as f32;
+            if doc_len == 0.0 {
+                continue;
+            }
+            let avgdl = field.avg_len().max(1.0);
+            let weight = self.field_weight(name);
+
+            for token in query_tokens {
+                let tf = field.term_freq(token, id) as f32;
+                if tf == 0.0 {
+                    continue;
+                }
+                let df = field.doc_freq(token) as f32;
+                let idf = ((n_docs - df + 0.5) / (df + 0.5) + 1.0).ln();
+                let denom = tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avgdl);
+                score += weight * idf * (tf * (BM25_K1 + 1.0)) / denom;
+            }
+        }
+        score
+    }
+}
+
+/// An in-memory [`SearchStore`] backend: a hand-rolled inverted index and
+/// BM25 ranking with no SQLite/Elastic/MeiliSearch dependency, so agents get
+/// retrieval out of the box. See [`super::SqliteFtsStore`] for a
+/// persistence-backed alternative with typo tolerance and bulk import.
+pub struct MemorySearchStore {
+    indexes: RwLock<HashMap<String, IndexState>>,
+}
+
+impl MemorySearchStore {
+    pub fn new() -> Self {
+        Self { indexes: RwLock::new(HashMap::new()) }
+    }
+}
+
+impl Default for MemorySearchStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SearchStore for MemorySearchStore {
+    async fn create_index(&self, name: &str, schema: &IndexSchema) -> Result<()> {
+        let mut indexes = self.indexes.write();
+        if indexes.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Index '{}'", name)));
+        }
+        indexes.insert(name.to_string(), IndexState::new(schema.clone()));
+        Ok(())
+    }
+
+    async fn delete_index(&self, name: &str) -> Result<()> {
+        let mut indexes = self.indexes.write();
+        if indexes.remove(name).is_none() {
+            return Err(StoreError::not_found(format!("Index '{}'", name)));
+        }
+        Ok(())
+    }
+
+    async fn index_exists(&self, name: &str) -> Result<bool> {
+        Ok(self.indexes.read().contains_key(name))
+    }
+
+    async fn list_indexes(&self) -> Result<Vec<String>> {
+        Ok(self.indexes.read().keys().cloned().collect())
+    }
+
+    async fn index_documents(&self, index: &str, docs: &[Document]) -> Result<usize> {
+        let mut indexes = self.indexes.write();
+        let state = indexes
+            .get_mut(index)
+            .ok_or_else(|| StoreError::not_found(format!("Index '{}'", index)))?;
+
+        for doc in docs {
+            state.upsert(doc);
+        }
+        Ok(docs.len())
+    }
+
+    async fn delete_documents(&self, index: &str, ids: &[String]) -> Result<usize> {
+        let mut indexes = self.indexes.write();
+        let state = indexes
+            .get_mut(index)
+            .ok_or_else(|| StoreError::not_found(format!("Index '{}'", index)))?;
+
+        let mut count = 0;
+        for id in ids {
+            if state.docs.contains_key(id) {
+                state.remove(id);
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    async fn search(&self, index: &str, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        self.search_with_filter(index, query, &SearchFilter::default(), None, limit).await
+    }
+
+    async fn search_with_filter(
+        &self,
+        index: &str,
+        query: &str,
+        filter: &SearchFilter,
+        highlight: Option<&HighlightOptions>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let indexes = self.indexes.read();
+        let state = indexes
+            .get(index)
+            .ok_or_else(|| StoreError::not_found(format!("Index '{}'", index)))?;
+
+        let query_tokens = tokenize(query);
+
+        let mut scored: Vec<(String, f32)> = state
+            .candidates(&query_tokens)
+            .into_iter()
+            .filter(|id| {
+                filter.is_empty() || state.docs.get(id).map(|doc| matches_filter(doc, filter)).unwrap_or(false)
+            })
+            .map(|id| {
+                let score = state.bm25_score(&id, &query_tokens);
+                (id, score)
+            })
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .filter_map(|(id, score)| {
+                let doc = state.docs.get(&id)?;
+                let highlights = highlight
+                    .and_then(|opts| build_highlight(&doc.content, &query_tokens, opts))
+                    .map(|snippet| vec![snippet])
+                    .unwrap_or_default();
+                Some(SearchHit {
+                    id,
+                    score,
+                    content: doc.content.clone(),
+                    highlights,
+                    fields: doc.fields.clone(),
+                })
+            })
+            .collect())
+    }
+
+    async fn facet_search(
+        &self,
+        index: &str,
+        query: &str,
+        filter: &SearchFilter,
+        facets: &[String],
+        limit: usize,
+    ) -> Result<FacetSearchResult> {
+        let hits = self.search_with_filter(index, query, filter, None, limit).await?;
+
+        let indexes = self.indexes.read();
+        let state = indexes
+            .get(index)
+            .ok_or_else(|| StoreError::not_found(format!("Index '{}'", index)))?;
+
+        let query_tokens = tokenize(query);
+        let candidates = state.candidates(&query_tokens);
+
+        let mut facet_distribution: HashMap<String, HashMap<String, usize>> = HashMap::new();
+        for facet in facets {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for id in &candidates {
+                let Some(doc) = state.docs.get(id) else { continue };
+                if !filter.is_empty() && !matches_filter(doc, filter) {
+                    continue;
+                }
+                if let Some(value) = doc.fields.get(facet).and_then(facet_value_to_key) {
+                    *counts.entry(value).or_insert(0) += 1;
+                }
+            }
+            facet_distribution.insert(facet.clone(), counts);
+        }
+
+        Ok(FacetSearchResult { hits, facet_distribution })
+    }
+
+    async fn count(&self, index: &str) -> Result<usize> {
+        let indexes = self.indexes.read();
+        let state = indexes
+            .get(index)
+            .ok_or_else(|| StoreError::not_found(format!("Index '{}'", index)))?;
+        Ok(state.docs.len())
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_index_lifecycle() {
+        let store = MemorySearchStore::new();
+        assert!(!store.index_exists("docs").await.unwrap());
+
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        assert!(store.index_exists("docs").await.unwrap());
+
+        let err = store.create_index("docs", &IndexSchema::new()).await;
+        assert!(err.is_err());
+
+        store.delete_index("docs").await.unwrap();
+        assert!(!store.index_exists("docs").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_index_and_search_ranks_by_relevance() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("a", "rust programming language"),
+                    Document::new("b", "rust rust rust systems programming"),
+                    Document::new("c", "cooking with cast iron"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(store.count("docs").await.unwrap(), 3);
+
+        let hits = store.search("docs", "rust", 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].id, "b", "doc with more occurrences of the query term should rank first");
+    }
+
+    #[tokio::test]
+    async fn test_search_respects_field_weight() {
+        let store = MemorySearchStore::new();
+        let schema = IndexSchema::new().add_text("title").weight(5.0);
+        store.create_index("docs", &schema).await.unwrap();
+
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("title-match", "unrelated body text").with_field("title", "widgets"),
+                    Document::new("body-match", "widgets mentioned here").with_field("title", "unrelated"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let hits = store.search("docs", "widgets", 10).await.unwrap();
+        assert_eq!(hits[0].id, "title-match", "a heavily-weighted field match should outrank a body match");
+    }
+
+    #[tokio::test]
+    async fn test_delete_documents_removes_from_index() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("docs", &[Document::new("a", "hello world")])
+            .await
+            .unwrap();
+
+        let deleted = store.delete_documents("docs", &["a".to_string()]).await.unwrap();
+        assert_eq!(deleted, 1);
+        assert_eq!(store.count("docs").await.unwrap(), 0);
+        assert!(store.search("docs", "hello", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_re_indexing_a_document_replaces_its_old_tokens() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("docs", &[Document::new("a", "original content")])
+            .await
+            .unwrap();
+        store
+            .index_documents("docs", &[Document::new("a", "updated text")])
+            .await
+            .unwrap();
+
+        assert!(store.search("docs", "original", 10).await.unwrap().is_empty());
+        assert_eq!(store.search("docs", "updated", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filter_narrows_by_field() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("a", "rust guide").with_field("category", "lang"),
+                    Document::new("b", "rust cookbook").with_field("category", "food"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let filter = SearchFilter::new().eq("category", "lang");
+        let hits = store.search_with_filter("docs", "rust", &filter, None, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "a");
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filter_range() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("a", "rust release notes").with_field("year", 2020),
+                    Document::new("b", "rust release notes").with_field("year", 2024),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let filter = SearchFilter::new().range("year", Some(serde_json::json!(2022)), None);
+        let hits = store.search_with_filter("docs", "rust", &filter, None, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "b");
+    }
+
+    #[tokio::test]
+    async fn test_facet_search_counts_distinct_values_among_matches() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents(
+                "docs",
+                &[
+                    Document::new("a", "rust guide").with_field("category", "lang"),
+                    Document::new("b", "rust cookbook").with_field("category", "food"),
+                    Document::new("c", "rust tools").with_field("category", "lang"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let result = store
+            .facet_search("docs", "rust", &SearchFilter::default(), &["category".to_string()], 10)
+            .await
+            .unwrap();
+
+        assert_eq!(result.hits.len(), 3);
+        let categories = &result.facet_distribution["category"];
+        assert_eq!(categories["lang"], 2);
+        assert_eq!(categories["food"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_populates_highlight_when_requested() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("docs", &[Document::new("a", "the quick brown fox jumps")])
+            .await
+            .unwrap();
+
+        let hits = store
+            .search_with_filter(
+                "docs",
+                "fox",
+                &SearchFilter::default(),
+                Some(&HighlightOptions::new()),
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].highlights.len(), 1);
+        assert!(hits[0].highlights[0].contains("<mark>fox</mark>"));
+    }
+
+    #[tokio::test]
+    async fn test_search_without_highlight_options_leaves_highlights_empty() {
+        let store = MemorySearchStore::new();
+        store.create_index("docs", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("docs", &[Document::new("a", "the quick brown fox jumps")])
+            .await
+            .unwrap();
+
+        let hits = store.search("docs", "fox", 10).await.unwrap();
+        assert!(hits[0].highlights.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_missing_index_errors() {
+        let store = MemorySearchStore::new();
+        assert!(store.search("missing", "x", 10).await.is_err());
+    }
+}
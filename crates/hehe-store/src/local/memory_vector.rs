@@ -1,15 +1,93 @@
+use super::hnsw::{HnswConfig, HnswIndex};
 use crate::error::{Result, StoreError};
 use crate::traits::{
-    cosine_similarity, CollectionInfo, SearchResult, VectorFilter, VectorRecord, VectorStore,
+    encode_order_key, BoxStream, CollectionInfo, DistanceMetric, Embedder, FilterCondition,
+    SearchResult, VectorFilter, VectorRecord, VectorStore,
 };
 use async_trait::async_trait;
+use futures::stream;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// On-disk format version for [`MemoryVectorStore::save_snapshot`], bumped
+/// whenever the snapshot layout changes so [`MemoryVectorStore::load_snapshot`]
+/// can reject snapshots it doesn't know how to read.
+const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct VectorSnapshot {
+    version: u32,
+    collections: Vec<CollectionSnapshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CollectionSnapshot {
+    name: String,
+    dimension: usize,
+    metric: DistanceMetric,
+    records: Vec<VectorRecord>,
+}
+
+/// Below this many records in a collection, the linear exact
+/// `cosine_similarity` scan is fast enough that the HNSW graph traversal
+/// isn't worth it, so [`MemoryVectorStore`] falls back to it even when an
+/// index is configured.
+const ANN_MIN_COLLECTION_SIZE: usize = 1000;
 
 struct Collection {
     dimension: usize,
     records: HashMap<String, VectorRecord>,
+    index: Option<HnswIndex>,
+    metric: DistanceMetric,
+    embedder: Option<Arc<dyn Embedder>>,
+    /// Sorted [`encode_order_key`] indexes built by [`MemoryVectorStore::create_field_index`],
+    /// keyed by metadata field name, kept in sync by `upsert`/`delete`.
+    field_indexes: HashMap<String, BTreeMap<Vec<u8>, Vec<String>>>,
+}
+
+/// Removes `id` from the bucket at `key` in `index`, dropping the bucket
+/// entirely once it's empty so an index doesn't accumulate dead keys.
+fn remove_from_field_index(index: &mut BTreeMap<Vec<u8>, Vec<String>>, key: &[u8], id: &str) {
+    if let Some(bucket) = index.get_mut(key) {
+        bucket.retain(|existing| existing != id);
+        if bucket.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
+/// Seeks `col`'s field index for `condition`'s field, if one exists, and
+/// returns the record ids in the matching range. Returns `None` (meaning
+/// "no index available, fall back to a full scan") for `Eq`/`Ne`/`Contains`
+/// conditions, which this index can't narrow any further than equality.
+fn field_index_candidates(col: &Collection, condition: &FilterCondition) -> Option<Vec<String>> {
+    use std::ops::Bound;
+
+    if let FilterCondition::In(field, values) = condition {
+        let index = col.field_indexes.get(field)?;
+        return Some(
+            values
+                .iter()
+                .filter_map(|value| index.get(&encode_order_key(value)))
+                .flatten()
+                .cloned()
+                .collect(),
+        );
+    }
+
+    let (field, range): (&str, (Bound<Vec<u8>>, Bound<Vec<u8>>)) = match condition {
+        FilterCondition::Gt(field, value) => (field, (Bound::Excluded(encode_order_key(value)), Bound::Unbounded)),
+        FilterCondition::Gte(field, value) => (field, (Bound::Included(encode_order_key(value)), Bound::Unbounded)),
+        FilterCondition::Lt(field, value) => (field, (Bound::Unbounded, Bound::Excluded(encode_order_key(value)))),
+        FilterCondition::Lte(field, value) => (field, (Bound::Unbounded, Bound::Included(encode_order_key(value)))),
+        _ => return None,
+    };
+
+    let index = col.field_indexes.get(field)?;
+    Some(index.range(range).flat_map(|(_, ids)| ids.iter().cloned()).collect())
 }
 
 pub struct MemoryVectorStore {
@@ -30,60 +108,360 @@ impl Default for MemoryVectorStore {
     }
 }
 
-fn matches_filter(record: &VectorRecord, filter: &VectorFilter) -> bool {
-    use crate::traits::vector::FilterCondition;
-
-    for condition in &filter.conditions {
-        let matched = match condition {
-            FilterCondition::Eq(field, value) => {
-                record.metadata.get(field).map(|v| v == value).unwrap_or(false)
-            }
-            FilterCondition::Ne(field, value) => {
-                record.metadata.get(field).map(|v| v != value).unwrap_or(true)
-            }
-            FilterCondition::Gt(field, value) => match (record.metadata.get(field), value) {
-                (Some(Value::Number(a)), Value::Number(b)) => {
-                    a.as_f64().unwrap_or(0.0) > b.as_f64().unwrap_or(0.0)
-                }
-                _ => false,
-            },
-            FilterCondition::Gte(field, value) => match (record.metadata.get(field), value) {
-                (Some(Value::Number(a)), Value::Number(b)) => {
-                    a.as_f64().unwrap_or(0.0) >= b.as_f64().unwrap_or(0.0)
-                }
-                _ => false,
+impl MemoryVectorStore {
+    /// Like [`VectorStore::create_collection`], but additionally builds an
+    /// incremental HNSW approximate nearest-neighbor index for the
+    /// collection, so that later `search`/`search_with_filter` calls scale
+    /// past the point where a linear `cosine_similarity` scan is fast
+    /// enough. Collections below [`ANN_MIN_COLLECTION_SIZE`] still use the
+    /// exact scan even when an index is configured.
+    pub async fn create_collection_with_index(
+        &self,
+        name: &str,
+        dimension: usize,
+        config: HnswConfig,
+    ) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Collection '{}'", name)));
+        }
+        collections.insert(
+            name.to_string(),
+            Collection {
+                dimension,
+                records: HashMap::new(),
+                index: Some(HnswIndex::new(config)),
+                metric: DistanceMetric::default(),
+                embedder: None,
+                field_indexes: HashMap::new(),
             },
-            FilterCondition::Lt(field, value) => match (record.metadata.get(field), value) {
-                (Some(Value::Number(a)), Value::Number(b)) => {
-                    a.as_f64().unwrap_or(0.0) < b.as_f64().unwrap_or(0.0)
-                }
-                _ => false,
+        );
+        Ok(())
+    }
+
+    /// Like [`VectorStore::create_collection`], but scores with `metric`
+    /// instead of the default [`DistanceMetric::Cosine`]. Note this is an
+    /// exact-search-only collection: HNSW-indexed collections (see
+    /// [`Self::create_collection_with_index`]) always score their graph
+    /// traversal by cosine similarity.
+    pub async fn create_collection_with_metric(
+        &self,
+        name: &str,
+        dimension: usize,
+        metric: DistanceMetric,
+    ) -> Result<()> {
+        let mut collections = self.collections.write();
+        if collections.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Collection '{}'", name)));
+        }
+        collections.insert(
+            name.to_string(),
+            Collection {
+                dimension,
+                records: HashMap::new(),
+                index: None,
+                metric,
+                embedder: None,
+                field_indexes: HashMap::new(),
             },
-            FilterCondition::Lte(field, value) => match (record.metadata.get(field), value) {
-                (Some(Value::Number(a)), Value::Number(b)) => {
-                    a.as_f64().unwrap_or(0.0) <= b.as_f64().unwrap_or(0.0)
-                }
-                _ => false,
+        );
+        Ok(())
+    }
+
+    /// Like [`VectorStore::create_collection`], but configures `embedder`
+    /// so documents and queries can be submitted as raw text via
+    /// [`Self::upsert_text`]/[`Self::search_text`] instead of pre-computed
+    /// vectors. `dimension` must match `embedder.dimension()`, since every
+    /// vector the embedder ever returns for this collection has to fit it.
+    pub async fn create_collection_with_embedder(
+        &self,
+        name: &str,
+        dimension: usize,
+        embedder: Arc<dyn Embedder>,
+    ) -> Result<()> {
+        if dimension != embedder.dimension() {
+            return Err(StoreError::invalid_input(format!(
+                "Collection dimension {} does not match embedder dimension {}",
+                dimension,
+                embedder.dimension()
+            )));
+        }
+
+        let mut collections = self.collections.write();
+        if collections.contains_key(name) {
+            return Err(StoreError::AlreadyExists(format!("Collection '{}'", name)));
+        }
+        collections.insert(
+            name.to_string(),
+            Collection {
+                dimension,
+                records: HashMap::new(),
+                index: None,
+                metric: DistanceMetric::default(),
+                embedder: Some(embedder),
+                field_indexes: HashMap::new(),
             },
-            FilterCondition::In(field, values) => record
-                .metadata
-                .get(field)
-                .map(|v| values.contains(v))
-                .unwrap_or(false),
-            FilterCondition::Contains(field, substr) => record
-                .metadata
-                .get(field)
-                .and_then(|v| v.as_str())
-                .map(|s| s.contains(substr))
-                .unwrap_or(false),
+        );
+        Ok(())
+    }
+
+    /// Embeds `items` (`id`, `content`, `metadata`) through `collection`'s
+    /// configured embedder and upserts the resulting vectors, batching all
+    /// texts through a single [`Embedder::embed`] call. Errors if
+    /// `collection` has no configured embedder (see
+    /// [`Self::create_collection_with_embedder`]) or if the embedder
+    /// returns vectors of the wrong dimension or count.
+    pub async fn upsert_text(
+        &self,
+        collection: &str,
+        items: &[(String, String, HashMap<String, Value>)],
+    ) -> Result<usize> {
+        let (embedder, dimension) = {
+            let collections = self.collections.read();
+            let col = collections
+                .get(collection)
+                .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+            let embedder = col.embedder.clone().ok_or_else(|| {
+                StoreError::invalid_input(format!(
+                    "Collection '{}' has no configured embedder; call upsert with pre-computed vectors instead",
+                    collection
+                ))
+            })?;
+            (embedder, col.dimension)
+        };
+
+        let texts: Vec<String> = items.iter().map(|(_, content, _)| content.clone()).collect();
+        let vectors = embedder.embed(&texts).await?;
+
+        if vectors.len() != items.len() {
+            return Err(StoreError::internal(format!(
+                "Embedder returned {} vectors for {} texts",
+                vectors.len(),
+                items.len()
+            )));
+        }
+
+        let mut records = Vec::with_capacity(items.len());
+        for ((id, content, metadata), vector) in items.iter().zip(vectors) {
+            if vector.len() != dimension {
+                return Err(StoreError::invalid_input(format!(
+                    "Embedder returned dimension {} but collection '{}' expects {}",
+                    vector.len(),
+                    collection,
+                    dimension
+                )));
+            }
+            let mut record = VectorRecord::new(id.clone(), vector).with_content(content.clone());
+            record.metadata = metadata.clone();
+            records.push(record);
+        }
+
+        self.upsert(collection, &records).await
+    }
+
+    /// Embeds `query_text` through `collection`'s configured embedder and
+    /// searches with the resulting vector. Errors if `collection` has no
+    /// configured embedder (see [`Self::create_collection_with_embedder`]).
+    pub async fn search_text(
+        &self,
+        collection: &str,
+        query_text: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let embedder = {
+            let collections = self.collections.read();
+            let col = collections
+                .get(collection)
+                .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+            col.embedder.clone().ok_or_else(|| {
+                StoreError::invalid_input(format!(
+                    "Collection '{}' has no configured embedder; call search with a pre-computed vector instead",
+                    collection
+                ))
+            })?
         };
 
-        if !matched {
-            return false;
+        let vector = embedder
+            .embed(&[query_text.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| StoreError::internal("Embedder returned no vector for query text"))?;
+
+        self.search(collection, &vector, limit).await
+    }
+
+    /// Forces the exact linear scan under the collection's configured
+    /// [`DistanceMetric`], bypassing any HNSW index configured for
+    /// `collection`. Useful for verifying the approximate search path
+    /// against ground truth.
+    pub async fn search_exact(
+        &self,
+        collection: &str,
+        query: &[f32],
+        filter: &VectorFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        self.linear_search(collection, query, filter, limit)
+    }
+
+    /// Builds (or rebuilds from scratch) a sorted [`encode_order_key`] index
+    /// over `field`, so a later `search`/`search_with_filter` call whose
+    /// filter is a single `Gt`/`Gte`/`Lt`/`Lte`/`In` condition on `field`
+    /// (see [`VectorFilter::as_single_condition`]) can seek the matching
+    /// range in [`Self::linear_search`] instead of scanning every record.
+    /// The index is then kept in sync incrementally by `upsert`/`delete`.
+    pub async fn create_field_index(&self, collection: &str, field: &str) -> Result<()> {
+        let mut collections = self.collections.write();
+        let col = collections
+            .get_mut(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let mut index: BTreeMap<Vec<u8>, Vec<String>> = BTreeMap::new();
+        for record in col.records.values() {
+            if let Some(value) = record.metadata.get(field) {
+                index.entry(encode_order_key(value)).or_default().push(record.id.clone());
+            }
         }
+        col.field_indexes.insert(field.to_string(), index);
+        Ok(())
     }
 
-    true
+    fn linear_search(
+        &self,
+        collection: &str,
+        query: &[f32],
+        filter: &VectorFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        if query.len() != col.dimension {
+            return Err(StoreError::invalid_input(format!(
+                "Query dimension mismatch: expected {}, got {}",
+                col.dimension,
+                query.len()
+            )));
+        }
+
+        // A single range/`In` condition on a field with a `create_field_index`
+        // built for it can seek the matching keys instead of scanning every
+        // record; anything more composed (or on an unindexed field) falls
+        // back to a full scan below. `filter.evaluate` still re-checks each
+        // candidate, so an index holding extra ids can only cost extra work;
+        // a record missing from the index is not returned.
+        let candidate_ids = filter
+            .as_single_condition()
+            .and_then(|condition| field_index_candidates(col, condition));
+
+        let mut scored: Vec<(String, f32, HashMap<String, Value>, Option<String>)> = match candidate_ids {
+            Some(ids) => ids
+                .iter()
+                .filter_map(|id| col.records.get(id))
+                .filter(|r| filter.evaluate(&r.metadata))
+                .map(|r| {
+                    let score = col.metric.score(query, &r.vector);
+                    (r.id.clone(), score, r.metadata.clone(), r.content.clone())
+                })
+                .collect(),
+            None => col
+                .records
+                .values()
+                .filter(|r| filter.evaluate(&r.metadata))
+                .map(|r| {
+                    let score = col.metric.score(query, &r.vector);
+                    (r.id.clone(), score, r.metadata.clone(), r.content.clone())
+                })
+                .collect(),
+        };
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(id, score, metadata, content)| SearchResult {
+                id,
+                score,
+                metadata,
+                content,
+            })
+            .collect())
+    }
+
+    /// Serializes every collection (dimension, metric, and all
+    /// [`VectorRecord`]s — not the HNSW index or embedder, which are
+    /// rebuilt/reconfigured on load) to a versioned JSON file at `path`.
+    pub async fn save_snapshot(&self, path: &str) -> Result<()> {
+        let snapshot = {
+            let collections = self.collections.read();
+            VectorSnapshot {
+                version: SNAPSHOT_VERSION,
+                collections: collections
+                    .iter()
+                    .map(|(name, col)| CollectionSnapshot {
+                        name: name.clone(),
+                        dimension: col.dimension,
+                        metric: col.metric,
+                        records: col.records.values().cloned().collect(),
+                    })
+                    .collect(),
+            }
+        };
+
+        let json = serde_json::to_vec_pretty(&snapshot)
+            .map_err(|e| StoreError::Serialization(e.to_string()))?;
+        tokio::fs::write(path, json)
+            .await
+            .map_err(|e| StoreError::connection(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Loads a snapshot written by [`Self::save_snapshot`], replacing any
+    /// existing collection of the same name. Collections are restored
+    /// without an HNSW index or embedder; reconfigure those afterward with
+    /// [`Self::create_collection_with_index`]/[`Self::create_collection_with_embedder`]
+    /// if needed.
+    pub async fn load_snapshot(&self, path: &str) -> Result<()> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| StoreError::connection(e.to_string()))?;
+        let snapshot: VectorSnapshot =
+            serde_json::from_slice(&bytes).map_err(|e| StoreError::Serialization(e.to_string()))?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(StoreError::invalid_input(format!(
+                "Unsupported vector snapshot version {} (expected {})",
+                snapshot.version, SNAPSHOT_VERSION
+            )));
+        }
+
+        let mut collections = self.collections.write();
+        for col_snapshot in snapshot.collections {
+            let records = col_snapshot
+                .records
+                .into_iter()
+                .map(|record| (record.id.clone(), record))
+                .collect();
+            collections.insert(
+                col_snapshot.name,
+                Collection {
+                    dimension: col_snapshot.dimension,
+                    records,
+                    index: None,
+                    metric: col_snapshot.metric,
+                    embedder: None,
+                    field_indexes: HashMap::new(),
+                },
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -98,6 +476,10 @@ impl VectorStore for MemoryVectorStore {
             Collection {
                 dimension,
                 records: HashMap::new(),
+                index: None,
+                metric: DistanceMetric::default(),
+                embedder: None,
+                field_indexes: HashMap::new(),
             },
         );
         Ok(())
@@ -119,6 +501,7 @@ impl VectorStore for MemoryVectorStore {
                 name: name.clone(),
                 dimension: col.dimension,
                 count: col.records.len(),
+                metric: col.metric,
             })
             .collect())
     }
@@ -142,7 +525,21 @@ impl VectorStore for MemoryVectorStore {
                     record.vector.len()
                 )));
             }
-            col.records.insert(record.id.clone(), record.clone());
+            if let Some(index) = col.index.as_mut() {
+                index.insert(&record.id, record.vector.clone());
+            }
+            let previous = col.records.insert(record.id.clone(), record.clone());
+            for (field, field_index) in col.field_indexes.iter_mut() {
+                if let Some(old_value) = previous.as_ref().and_then(|r| r.metadata.get(field)) {
+                    remove_from_field_index(field_index, &encode_order_key(old_value), &record.id);
+                }
+                if let Some(new_value) = record.metadata.get(field) {
+                    field_index
+                        .entry(encode_order_key(new_value))
+                        .or_default()
+                        .push(record.id.clone());
+                }
+            }
             count += 1;
         }
 
@@ -166,6 +563,26 @@ impl VectorStore for MemoryVectorStore {
         filter: &VectorFilter,
         limit: usize,
     ) -> Result<Vec<SearchResult>> {
+        // HNSW has no notion of metadata filters and a tiny collection is
+        // faster to scan than to traverse, so both fall back to the exact
+        // linear search; see `linear_search`.
+        let use_ann = {
+            let collections = self.collections.read();
+            let col = collections
+                .get(collection)
+                .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+            filter.is_empty()
+                && col
+                    .index
+                    .as_ref()
+                    .map(|index| index.len() >= ANN_MIN_COLLECTION_SIZE)
+                    .unwrap_or(false)
+        };
+
+        if !use_ann {
+            return self.linear_search(collection, query, filter, limit);
+        }
+
         let collections = self.collections.read();
         let col = collections
             .get(collection)
@@ -179,26 +596,17 @@ impl VectorStore for MemoryVectorStore {
             )));
         }
 
-        let mut scored: Vec<(String, f32, HashMap<String, Value>, Option<String>)> = col
-            .records
-            .values()
-            .filter(|r| filter.is_empty() || matches_filter(r, filter))
-            .map(|r| {
-                let score = cosine_similarity(query, &r.vector);
-                (r.id.clone(), score, r.metadata.clone(), r.content.clone())
-            })
-            .collect();
-
-        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-        Ok(scored
+        let index = col.index.as_ref().expect("checked above");
+        Ok(index
+            .search(query, limit)
             .into_iter()
-            .take(limit)
-            .map(|(id, score, metadata, content)| SearchResult {
-                id,
-                score,
-                metadata,
-                content,
+            .filter_map(|(id, score)| {
+                col.records.get(&id).map(|record| SearchResult {
+                    id,
+                    score,
+                    metadata: record.metadata.clone(),
+                    content: record.content.clone(),
+                })
             })
             .collect())
     }
@@ -220,7 +628,15 @@ impl VectorStore for MemoryVectorStore {
 
         let mut count = 0;
         for id in ids {
-            if col.records.remove(id).is_some() {
+            if let Some(removed) = col.records.remove(id) {
+                if let Some(index) = col.index.as_mut() {
+                    index.remove(id);
+                }
+                for (field, field_index) in col.field_indexes.iter_mut() {
+                    if let Some(value) = removed.metadata.get(field) {
+                        remove_from_field_index(field_index, &encode_order_key(value), id);
+                    }
+                }
                 count += 1;
             }
         }
@@ -237,6 +653,16 @@ impl VectorStore for MemoryVectorStore {
         Ok(col.records.len())
     }
 
+    async fn export(&self, collection: &str) -> Result<BoxStream<VectorRecord>> {
+        let collections = self.collections.read();
+        let col = collections
+            .get(collection)
+            .ok_or_else(|| StoreError::not_found(format!("Collection '{}'", collection)))?;
+
+        let records: Vec<VectorRecord> = col.records.values().cloned().collect();
+        Ok(Box::pin(stream::iter(records.into_iter().map(Ok))))
+    }
+
     fn backend_name(&self) -> &'static str {
         "memory"
     }
@@ -396,4 +822,312 @@ mod tests {
             Some(&Value::Number(2.into()))
         );
     }
+
+    #[tokio::test]
+    async fn test_field_index_range_query_matches_linear_scan() {
+        let store = MemoryVectorStore::new();
+        store.create_collection("items", 2).await.unwrap();
+
+        let records = vec![
+            VectorRecord::new("cheap", vec![1.0, 0.0]).with_metadata("price", 5),
+            VectorRecord::new("mid", vec![0.9, 0.1]).with_metadata("price", 50),
+            VectorRecord::new("pricey", vec![0.8, 0.2]).with_metadata("price", 500),
+        ];
+        store.upsert("items", &records).await.unwrap();
+        store.create_field_index("items", "price").await.unwrap();
+
+        let filter = VectorFilter::new().gt("price", 10);
+        let mut ids: Vec<String> = store
+            .search_with_filter("items", &[1.0, 0.0], &filter, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        ids.sort();
+
+        assert_eq!(ids, vec!["mid".to_string(), "pricey".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_field_index_stays_in_sync_with_upsert_and_delete() {
+        let store = MemoryVectorStore::new();
+        store.create_collection("items", 2).await.unwrap();
+        store.create_field_index("items", "price").await.unwrap();
+
+        store
+            .upsert(
+                "items",
+                &[VectorRecord::new("a", vec![1.0, 0.0]).with_metadata("price", 5)],
+            )
+            .await
+            .unwrap();
+
+        // Updating "a" to a higher price should move it out of a `lt`
+        // range that used to match it.
+        store
+            .upsert(
+                "items",
+                &[VectorRecord::new("a", vec![1.0, 0.0]).with_metadata("price", 999)],
+            )
+            .await
+            .unwrap();
+
+        let still_cheap = store
+            .search_with_filter("items", &[1.0, 0.0], &VectorFilter::new().lt("price", 10), 10)
+            .await
+            .unwrap();
+        assert!(still_cheap.is_empty());
+
+        store.delete("items", &["a".to_string()]).await.unwrap();
+
+        let after_delete = store
+            .search_with_filter("items", &[1.0, 0.0], &VectorFilter::new().gt("price", 0), 10)
+            .await
+            .unwrap();
+        assert!(after_delete.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_index_returns_same_top_result_as_linear_scan() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_index("vectors", 2, HnswConfig::new())
+            .await
+            .unwrap();
+
+        let records: Vec<VectorRecord> = (0..64)
+            .map(|i| {
+                let angle = (i as f32) * std::f32::consts::TAU / 64.0;
+                VectorRecord::new(format!("v{i}"), vec![angle.cos(), angle.sin()])
+            })
+            .collect();
+        store.upsert("vectors", &records).await.unwrap();
+
+        let query = vec![1.0, 0.0];
+        let exact = store
+            .search_exact("vectors", &query, &VectorFilter::default(), 1)
+            .await
+            .unwrap();
+
+        // Below ANN_MIN_COLLECTION_SIZE the regular search path still uses
+        // the exact scan, so the collection's HnswIndex is queried directly.
+        let ann = {
+            let collections = store.collections.read();
+            collections["vectors"].index.as_ref().unwrap().search(&query, 1)
+        };
+        assert_eq!(exact[0].id, "v0");
+        assert_eq!(ann[0].0, exact[0].id);
+    }
+
+    #[tokio::test]
+    async fn test_hnsw_index_upsert_and_delete_stay_consistent_with_records() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_index("vectors", 2, HnswConfig::new())
+            .await
+            .unwrap();
+
+        store
+            .upsert("vectors", &[VectorRecord::new("a", vec![1.0, 0.0])])
+            .await
+            .unwrap();
+        store
+            .delete("vectors", &["a".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(store.count("vectors").await.unwrap(), 0);
+        assert!(store.get("vectors", "a").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_with_metric_scores_by_dot_product() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_metric("vectors", 2, DistanceMetric::DotProduct)
+            .await
+            .unwrap();
+
+        store
+            .upsert(
+                "vectors",
+                &[
+                    VectorRecord::new("small", vec![0.1, 0.0]),
+                    VectorRecord::new("large", vec![10.0, 0.0]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        // Under cosine similarity these two records would tie; dot product
+        // must rank the larger-magnitude vector first.
+        let results = store.search("vectors", &[1.0, 0.0], 2).await.unwrap();
+        assert_eq!(results[0].id, "large");
+    }
+
+    #[tokio::test]
+    async fn test_list_collections_reports_configured_metric() {
+        let store = MemoryVectorStore::new();
+        store.create_collection("default", 2).await.unwrap();
+        store
+            .create_collection_with_metric("euclidean", 2, DistanceMetric::EuclideanL2)
+            .await
+            .unwrap();
+
+        let list = store.list_collections().await.unwrap();
+        let default = list.iter().find(|c| c.name == "default").unwrap();
+        let euclidean = list.iter().find(|c| c.name == "euclidean").unwrap();
+
+        assert_eq!(default.metric, DistanceMetric::Cosine);
+        assert_eq!(euclidean.metric, DistanceMetric::EuclideanL2);
+    }
+
+    struct LengthEmbedder;
+
+    #[async_trait]
+    impl Embedder for LengthEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| vec![t.len() as f32, 1.0])
+                .collect())
+        }
+
+        fn dimension(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_text_and_search_text_round_trip_through_embedder() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_embedder("docs", 2, Arc::new(LengthEmbedder))
+            .await
+            .unwrap();
+
+        store
+            .upsert_text(
+                "docs",
+                &[
+                    ("short".to_string(), "hi".to_string(), HashMap::new()),
+                    ("long".to_string(), "a much longer piece of text".to_string(), HashMap::new()),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let results = store.search_text("docs", "hi", 1).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "short");
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_with_embedder_rejects_dimension_mismatch() {
+        let store = MemoryVectorStore::new();
+        let result = store
+            .create_collection_with_embedder("docs", 3, Arc::new(LengthEmbedder))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_text_without_embedder_errors() {
+        let store = MemoryVectorStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+
+        let result = store
+            .upsert_text("docs", &[("id1".to_string(), "hello".to_string(), HashMap::new())])
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_text_without_embedder_errors() {
+        let store = MemoryVectorStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+
+        let result = store.search_text("docs", "hello", 10).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_with_index_rejects_duplicate_name() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_index("vectors", 2, HnswConfig::new())
+            .await
+            .unwrap();
+
+        let err = store
+            .create_collection_with_index("vectors", 2, HnswConfig::new())
+            .await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_snapshot_round_trips_collections() {
+        let store = MemoryVectorStore::new();
+        store
+            .create_collection_with_metric("docs", 2, DistanceMetric::DotProduct)
+            .await
+            .unwrap();
+        store
+            .upsert(
+                "docs",
+                &[VectorRecord::new("id1", vec![1.0, 0.0]).with_metadata("tag", "a")],
+            )
+            .await
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("hehe-vector-snapshot-{}.json", hehe_core::Id::new()));
+        let path = path.to_str().unwrap().to_string();
+
+        store.save_snapshot(&path).await.unwrap();
+
+        let restored = MemoryVectorStore::new();
+        restored.load_snapshot(&path).await.unwrap();
+
+        assert_eq!(restored.count("docs").await.unwrap(), 1);
+        let record = restored.get("docs", "id1").await.unwrap().unwrap();
+        assert_eq!(record.vector, vec![1.0, 0.0]);
+
+        let list = restored.list_collections().await.unwrap();
+        assert_eq!(list[0].metric, DistanceMetric::DotProduct);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_import_from_copies_records_between_stores() {
+        let source = MemoryVectorStore::new();
+        source.create_collection("docs", 2).await.unwrap();
+        source
+            .upsert(
+                "docs",
+                &[
+                    VectorRecord::new("a", vec![1.0, 0.0]),
+                    VectorRecord::new("b", vec![0.0, 1.0]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let target = MemoryVectorStore::new();
+        target.create_collection("docs", 2).await.unwrap();
+
+        let copied = target.import_from(&source, "docs").await.unwrap();
+
+        assert_eq!(copied, 2);
+        assert_eq!(target.count("docs").await.unwrap(), 2);
+        assert!(target.get("docs", "a").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_export_nonexistent_collection_errors() {
+        let store = MemoryVectorStore::new();
+        let result = store.export("missing").await;
+        assert!(result.is_err());
+    }
 }
@@ -4,18 +4,53 @@ mod sqlite;
 #[cfg(feature = "memory-cache")]
 mod memory_cache;
 
+#[cfg(feature = "redis")]
+mod redis_cache;
+
+mod hnsw;
+mod hnsw_store;
+mod lsh_store;
+mod memory_search;
 mod memory_vector;
 
 #[cfg(feature = "sqlite")]
 mod fts;
 
+#[cfg(feature = "sqlite")]
+mod import;
+
+#[cfg(feature = "sqlite")]
+mod typo;
+
+#[cfg(feature = "sqlite")]
+mod kv;
+
 #[cfg(feature = "sqlite")]
 pub use sqlite::SqliteStore;
 
+#[cfg(feature = "sqlcipher")]
+pub use sqlite::CipherConfig;
+
 #[cfg(feature = "memory-cache")]
 pub use memory_cache::MemoryCache;
 
+#[cfg(feature = "redis")]
+pub use redis_cache::RedisCache;
+
+pub use memory_search::MemorySearchStore;
 pub use memory_vector::MemoryVectorStore;
+pub use hnsw::HnswConfig;
+pub use hnsw_store::HnswStore;
+pub use lsh_store::{LshConfig, LshStore};
 
 #[cfg(feature = "sqlite")]
 pub use fts::SqliteFtsStore;
+
+#[cfg(feature = "sqlite")]
+pub use import::{CsvImportConfig, ImportFormat, JsonlImportConfig};
+
+#[cfg(feature = "sqlite")]
+pub use typo::TypoToleranceConfig;
+
+#[cfg(feature = "sqlite")]
+pub use kv::{AtomicWrite, CommitOutcome, KvEntry, SqliteKvStore, VersionStamp};
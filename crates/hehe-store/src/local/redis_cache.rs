@@ -0,0 +1,201 @@
+use crate::error::{Result, StoreError};
+use crate::pool::{Pool, PoolConfig, PoolManager, PooledConnection};
+use crate::traits::CacheStore;
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+/// Keys fetched per `SCAN` round trip. `CacheStore::keys` pages through the
+/// whole keyspace with this rather than blocking the server with `KEYS`.
+const SCAN_BATCH_SIZE: usize = 200;
+
+struct RedisConnectionManager {
+    client: redis::Client,
+}
+
+#[async_trait]
+impl PoolManager for RedisConnectionManager {
+    type Connection = redis::aio::MultiplexedConnection;
+
+    async fn create(&self) -> Result<Self::Connection> {
+        self.client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| StoreError::connection(e.to_string()))
+    }
+
+    async fn ping(&self, conn: &Self::Connection) -> Result<()> {
+        let mut conn = conn.clone();
+        redis::cmd("PING")
+            .query_async::<_, String>(&mut conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| StoreError::connection(e.to_string()))
+    }
+}
+
+/// A [`CacheStore`] backed by Redis, for multi-process agent deployments
+/// that need a cache shared across instances rather than per-process
+/// in-memory state. Connections are checked out of a [`Pool`] so concurrent
+/// callers share a bounded set of sockets instead of opening one each.
+pub struct RedisCache {
+    pool: Pool<RedisConnectionManager>,
+    command_timeout: Duration,
+}
+
+impl RedisCache {
+    pub fn connect(url: impl AsRef<str>) -> Result<Self> {
+        Self::connect_with_config(url, PoolConfig::default())
+    }
+
+    /// Like [`Self::connect`], but lets the caller tune the connection pool
+    /// (`max_size`, `acquire_timeout`) for this cache's workload.
+    pub fn connect_with_config(url: impl AsRef<str>, config: PoolConfig) -> Result<Self> {
+        let client = redis::Client::open(url.as_ref()).map_err(|e| StoreError::connection(e.to_string()))?;
+        let manager = RedisConnectionManager { client };
+        Ok(Self {
+            pool: Pool::new(manager, config),
+            command_timeout: Duration::from_secs(5),
+        })
+    }
+
+    /// Overrides how long a single Redis command may take before this cache
+    /// gives up on it and returns [`StoreError::Timeout`]. Independent of
+    /// `PoolConfig::acquire_timeout`, which only bounds waiting for a
+    /// connection to become available.
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = timeout;
+        self
+    }
+
+    async fn conn(&self) -> Result<PooledConnection<RedisConnectionManager>> {
+        self.pool.get().await
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        fut: impl std::future::Future<Output = std::result::Result<T, redis::RedisError>>,
+    ) -> Result<T> {
+        tokio::time::timeout(self.command_timeout, fut)
+            .await
+            .map_err(|_| StoreError::Timeout)?
+            .map_err(|e| StoreError::connection(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut conn = self.conn().await?;
+        self.with_timeout(conn.get(key)).await
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()> {
+        let mut conn = self.conn().await?;
+        match ttl {
+            Some(ttl) => self.with_timeout(conn.set_ex(key, value, ttl.as_secs().max(1))).await,
+            None => self.with_timeout(conn.set(key, value)).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool> {
+        let mut conn = self.conn().await?;
+        let deleted: i64 = self.with_timeout(conn.del(key)).await?;
+        Ok(deleted > 0)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        let mut conn = self.conn().await?;
+        self.with_timeout(conn.exists(key)).await
+    }
+
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Vec<u8>>>> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut conn = self.conn().await?;
+        self.with_timeout(conn.mget(keys)).await
+    }
+
+    async fn mset(&self, entries: &[(&str, &[u8])], ttl: Option<Duration>) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut conn = self.conn().await?;
+        let mut pipeline = redis::pipe();
+        pipeline.atomic();
+        for (key, value) in entries {
+            match ttl {
+                Some(ttl) => {
+                    pipeline.set_ex(*key, *value, ttl.as_secs().max(1));
+                }
+                None => {
+                    pipeline.set(*key, *value);
+                }
+            }
+        }
+        self.with_timeout(pipeline.query_async(&mut *conn)).await
+    }
+
+    async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
+        let mut conn = self.conn().await?;
+        self.with_timeout(conn.incr(key, delta)).await
+    }
+
+    async fn expire(&self, key: &str, ttl: Duration) -> Result<bool> {
+        let mut conn = self.conn().await?;
+        self.with_timeout(conn.expire(key, ttl.as_secs().max(1) as i64)).await
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>> {
+        let mut conn = self.conn().await?;
+        let secs: i64 = self.with_timeout(conn.ttl(key)).await?;
+        // Redis returns -2 for a missing key and -1 for one with no expiry.
+        Ok(if secs < 0 { None } else { Some(Duration::from_secs(secs as u64)) })
+    }
+
+    async fn keys(&self, pattern: &str) -> Result<Vec<String>> {
+        let mut conn = self.conn().await?;
+        let mut cursor: u64 = 0;
+        let mut found = Vec::new();
+
+        loop {
+            let (next_cursor, batch): (u64, Vec<String>) = self
+                .with_timeout(
+                    redis::cmd("SCAN")
+                        .arg(cursor)
+                        .arg("MATCH")
+                        .arg(pattern)
+                        .arg("COUNT")
+                        .arg(SCAN_BATCH_SIZE)
+                        .query_async(&mut *conn),
+                )
+                .await?;
+
+            found.extend(batch);
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        Ok(found)
+    }
+
+    async fn clear(&self) -> Result<()> {
+        let mut conn = self.conn().await?;
+        self.with_timeout(redis::cmd("FLUSHDB").query_async(&mut *conn)).await
+    }
+
+    async fn len(&self) -> Result<usize> {
+        let mut conn = self.conn().await?;
+        let count: i64 = self
+            .with_timeout(redis::cmd("DBSIZE").query_async(&mut *conn))
+            .await?;
+        Ok(count.max(0) as usize)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "redis"
+    }
+}
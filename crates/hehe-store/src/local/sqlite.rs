@@ -1,41 +1,203 @@
 use crate::error::{Result, StoreError};
+use crate::pool::{Pool, PoolConfig, PoolManager, PooledConnection};
 use crate::traits::{Migration, RelationalStore, Row, Transaction};
 use async_trait::async_trait;
 use parking_lot::Mutex;
 use rusqlite::{params_from_iter, Connection, ToSql};
 use serde_json::Value;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
+/// Where a [`SqliteConnectionManager`] opens its connections: a file (safe
+/// to pool — WAL mode lets multiple connections share one database file)
+/// or `:memory:` (each `Connection::open_in_memory()` call is its own,
+/// unconnected database, so pooling more than one would silently lose
+/// data; [`SqliteStore::memory`] forces `max_size: 1` to avoid that).
+enum SqliteTarget {
+    File(PathBuf),
+    #[cfg(feature = "sqlcipher")]
+    EncryptedFile(PathBuf, String, CipherConfig),
+    Memory,
+}
+
+/// Tuning knobs for a SQLCipher-encrypted database, applied immediately
+/// after the key pragma. Left at `None` to keep SQLCipher's compiled-in
+/// defaults.
+#[cfg(feature = "sqlcipher")]
+#[derive(Clone, Debug, Default)]
+pub struct CipherConfig {
+    pub page_size: Option<u32>,
+    pub kdf_iter: Option<u32>,
+}
+
+struct SqliteConnectionManager {
+    target: SqliteTarget,
+}
+
+impl SqliteConnectionManager {
+    fn open_connection(target: &SqliteTarget) -> Result<Connection> {
+        match target {
+            SqliteTarget::File(path) => {
+                let conn = Connection::open(path)?;
+                conn.execute_batch(
+                    "PRAGMA journal_mode=WAL;
+                     PRAGMA synchronous=NORMAL;
+                     PRAGMA foreign_keys=ON;
+                     PRAGMA busy_timeout=5000;",
+                )?;
+                Ok(conn)
+            }
+            #[cfg(feature = "sqlcipher")]
+            SqliteTarget::EncryptedFile(path, key, cipher) => {
+                let conn = Connection::open(path)?;
+
+                // Must be the very first statement run on this connection:
+                // SQLCipher derives the page cipher from it, and once any
+                // other statement has touched the file it's too late — the
+                // file would just be read (and fail) as plaintext.
+                conn.pragma_update(None, "key", key)?;
+
+                if let Some(page_size) = cipher.page_size {
+                    conn.pragma_update(None, "cipher_page_size", page_size)?;
+                }
+                if let Some(kdf_iter) = cipher.kdf_iter {
+                    conn.pragma_update(None, "kdf_iter", kdf_iter)?;
+                }
+
+                conn.execute_batch(
+                    "PRAGMA journal_mode=WAL;
+                     PRAGMA synchronous=NORMAL;
+                     PRAGMA foreign_keys=ON;
+                     PRAGMA busy_timeout=5000;",
+                )?;
+
+                // `PRAGMA key` never fails by itself, even with the wrong
+                // key — only a statement that actually reads the database
+                // forces SQLCipher to verify it.
+                conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+                    .map_err(|_| StoreError::internal("invalid encryption key: failed to read database"))?;
+
+                Ok(conn)
+            }
+            SqliteTarget::Memory => {
+                let conn = Connection::open_in_memory()?;
+                conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+                Ok(conn)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PoolManager for SqliteConnectionManager {
+    type Connection = Connection;
+
+    async fn create(&self) -> Result<Connection> {
+        Self::open_connection(&self.target)
+    }
+
+    async fn ping(&self, conn: &Connection) -> Result<()> {
+        conn.execute_batch("SELECT 1")?;
+        Ok(())
+    }
+}
+
+/// Runs a blocking rusqlite call on the blocking-task thread pool, so a slow
+/// query never stalls the async executor thread it was polled on. `f` owns
+/// everything it touches (typically a [`PooledConnection`] plus its SQL and
+/// params) since `spawn_blocking` requires a `'static` closure.
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| StoreError::internal(format!("blocking sqlite task panicked: {e}")))?
+}
+
 pub struct SqliteStore {
-    conn: Arc<Mutex<Connection>>,
+    pool: Pool<SqliteConnectionManager>,
 }
 
 impl SqliteStore {
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
-        let conn = Connection::open(path)?;
-        conn.execute_batch(
-            "PRAGMA journal_mode=WAL;
-             PRAGMA synchronous=NORMAL;
-             PRAGMA foreign_keys=ON;
-             PRAGMA busy_timeout=5000;",
-        )?;
+        Self::open_with_config(path, PoolConfig::default())
+    }
+
+    /// Like [`Self::open`], but lets the caller tune the connection pool
+    /// (e.g. `max_size` for how many concurrent queries can run against
+    /// this file at once).
+    pub fn open_with_config(path: impl AsRef<Path>, config: PoolConfig) -> Result<Self> {
+        let target = SqliteTarget::File(path.as_ref().to_path_buf());
+        let conn = SqliteConnectionManager::open_connection(&target)?;
+        let manager = SqliteConnectionManager { target };
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            pool: Pool::with_seed(manager, config, conn),
+        })
+    }
+
+    /// Like [`Self::open`], but as a shorthand for the common case of just
+    /// wanting to tune how many concurrent connections the pool may hold
+    /// (WAL mode lets several readers and one writer share the file safely).
+    pub fn open_with_pool(path: impl AsRef<Path>, max_size: usize) -> Result<Self> {
+        Self::open_with_config(path, PoolConfig { max_size, ..PoolConfig::default() })
+    }
+
+    /// Opens (or creates) a SQLCipher-encrypted database at `path`, keyed
+    /// with `key`. See [`Self::open_encrypted_with_config`] to also tune
+    /// `cipher_page_size`/`kdf_iter` or the connection pool.
+    #[cfg(feature = "sqlcipher")]
+    pub fn open_encrypted(path: impl AsRef<Path>, key: impl Into<String>) -> Result<Self> {
+        Self::open_encrypted_with_config(path, key, CipherConfig::default(), PoolConfig::default())
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    pub fn open_encrypted_with_config(
+        path: impl AsRef<Path>,
+        key: impl Into<String>,
+        cipher: CipherConfig,
+        pool: PoolConfig,
+    ) -> Result<Self> {
+        let target = SqliteTarget::EncryptedFile(path.as_ref().to_path_buf(), key.into(), cipher);
+        let conn = SqliteConnectionManager::open_connection(&target)?;
+        let manager = SqliteConnectionManager { target };
+        Ok(Self {
+            pool: Pool::with_seed(manager, pool, conn),
+        })
+    }
+
+    /// Re-encrypts the database with `new_key`, issuing `PRAGMA rekey`.
+    /// Callers must persist `new_key` themselves — later opens of this file
+    /// need it, not the old one.
+    #[cfg(feature = "sqlcipher")]
+    pub async fn rekey(&self, new_key: impl Into<String>) -> Result<()> {
+        let conn = self.pool.get().await?;
+        let new_key = new_key.into();
+        run_blocking(move || {
+            conn.pragma_update(None, "rekey", &new_key)?;
+            Ok(())
         })
+        .await
     }
 
     pub fn memory() -> Result<Self> {
-        let conn = Connection::open_in_memory()?;
-        conn.execute_batch(
-            "PRAGMA foreign_keys=ON;",
-        )?;
+        let target = SqliteTarget::Memory;
+        let conn = SqliteConnectionManager::open_connection(&target)?;
+        let manager = SqliteConnectionManager { target };
+        // An in-memory database only exists inside the one `Connection`
+        // that created it, so this pool may never grow past one connection.
+        let config = PoolConfig {
+            max_size: 1,
+            ..PoolConfig::default()
+        };
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            pool: Pool::with_seed(manager, config, conn),
         })
     }
 
-    fn value_to_sql(value: &Value) -> Box<dyn ToSql> {
+    fn value_to_sql(value: &Value) -> Box<dyn ToSql + Send> {
         match value {
             Value::Null => Box::new(rusqlite::types::Null),
             Value::Bool(b) => Box::new(*b),
@@ -77,31 +239,41 @@ impl SqliteStore {
 #[async_trait]
 impl RelationalStore for SqliteStore {
     async fn execute(&self, sql: &str, params: &[Value]) -> Result<u64> {
-        let conn = self.conn.lock();
-        let sql_params: Vec<Box<dyn ToSql>> = params.iter().map(Self::value_to_sql).collect();
-        let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
-        let affected = conn.execute(sql, params_from_iter(param_refs))?;
-        Ok(affected as u64)
+        let conn = self.pool.get().await?;
+        let sql = sql.to_string();
+        let sql_params: Vec<Box<dyn ToSql + Send>> = params.iter().map(Self::value_to_sql).collect();
+
+        run_blocking(move || {
+            let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
+            let affected = conn.execute(&sql, params_from_iter(param_refs))?;
+            Ok(affected as u64)
+        })
+        .await
     }
 
     async fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Row>> {
-        let conn = self.conn.lock();
-        let sql_params: Vec<Box<dyn ToSql>> = params.iter().map(Self::value_to_sql).collect();
-        let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
+        let conn = self.pool.get().await?;
+        let sql = sql.to_string();
+        let sql_params: Vec<Box<dyn ToSql + Send>> = params.iter().map(Self::value_to_sql).collect();
 
-        let mut stmt = conn.prepare(sql)?;
-        let columns: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+        run_blocking(move || {
+            let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
 
-        let rows = stmt.query_map(params_from_iter(param_refs), |row| {
-            Ok(Self::row_to_values(row, &columns))
-        })?;
+            let mut stmt = conn.prepare(&sql)?;
+            let columns: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
 
-        let mut result = Vec::new();
-        for row in rows {
-            let values = row?.map_err(|e| StoreError::Query(e.to_string()))?;
-            result.push(Row::new(columns.clone(), values));
-        }
-        Ok(result)
+            let rows = stmt.query_map(params_from_iter(param_refs), |row| {
+                Ok(Self::row_to_values(row, &columns))
+            })?;
+
+            let mut result = Vec::new();
+            for row in rows {
+                let values = row?.map_err(|e| StoreError::Query(e.to_string()))?;
+                result.push(Row::new(columns.clone(), values));
+            }
+            Ok(result)
+        })
+        .await
     }
 
     async fn query_one(&self, sql: &str, params: &[Value]) -> Result<Option<Row>> {
@@ -110,64 +282,85 @@ impl RelationalStore for SqliteStore {
     }
 
     async fn begin(&self) -> Result<Box<dyn Transaction>> {
-        Err(StoreError::internal(
-            "SQLite transactions not yet implemented in async context",
-        ))
+        let conn = self.pool.get().await?;
+        let conn = run_blocking(move || {
+            conn.execute_batch("BEGIN")?;
+            Ok(conn)
+        })
+        .await?;
+
+        Ok(Box::new(SqliteTransaction {
+            conn: Arc::new(Mutex::new(conn)),
+            savepoint: None,
+            next_savepoint_id: Arc::new(AtomicU32::new(1)),
+            finished: false,
+        }))
     }
 
     async fn migrate(&self, migrations: &[Migration]) -> Result<()> {
-        let conn = self.conn.lock();
-
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS _migrations (
-                version INTEGER PRIMARY KEY,
-                name TEXT NOT NULL,
-                applied_at TEXT NOT NULL DEFAULT (datetime('now'))
-            )",
-            [],
-        )?;
-
-        let applied: std::collections::HashSet<u32> = {
-            let mut stmt = conn.prepare("SELECT version FROM _migrations")?;
-            let rows = stmt.query_map([], |row| row.get(0))?;
-            rows.filter_map(|r| r.ok()).collect()
-        };
+        let conn = self.pool.get().await?;
+        let migrations = migrations.to_vec();
 
-        for migration in migrations {
-            if applied.contains(&migration.version) {
-                continue;
-            }
+        run_blocking(move || {
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS _migrations (
+                    version INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+                )",
+                [],
+            )?;
 
-            tracing::info!(
-                version = migration.version,
-                name = %migration.name,
-                "Applying migration"
-            );
+            let applied: std::collections::HashSet<u32> = {
+                let mut stmt = conn.prepare("SELECT version FROM _migrations")?;
+                let rows = stmt.query_map([], |row| row.get(0))?;
+                rows.filter_map(|r| r.ok()).collect()
+            };
 
-            conn.execute_batch(&migration.up)?;
+            for migration in &migrations {
+                if applied.contains(&migration.version) {
+                    continue;
+                }
 
-            conn.execute(
-                "INSERT INTO _migrations (version, name) VALUES (?1, ?2)",
-                rusqlite::params![migration.version, migration.name],
-            )?;
-        }
+                tracing::info!(
+                    version = migration.version,
+                    name = %migration.name,
+                    "Applying migration"
+                );
 
-        Ok(())
+                conn.execute_batch(&migration.up)?;
+
+                conn.execute(
+                    "INSERT INTO _migrations (version, name) VALUES (?1, ?2)",
+                    rusqlite::params![migration.version, migration.name],
+                )?;
+            }
+
+            Ok(())
+        })
+        .await
     }
 
     async fn ping(&self) -> Result<()> {
-        let conn = self.conn.lock();
-        conn.execute_batch("SELECT 1")?;
-        Ok(())
+        let conn = self.pool.get().await?;
+        run_blocking(move || {
+            conn.execute_batch("SELECT 1")?;
+            Ok(())
+        })
+        .await
     }
 
     async fn table_exists(&self, table: &str) -> Result<bool> {
-        let conn = self.conn.lock();
-        let mut stmt = conn.prepare(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
-        )?;
-        let count: i64 = stmt.query_row([table], |row| row.get(0))?;
-        Ok(count > 0)
+        let conn = self.pool.get().await?;
+        let table = table.to_string();
+        run_blocking(move || {
+            let mut stmt = conn.prepare(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+            )?;
+            let count: i64 = stmt.query_row([table], |row| row.get(0))?;
+            Ok(count > 0)
+        })
+        .await
     }
 
     fn backend_name(&self) -> &'static str {
@@ -175,6 +368,134 @@ impl RelationalStore for SqliteStore {
     }
 }
 
+/// A transaction holding a dedicated pooled connection for its lifetime, shared
+/// (via `conn`) with any [`Self::begin`]-nested transactions so savepoint state
+/// stays on the one connection it was opened on.
+///
+/// `savepoint` is `None` for the transaction opened by
+/// [`SqliteStore::begin`] (which issued a plain `BEGIN`) and `Some(name)`
+/// for one opened by [`Transaction::begin`] on another `SqliteTransaction`
+/// (which issued `SAVEPOINT name` instead). `commit`/`rollback` issue the
+/// matching `COMMIT`/`ROLLBACK` or `RELEASE`/`ROLLBACK TO` pair; a
+/// transaction dropped without either is rolled back automatically so a
+/// connection never returns to the pool mid-transaction.
+struct SqliteTransaction {
+    conn: Arc<Mutex<PooledConnection<SqliteConnectionManager>>>,
+    savepoint: Option<String>,
+    /// Shared across the whole transaction tree so nested savepoints opened
+    /// from different handles never collide on the same name.
+    next_savepoint_id: Arc<AtomicU32>,
+    finished: bool,
+}
+
+impl SqliteTransaction {
+    async fn execute_batch(&self, sql: &str) -> Result<()> {
+        let conn = self.conn.clone();
+        let sql = sql.to_string();
+        run_blocking(move || {
+            conn.lock().execute_batch(&sql)?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl Transaction for SqliteTransaction {
+    async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
+        let conn = self.conn.clone();
+        let sql = sql.to_string();
+        let sql_params: Vec<Box<dyn ToSql + Send>> = params.iter().map(SqliteStore::value_to_sql).collect();
+
+        run_blocking(move || {
+            let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
+            let affected = conn.lock().execute(&sql, params_from_iter(param_refs))?;
+            Ok(affected as u64)
+        })
+        .await
+    }
+
+    async fn query(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Row>> {
+        let conn = self.conn.clone();
+        let sql = sql.to_string();
+        let sql_params: Vec<Box<dyn ToSql + Send>> = params.iter().map(SqliteStore::value_to_sql).collect();
+
+        run_blocking(move || {
+            let param_refs: Vec<&dyn ToSql> = sql_params.iter().map(|p| p.as_ref()).collect();
+
+            let conn = conn.lock();
+            let mut stmt = conn.prepare(&sql)?;
+            let columns: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+
+            let rows = stmt.query_map(params_from_iter(param_refs), |row| {
+                Ok(SqliteStore::row_to_values(row, &columns))
+            })?;
+
+            let mut result = Vec::new();
+            for row in rows {
+                let values = row?.map_err(|e| StoreError::Query(e.to_string()))?;
+                result.push(Row::new(columns.clone(), values));
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    async fn query_one(&mut self, sql: &str, params: &[Value]) -> Result<Option<Row>> {
+        let rows = self.query(sql, params).await?;
+        Ok(rows.into_iter().next())
+    }
+
+    async fn begin(&mut self) -> Result<Box<dyn Transaction>> {
+        let id = self.next_savepoint_id.fetch_add(1, Ordering::SeqCst);
+        let name = format!("sp_{id}");
+        self.execute_batch(&format!("SAVEPOINT {name}")).await?;
+        Ok(Box::new(SqliteTransaction {
+            conn: self.conn.clone(),
+            savepoint: Some(name),
+            next_savepoint_id: self.next_savepoint_id.clone(),
+            finished: false,
+        }))
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<()> {
+        match &self.savepoint {
+            Some(name) => self.execute_batch(&format!("RELEASE SAVEPOINT {name}")).await?,
+            None => self.execute_batch("COMMIT").await?,
+        }
+        self.finished = true;
+        Ok(())
+    }
+
+    async fn rollback(mut self: Box<Self>) -> Result<()> {
+        match &self.savepoint {
+            Some(name) => {
+                self.execute_batch(&format!("ROLLBACK TO SAVEPOINT {name}; RELEASE SAVEPOINT {name}")).await?
+            }
+            None => self.execute_batch("ROLLBACK").await?,
+        }
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl Drop for SqliteTransaction {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        let sql = match &self.savepoint {
+            Some(name) => format!("ROLLBACK TO SAVEPOINT {name}; RELEASE SAVEPOINT {name}"),
+            None => "ROLLBACK".to_string(),
+        };
+
+        if let Some(conn) = self.conn.try_lock() {
+            let _ = conn.execute_batch(&sql);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +627,124 @@ mod tests {
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].get("data"), Some(&Value::Null));
     }
+
+    #[tokio::test]
+    async fn test_sqlite_transaction_commit_persists_changes() {
+        let store = SqliteStore::memory().unwrap();
+        store.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY, balance INTEGER)", &[]).await.unwrap();
+
+        let mut tx = store.begin().await.unwrap();
+        tx.execute("INSERT INTO accounts (id, balance) VALUES (1, 100)", &[]).await.unwrap();
+        tx.execute("UPDATE accounts SET balance = balance - 10 WHERE id = 1", &[]).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let row = store.query_one("SELECT * FROM accounts WHERE id = 1", &[]).await.unwrap().unwrap();
+        assert_eq!(row.get_i64("balance"), Some(90));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_transaction_rollback_discards_changes() {
+        let store = SqliteStore::memory().unwrap();
+        store.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY, balance INTEGER)", &[]).await.unwrap();
+        store.execute("INSERT INTO accounts (id, balance) VALUES (1, 100)", &[]).await.unwrap();
+
+        let mut tx = store.begin().await.unwrap();
+        tx.execute("UPDATE accounts SET balance = 0 WHERE id = 1", &[]).await.unwrap();
+        tx.rollback().await.unwrap();
+
+        let row = store.query_one("SELECT * FROM accounts WHERE id = 1", &[]).await.unwrap().unwrap();
+        assert_eq!(row.get_i64("balance"), Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_connection_is_returned_to_pool_after_use() {
+        let path = std::env::temp_dir().join(format!("hehe-store-pool-test-{}.sqlite3", hehe_core::Id::new()));
+        let store = SqliteStore::open_with_config(
+            path,
+            PoolConfig { max_size: 2, ..PoolConfig::default() },
+        )
+        .unwrap();
+
+        store.execute("CREATE TABLE IF NOT EXISTS t (id INTEGER)", &[]).await.unwrap();
+        store.query("SELECT * FROM t", &[]).await.unwrap();
+
+        assert_eq!(store.pool.idle_count(), 1, "the connection used above should be back in the idle queue");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_nested_transaction_commit_and_rollback() {
+        let store = SqliteStore::memory().unwrap();
+        store.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY, balance INTEGER)", &[]).await.unwrap();
+        store.execute("INSERT INTO accounts (id, balance) VALUES (1, 100)", &[]).await.unwrap();
+
+        let mut tx = store.begin().await.unwrap();
+        tx.execute("UPDATE accounts SET balance = 90 WHERE id = 1", &[]).await.unwrap();
+
+        let mut nested = tx.begin().await.unwrap();
+        nested.execute("UPDATE accounts SET balance = 0 WHERE id = 1", &[]).await.unwrap();
+        nested.rollback().await.unwrap();
+
+        tx.commit().await.unwrap();
+
+        let row = store.query_one("SELECT * FROM accounts WHERE id = 1", &[]).await.unwrap().unwrap();
+        assert_eq!(row.get_i64("balance"), Some(90), "outer commit should keep its own update but discard the rolled-back nested one");
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_distinct_savepoints_do_not_collide() {
+        let store = SqliteStore::memory().unwrap();
+        store.execute("CREATE TABLE t (id INTEGER)", &[]).await.unwrap();
+
+        let mut tx = store.begin().await.unwrap();
+        let mut nested_a = tx.begin().await.unwrap();
+        nested_a.commit().await.unwrap();
+        let mut nested_b = tx.begin().await.unwrap();
+        nested_b.commit().await.unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query_as_typed_tuples() {
+        use crate::traits::RelationalStoreExt;
+
+        let store = SqliteStore::memory().unwrap();
+        store
+            .execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)", &[])
+            .await
+            .unwrap();
+        store
+            .execute("INSERT INTO users (name) VALUES (?1)", &[Value::String("alice".into())])
+            .await
+            .unwrap();
+
+        let rows: Vec<(i64, String)> = store.query_as("SELECT id, name FROM users", &[]).await.unwrap();
+        assert_eq!(rows, vec![(1, "alice".to_string())]);
+
+        let one: Option<(i64, String)> = store
+            .query_one_as("SELECT id, name FROM users WHERE name = ?1", &[Value::String("alice".into())])
+            .await
+            .unwrap();
+        assert_eq!(one, Some((1, "alice".to_string())));
+
+        let none: Option<(i64, String)> = store
+            .query_one_as("SELECT id, name FROM users WHERE name = ?1", &[Value::String("nobody".into())])
+            .await
+            .unwrap();
+        assert_eq!(none, None);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_transaction_dropped_without_commit_rolls_back() {
+        let store = SqliteStore::memory().unwrap();
+        store.execute("CREATE TABLE accounts (id INTEGER PRIMARY KEY, balance INTEGER)", &[]).await.unwrap();
+        store.execute("INSERT INTO accounts (id, balance) VALUES (1, 100)", &[]).await.unwrap();
+
+        {
+            let mut tx = store.begin().await.unwrap();
+            tx.execute("UPDATE accounts SET balance = 0 WHERE id = 1", &[]).await.unwrap();
+        }
+
+        let row = store.query_one("SELECT * FROM accounts WHERE id = 1", &[]).await.unwrap().unwrap();
+        assert_eq!(row.get_i64("balance"), Some(100), "dropping a transaction without commit/rollback should undo its changes");
+    }
 }
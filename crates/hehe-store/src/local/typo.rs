@@ -0,0 +1,96 @@
+/// Thresholds for [`SqliteFtsStore::search_typo_tolerant`](super::SqliteFtsStore::search_typo_tolerant)'s
+/// edit-distance term expansion: how long a query term must be before it's
+/// allowed to match a vocabulary term one or two edits away, and how many
+/// such candidates a single term may expand into.
+#[derive(Clone, Debug)]
+pub struct TypoToleranceConfig {
+    /// Minimum term length eligible for distance-1 candidates (e.g. `brwn` -> `brown`).
+    pub min_len_for_distance_1: usize,
+    /// Minimum term length eligible for distance-2 candidates (e.g. `tehcnology` -> `technology`).
+    pub min_len_for_distance_2: usize,
+    /// Maximum number of candidate terms a single query term may expand into.
+    pub max_candidates: usize,
+}
+
+impl Default for TypoToleranceConfig {
+    fn default() -> Self {
+        Self {
+            min_len_for_distance_1: 4,
+            min_len_for_distance_2: 8,
+            max_candidates: 5,
+        }
+    }
+}
+
+impl TypoToleranceConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_min_len_for_distance_1(mut self, len: usize) -> Self {
+        self.min_len_for_distance_1 = len;
+        self
+    }
+
+    pub fn with_min_len_for_distance_2(mut self, len: usize) -> Self {
+        self.min_len_for_distance_2 = len;
+        self
+    }
+
+    pub fn with_max_candidates(mut self, max: usize) -> Self {
+        self.max_candidates = max;
+        self
+    }
+}
+
+/// Classic Wagner-Fischer edit distance between two strings, operating on
+/// `char`s rather than bytes so multi-byte UTF-8 input isn't miscounted.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_identical_strings_is_zero() {
+        assert_eq!(levenshtein("technology", "technology"), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_single_insertion() {
+        assert_eq!(levenshtein("technlogy", "technology"), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_counts_chars_not_bytes() {
+        assert_eq!(levenshtein("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn test_typo_tolerance_config_defaults() {
+        let config = TypoToleranceConfig::new();
+        assert_eq!(config.min_len_for_distance_1, 4);
+        assert_eq!(config.min_len_for_distance_2, 8);
+        assert_eq!(config.max_candidates, 5);
+
+        let config = config.with_max_candidates(2);
+        assert_eq!(config.max_candidates, 2);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::error::{Result, StoreError};
+use crate::traits::{Migration, RelationalStore};
+use hehe_core::Timestamp;
+use serde_json::Value;
+
+/// Name of the bookkeeping table [`Migrator`] creates in the target store to
+/// track which migrations have been applied.
+const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// Runs a set of [`Migration`]s against any [`RelationalStore`] backend,
+/// tracking applied versions in a `schema_migrations` table and executing
+/// each migration's `up`/`down` inside its own transaction so a failure
+/// partway through never leaves the schema half-migrated.
+pub struct Migrator<'a> {
+    store: &'a dyn RelationalStore,
+    migrations: &'a [Migration],
+}
+
+/// One row of `schema_migrations`, as returned by [`Migrator::migration_status`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: String,
+}
+
+impl<'a> Migrator<'a> {
+    pub fn new(store: &'a dyn RelationalStore, migrations: &'a [Migration]) -> Self {
+        Self { store, migrations }
+    }
+
+    async fn ensure_migrations_table(&self) -> Result<()> {
+        if !self.store.table_exists(MIGRATIONS_TABLE).await? {
+            self.store
+                .execute(
+                    &format!(
+                        "CREATE TABLE {MIGRATIONS_TABLE} (version INTEGER PRIMARY KEY, name TEXT, applied_at TEXT)"
+                    ),
+                    &[],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn max_applied_version(&self) -> Result<u32> {
+        let row = self
+            .store
+            .query_one(&format!("SELECT MAX(version) as version FROM {MIGRATIONS_TABLE}"), &[])
+            .await?;
+
+        Ok(row.and_then(|r| r.get_i64("version")).map(|v| v as u32).unwrap_or(0))
+    }
+
+    /// Versions currently recorded in `schema_migrations`, descending.
+    async fn applied_versions_desc(&self) -> Result<Vec<u32>> {
+        let rows = self
+            .store
+            .query(&format!("SELECT version FROM {MIGRATIONS_TABLE} ORDER BY version DESC"), &[])
+            .await?;
+
+        Ok(rows.iter().filter_map(|r| r.get_i64("version")).map(|v| v as u32).collect())
+    }
+
+    /// Applies every migration with a version greater than the highest
+    /// already-applied version, ascending, each in its own transaction.
+    /// Idempotent: migrations already recorded in `schema_migrations` are
+    /// skipped.
+    pub async fn migrate(&self) -> Result<()> {
+        self.ensure_migrations_table().await?;
+        let max_version = self.max_applied_version().await?;
+
+        let mut pending: Vec<&Migration> =
+            self.migrations.iter().filter(|m| m.version > max_version).collect();
+        pending.sort_by_key(|m| m.version);
+
+        for migration in pending {
+            tracing::info!(version = migration.version, name = %migration.name, "applying migration");
+
+            let mut tx = self.store.begin().await?;
+            let result: Result<()> = async {
+                tx.execute(&migration.up, &[]).await?;
+                tx.execute(
+                    &format!("INSERT INTO {MIGRATIONS_TABLE} (version, name, applied_at) VALUES (?, ?, ?)"),
+                    &[
+                        Value::from(migration.version),
+                        Value::from(migration.name.clone()),
+                        Value::from(Timestamp::now().to_string()),
+                    ],
+                )
+                .await?;
+                Ok(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => tx.commit().await?,
+                Err(e) => {
+                    tx.rollback().await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reverts every applied migration with a version greater than
+    /// `target_version`, descending, each in its own transaction. Errors if
+    /// any such migration has no `down` SQL, or isn't present in the
+    /// `migrations` this [`Migrator`] was built with.
+    pub async fn rollback(&self, target_version: u32) -> Result<()> {
+        self.ensure_migrations_table().await?;
+
+        for version in self.applied_versions_desc().await? {
+            if version <= target_version {
+                break;
+            }
+
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| m.version == version)
+                .ok_or_else(|| StoreError::migration(format!("no migration registered for version {version}")))?;
+
+            let down = migration
+                .down
+                .as_ref()
+                .ok_or_else(|| StoreError::migration(format!("migration {version} ({}) has no down SQL", migration.name)))?;
+
+            tracing::info!(version, name = %migration.name, "rolling back migration");
+
+            let mut tx = self.store.begin().await?;
+            let result: Result<()> = async {
+                tx.execute(down, &[]).await?;
+                tx.execute(
+                    &format!("DELETE FROM {MIGRATIONS_TABLE} WHERE version = ?"),
+                    &[Value::from(version)],
+                )
+                .await?;
+                Ok(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => tx.commit().await?,
+                Err(e) => {
+                    tx.rollback().await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists every applied migration, ascending by version, so callers can
+    /// diff the desired migration set against what's actually been run.
+    pub async fn migration_status(&self) -> Result<Vec<AppliedMigration>> {
+        self.ensure_migrations_table().await?;
+
+        let rows = self
+            .store
+            .query(
+                &format!("SELECT version, name, applied_at FROM {MIGRATIONS_TABLE} ORDER BY version ASC"),
+                &[],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(AppliedMigration {
+                    version: row
+                        .get_i64("version")
+                        .ok_or_else(|| StoreError::migration("applied migration row missing version"))?
+                        as u32,
+                    name: row
+                        .get_str("name")
+                        .ok_or_else(|| StoreError::migration("applied migration row missing name"))?
+                        .to_string(),
+                    applied_at: row
+                        .get_str("applied_at")
+                        .ok_or_else(|| StoreError::migration("applied migration row missing applied_at"))?
+                        .to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{Row, Transaction};
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    /// Bare-bones [`RelationalStore`] double: it doesn't execute arbitrary
+    /// SQL, just enough pattern-matching on the statements [`Migrator`]
+    /// itself issues (`schema_migrations` DDL/DML) to exercise version
+    /// tracking and transactional commit/rollback, plus recording every
+    /// other statement (a migration's `up`/`down`) so tests can assert on
+    /// what ran.
+    #[derive(Clone, Default)]
+    struct FakeState {
+        migrations_table_exists: bool,
+        applied: Vec<(u32, String, String)>,
+        executed_sql: Vec<String>,
+        fail_sql_containing: Option<String>,
+    }
+
+    impl FakeState {
+        fn apply(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
+            if sql.starts_with(&format!("CREATE TABLE {MIGRATIONS_TABLE}")) {
+                self.migrations_table_exists = true;
+            } else if sql.starts_with(&format!("INSERT INTO {MIGRATIONS_TABLE}")) {
+                let version = params[0].as_u64().unwrap() as u32;
+                let name = params[1].as_str().unwrap().to_string();
+                let applied_at = params[2].as_str().unwrap().to_string();
+                self.applied.push((version, name, applied_at));
+            } else if sql.starts_with(&format!("DELETE FROM {MIGRATIONS_TABLE}")) {
+                let version = params[0].as_u64().unwrap() as u32;
+                self.applied.retain(|(v, _, _)| *v != version);
+            } else {
+                if self.fail_sql_containing.as_deref().is_some_and(|needle| sql.contains(needle)) {
+                    return Err(StoreError::query(format!("simulated failure running: {sql}")));
+                }
+                self.executed_sql.push(sql.to_string());
+            }
+            Ok(1)
+        }
+
+        fn run_query(&self, sql: &str) -> Vec<Row> {
+            if sql.starts_with("SELECT MAX(version)") {
+                let max = self.applied.iter().map(|(v, _, _)| *v).max();
+                vec![Row::new(
+                    vec!["version".to_string()],
+                    vec![max.map(Value::from).unwrap_or(Value::Null)],
+                )]
+            } else if sql.starts_with(&format!("SELECT version, name, applied_at FROM {MIGRATIONS_TABLE}")) {
+                let mut applied = self.applied.clone();
+                applied.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                applied
+                    .into_iter()
+                    .map(|(version, name, applied_at)| {
+                        Row::new(
+                            vec!["version".to_string(), "name".to_string(), "applied_at".to_string()],
+                            vec![Value::from(version), Value::from(name), Value::from(applied_at)],
+                        )
+                    })
+                    .collect()
+            } else if sql.starts_with(&format!("SELECT version FROM {MIGRATIONS_TABLE}")) {
+                let mut versions: Vec<u32> = self.applied.iter().map(|(v, _, _)| *v).collect();
+                versions.sort_unstable_by(|a, b| b.cmp(a));
+                versions
+                    .into_iter()
+                    .map(|v| Row::new(vec!["version".to_string()], vec![Value::from(v)]))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    struct FakeRelationalStore {
+        state: Arc<Mutex<FakeState>>,
+    }
+
+    impl FakeRelationalStore {
+        fn new() -> Self {
+            Self {
+                state: Arc::new(Mutex::new(FakeState::default())),
+            }
+        }
+
+        fn failing_on(sql_needle: &str) -> Self {
+            let store = Self::new();
+            store.state.lock().fail_sql_containing = Some(sql_needle.to_string());
+            store
+        }
+
+        fn executed_sql(&self) -> Vec<String> {
+            self.state.lock().executed_sql.clone()
+        }
+
+        fn applied_versions(&self) -> Vec<u32> {
+            self.state.lock().applied.iter().map(|(v, _, _)| *v).collect()
+        }
+    }
+
+    struct FakeTransaction {
+        shared: Arc<Mutex<FakeState>>,
+        local: FakeState,
+    }
+
+    #[async_trait]
+    impl Transaction for FakeTransaction {
+        async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64> {
+            self.local.apply(sql, params)
+        }
+
+        async fn query(&mut self, sql: &str, _params: &[Value]) -> Result<Vec<Row>> {
+            Ok(self.local.run_query(sql))
+        }
+
+        async fn query_one(&mut self, sql: &str, params: &[Value]) -> Result<Option<Row>> {
+            Ok(self.query(sql, params).await?.into_iter().next())
+        }
+
+        async fn commit(self: Box<Self>) -> Result<()> {
+            *self.shared.lock() = self.local;
+            Ok(())
+        }
+
+        async fn rollback(self: Box<Self>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl RelationalStore for FakeRelationalStore {
+        async fn execute(&self, sql: &str, params: &[Value]) -> Result<u64> {
+            self.state.lock().apply(sql, params)
+        }
+
+        async fn query(&self, sql: &str, _params: &[Value]) -> Result<Vec<Row>> {
+            Ok(self.state.lock().run_query(sql))
+        }
+
+        async fn query_one(&self, sql: &str, params: &[Value]) -> Result<Option<Row>> {
+            Ok(self.query(sql, params).await?.into_iter().next())
+        }
+
+        async fn begin(&self) -> Result<Box<dyn Transaction>> {
+            let local = self.state.lock().clone();
+            Ok(Box::new(FakeTransaction {
+                shared: self.state.clone(),
+                local,
+            }))
+        }
+
+        async fn migrate(&self, _migrations: &[Migration]) -> Result<()> {
+            unimplemented!("tests drive schema changes through Migrator, not this method")
+        }
+
+        async fn ping(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn table_exists(&self, table: &str) -> Result<bool> {
+            Ok(table == MIGRATIONS_TABLE && self.state.lock().migrations_table_exists)
+        }
+
+        fn backend_name(&self) -> &'static str {
+            "fake"
+        }
+    }
+
+    fn sample_migrations() -> Vec<Migration> {
+        vec![
+            Migration::new(1, "create_users", "CREATE TABLE users (id INTEGER PRIMARY KEY)")
+                .with_down("DROP TABLE users"),
+            Migration::new(2, "create_posts", "CREATE TABLE posts (id INTEGER PRIMARY KEY)")
+                .with_down("DROP TABLE posts"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_migrate_applies_pending_migrations_in_order() {
+        let store = FakeRelationalStore::new();
+        let migrations = sample_migrations();
+
+        Migrator::new(&store, &migrations).migrate().await.unwrap();
+
+        assert_eq!(store.applied_versions(), vec![1, 2]);
+        assert_eq!(
+            store.executed_sql(),
+            vec![
+                "CREATE TABLE users (id INTEGER PRIMARY KEY)".to_string(),
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY)".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_is_idempotent() {
+        let store = FakeRelationalStore::new();
+        let migrations = sample_migrations();
+
+        let migrator = Migrator::new(&store, &migrations);
+        migrator.migrate().await.unwrap();
+        migrator.migrate().await.unwrap();
+
+        assert_eq!(store.applied_versions(), vec![1, 2]);
+        assert_eq!(store.executed_sql().len(), 2, "already-applied migrations must not re-run");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_rolls_back_and_does_not_record_a_failed_migration() {
+        let store = FakeRelationalStore::failing_on("posts");
+        let migrations = sample_migrations();
+
+        let err = Migrator::new(&store, &migrations).migrate().await.unwrap_err();
+
+        assert!(err.to_string().contains("simulated failure"));
+        assert_eq!(store.applied_versions(), vec![1], "the failing migration must not be recorded");
+        assert_eq!(store.executed_sql(), vec!["CREATE TABLE users (id INTEGER PRIMARY KEY)".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reverts_versions_above_target_in_descending_order() {
+        let store = FakeRelationalStore::new();
+        let migrations = sample_migrations();
+        let migrator = Migrator::new(&store, &migrations);
+        migrator.migrate().await.unwrap();
+
+        migrator.rollback(0).await.unwrap();
+
+        assert!(store.applied_versions().is_empty());
+        assert_eq!(
+            store.executed_sql(),
+            vec![
+                "CREATE TABLE users (id INTEGER PRIMARY KEY)".to_string(),
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY)".to_string(),
+                "DROP TABLE posts".to_string(),
+                "DROP TABLE users".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_errors_when_down_sql_is_missing() {
+        let store = FakeRelationalStore::new();
+        let migrations = vec![Migration::new(1, "create_users", "CREATE TABLE users (id INTEGER PRIMARY KEY)")];
+        let migrator = Migrator::new(&store, &migrations);
+        migrator.migrate().await.unwrap();
+
+        let err = migrator.rollback(0).await.unwrap_err();
+        assert!(err.to_string().contains("no down SQL"));
+        assert_eq!(store.applied_versions(), vec![1], "the failed rollback must leave the version recorded");
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_lists_applied_versions_ascending() {
+        let store = FakeRelationalStore::new();
+        let migrations = sample_migrations();
+        let migrator = Migrator::new(&store, &migrations);
+        migrator.migrate().await.unwrap();
+
+        let status = migrator.migration_status().await.unwrap();
+
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].version, 1);
+        assert_eq!(status[0].name, "create_users");
+        assert_eq!(status[1].version, 2);
+        assert_eq!(status[1].name, "create_posts");
+        assert!(!status[0].applied_at.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_is_empty_before_any_migration_runs() {
+        let store = FakeRelationalStore::new();
+        let migrations = sample_migrations();
+
+        let status = Migrator::new(&store, &migrations).migration_status().await.unwrap();
+
+        assert!(status.is_empty());
+    }
+}
@@ -0,0 +1,342 @@
+use crate::error::{Result, StoreError};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Tunables for a [`Pool`], mirroring the knobs `deadpool` exposes: how many
+/// connections may exist at once, how many to keep warm up front, how long
+/// [`Pool::get`] waits for a slot to free up, and how long an idle
+/// connection may sit before it's treated as stale and discarded rather
+/// than handed back to a caller.
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    pub max_size: usize,
+    pub min_idle: usize,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            min_idle: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+/// Knows how to create and health-check the connections a [`Pool`] manages.
+/// Implemented once per backend (e.g. a `SqliteConnectionManager` opening
+/// `rusqlite::Connection`s); [`Pool`] itself doesn't know anything about
+/// the concrete connection type or how to reach the backend.
+#[async_trait]
+pub trait PoolManager: Send + Sync {
+    type Connection: Send;
+
+    async fn create(&self) -> Result<Self::Connection>;
+
+    /// Health-checks a connection pulled from the idle queue before it's
+    /// handed to a caller. A connection that fails this is dropped instead
+    /// of reused, and [`Pool::get`] tries the next idle one (or creates a
+    /// fresh connection if none are left).
+    async fn ping(&self, conn: &Self::Connection) -> Result<()>;
+}
+
+struct IdleConnection<C> {
+    conn: C,
+    idle_since: Instant,
+}
+
+struct PoolInner<M: PoolManager> {
+    manager: M,
+    config: PoolConfig,
+    idle: Mutex<VecDeque<IdleConnection<M::Connection>>>,
+    semaphore: Arc<Semaphore>,
+}
+
+/// A set of recyclable connections to a backend. Connections are handed out
+/// via [`Pool::get`] as [`PooledConnection`] guards that return to the idle
+/// queue when dropped, so callers never manage connection lifetime by hand.
+pub struct Pool<M: PoolManager> {
+    inner: Arc<PoolInner<M>>,
+}
+
+impl<M: PoolManager> Clone for Pool<M> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<M: PoolManager> Pool<M> {
+    /// Builds a pool with no connections pre-created; the first `get()`
+    /// call creates one lazily. Call [`Self::warm_up`] afterwards to
+    /// pre-create `config.min_idle` connections instead.
+    pub fn new(manager: M, config: PoolConfig) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                semaphore: Arc::new(Semaphore::new(config.max_size)),
+                manager,
+                idle: Mutex::new(VecDeque::new()),
+                config,
+            }),
+        }
+    }
+
+    /// Like [`Self::new`], but seeds the idle queue with an
+    /// already-created `conn`. Useful for backends that want to eagerly
+    /// validate the first connection (e.g. surface a bad path or bad
+    /// config as a constructor error) without requiring an async context.
+    pub fn with_seed(manager: M, config: PoolConfig, conn: M::Connection) -> Self {
+        let pool = Self::new(manager, config);
+        pool.inner.idle.lock().push_back(IdleConnection { conn, idle_since: Instant::now() });
+        pool
+    }
+
+    /// Eagerly creates idle connections until `config.min_idle` are
+    /// sitting ready (capped at `config.max_size`), so the next callers
+    /// don't pay connection setup cost on the hot path.
+    pub async fn warm_up(&self) -> Result<()> {
+        let target = self.inner.config.min_idle.min(self.inner.config.max_size);
+        while self.idle_count() < target {
+            let conn = self.inner.manager.create().await?;
+            self.inner.idle.lock().push_back(IdleConnection { conn, idle_since: Instant::now() });
+        }
+        Ok(())
+    }
+
+    /// Acquires a connection: reuses a healthy idle one if available,
+    /// otherwise creates a fresh one, waiting up to `config.acquire_timeout`
+    /// for a slot under `max_size` to free up.
+    pub async fn get(&self) -> Result<PooledConnection<M>> {
+        let permit = tokio::time::timeout(
+            self.inner.config.acquire_timeout,
+            self.inner.semaphore.clone().acquire_owned(),
+        )
+        .await
+        .map_err(|_| StoreError::PoolExhausted)?
+        .map_err(|_| StoreError::internal("connection pool closed"))?;
+
+        loop {
+            let candidate = self.inner.idle.lock().pop_front();
+
+            let Some(candidate) = candidate else {
+                let conn = self.inner.manager.create().await?;
+                return Ok(PooledConnection::new(conn, self.inner.clone(), permit));
+            };
+
+            if candidate.idle_since.elapsed() > self.inner.config.idle_timeout {
+                continue;
+            }
+
+            if self.inner.manager.ping(&candidate.conn).await.is_ok() {
+                return Ok(PooledConnection::new(candidate.conn, self.inner.clone(), permit));
+            }
+            // Failed health check: drop `candidate` and loop to try the next one.
+        }
+    }
+
+    /// Number of connections currently idle in the pool (for tests/metrics).
+    pub fn idle_count(&self) -> usize {
+        self.inner.idle.lock().len()
+    }
+}
+
+/// A connection checked out of a [`Pool`]. Derefs to `M::Connection`;
+/// returns the connection to the pool's idle queue on drop unless
+/// [`Self::discard`] was called first.
+pub struct PooledConnection<M: PoolManager> {
+    conn: Option<M::Connection>,
+    pool: Arc<PoolInner<M>>,
+    _permit: tokio::sync::OwnedSemaphorePermit,
+}
+
+impl<M: PoolManager> PooledConnection<M> {
+    fn new(conn: M::Connection, pool: Arc<PoolInner<M>>, permit: tokio::sync::OwnedSemaphorePermit) -> Self {
+        Self { conn: Some(conn), pool, _permit: permit }
+    }
+
+    /// Drops the underlying connection instead of returning it to the pool
+    /// — for a caller that knows it left the connection in a bad state
+    /// (e.g. an unrecoverable error mid-transaction).
+    pub fn discard(mut self) {
+        self.conn.take();
+    }
+}
+
+impl<M: PoolManager> Deref for PooledConnection<M> {
+    type Target = M::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn.as_ref().expect("connection already taken")
+    }
+}
+
+impl<M: PoolManager> DerefMut for PooledConnection<M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn.as_mut().expect("connection already taken")
+    }
+}
+
+impl<M: PoolManager> Drop for PooledConnection<M> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.idle.lock().push_back(IdleConnection { conn, idle_since: Instant::now() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// A manager whose "connections" are just incrementing ids, with
+    /// switches to simulate a dead connection (fails [`PoolManager::ping`])
+    /// or an unreachable backend (fails [`PoolManager::create`]).
+    struct CountingManager {
+        next_id: AtomicU64,
+        created: AtomicUsize,
+        dead_ids: Mutex<Vec<u64>>,
+        fail_create: std::sync::atomic::AtomicBool,
+    }
+
+    impl CountingManager {
+        fn new() -> Self {
+            Self {
+                next_id: AtomicU64::new(0),
+                created: AtomicUsize::new(0),
+                dead_ids: Mutex::new(Vec::new()),
+                fail_create: std::sync::atomic::AtomicBool::new(false),
+            }
+        }
+
+        fn created_count(&self) -> usize {
+            self.created.load(Ordering::SeqCst)
+        }
+
+        fn mark_dead(&self, id: u64) {
+            self.dead_ids.lock().push(id);
+        }
+    }
+
+    #[async_trait]
+    impl PoolManager for CountingManager {
+        type Connection = u64;
+
+        async fn create(&self) -> Result<u64> {
+            if self.fail_create.load(Ordering::SeqCst) {
+                return Err(StoreError::connection("backend unreachable"));
+            }
+            self.created.fetch_add(1, Ordering::SeqCst);
+            Ok(self.next_id.fetch_add(1, Ordering::SeqCst))
+        }
+
+        async fn ping(&self, conn: &u64) -> Result<()> {
+            if self.dead_ids.lock().contains(conn) {
+                Err(StoreError::connection("connection is dead"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn config(max_size: usize) -> PoolConfig {
+        PoolConfig {
+            max_size,
+            min_idle: 0,
+            acquire_timeout: Duration::from_millis(200),
+            idle_timeout: Duration::from_secs(600),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_creates_new_connections_up_to_max_size() {
+        let manager = CountingManager::new();
+        let pool = Pool::new(manager, config(2));
+
+        let a = pool.get().await.unwrap();
+        let b = pool.get().await.unwrap();
+        assert_ne!(*a, *b);
+    }
+
+    #[tokio::test]
+    async fn test_get_blocks_until_a_slot_frees_up() {
+        let manager = CountingManager::new();
+        let pool = Pool::new(manager, config(1));
+
+        let first = pool.get().await.unwrap();
+        assert!(pool.get().await.is_err(), "no slot should be free while `first` is held");
+
+        drop(first);
+        assert!(pool.get().await.is_ok(), "dropping the guard should free the slot");
+    }
+
+    #[tokio::test]
+    async fn test_connection_returns_to_idle_queue_on_drop_and_is_reused() {
+        let manager = CountingManager::new();
+        let pool = Pool::new(manager, config(5));
+
+        let conn = pool.get().await.unwrap();
+        let id = *conn;
+        drop(conn);
+
+        assert_eq!(pool.idle_count(), 1);
+        let reused = pool.get().await.unwrap();
+        assert_eq!(*reused, id);
+        assert_eq!(pool.inner.manager.created_count(), 1, "no new connection should have been created");
+    }
+
+    #[tokio::test]
+    async fn test_dead_idle_connection_is_discarded_and_replaced() {
+        let manager = CountingManager::new();
+        let pool = Pool::new(manager, config(5));
+
+        let conn = pool.get().await.unwrap();
+        let dead_id = *conn;
+        pool.inner.manager.mark_dead(dead_id);
+        drop(conn);
+
+        let replacement = pool.get().await.unwrap();
+        assert_ne!(*replacement, dead_id);
+        assert_eq!(pool.inner.manager.created_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_prefills_min_idle_connections() {
+        let manager = CountingManager::new();
+        let mut cfg = config(5);
+        cfg.min_idle = 3;
+
+        let pool = Pool::new(manager, cfg);
+        pool.warm_up().await.unwrap();
+
+        assert_eq!(pool.idle_count(), 3);
+        assert_eq!(pool.inner.manager.created_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_discard_prevents_connection_from_returning_to_idle_queue() {
+        let manager = CountingManager::new();
+        let pool = Pool::new(manager, config(5));
+
+        let conn = pool.get().await.unwrap();
+        conn.discard();
+
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    #[test]
+    fn test_with_seed_makes_the_seeded_connection_immediately_idle() {
+        let manager = CountingManager::new();
+        let pool = Pool::with_seed(manager, config(2), 42);
+
+        assert_eq!(pool.idle_count(), 1);
+        assert_eq!(pool.inner.manager.created_count(), 0, "seeding shouldn't call create()");
+    }
+}
@@ -1,12 +1,31 @@
 use crate::error::Result;
-use crate::traits::{CacheStore, RelationalStore, SearchStore, VectorStore};
+use crate::local::MemoryVectorStore;
+use crate::sync::{RelationalSyncable, SyncEngine, SyncReport, Syncable};
+use crate::traits::{CacheStore, RelationalStore, SearchResult, SearchStore, VectorStore};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Default reciprocal rank fusion constant used by [`StoreRouter::hybrid_search`].
+/// Larger values flatten the influence of rank differences between results.
+const DEFAULT_RRF_K: f32 = 60.0;
+
+struct FusedHit {
+    id: String,
+    content: Option<String>,
+    rrf_score: f32,
+    vector_score: Option<f32>,
+    text_score: Option<f32>,
+}
+
 pub struct StoreRouter {
     relational: Arc<dyn RelationalStore>,
     vector: Arc<dyn VectorStore>,
     cache: Arc<dyn CacheStore>,
     search: Arc<dyn SearchStore>,
+    /// Set by [`Self::local_persistent`] when `vector` is backed by a
+    /// concrete [`MemoryVectorStore`], so [`Self::flush_vector_snapshot`]
+    /// can write it back out without downcasting the trait object.
+    vector_snapshot: Option<(Arc<MemoryVectorStore>, String)>,
 }
 
 impl StoreRouter {
@@ -21,6 +40,7 @@ impl StoreRouter {
             vector,
             cache,
             search,
+            vector_snapshot: None,
         }
     }
 
@@ -40,43 +60,196 @@ impl StoreRouter {
         self.search.as_ref()
     }
 
+    /// Combines semantic vector search with keyword/full-text search into a
+    /// single ranking via Reciprocal Rank Fusion, using the default `k` of
+    /// [`DEFAULT_RRF_K`]. See [`Self::hybrid_search_with_k`] for the scoring
+    /// details.
+    pub async fn hybrid_search(
+        &self,
+        collection: &str,
+        query_text: &str,
+        query_vector: &[f32],
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        self.hybrid_search_with_k(collection, query_text, query_vector, limit, DEFAULT_RRF_K)
+            .await
+    }
+
+    /// Runs a vector search on `self.vector()` and a full-text search on
+    /// `self.search()` concurrently, then fuses the two ranked lists with
+    /// Reciprocal Rank Fusion: for each document id, `score = Σ 1/(k +
+    /// rank_i)` over every result list it appears in, where `rank_i` is its
+    /// 0-based position in that list. A document found by only one backend
+    /// is still scored, just from that single list. The fused list is
+    /// sorted descending by score and truncated to `limit`; each returned
+    /// [`SearchResult`] carries the fused score plus the original
+    /// `vector_score`/`text_score` (whichever apply) in `metadata`.
+    pub async fn hybrid_search_with_k(
+        &self,
+        collection: &str,
+        query_text: &str,
+        query_vector: &[f32],
+        limit: usize,
+        k: f32,
+    ) -> Result<Vec<SearchResult>> {
+        let (vector_hits, text_hits) = tokio::try_join!(
+            self.vector.search(collection, query_vector, limit),
+            self.search.search(collection, query_text, limit),
+        )?;
+
+        let mut fused: HashMap<String, FusedHit> = HashMap::new();
+
+        for (rank, hit) in vector_hits.into_iter().enumerate() {
+            let entry = fused.entry(hit.id.clone()).or_insert_with(|| FusedHit {
+                id: hit.id.clone(),
+                content: hit.content.clone(),
+                rrf_score: 0.0,
+                vector_score: None,
+                text_score: None,
+            });
+            entry.rrf_score += 1.0 / (k + rank as f32);
+            entry.vector_score = Some(hit.score);
+        }
+
+        for (rank, hit) in text_hits.into_iter().enumerate() {
+            let entry = fused.entry(hit.id.clone()).or_insert_with(|| FusedHit {
+                id: hit.id.clone(),
+                content: Some(hit.content.clone()),
+                rrf_score: 0.0,
+                vector_score: None,
+                text_score: None,
+            });
+            entry.rrf_score += 1.0 / (k + rank as f32);
+            entry.text_score = Some(hit.score);
+            if entry.content.is_none() {
+                entry.content = Some(hit.content.clone());
+            }
+        }
+
+        let mut results: Vec<SearchResult> = fused
+            .into_values()
+            .map(|entry| {
+                let mut metadata = HashMap::new();
+                if let Some(score) = entry.vector_score {
+                    metadata.insert("vector_score".to_string(), serde_json::json!(score));
+                }
+                if let Some(score) = entry.text_score {
+                    metadata.insert("text_score".to_string(), serde_json::json!(score));
+                }
+                SearchResult {
+                    id: entry.id,
+                    score: entry.rrf_score,
+                    metadata,
+                    content: entry.content,
+                }
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
+    /// Pings every backend concurrently and reports per-backend health,
+    /// including latency and any error, rather than assuming a backend is
+    /// healthy just because it's configured.
     pub async fn health_check(&self) -> StoreHealth {
-        let relational_ok = self.relational.ping().await.is_ok();
+        let (relational, vector, cache, search) = tokio::join!(
+            Self::probe(self.relational.ping()),
+            Self::probe(self.vector.ping()),
+            Self::probe(self.cache.ping()),
+            Self::probe(self.search.ping()),
+        );
 
         StoreHealth {
-            relational: relational_ok,
-            vector: true,
-            cache: true,
-            search: true,
+            relational,
+            vector,
+            cache,
+            search,
         }
     }
+
+    async fn probe(fut: impl std::future::Future<Output = Result<()>>) -> BackendHealth {
+        let started = std::time::Instant::now();
+
+        match fut.await {
+            Ok(()) => BackendHealth {
+                healthy: true,
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                error: None,
+            },
+            Err(e) => BackendHealth {
+                healthy: false,
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Reconciles `collection` in this router's relational store against
+    /// `remote` via [`SyncEngine::sync_once`]: records this router has
+    /// written since the last sync are pushed to `remote`, and `remote`'s
+    /// new records are pulled in, last-writer-wins on conflicting keys. The
+    /// local side of the journal is keyed by `local_peer_id`, which should
+    /// stay stable across restarts so the persisted [`crate::sync::SyncState`]
+    /// cursor keeps applying.
+    pub async fn sync_once(
+        &self,
+        collection: &str,
+        local_peer_id: &str,
+        remote: &dyn Syncable,
+    ) -> Result<SyncReport> {
+        let local = RelationalSyncable::new(Arc::clone(&self.relational), collection, local_peer_id);
+        SyncEngine::sync_once(&local, remote).await
+    }
+
+    /// Writes the current vector store contents to disk if this router was
+    /// built with [`Self::local_persistent`]; a no-op otherwise (e.g. for
+    /// [`Self::local_default`] or a router assembled from caller-supplied
+    /// backends).
+    pub async fn flush_vector_snapshot(&self) -> Result<()> {
+        if let Some((store, path)) = &self.vector_snapshot {
+            store.save_snapshot(path).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The result of pinging a single backend via [`StoreRouter::health_check`].
+#[derive(Debug, Clone)]
+pub struct BackendHealth {
+    pub healthy: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct StoreHealth {
-    pub relational: bool,
-    pub vector: bool,
-    pub cache: bool,
-    pub search: bool,
+    pub relational: BackendHealth,
+    pub vector: BackendHealth,
+    pub cache: BackendHealth,
+    pub search: BackendHealth,
 }
 
 impl StoreHealth {
     pub fn is_healthy(&self) -> bool {
-        self.relational && self.vector && self.cache && self.search
+        self.relational.healthy && self.vector.healthy && self.cache.healthy && self.search.healthy
     }
 
     pub fn unhealthy_backends(&self) -> Vec<&'static str> {
         let mut unhealthy = Vec::new();
-        if !self.relational {
+        if !self.relational.healthy {
             unhealthy.push("relational");
         }
-        if !self.vector {
+        if !self.vector.healthy {
             unhealthy.push("vector");
         }
-        if !self.cache {
+        if !self.cache.healthy {
             unhealthy.push("cache");
         }
-        if !self.search {
+        if !self.search.healthy {
             unhealthy.push("search");
         }
         unhealthy
@@ -86,7 +259,7 @@ impl StoreHealth {
 #[cfg(all(feature = "sqlite", feature = "memory-cache"))]
 impl StoreRouter {
     pub fn local_default() -> Result<Self> {
-        use crate::local::{MemoryCache, MemoryVectorStore, SqliteFtsStore, SqliteStore};
+        use crate::local::{MemoryCache, SqliteFtsStore, SqliteStore};
 
         let sqlite = Arc::new(SqliteStore::memory()?);
         let vector = Arc::new(MemoryVectorStore::new());
@@ -98,11 +271,16 @@ impl StoreRouter {
             vector,
             cache,
             search,
+            vector_snapshot: None,
         })
     }
 
-    pub fn local_persistent(data_dir: &str) -> Result<Self> {
-        use crate::local::{MemoryCache, MemoryVectorStore, SqliteFtsStore, SqliteStore};
+    /// Like [`Self::local_default`], but persists to `data_dir`: the
+    /// relational store opens `hehe.db` there (as before), and the vector
+    /// store loads `vectors.json` from the same directory if present. Call
+    /// [`Self::flush_vector_snapshot`] before shutdown to write it back out.
+    pub async fn local_persistent(data_dir: &str) -> Result<Self> {
+        use crate::local::{MemoryCache, SqliteFtsStore, SqliteStore};
 
         std::fs::create_dir_all(data_dir)
             .map_err(|e| crate::error::StoreError::connection(e.to_string()))?;
@@ -113,11 +291,20 @@ impl StoreRouter {
         let cache = Arc::new(MemoryCache::new(10000));
         let search = Arc::new(SqliteFtsStore::new(Arc::clone(&sqlite)));
 
+        let vector_snapshot_path = format!("{}/vectors.json", data_dir);
+        if tokio::fs::try_exists(&vector_snapshot_path)
+            .await
+            .unwrap_or(false)
+        {
+            vector.load_snapshot(&vector_snapshot_path).await?;
+        }
+
         Ok(Self {
             relational: sqlite,
-            vector,
+            vector: vector.clone(),
             cache,
             search,
+            vector_snapshot: Some((vector, vector_snapshot_path)),
         })
     }
 }
@@ -145,5 +332,117 @@ mod tests {
 
         assert!(health.is_healthy());
         assert!(health.unhealthy_backends().is_empty());
+        assert!(health.relational.latency_ms.is_some());
+        assert!(health.relational.error.is_none());
+    }
+
+    #[cfg(all(feature = "sqlite", feature = "memory-cache"))]
+    #[tokio::test]
+    async fn test_hybrid_search_ranks_documents_found_by_both_backends_highest() {
+        use crate::traits::{Document, IndexSchema, VectorRecord};
+
+        let router = StoreRouter::local_default().unwrap();
+
+        router
+            .search()
+            .create_index("articles", &IndexSchema::new().add_text("content"))
+            .await
+            .unwrap();
+        router
+            .search()
+            .index_documents(
+                "articles",
+                &[
+                    Document::new("doc1", "rust programming language"),
+                    Document::new("doc2", "python programming language"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        router.vector().create_collection("articles", 2).await.unwrap();
+        router
+            .vector()
+            .upsert(
+                "articles",
+                &[
+                    VectorRecord::new("doc1", vec![1.0, 0.0]),
+                    VectorRecord::new("doc3", vec![0.9, 0.1]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let results = router
+            .hybrid_search("articles", "rust programming", &[1.0, 0.0], 10)
+            .await
+            .unwrap();
+
+        let doc1 = results.iter().find(|r| r.id == "doc1").unwrap();
+        let doc2 = results.iter().find(|r| r.id == "doc2").unwrap();
+        let doc3 = results.iter().find(|r| r.id == "doc3").unwrap();
+
+        assert!(doc1.score > doc2.score);
+        assert!(doc1.score > doc3.score);
+        assert!(doc1.metadata.contains_key("vector_score"));
+        assert!(doc1.metadata.contains_key("text_score"));
+        assert!(!doc2.metadata.contains_key("vector_score"));
+        assert!(!doc3.metadata.contains_key("text_score"));
+    }
+
+    #[cfg(all(feature = "sqlite", feature = "memory-cache"))]
+    #[tokio::test]
+    async fn test_hybrid_search_truncates_to_limit() {
+        use crate::traits::{Document, IndexSchema};
+
+        let router = StoreRouter::local_default().unwrap();
+
+        router
+            .search()
+            .create_index("notes", &IndexSchema::new().add_text("content"))
+            .await
+            .unwrap();
+        let docs: Vec<Document> = (0..5)
+            .map(|i| Document::new(format!("doc{i}"), "shared keyword"))
+            .collect();
+        router.search().index_documents("notes", &docs).await.unwrap();
+        router.vector().create_collection("notes", 1).await.unwrap();
+
+        let results = router
+            .hybrid_search("notes", "shared", &[1.0], 2)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[cfg(all(feature = "sqlite", feature = "memory-cache"))]
+    #[tokio::test]
+    async fn test_local_persistent_flushes_and_reloads_vector_snapshot() {
+        use crate::traits::VectorRecord;
+
+        let data_dir = std::env::temp_dir().join(format!("hehe-router-{}", hehe_core::Id::new()));
+        let data_dir = data_dir.to_str().unwrap().to_string();
+
+        let router = StoreRouter::local_persistent(&data_dir).await.unwrap();
+        router.vector().create_collection("docs", 2).await.unwrap();
+        router
+            .vector()
+            .upsert("docs", &[VectorRecord::new("id1", vec![1.0, 0.0])])
+            .await
+            .unwrap();
+        router.flush_vector_snapshot().await.unwrap();
+
+        let reopened = StoreRouter::local_persistent(&data_dir).await.unwrap();
+        assert_eq!(reopened.vector().count("docs").await.unwrap(), 1);
+
+        std::fs::remove_dir_all(&data_dir).ok();
+    }
+
+    #[cfg(all(feature = "sqlite", feature = "memory-cache"))]
+    #[tokio::test]
+    async fn test_flush_vector_snapshot_is_noop_for_local_default() {
+        let router = StoreRouter::local_default().unwrap();
+        router.flush_vector_snapshot().await.unwrap();
     }
 }
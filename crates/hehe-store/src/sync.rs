@@ -0,0 +1,501 @@
+use crate::error::{Result, StoreError};
+use crate::traits::{RelationalStore, Row};
+use async_trait::async_trait;
+use hehe_core::{Id, Timestamp};
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// One change recorded in a [`Syncable`]'s journal: either an upsert
+/// (`tombstone: false`, `payload` the new value) or a deletion
+/// (`tombstone: true`, `payload` is `Value::Null`). `id` is the UUIDv7
+/// assigned when the change was journaled — not necessarily anything the
+/// application uses as a primary key — so it doubles as a time-ordered sync
+/// cursor: "give me every change with `id` greater than the last one I've
+/// seen" is a single indexed range scan, not a full table diff.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncRecord {
+    pub id: Id,
+    pub key: String,
+    pub timestamp: Timestamp,
+    pub tombstone: bool,
+    pub payload: Value,
+}
+
+impl SyncRecord {
+    pub fn upsert(key: impl Into<String>, payload: Value) -> Self {
+        Self {
+            id: Id::new(),
+            key: key.into(),
+            timestamp: Timestamp::now(),
+            tombstone: false,
+            payload,
+        }
+    }
+
+    pub fn delete(key: impl Into<String>) -> Self {
+        Self {
+            id: Id::new(),
+            key: key.into(),
+            timestamp: Timestamp::now(),
+            tombstone: true,
+            payload: Value::Null,
+        }
+    }
+}
+
+/// How many of an `apply_changes` batch actually changed local state, versus
+/// were discarded as stale under last-writer-wins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ApplyReport {
+    pub applied: usize,
+    pub skipped_stale: usize,
+}
+
+/// Cursor + timestamp bookkeeping for one sync relationship with `peer_id`,
+/// persisted by [`Syncable::save_sync_state`] so a restart resumes an
+/// incremental sync instead of re-diffing everything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncState {
+    pub peer_id: String,
+    pub cursor: Option<Id>,
+    pub last_synced_at: Option<Timestamp>,
+}
+
+impl SyncState {
+    pub fn new(peer_id: impl Into<String>) -> Self {
+        Self {
+            peer_id: peer_id.into(),
+            cursor: None,
+            last_synced_at: None,
+        }
+    }
+}
+
+/// A journal that can report changes since a cursor and accept a peer's
+/// changes, resolving conflicts last-writer-wins by [`SyncRecord::timestamp`].
+/// Implemented here by [`RelationalSyncable`]; the same envelope generalizes
+/// to `VectorStore`/`CacheStore`/`SearchStore` by journaling their writes the
+/// same way and backing `sync_state` with the same table.
+#[async_trait]
+pub trait Syncable: Send + Sync {
+    /// Stable identity of this side of the sync relationship, stored as the
+    /// counterparty's `peer_id` in whichever side's [`SyncState`] refers to it.
+    fn peer_id(&self) -> &str;
+
+    /// Every change journaled after `cursor` (all of them, if `None`),
+    /// oldest first.
+    async fn changes_since(&self, cursor: Option<Id>) -> Result<Vec<SyncRecord>>;
+
+    /// Applies changes from a peer, keeping whichever side's record has the
+    /// newer `timestamp` for each `key`.
+    async fn apply_changes(&self, changes: &[SyncRecord]) -> Result<ApplyReport>;
+
+    /// The persisted [`SyncState`] for syncing against `peer`, or a fresh
+    /// one with `cursor: None` if this is the first sync.
+    async fn sync_state(&self, peer: &str) -> Result<SyncState>;
+
+    async fn save_sync_state(&self, state: &SyncState) -> Result<()>;
+}
+
+/// Result of [`SyncEngine::sync_once`]: how many changes crossed in each
+/// direction and what each side's [`Syncable::apply_changes`] did with them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub pulled: usize,
+    pub pushed: usize,
+    pub pull: ApplyReport,
+    pub push: ApplyReport,
+}
+
+/// Drives one incremental pull-then-push reconciliation between two
+/// [`Syncable`]s. Stateless itself — all cursor bookkeeping lives in each
+/// side's persisted [`SyncState`] — so it's just a pair of free functions
+/// bundled for discoverability.
+pub struct SyncEngine;
+
+impl SyncEngine {
+    /// Pulls `remote`'s changes since the last cursor we have for it into
+    /// `local`, then pushes `local`'s changes since the last cursor `remote`
+    /// has for it. Both legs use the same last-writer-wins merge via
+    /// [`Syncable::apply_changes`], so running this repeatedly (including
+    /// concurrently from both ends) converges rather than duplicating work.
+    pub async fn sync_once(local: &dyn Syncable, remote: &dyn Syncable) -> Result<SyncReport> {
+        let mut pull_state = local.sync_state(remote.peer_id()).await?;
+        let incoming = remote.changes_since(pull_state.cursor).await?;
+        let pulled = incoming.len();
+        let pull_report = local.apply_changes(&incoming).await?;
+        if let Some(max_id) = incoming.iter().map(|r| r.id).max() {
+            pull_state.cursor = Some(max_id);
+        }
+        pull_state.last_synced_at = Some(Timestamp::now());
+        local.save_sync_state(&pull_state).await?;
+
+        let mut push_state = remote.sync_state(local.peer_id()).await?;
+        let local_changes = local.changes_since(push_state.cursor).await?;
+        if let Some(max_id) = local_changes.iter().map(|r| r.id).max() {
+            push_state.cursor = Some(max_id);
+        }
+        // Records just pulled from `remote` are not sent back to it.
+        let outgoing: Vec<SyncRecord> = local_changes
+            .into_iter()
+            .filter(|r| !incoming.iter().any(|i| i.id == r.id))
+            .collect();
+        let pushed = outgoing.len();
+        let push_report = remote.apply_changes(&outgoing).await?;
+        push_state.last_synced_at = Some(Timestamp::now());
+        remote.save_sync_state(&push_state).await?;
+
+        Ok(SyncReport {
+            pulled,
+            pushed,
+            pull: pull_report,
+            push: push_report,
+        })
+    }
+}
+
+/// A [`Syncable`] journal for one named `collection`, backed by a
+/// [`RelationalStore`]: every [`Self::put`]/[`Self::tombstone`] appends a row
+/// keyed by a fresh UUIDv7 [`Id`], so [`Syncable::changes_since`] is a single
+/// `id > cursor` range scan and the latest value for a key is just its
+/// highest-`id` row.
+pub struct RelationalSyncable {
+    store: Arc<dyn RelationalStore>,
+    collection: String,
+    peer_id: String,
+}
+
+impl RelationalSyncable {
+    pub fn new(store: Arc<dyn RelationalStore>, collection: impl Into<String>, peer_id: impl Into<String>) -> Self {
+        Self {
+            store,
+            collection: collection.into(),
+            peer_id: peer_id.into(),
+        }
+    }
+
+    fn journal_table(&self) -> String {
+        format!("sync_journal_{}", self.collection)
+    }
+
+    async fn ensure_tables(&self) -> Result<()> {
+        self.store
+            .execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {} (
+                        id TEXT PRIMARY KEY,
+                        key TEXT NOT NULL,
+                        timestamp_ms INTEGER NOT NULL,
+                        tombstone INTEGER NOT NULL,
+                        payload TEXT NOT NULL
+                    )",
+                    self.journal_table()
+                ),
+                &[],
+            )
+            .await?;
+        self.store
+            .execute(
+                &format!(
+                    "CREATE INDEX IF NOT EXISTS idx_{}_key ON {} (key, id)",
+                    self.journal_table(),
+                    self.journal_table()
+                ),
+                &[],
+            )
+            .await?;
+        self.store
+            .execute(
+                "CREATE TABLE IF NOT EXISTS sync_state (
+                    collection TEXT NOT NULL,
+                    peer_id TEXT NOT NULL,
+                    cursor TEXT,
+                    last_synced_at_ms INTEGER,
+                    PRIMARY KEY (collection, peer_id)
+                )",
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Journals an upsert of `key` and returns the change's journal id.
+    pub async fn put(&self, key: impl Into<String>, payload: Value) -> Result<Id> {
+        self.append(SyncRecord::upsert(key, payload)).await
+    }
+
+    /// Journals a deletion of `key` (a tombstone) and returns the change's
+    /// journal id.
+    pub async fn tombstone(&self, key: impl Into<String>) -> Result<Id> {
+        self.append(SyncRecord::delete(key)).await
+    }
+
+    async fn append(&self, record: SyncRecord) -> Result<Id> {
+        self.ensure_tables().await?;
+        self.insert_record(&record).await?;
+        Ok(record.id)
+    }
+
+    async fn insert_record(&self, record: &SyncRecord) -> Result<()> {
+        let payload = serde_json::to_string(&record.payload).map_err(|e| StoreError::Serialization(e.to_string()))?;
+        self.store
+            .execute(
+                &format!(
+                    "INSERT OR IGNORE INTO {} (id, key, timestamp_ms, tombstone, payload)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    self.journal_table()
+                ),
+                &[
+                    Value::String(record.id.to_string()),
+                    Value::String(record.key.clone()),
+                    serde_json::json!(record.timestamp.unix_millis()),
+                    serde_json::json!(record.tombstone),
+                    Value::String(payload),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// The current value for `key`: its highest-`id` journal row, or `None`
+    /// if it was never written or its latest row is a tombstone.
+    pub async fn latest(&self, key: &str) -> Result<Option<SyncRecord>> {
+        self.ensure_tables().await?;
+        let row = self
+            .store
+            .query_one(
+                &format!(
+                    "SELECT id, key, timestamp_ms, tombstone, payload FROM {}
+                     WHERE key = ?1 ORDER BY id DESC LIMIT 1",
+                    self.journal_table()
+                ),
+                &[Value::String(key.to_string())],
+            )
+            .await?;
+
+        row.map(|row| Self::row_to_record(&row)).transpose()
+    }
+
+    fn row_to_record(row: &Row) -> Result<SyncRecord> {
+        let id = Id::from_str(row.get_str("id").unwrap_or_default())
+            .map_err(|e| StoreError::internal(format!("corrupt sync journal row: bad id ({e})")))?;
+        let key = row.get_str("key").unwrap_or_default().to_string();
+        let timestamp = Timestamp::from_unix_millis(row.get_i64("timestamp_ms").unwrap_or_default())
+            .ok_or_else(|| StoreError::internal("corrupt sync journal row: bad timestamp"))?;
+        let tombstone = row.get_bool("tombstone").unwrap_or(false);
+        let payload = row
+            .get_str("payload")
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| StoreError::Serialization(e.to_string()))?
+            .unwrap_or(Value::Null);
+
+        Ok(SyncRecord {
+            id,
+            key,
+            timestamp,
+            tombstone,
+            payload,
+        })
+    }
+}
+
+#[async_trait]
+impl Syncable for RelationalSyncable {
+    fn peer_id(&self) -> &str {
+        &self.peer_id
+    }
+
+    async fn changes_since(&self, cursor: Option<Id>) -> Result<Vec<SyncRecord>> {
+        self.ensure_tables().await?;
+        let table = self.journal_table();
+        let rows = match cursor {
+            Some(cursor) => {
+                self.store
+                    .query(
+                        &format!("SELECT id, key, timestamp_ms, tombstone, payload FROM {table} WHERE id > ?1 ORDER BY id ASC"),
+                        &[Value::String(cursor.to_string())],
+                    )
+                    .await?
+            }
+            None => {
+                self.store
+                    .query(
+                        &format!("SELECT id, key, timestamp_ms, tombstone, payload FROM {table} ORDER BY id ASC"),
+                        &[],
+                    )
+                    .await?
+            }
+        };
+
+        rows.iter().map(Self::row_to_record).collect()
+    }
+
+    async fn apply_changes(&self, changes: &[SyncRecord]) -> Result<ApplyReport> {
+        self.ensure_tables().await?;
+        let mut report = ApplyReport::default();
+
+        for change in changes {
+            let existing = self.latest(&change.key).await?;
+            let is_newer = existing.as_ref().map(|e| change.timestamp >= e.timestamp).unwrap_or(true);
+
+            if is_newer {
+                self.insert_record(change).await?;
+                report.applied += 1;
+            } else {
+                report.skipped_stale += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn sync_state(&self, peer: &str) -> Result<SyncState> {
+        self.ensure_tables().await?;
+        let row = self
+            .store
+            .query_one(
+                "SELECT cursor, last_synced_at_ms FROM sync_state WHERE collection = ?1 AND peer_id = ?2",
+                &[Value::String(self.collection.clone()), Value::String(peer.to_string())],
+            )
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(SyncState::new(peer));
+        };
+
+        let cursor = row
+            .get_str("cursor")
+            .map(Id::from_str)
+            .transpose()
+            .map_err(|e| StoreError::internal(format!("corrupt sync state: bad cursor ({e})")))?;
+        let last_synced_at = row.get_i64("last_synced_at_ms").and_then(Timestamp::from_unix_millis);
+
+        Ok(SyncState {
+            peer_id: peer.to_string(),
+            cursor,
+            last_synced_at,
+        })
+    }
+
+    async fn save_sync_state(&self, state: &SyncState) -> Result<()> {
+        self.ensure_tables().await?;
+        self.store
+            .execute(
+                "INSERT INTO sync_state (collection, peer_id, cursor, last_synced_at_ms) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(collection, peer_id) DO UPDATE SET cursor = excluded.cursor, last_synced_at_ms = excluded.last_synced_at_ms",
+                &[
+                    Value::String(self.collection.clone()),
+                    Value::String(state.peer_id.clone()),
+                    state
+                        .cursor
+                        .map(|id| Value::String(id.to_string()))
+                        .unwrap_or(Value::Null),
+                    state
+                        .last_synced_at
+                        .map(|ts| serde_json::json!(ts.unix_millis()))
+                        .unwrap_or(Value::Null),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::local::SqliteStore;
+
+    fn syncable(collection: &str, peer_id: &str) -> RelationalSyncable {
+        RelationalSyncable::new(Arc::new(SqliteStore::memory().unwrap()), collection, peer_id)
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_returns_only_newer_rows() {
+        let local = syncable("notes", "local");
+        let first = local.put("a", serde_json::json!("1")).await.unwrap();
+        local.put("b", serde_json::json!("2")).await.unwrap();
+
+        let since_start = local.changes_since(None).await.unwrap();
+        assert_eq!(since_start.len(), 2);
+
+        let since_first = local.changes_since(Some(first)).await.unwrap();
+        assert_eq!(since_first.len(), 1);
+        assert_eq!(since_first[0].key, "b");
+    }
+
+    #[tokio::test]
+    async fn test_latest_reflects_tombstone() {
+        let local = syncable("notes", "local");
+        local.put("a", serde_json::json!("1")).await.unwrap();
+        local.tombstone("a").await.unwrap();
+
+        let latest = local.latest("a").await.unwrap().unwrap();
+        assert!(latest.tombstone);
+    }
+
+    #[tokio::test]
+    async fn test_apply_changes_rejects_stale_writes() {
+        let local = syncable("notes", "local");
+        local.put("a", serde_json::json!("fresh")).await.unwrap();
+
+        let stale = SyncRecord {
+            id: Id::new(),
+            key: "a".to_string(),
+            timestamp: Timestamp::from_unix_millis(0).unwrap(),
+            tombstone: false,
+            payload: serde_json::json!("stale"),
+        };
+
+        let report = local.apply_changes(&[stale]).await.unwrap();
+        assert_eq!(report, ApplyReport { applied: 0, skipped_stale: 1 });
+        assert_eq!(local.latest("a").await.unwrap().unwrap().payload, serde_json::json!("fresh"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_once_reconciles_both_directions() {
+        let local = syncable("notes", "local-peer");
+        let remote = syncable("notes", "remote-peer");
+
+        local.put("from-local", serde_json::json!("a")).await.unwrap();
+        remote.put("from-remote", serde_json::json!("b")).await.unwrap();
+
+        let report = SyncEngine::sync_once(&local, &remote).await.unwrap();
+        assert_eq!(report.pulled, 1);
+        assert_eq!(report.pushed, 1);
+
+        assert_eq!(
+            local.latest("from-remote").await.unwrap().unwrap().payload,
+            serde_json::json!("b")
+        );
+        assert_eq!(
+            remote.latest("from-local").await.unwrap().unwrap().payload,
+            serde_json::json!("a")
+        );
+
+        // A second pass with no new writes should be a no-op.
+        let second = SyncEngine::sync_once(&local, &remote).await.unwrap();
+        assert_eq!(second.pulled, 0);
+        assert_eq!(second.pushed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_once_last_writer_wins_on_conflicting_key() {
+        let local = syncable("notes", "local-peer");
+        let remote = syncable("notes", "remote-peer");
+
+        local.put("shared", serde_json::json!("local-write")).await.unwrap();
+        remote.put("shared", serde_json::json!("remote-write")).await.unwrap();
+
+        SyncEngine::sync_once(&local, &remote).await.unwrap();
+
+        // Both sides converge on whichever write has the later timestamp;
+        // since `remote` wrote after `local` here, its value should win.
+        assert_eq!(
+            local.latest("shared").await.unwrap().unwrap().payload,
+            remote.latest("shared").await.unwrap().unwrap().payload
+        );
+    }
+}
@@ -45,6 +45,12 @@ pub trait CacheStore: Send + Sync {
 
     async fn len(&self) -> Result<usize>;
 
+    /// Verifies the backend is reachable and responsive. The default
+    /// implementation just reads the current size.
+    async fn ping(&self) -> Result<()> {
+        self.len().await.map(|_| ())
+    }
+
     fn backend_name(&self) -> &'static str;
 }
 
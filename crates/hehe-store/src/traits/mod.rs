@@ -4,9 +4,13 @@ mod search;
 pub mod vector;
 
 pub use cache::CacheStore;
-pub use relational::{Migration, RelationalStore, Row, Transaction};
-pub use search::{Document, IndexField, IndexFieldType, IndexSchema, SearchFilter, SearchHit, SearchStore};
+pub use relational::{FromColumn, FromRow, Migration, RelationalStore, RelationalStoreExt, Row, Transaction};
+pub use search::{
+    Document, FacetSearchResult, HighlightOptions, IndexField, IndexFieldType, IndexSchema,
+    SearchCondition, SearchFilter, SearchHit, SearchStore,
+};
 pub use vector::{
-    cosine_similarity, euclidean_distance, CollectionInfo, FilterCondition, SearchResult, VectorFilter,
+    cosine_similarity, decode_order_key, dot_product, encode_order_key, euclidean_distance, BoxStream,
+    CollectionInfo, DistanceMetric, Embedder, FilterCondition, FilterExpr, SearchResult, VectorFilter,
     VectorRecord, VectorStore,
 };
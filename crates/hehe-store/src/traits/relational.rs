@@ -1,4 +1,4 @@
-use crate::error::Result;
+use crate::error::{Result, StoreError};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -76,6 +76,17 @@ pub trait Transaction: Send {
     async fn execute(&mut self, sql: &str, params: &[Value]) -> Result<u64>;
     async fn query(&mut self, sql: &str, params: &[Value]) -> Result<Vec<Row>>;
     async fn query_one(&mut self, sql: &str, params: &[Value]) -> Result<Option<Row>>;
+
+    /// Starts a transaction nested inside this one, typically a SQL
+    /// `SAVEPOINT` rather than a second `BEGIN`. Committing the returned
+    /// handle releases just that savepoint; rolling it back undoes only its
+    /// statements, leaving this transaction free to commit or roll back
+    /// further work of its own. Backends that can't nest report that
+    /// through this default rather than implementing it.
+    async fn begin(&mut self) -> Result<Box<dyn Transaction>> {
+        Err(StoreError::internal("nested transactions are not supported by this backend"))
+    }
+
     async fn commit(self: Box<Self>) -> Result<()>;
     async fn rollback(self: Box<Self>) -> Result<()>;
 }
@@ -106,6 +117,121 @@ pub trait RelationalStore: Send + Sync {
     fn backend_name(&self) -> &'static str;
 }
 
+/// Typed row extraction on top of [`RelationalStore`]. Kept as a separate,
+/// blanket-implemented trait rather than methods on `RelationalStore` itself
+/// because `query_as`'s generic type parameter would make `RelationalStore`
+/// unusable as `dyn RelationalStore` (already relied on throughout the
+/// workspace, e.g. `hehe-server`'s event log).
+#[async_trait]
+pub trait RelationalStoreExt: RelationalStore {
+    /// Runs `query` and converts every row with [`FromRow`], so callers who
+    /// know their schema can skip `Row::get_i64`-style stringly-typed pulls.
+    async fn query_as<T: FromRow + Send>(&self, sql: &str, params: &[Value]) -> Result<Vec<T>> {
+        let rows = self.query(sql, params).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+
+    /// Like [`Self::query_as`], but for `query_one`.
+    async fn query_one_as<T: FromRow + Send>(&self, sql: &str, params: &[Value]) -> Result<Option<T>> {
+        match self.query_one(sql, params).await? {
+            Some(row) => Ok(Some(T::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: RelationalStore + ?Sized> RelationalStoreExt for T {}
+
+/// Extracts a single column's value into a concrete Rust type. The
+/// [`FromRow`] tuple impls below pull each of their elements out this way,
+/// by position, matching the JSON shapes [`Row`] actually carries (strings,
+/// integers, floats, and the null case nested structs use `Option` for).
+pub trait FromColumn: Sized {
+    fn from_column(value: &Value) -> Result<Self>;
+}
+
+impl FromColumn for i64 {
+    fn from_column(value: &Value) -> Result<Self> {
+        value
+            .as_i64()
+            .ok_or_else(|| StoreError::Query(format!("expected an integer column, got {value}")))
+    }
+}
+
+impl FromColumn for f64 {
+    fn from_column(value: &Value) -> Result<Self> {
+        value
+            .as_f64()
+            .ok_or_else(|| StoreError::Query(format!("expected a numeric column, got {value}")))
+    }
+}
+
+impl FromColumn for bool {
+    fn from_column(value: &Value) -> Result<Self> {
+        value
+            .as_bool()
+            .ok_or_else(|| StoreError::Query(format!("expected a boolean column, got {value}")))
+    }
+}
+
+impl FromColumn for String {
+    fn from_column(value: &Value) -> Result<Self> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| StoreError::Query(format!("expected a text column, got {value}")))
+    }
+}
+
+impl<T: FromColumn> FromColumn for Option<T> {
+    fn from_column(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(T::from_column(value)?))
+        }
+    }
+}
+
+/// Converts a generic [`Row`] into a concrete type. Implemented here for
+/// tuples up to arity 12 whose elements all implement [`FromColumn`]; a
+/// user-defined struct can implement it by hand the same way. See
+/// [`RelationalStoreExt::query_as`].
+pub trait FromRow: Sized {
+    fn from_row(row: &Row) -> Result<Self>;
+}
+
+macro_rules! column {
+    ($row:ident, $idx:tt, $ty:ident) => {
+        $ty::from_column($row.values.get($idx).ok_or_else(|| {
+            StoreError::Query(format!("row has no column at index {}", $idx))
+        })?)?
+    };
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($($idx:tt => $ty:ident),+) => {
+        impl<$($ty: FromColumn),+> FromRow for ($($ty,)+) {
+            fn from_row(row: &Row) -> Result<Self> {
+                Ok(($(column!(row, $idx, $ty),)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(0 => A);
+impl_from_row_for_tuple!(0 => A, 1 => B);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K, 11 => L);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +274,28 @@ mod tests {
         assert_eq!(m.name, "create_users");
         assert!(m.down.is_some());
     }
+
+    #[test]
+    fn test_from_row_tuple() {
+        let row = Row::new(
+            vec!["id".into(), "name".into(), "email".into()],
+            vec![
+                Value::Number(1.into()),
+                Value::String("alice".into()),
+                Value::Null,
+            ],
+        );
+
+        let (id, name, email): (i64, String, Option<String>) = FromRow::from_row(&row).unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(name, "alice");
+        assert_eq!(email, None);
+    }
+
+    #[test]
+    fn test_from_row_wrong_type_is_an_error() {
+        let row = Row::new(vec!["id".into()], vec![Value::String("not a number".into())]);
+        let result: Result<(i64,)> = FromRow::from_row(&row);
+        assert!(result.is_err());
+    }
 }
@@ -72,25 +72,82 @@ impl SearchFilter {
         self
     }
 
+    pub fn in_list(mut self, field: impl Into<String>, values: Vec<Value>) -> Self {
+        self.conditions
+            .push(SearchCondition::In(field.into(), values));
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         self.conditions.is_empty()
     }
 }
 
+/// Controls how [`SearchStore::search_with_filter`] highlights matched terms,
+/// mirroring FTS5's `highlight()`/`snippet()` auxiliary functions so callers get
+/// MeiliSearch-like `_formatted` output without post-processing raw content.
 #[derive(Clone, Debug)]
+pub struct HighlightOptions {
+    pub open_tag: String,
+    pub close_tag: String,
+    /// Number of tokens to include around each match when cropping (ignored
+    /// when [`HighlightOptions::crop_to_snippet`] is `false`).
+    pub snippet_tokens: u32,
+    /// `true` wraps matches within a cropped snippet (FTS5 `snippet()`);
+    /// `false` wraps matches within the full content (FTS5 `highlight()`).
+    pub crop_to_snippet: bool,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            open_tag: "<mark>".to_string(),
+            close_tag: "</mark>".to_string(),
+            snippet_tokens: 32,
+            crop_to_snippet: true,
+        }
+    }
+}
+
+impl HighlightOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tags(mut self, open: impl Into<String>, close: impl Into<String>) -> Self {
+        self.open_tag = open.into();
+        self.close_tag = close.into();
+        self
+    }
+
+    pub fn with_snippet_tokens(mut self, tokens: u32) -> Self {
+        self.snippet_tokens = tokens;
+        self
+    }
+
+    pub fn with_full_content(mut self) -> Self {
+        self.crop_to_snippet = false;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IndexSchema {
     pub fields: Vec<IndexField>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IndexField {
     pub name: String,
     pub field_type: IndexFieldType,
     pub stored: bool,
     pub indexed: bool,
+    /// Relative BM25 ranking weight for this field, e.g. `2.0` to rank matches
+    /// in a `title` field above matches in a `body` field. Defaults to `1.0`.
+    pub weight: f32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum IndexFieldType {
     Text,
     Keyword,
@@ -111,6 +168,7 @@ impl IndexSchema {
             field_type: IndexFieldType::Text,
             stored: true,
             indexed: true,
+            weight: 1.0,
         });
         self
     }
@@ -121,6 +179,7 @@ impl IndexSchema {
             field_type: IndexFieldType::Keyword,
             stored: true,
             indexed: true,
+            weight: 1.0,
         });
         self
     }
@@ -131,9 +190,20 @@ impl IndexSchema {
             field_type: IndexFieldType::Integer,
             stored: true,
             indexed: true,
+            weight: 1.0,
         });
         self
     }
+
+    /// Set the BM25 ranking weight of the most recently added field, e.g.
+    /// `IndexSchema::new().add_text("title").weight(2.0).add_text("body")`
+    /// ranks matches in `title` twice as heavily as matches in `body`.
+    pub fn weight(mut self, weight: f32) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.weight = weight;
+        }
+        self
+    }
 }
 
 impl Default for IndexSchema {
@@ -142,6 +212,15 @@ impl Default for IndexSchema {
     }
 }
 
+/// The result of [`SearchStore::facet_search`]: the matching hits, plus a
+/// per-facet count of each distinct value among them, mirroring
+/// MeiliSearch's `facetDistribution` (e.g. `{"category": {"tech": 12, "sports": 3}}`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FacetSearchResult {
+    pub hits: Vec<SearchHit>,
+    pub facet_distribution: HashMap<String, HashMap<String, usize>>,
+}
+
 #[async_trait]
 pub trait SearchStore: Send + Sync {
     async fn create_index(&self, name: &str, schema: &IndexSchema) -> Result<()>;
@@ -163,11 +242,30 @@ pub trait SearchStore: Send + Sync {
         index: &str,
         query: &str,
         filter: &SearchFilter,
+        highlight: Option<&HighlightOptions>,
         limit: usize,
     ) -> Result<Vec<SearchHit>>;
 
+    /// Like [`Self::search_with_filter`], but additionally computes a count
+    /// of each distinct value of every field in `facets` among the matching
+    /// documents (not just the returned page of `hits`).
+    async fn facet_search(
+        &self,
+        index: &str,
+        query: &str,
+        filter: &SearchFilter,
+        facets: &[String],
+        limit: usize,
+    ) -> Result<FacetSearchResult>;
+
     async fn count(&self, index: &str) -> Result<usize>;
 
+    /// Verifies the backend is reachable and responsive. The default
+    /// implementation just lists indexes.
+    async fn ping(&self) -> Result<()> {
+        self.list_indexes().await.map(|_| ())
+    }
+
     fn backend_name(&self) -> &'static str;
 }
 
@@ -196,6 +294,17 @@ mod tests {
         assert_eq!(schema.fields.len(), 3);
     }
 
+    #[test]
+    fn test_index_schema_weight_applies_to_last_added_field() {
+        let schema = IndexSchema::new()
+            .add_text("title")
+            .weight(2.0)
+            .add_text("body");
+
+        assert_eq!(schema.fields[0].weight, 2.0);
+        assert_eq!(schema.fields[1].weight, 1.0);
+    }
+
     #[test]
     fn test_search_filter() {
         let filter = SearchFilter::new()
@@ -204,4 +313,22 @@ mod tests {
 
         assert_eq!(filter.conditions.len(), 2);
     }
+
+    #[test]
+    fn test_facet_search_result_defaults_to_empty() {
+        let result = FacetSearchResult::default();
+        assert!(result.hits.is_empty());
+        assert!(result.facet_distribution.is_empty());
+    }
+
+    #[test]
+    fn test_highlight_options_defaults_to_cropped_snippet() {
+        let opts = HighlightOptions::new();
+        assert_eq!(opts.open_tag, "<mark>");
+        assert!(opts.crop_to_snippet);
+
+        let opts = opts.with_tags("[", "]").with_full_content();
+        assert_eq!(opts.open_tag, "[");
+        assert!(!opts.crop_to_snippet);
+    }
 }
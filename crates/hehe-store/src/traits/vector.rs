@@ -1,8 +1,33 @@
 use crate::error::Result;
 use async_trait::async_trait;
+use futures::stream::StreamExt;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::pin::Pin;
+
+/// An owned, boxed stream of fallible items, mirroring `hehe_llm`'s
+/// `BoxStream` so async iteration reads the same way across crates.
+pub type BoxStream<T> = Pin<Box<dyn Stream<Item = Result<T>> + Send>>;
+
+/// Records per [`VectorStore::import_from`] batch upsert call.
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Default Reciprocal Rank Fusion constant used by [`VectorStore::hybrid_search`].
+/// Larger values flatten the influence of rank differences between results.
+const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Default weight given to the vector-ranked list in
+/// [`VectorStore::hybrid_search`]; `0.5` weighs semantic and keyword results
+/// equally. See [`VectorStore::hybrid_search_with_params`].
+const DEFAULT_HYBRID_ALPHA: f32 = 0.5;
+
+/// BM25 free parameters used by the brute-force lexical scan in
+/// [`VectorStore::hybrid_search_with_params`]. These are the standard
+/// Okapi BM25 defaults.
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VectorRecord {
@@ -45,11 +70,6 @@ pub struct SearchResult {
     pub content: Option<String>,
 }
 
-#[derive(Clone, Debug, Default)]
-pub struct VectorFilter {
-    pub conditions: Vec<FilterCondition>,
-}
-
 #[derive(Clone, Debug)]
 pub enum FilterCondition {
     Eq(String, Value),
@@ -62,43 +82,224 @@ pub enum FilterCondition {
     Contains(String, String),
 }
 
+/// A node in a [`VectorFilter`]'s predicate tree. `Leaf` is a single
+/// [`FilterCondition`]; `And`/`Or` combine their children with the
+/// corresponding boolean operator (an empty `And`/`Or` evaluates to `true`/
+/// `false` respectively, so normalization can drop empty groups without
+/// changing meaning); `Not` inverts its single child.
+#[derive(Clone, Debug)]
+pub enum FilterExpr {
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Leaf(FilterCondition),
+}
+
+impl FilterExpr {
+    fn evaluate(&self, metadata: &HashMap<String, Value>) -> bool {
+        match self {
+            Self::And(children) => children.iter().all(|c| c.evaluate(metadata)),
+            Self::Or(children) => children.iter().any(|c| c.evaluate(metadata)),
+            Self::Not(inner) => !inner.evaluate(metadata),
+            Self::Leaf(condition) => evaluate_condition(metadata, condition),
+        }
+    }
+
+    /// Collapses nested same-kind `And`/`Or` nodes (`And[And[a,b],c] ->
+    /// And[a,b,c]`) and drops empty groups, so repeated programmatic merges
+    /// of filters (e.g. [`VectorFilter::or`]/[`VectorFilter::group`]) don't
+    /// build up deeply nested redundant trees.
+    fn normalize(self) -> Self {
+        match self {
+            Self::And(children) => {
+                let flat: Vec<FilterExpr> = children
+                    .into_iter()
+                    .map(FilterExpr::normalize)
+                    .flat_map(|child| match child {
+                        Self::And(inner) => inner,
+                        other => vec![other],
+                    })
+                    .collect();
+                match flat.len() {
+                    1 => flat.into_iter().next().unwrap(),
+                    _ => Self::And(flat),
+                }
+            }
+            Self::Or(children) => {
+                let flat: Vec<FilterExpr> = children
+                    .into_iter()
+                    .map(FilterExpr::normalize)
+                    .flat_map(|child| match child {
+                        Self::Or(inner) => inner,
+                        other => vec![other],
+                    })
+                    .collect();
+                match flat.len() {
+                    1 => flat.into_iter().next().unwrap(),
+                    _ => Self::Or(flat),
+                }
+            }
+            Self::Not(inner) => Self::Not(Box::new(inner.normalize())),
+            leaf @ Self::Leaf(_) => leaf,
+        }
+    }
+}
+
+fn evaluate_condition(metadata: &HashMap<String, Value>, condition: &FilterCondition) -> bool {
+    match condition {
+        FilterCondition::Eq(field, value) => metadata.get(field).map(|v| v == value).unwrap_or(false),
+        FilterCondition::Ne(field, value) => metadata.get(field).map(|v| v != value).unwrap_or(true),
+        FilterCondition::Gt(field, value) => match (metadata.get(field), value) {
+            (Some(Value::Number(a)), Value::Number(b)) => a.as_f64().unwrap_or(0.0) > b.as_f64().unwrap_or(0.0),
+            _ => false,
+        },
+        FilterCondition::Gte(field, value) => match (metadata.get(field), value) {
+            (Some(Value::Number(a)), Value::Number(b)) => a.as_f64().unwrap_or(0.0) >= b.as_f64().unwrap_or(0.0),
+            _ => false,
+        },
+        FilterCondition::Lt(field, value) => match (metadata.get(field), value) {
+            (Some(Value::Number(a)), Value::Number(b)) => a.as_f64().unwrap_or(0.0) < b.as_f64().unwrap_or(0.0),
+            _ => false,
+        },
+        FilterCondition::Lte(field, value) => match (metadata.get(field), value) {
+            (Some(Value::Number(a)), Value::Number(b)) => a.as_f64().unwrap_or(0.0) <= b.as_f64().unwrap_or(0.0),
+            _ => false,
+        },
+        FilterCondition::In(field, values) => metadata.get(field).map(|v| values.contains(v)).unwrap_or(false),
+        FilterCondition::Contains(field, substr) => metadata
+            .get(field)
+            .and_then(|v| v.as_str())
+            .map(|s| s.contains(substr.as_str()))
+            .unwrap_or(false),
+    }
+}
+
+/// A metadata predicate tree over a [`VectorRecord`]'s `metadata`, matched
+/// with [`Self::evaluate`]. The fluent `eq`/`ne`/`gt`/`lt`/`contains`
+/// methods build an implicit all-AND filter exactly as before; `or`,
+/// `not`, and `group` add boolean composition on top, normalizing the tree
+/// ([`FilterExpr::normalize`]) after every merge.
+#[derive(Clone, Debug, Default)]
+pub struct VectorFilter {
+    expr: Option<FilterExpr>,
+}
+
 impl VectorFilter {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn eq(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
-        self.conditions
-            .push(FilterCondition::Eq(field.into(), value.into()));
+    fn and_node(mut self, node: FilterExpr) -> Self {
+        self.expr = Some(
+            match self.expr.take() {
+                None => node,
+                Some(existing) => FilterExpr::And(vec![existing, node]),
+            }
+            .normalize(),
+        );
         self
     }
 
-    pub fn ne(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
-        self.conditions
-            .push(FilterCondition::Ne(field.into(), value.into()));
-        self
+    fn and_leaf(self, condition: FilterCondition) -> Self {
+        self.and_node(FilterExpr::Leaf(condition))
     }
 
-    pub fn gt(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
-        self.conditions
-            .push(FilterCondition::Gt(field.into(), value.into()));
-        self
+    pub fn eq(self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.and_leaf(FilterCondition::Eq(field.into(), value.into()))
+    }
+
+    pub fn ne(self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.and_leaf(FilterCondition::Ne(field.into(), value.into()))
     }
 
-    pub fn lt(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
-        self.conditions
-            .push(FilterCondition::Lt(field.into(), value.into()));
+    pub fn gt(self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.and_leaf(FilterCondition::Gt(field.into(), value.into()))
+    }
+
+    pub fn lt(self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.and_leaf(FilterCondition::Lt(field.into(), value.into()))
+    }
+
+    pub fn contains(self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.and_leaf(FilterCondition::Contains(field.into(), value.into()))
+    }
+
+    /// ORs `self` with `other`. If either side is empty, the other side is
+    /// returned unchanged: an empty side is treated as absent rather than
+    /// as a match-everything operand, so the result matches only what the
+    /// non-empty side matches.
+    pub fn or(mut self, other: Self) -> Self {
+        self.expr = match (self.expr.take(), other.expr) {
+            (None, other) => other,
+            (existing, None) => existing,
+            (Some(a), Some(b)) => Some(FilterExpr::Or(vec![a, b]).normalize()),
+        };
         self
     }
 
-    pub fn contains(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
-        self.conditions
-            .push(FilterCondition::Contains(field.into(), value.into()));
+    /// Negates the whole filter built so far. Negating an empty filter is a
+    /// no-op, since there is no predicate to invert.
+    pub fn not(mut self) -> Self {
+        self.expr = self.expr.map(|e| FilterExpr::Not(Box::new(e)).normalize());
         self
     }
 
+    /// ANDs a nested, independently-built sub-filter onto `self`, the
+    /// mechanism for grouping (e.g. `base.group(VectorFilter::new().eq("a",
+    /// 1).or(VectorFilter::new().eq("b", 2)))` for `base AND (a=1 OR
+    /// b=2)`). An empty `inner` is a no-op.
+    pub fn group(self, inner: Self) -> Self {
+        match inner.expr {
+            None => self,
+            Some(node) => self.and_node(node),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.conditions.is_empty()
+        self.expr.is_none()
+    }
+
+    /// Matches `metadata` against this filter's predicate tree. An empty
+    /// filter matches everything.
+    pub fn evaluate(&self, metadata: &HashMap<String, Value>) -> bool {
+        self.expr.as_ref().map(|e| e.evaluate(metadata)).unwrap_or(true)
+    }
+
+    /// Returns the single leaf condition this filter boils down to, if it
+    /// is nothing more than one `eq`/`ne`/.../`contains` call (no `or`/`not`/
+    /// `group` composition). Backends use this to recognize the common case
+    /// where a sorted field index (see [`encode_order_key`]) can answer the
+    /// query with a seek instead of a full scan; anything more composed
+    /// falls back to [`Self::evaluate`] per record.
+    pub(crate) fn as_single_condition(&self) -> Option<&FilterCondition> {
+        match &self.expr {
+            Some(FilterExpr::Leaf(condition)) => Some(condition),
+            _ => None,
+        }
+    }
+}
+
+/// The distance function a collection scores vectors with. Embedding models
+/// are trained against one of these, and search quality degrades if the
+/// store doesn't match it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMetric {
+    #[default]
+    Cosine,
+    DotProduct,
+    EuclideanL2,
+}
+
+impl DistanceMetric {
+    /// Scores `a` against `b` under this metric, always higher-is-better
+    /// regardless of which metric is configured.
+    pub fn score(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => cosine_similarity(a, b),
+            Self::DotProduct => dot_product(a, b),
+            Self::EuclideanL2 => 1.0 / (1.0 + euclidean_distance(a, b)),
+        }
     }
 }
 
@@ -107,6 +308,7 @@ pub struct CollectionInfo {
     pub name: String,
     pub dimension: usize,
     pub count: usize,
+    pub metric: DistanceMetric,
 }
 
 #[async_trait]
@@ -142,9 +344,268 @@ pub trait VectorStore: Send + Sync {
 
     async fn count(&self, collection: &str) -> Result<usize>;
 
+    /// Verifies the backend is reachable and responsive. The default
+    /// implementation lists collections, which for an in-process store like
+    /// [`crate::local::MemoryVectorStore`] also proves its lock is
+    /// acquirable.
+    async fn ping(&self) -> Result<()> {
+        self.list_collections().await.map(|_| ())
+    }
+
+    /// Streams every record in `collection`, backend by backend, so callers
+    /// can migrate data without a bespoke export format per pair of
+    /// backends. See [`Self::import_from`].
+    async fn export(&self, collection: &str) -> Result<BoxStream<VectorRecord>>;
+
+    /// Copies every record `source` has for `collection` into `self` via
+    /// [`Self::export`] and [`Self::upsert`], batching
+    /// [`IMPORT_BATCH_SIZE`] records per upsert call. `collection` must
+    /// already exist on `self` with a matching dimension; this default
+    /// implementation never creates it. Returns the number of records
+    /// copied.
+    async fn import_from(&self, source: &dyn VectorStore, collection: &str) -> Result<usize> {
+        let mut stream = source.export(collection).await?;
+        let mut total = 0;
+        let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+
+        while let Some(record) = stream.next().await {
+            batch.push(record?);
+            if batch.len() >= IMPORT_BATCH_SIZE {
+                total += self.upsert(collection, &batch).await?;
+                batch.clear();
+            }
+        }
+
+        if !batch.is_empty() {
+            total += self.upsert(collection, &batch).await?;
+        }
+
+        Ok(total)
+    }
+
+    /// Combines semantic vector search with a brute-force lexical (BM25)
+    /// scan over every record's `content` field, fused via Reciprocal Rank
+    /// Fusion with the default `k` of [`DEFAULT_RRF_K`] and an even
+    /// `alpha` of [`DEFAULT_HYBRID_ALPHA`]. See
+    /// [`Self::hybrid_search_with_params`] for the scoring details.
+    async fn hybrid_search(
+        &self,
+        collection: &str,
+        query_text: &str,
+        query_vector: &[f32],
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        self.hybrid_search_with_params(
+            collection,
+            query_text,
+            query_vector,
+            limit,
+            DEFAULT_RRF_K,
+            DEFAULT_HYBRID_ALPHA,
+        )
+        .await
+    }
+
+    /// Runs [`Self::search`] for `query_vector` and a brute-force BM25 scan
+    /// over `content` for `query_text`, then fuses the two ranked lists
+    /// with Reciprocal Rank Fusion: for each record id, `score = Σ alpha_i /
+    /// (k + rank_i)` over every list it appears in at 1-based `rank_i`,
+    /// where `alpha_i` is `alpha` for the vector list and `1.0 - alpha` for
+    /// the lexical list. A record found by only one list is still scored,
+    /// just from that single list. The fused list is sorted descending by
+    /// score and truncated to `limit`; each returned [`SearchResult`]
+    /// carries the fused score plus the original `vector_score`/
+    /// `text_score` (whichever apply) in `metadata`, so callers can see why
+    /// a result ranked where it did. The lexical scan is brute-force via
+    /// [`Self::export`] — backends with their own full-text index should
+    /// override this method rather than rely on the default.
+    async fn hybrid_search_with_params(
+        &self,
+        collection: &str,
+        query_text: &str,
+        query_vector: &[f32],
+        limit: usize,
+        k: f32,
+        alpha: f32,
+    ) -> Result<Vec<SearchResult>> {
+        let vector_hits = self.search(collection, query_vector, limit).await?;
+        let text_hits = bm25_rank(self, collection, query_text, limit).await?;
+
+        let mut fused: HashMap<String, HybridHit> = HashMap::new();
+
+        for (rank, hit) in vector_hits.into_iter().enumerate() {
+            let entry = fused.entry(hit.id.clone()).or_insert_with(|| HybridHit {
+                id: hit.id.clone(),
+                metadata: hit.metadata.clone(),
+                content: hit.content.clone(),
+                rrf_score: 0.0,
+                vector_score: None,
+                text_score: None,
+            });
+            entry.rrf_score += alpha / (k + (rank + 1) as f32);
+            entry.vector_score = Some(hit.score);
+        }
+
+        for (rank, hit) in text_hits.into_iter().enumerate() {
+            let entry = fused.entry(hit.id.clone()).or_insert_with(|| HybridHit {
+                id: hit.id.clone(),
+                metadata: hit.metadata.clone(),
+                content: hit.content.clone(),
+                rrf_score: 0.0,
+                vector_score: None,
+                text_score: None,
+            });
+            entry.rrf_score += (1.0 - alpha) / (k + (rank + 1) as f32);
+            entry.text_score = Some(hit.score);
+            if entry.content.is_none() {
+                entry.content = hit.content.clone();
+            }
+        }
+
+        let mut results: Vec<SearchResult> = fused
+            .into_values()
+            .map(|hit| {
+                let mut metadata = hit.metadata;
+                if let Some(score) = hit.vector_score {
+                    metadata.insert("vector_score".to_string(), serde_json::json!(score));
+                }
+                if let Some(score) = hit.text_score {
+                    metadata.insert("text_score".to_string(), serde_json::json!(score));
+                }
+                SearchResult {
+                    id: hit.id,
+                    score: hit.rrf_score,
+                    metadata,
+                    content: hit.content,
+                }
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
     fn backend_name(&self) -> &'static str;
 }
 
+/// One record's contribution to a fused [`SearchResult`] while
+/// [`VectorStore::hybrid_search_with_params`] is accumulating scores across
+/// both ranked lists.
+struct HybridHit {
+    id: String,
+    metadata: HashMap<String, Value>,
+    content: Option<String>,
+    rrf_score: f32,
+    vector_score: Option<f32>,
+    text_score: Option<f32>,
+}
+
+/// Splits `text` into lowercased alphanumeric tokens for the BM25 scan in
+/// [`bm25_rank`].
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Ranks every record in `collection` that has `content` against `query`
+/// using Okapi BM25, scanning the full collection via
+/// [`VectorStore::export`]. Returns at most `limit` hits sorted descending
+/// by score; a record with no `content`, or whose content shares no terms
+/// with `query`, never appears.
+async fn bm25_rank(
+    store: &(impl VectorStore + ?Sized),
+    collection: &str,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchResult>> {
+    let query_terms = tokenize(query);
+    if query_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stream = store.export(collection).await?;
+    let mut docs: Vec<(VectorRecord, Vec<String>)> = Vec::new();
+    let mut doc_freq: HashMap<String, usize> = HashMap::new();
+    let mut total_len = 0usize;
+
+    while let Some(record) = stream.next().await {
+        let record = record?;
+        let Some(content) = record.content.clone() else {
+            continue;
+        };
+        let terms = tokenize(&content);
+        total_len += terms.len();
+        for term in terms.iter().collect::<std::collections::HashSet<_>>() {
+            *doc_freq.entry(term.clone()).or_insert(0) += 1;
+        }
+        docs.push((record, terms));
+    }
+
+    if docs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let n = docs.len() as f32;
+    let avg_len = total_len as f32 / n;
+
+    let mut scored: Vec<SearchResult> = docs
+        .into_iter()
+        .filter_map(|(record, terms)| {
+            if terms.is_empty() {
+                return None;
+            }
+            let mut term_freq: HashMap<&str, usize> = HashMap::new();
+            for term in &terms {
+                *term_freq.entry(term.as_str()).or_insert(0) += 1;
+            }
+            let doc_len = terms.len() as f32;
+            let mut score = 0.0f32;
+            for qterm in &query_terms {
+                let Some(&tf) = term_freq.get(qterm.as_str()) else {
+                    continue;
+                };
+                let df = *doc_freq.get(qterm).unwrap_or(&0) as f32;
+                let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                let tf = tf as f32;
+                score += idf * (tf * (BM25_K1 + 1.0))
+                    / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avg_len));
+            }
+            if score <= 0.0 {
+                return None;
+            }
+            Some(SearchResult {
+                id: record.id,
+                score,
+                metadata: record.metadata,
+                content: record.content,
+            })
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(limit);
+
+    Ok(scored)
+}
+
+/// Vectorizes text on behalf of a [`VectorStore`] collection, so callers can
+/// index and query by raw text instead of computing embeddings themselves.
+/// See [`VectorStore`] implementations' `upsert_text`/`search_text` helpers.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// The length of every vector [`Self::embed`] returns, so a collection
+    /// configured with this embedder can be created with a matching
+    /// dimension up front instead of discovering a mismatch on the first
+    /// `upsert_text` call.
+    fn dimension(&self) -> usize;
+}
+
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() {
         return 0.0;
@@ -161,6 +622,14 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     dot / (norm_a * norm_b)
 }
 
+pub fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
 pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() {
         return f32::MAX;
@@ -173,6 +642,96 @@ pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
         .sqrt()
 }
 
+const ORDER_KEY_TAG_NULL: u8 = 0x00;
+const ORDER_KEY_TAG_FALSE: u8 = 0x01;
+const ORDER_KEY_TAG_TRUE: u8 = 0x02;
+const ORDER_KEY_TAG_NUMBER: u8 = 0x03;
+const ORDER_KEY_TAG_STRING: u8 = 0x04;
+const ORDER_KEY_TAG_BYTES: u8 = 0x05;
+const ORDER_KEY_STRING_TERMINATOR: u8 = 0x00;
+const ORDER_KEY_NUMBER_SIGN_MASK: u64 = 1 << 63;
+
+/// Encodes `value` into a byte sequence whose lexicographic (memcmp) order
+/// matches `value`'s logical order, so a sorted index (e.g. a
+/// `BTreeMap<Vec<u8>, _>` keyed by this) can answer `Gt`/`Gte`/`Lt`/`Lte`/`In`
+/// range queries with a seek instead of scanning every record. A leading
+/// type-tag byte orders `null < false < true < number < string`; arrays and
+/// objects have no natural total order, so they fall back to an opaque tag
+/// followed by their canonical JSON text, comparable only for equality.
+///
+/// Numbers use the standard order-preserving transform for IEEE-754
+/// doubles: the sign bit is set for non-negative numbers and every bit is
+/// flipped for negative ones, so comparing the resulting big-endian bytes
+/// gives the same order as comparing the floats. Strings append their raw
+/// UTF-8 bytes followed by a `0x00` terminator, so a string sorts before
+/// any other string it's a strict prefix of; a string containing an
+/// embedded NUL byte is not escaped and can collide with that terminator.
+pub fn encode_order_key(value: &Value) -> Vec<u8> {
+    match value {
+        Value::Null => vec![ORDER_KEY_TAG_NULL],
+        Value::Bool(false) => vec![ORDER_KEY_TAG_FALSE],
+        Value::Bool(true) => vec![ORDER_KEY_TAG_TRUE],
+        Value::Number(n) => {
+            let bits = n.as_f64().unwrap_or(0.0).to_bits();
+            let ordered = if bits & ORDER_KEY_NUMBER_SIGN_MASK != 0 {
+                !bits
+            } else {
+                bits | ORDER_KEY_NUMBER_SIGN_MASK
+            };
+            let mut key = Vec::with_capacity(9);
+            key.push(ORDER_KEY_TAG_NUMBER);
+            key.extend_from_slice(&ordered.to_be_bytes());
+            key
+        }
+        Value::String(s) => {
+            let mut key = Vec::with_capacity(s.len() + 2);
+            key.push(ORDER_KEY_TAG_STRING);
+            key.extend_from_slice(s.as_bytes());
+            key.push(ORDER_KEY_STRING_TERMINATOR);
+            key
+        }
+        other => {
+            let mut key = vec![ORDER_KEY_TAG_BYTES];
+            key.extend_from_slice(other.to_string().as_bytes());
+            key
+        }
+    }
+}
+
+/// Reverses [`encode_order_key`] for the scalar variants it round-trips
+/// (`Null`/`Bool`/`Number`/`String`). Numbers are stored as f64, so a
+/// whole number within ±2^53 decodes as an integer (`7.0` comes back as
+/// `7`) and any other number as a float. Returns `None` for a
+/// malformed key or one produced from the opaque array/object fallback,
+/// since that encoding isn't meant to be decoded.
+pub fn decode_order_key(key: &[u8]) -> Option<Value> {
+    let (tag, rest) = key.split_first()?;
+    match *tag {
+        ORDER_KEY_TAG_NULL => Some(Value::Null),
+        ORDER_KEY_TAG_FALSE => Some(Value::Bool(false)),
+        ORDER_KEY_TAG_TRUE => Some(Value::Bool(true)),
+        ORDER_KEY_TAG_NUMBER => {
+            let bytes: [u8; 8] = rest.try_into().ok()?;
+            let ordered = u64::from_be_bytes(bytes);
+            let bits = if ordered & ORDER_KEY_NUMBER_SIGN_MASK != 0 {
+                ordered & !ORDER_KEY_NUMBER_SIGN_MASK
+            } else {
+                !ordered
+            };
+            let number = f64::from_bits(bits);
+            if number.fract() == 0.0 && number.abs() <= 9_007_199_254_740_992.0 {
+                return Some(Value::from(number as i64));
+            }
+            serde_json::Number::from_f64(number).map(Value::Number)
+        }
+        ORDER_KEY_TAG_STRING => {
+            let body = rest.strip_suffix(&[ORDER_KEY_STRING_TERMINATOR])?;
+            std::str::from_utf8(body).ok().map(|s| Value::String(s.to_string()))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,8 +754,73 @@ mod tests {
             .eq("type", "article")
             .gt("score", 0.5);
 
-        assert_eq!(filter.conditions.len(), 2);
         assert!(!filter.is_empty());
+
+        let mut matching = HashMap::new();
+        matching.insert("type".to_string(), serde_json::json!("article"));
+        matching.insert("score".to_string(), serde_json::json!(0.9));
+        assert!(filter.evaluate(&matching));
+
+        let mut non_matching = matching.clone();
+        non_matching.insert("score".to_string(), serde_json::json!(0.1));
+        assert!(!filter.evaluate(&non_matching));
+    }
+
+    #[test]
+    fn test_vector_filter_or_matches_either_side() {
+        let filter = VectorFilter::new()
+            .eq("kind", "article")
+            .or(VectorFilter::new().eq("kind", "note"));
+
+        let mut note = HashMap::new();
+        note.insert("kind".to_string(), serde_json::json!("note"));
+        assert!(filter.evaluate(&note));
+
+        let mut other = HashMap::new();
+        other.insert("kind".to_string(), serde_json::json!("draft"));
+        assert!(!filter.evaluate(&other));
+    }
+
+    #[test]
+    fn test_vector_filter_not_inverts_match() {
+        let filter = VectorFilter::new().eq("kind", "article").not();
+
+        let mut article = HashMap::new();
+        article.insert("kind".to_string(), serde_json::json!("article"));
+        assert!(!filter.evaluate(&article));
+
+        let mut other = HashMap::new();
+        other.insert("kind".to_string(), serde_json::json!("note"));
+        assert!(filter.evaluate(&other));
+    }
+
+    #[test]
+    fn test_vector_filter_group_nests_a_sub_filter() {
+        let filter = VectorFilter::new()
+            .eq("type", "article")
+            .group(VectorFilter::new().eq("a", 1).or(VectorFilter::new().eq("b", 2)));
+
+        let mut matches_via_a = HashMap::new();
+        matches_via_a.insert("type".to_string(), serde_json::json!("article"));
+        matches_via_a.insert("a".to_string(), serde_json::json!(1));
+        assert!(filter.evaluate(&matches_via_a));
+
+        let mut wrong_type = HashMap::new();
+        wrong_type.insert("type".to_string(), serde_json::json!("note"));
+        wrong_type.insert("a".to_string(), serde_json::json!(1));
+        assert!(!filter.evaluate(&wrong_type));
+    }
+
+    #[test]
+    fn test_vector_filter_normalize_flattens_nested_and() {
+        let filter = VectorFilter::new()
+            .eq("a", 1)
+            .group(VectorFilter::new().eq("b", 2).eq("c", 3));
+
+        match filter.expr.as_ref().unwrap() {
+            FilterExpr::And(children) => assert_eq!(children.len(), 3),
+            other => panic!("expected a flattened And, got {other:?}"),
+        }
     }
 
     #[test]
@@ -221,4 +845,208 @@ mod tests {
         let c = vec![3.0, 4.0, 0.0];
         assert!((euclidean_distance(&a, &c) - 5.0).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_dot_product() {
+        let a = vec![1.0, 2.0, 3.0];
+        let b = vec![4.0, 5.0, 6.0];
+        assert!((dot_product(&a, &b) - 32.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_distance_metric_score_is_higher_is_better_for_every_variant() {
+        let a = vec![1.0, 0.0];
+        let identical = vec![1.0, 0.0];
+        let different = vec![0.0, 1.0];
+
+        for metric in [
+            DistanceMetric::Cosine,
+            DistanceMetric::DotProduct,
+            DistanceMetric::EuclideanL2,
+        ] {
+            assert!(
+                metric.score(&a, &identical) > metric.score(&a, &different),
+                "{metric:?} should rank the identical vector higher"
+            );
+        }
+    }
+
+    #[test]
+    fn test_distance_metric_defaults_to_cosine() {
+        assert_eq!(DistanceMetric::default(), DistanceMetric::Cosine);
+    }
+
+    struct ConstantEmbedder;
+
+    #[async_trait]
+    impl Embedder for ConstantEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|_| vec![0.1, 0.2]).collect())
+        }
+
+        fn dimension(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embedder_batches_one_vector_per_text() {
+        let embedder = ConstantEmbedder;
+        let vectors = embedder
+            .embed(&["a".to_string(), "b".to_string(), "c".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(vectors.len(), 3);
+        assert_eq!(vectors[0], vec![0.1, 0.2]);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_ranks_record_found_by_both_lists_highest() {
+        use crate::local::MemoryVectorStore;
+
+        let store = MemoryVectorStore::new();
+        store.create_collection("articles", 2).await.unwrap();
+        store
+            .upsert(
+                "articles",
+                &[
+                    VectorRecord::new("doc1", vec![1.0, 0.0])
+                        .with_content("rust programming language"),
+                    VectorRecord::new("doc2", vec![0.0, 1.0])
+                        .with_content("python programming language"),
+                    VectorRecord::new("doc3", vec![0.9, 0.1]).with_content("unrelated text"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let results = store
+            .hybrid_search("articles", "rust programming", &[1.0, 0.0], 10)
+            .await
+            .unwrap();
+
+        let doc1 = results.iter().find(|r| r.id == "doc1").unwrap();
+        assert!(doc1.metadata.contains_key("vector_score"));
+        assert!(doc1.metadata.contains_key("text_score"));
+        assert_eq!(results[0].id, "doc1");
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_truncates_to_limit() {
+        use crate::local::MemoryVectorStore;
+
+        let store = MemoryVectorStore::new();
+        store.create_collection("notes", 1).await.unwrap();
+        let records: Vec<VectorRecord> = (0..5)
+            .map(|i| VectorRecord::new(format!("doc{i}"), vec![1.0]).with_content("shared keyword"))
+            .collect();
+        store.upsert("notes", &records).await.unwrap();
+
+        let results = store
+            .hybrid_search("notes", "shared", &[1.0], 2)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_alpha_zero_ignores_vector_list() {
+        use crate::local::MemoryVectorStore;
+
+        let store = MemoryVectorStore::new();
+        store.create_collection("docs", 2).await.unwrap();
+        store
+            .upsert(
+                "docs",
+                &[
+                    VectorRecord::new("close_but_no_text", vec![1.0, 0.0]),
+                    VectorRecord::new("far_with_text", vec![0.0, 1.0]).with_content("keyword"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let results = store
+            .hybrid_search_with_params("docs", "keyword", &[1.0, 0.0], 10, DEFAULT_RRF_K, 0.0)
+            .await
+            .unwrap();
+
+        assert_eq!(results[0].id, "far_with_text");
+    }
+
+    #[test]
+    fn test_tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(
+            tokenize("Rust, Programming!"),
+            vec!["rust".to_string(), "programming".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_encode_order_key_preserves_type_tag_order() {
+        let mut keys = vec![
+            encode_order_key(&Value::String("x".into())),
+            encode_order_key(&serde_json::json!(1)),
+            encode_order_key(&Value::Bool(true)),
+            encode_order_key(&Value::Bool(false)),
+            encode_order_key(&Value::Null),
+        ];
+        let sorted = {
+            let mut k = keys.clone();
+            k.sort();
+            k
+        };
+        keys.sort();
+        assert_eq!(keys, sorted);
+        assert!(keys[0] < keys[1]); // null < false
+        assert!(keys[1] < keys[2]); // false < true
+        assert!(keys[2] < keys[3]); // true < number
+        assert!(keys[3] < keys[4]); // number < string
+    }
+
+    #[test]
+    fn test_encode_order_key_preserves_numeric_order() {
+        let values = [-100.5, -1.0, 0.0, 1.0, 100.5];
+        let mut keys: Vec<Vec<u8>> = values.iter().map(|v| encode_order_key(&serde_json::json!(v))).collect();
+        let original = keys.clone();
+        keys.sort();
+        assert_eq!(keys, original, "encoded keys should already be in ascending order");
+    }
+
+    #[test]
+    fn test_encode_order_key_preserves_string_prefix_order() {
+        let a = encode_order_key(&Value::String("app".into()));
+        let b = encode_order_key(&Value::String("apple".into()));
+        let c = encode_order_key(&Value::String("banana".into()));
+        assert!(a < b, "a shorter prefix sorts before a string it prefixes");
+        assert!(b < c);
+    }
+
+    #[test]
+    fn test_decode_order_key_round_trips_scalars() {
+        for value in [Value::Null, Value::Bool(false), Value::Bool(true), serde_json::json!(-42.5), serde_json::json!(7), Value::String("hello".into())] {
+            let key = encode_order_key(&value);
+            assert_eq!(decode_order_key(&key), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_decode_order_key_rejects_opaque_array_encoding() {
+        let key = encode_order_key(&serde_json::json!([1, 2, 3]));
+        assert_eq!(decode_order_key(&key), None);
+    }
+
+    #[test]
+    fn test_vector_filter_as_single_condition() {
+        let single = VectorFilter::new().eq("kind", "note");
+        assert!(matches!(single.as_single_condition(), Some(FilterCondition::Eq(field, _)) if field == "kind"));
+
+        let composed = VectorFilter::new().eq("a", 1).eq("b", 2);
+        assert!(composed.as_single_condition().is_none());
+
+        assert!(VectorFilter::new().is_empty());
+        assert!(VectorFilter::new().as_single_condition().is_none());
+    }
 }
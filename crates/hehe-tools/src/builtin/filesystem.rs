@@ -1,11 +1,41 @@
+use super::walk;
 use crate::error::{Result, ToolError};
+use crate::fs::{ctx_fs, CopyOptions, CreateOptions, Fs, RemoveOptions, RenameOptions};
 use crate::traits::{Tool, ToolOutput};
 use async_trait::async_trait;
+use encoding_rs::Encoding;
 use hehe_core::{Context, ToolDefinition, ToolParameter};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::Path;
-use tokio::fs;
+use std::sync::Arc;
+
+/// Classifies a text file's line-ending convention so `ReadFileTool` can
+/// report it and `WriteFileTool` can preserve it.
+fn detect_line_ending(text: &str) -> &'static str {
+    let crlf = text.matches("\r\n").count();
+    let total_newlines = text.matches('\n').count();
+    if total_newlines == 0 {
+        "none"
+    } else if crlf == total_newlines {
+        "crlf"
+    } else if crlf == 0 {
+        "lf"
+    } else {
+        "mixed"
+    }
+}
+
+/// Rewrites every line ending in `text` to match `target` ("lf" or "crlf").
+/// Any other value for `target` leaves `text` untouched.
+fn normalize_to_line_ending(text: &str, target: &str) -> String {
+    let lf_normalized = text.replace("\r\n", "\n");
+    match target {
+        "crlf" => lf_normalized.replace('\n', "\r\n"),
+        "lf" => lf_normalized,
+        _ => text.to_string(),
+    }
+}
 
 pub struct ReadFileTool {
     def: ToolDefinition,
@@ -51,23 +81,41 @@ impl Tool for ReadFileTool {
         &self.def
     }
 
-    async fn execute(&self, _ctx: &Context, input: Value) -> Result<ToolOutput> {
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
         let input: ReadFileInput = serde_json::from_value(input)?;
-        
+        let fs = ctx_fs(ctx);
         let path = Path::new(&input.path);
-        if !path.exists() {
+
+        if fs.metadata(path).await?.is_none() {
             return Ok(ToolOutput::error(format!("File not found: {}", input.path)));
         }
 
-        match fs::read_to_string(path).await {
-            Ok(content) => {
-                let size = content.len();
-                Ok(ToolOutput::text(content)
-                    .with_metadata("path", &input.path)
-                    .with_metadata("size", size))
-            }
-            Err(e) => Ok(ToolOutput::error(format!("Failed to read file: {}", e))),
+        let Some(encoding) = Encoding::for_label(input.encoding.as_bytes()) else {
+            return Ok(ToolOutput::error(format!("Unknown encoding: {}", input.encoding)));
+        };
+
+        let bytes = match fs.read_bytes(path).await {
+            Ok(bytes) => bytes,
+            Err(e) => return Ok(ToolOutput::error(format!("Failed to read file: {}", e))),
+        };
+
+        let (content, _, had_errors) = encoding.decode(&bytes);
+        if had_errors {
+            return Ok(ToolOutput::error(format!(
+                "Failed to decode {} as {}: invalid byte sequence",
+                input.path,
+                encoding.name()
+            )));
         }
+        let content = content.into_owned();
+
+        let size = content.len();
+        let line_ending = detect_line_ending(&content);
+        Ok(ToolOutput::text(content)
+            .with_metadata("path", &input.path)
+            .with_metadata("size", size)
+            .with_metadata("encoding", encoding.name())
+            .with_metadata("line_ending", line_ending))
     }
 }
 
@@ -92,6 +140,14 @@ impl WriteFileTool {
                     .with_description("Append to file instead of overwriting")
                     .with_default(Value::Bool(false)),
             )
+            .with_param(
+                "normalize_line_endings",
+                ToolParameter::boolean()
+                    .with_description(
+                        "Rewrite the written content's line endings to match the existing file's convention",
+                    )
+                    .with_default(Value::Bool(false)),
+            )
             .dangerous();
         Self { def }
     }
@@ -109,6 +165,8 @@ struct WriteFileInput {
     content: String,
     #[serde(default)]
     append: bool,
+    #[serde(default)]
+    normalize_line_endings: bool,
 }
 
 #[async_trait]
@@ -117,30 +175,42 @@ impl Tool for WriteFileTool {
         &self.def
     }
 
-    async fn execute(&self, _ctx: &Context, input: Value) -> Result<ToolOutput> {
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
         let input: WriteFileInput = serde_json::from_value(input)?;
-        
+        let fs = ctx_fs(ctx);
         let path = Path::new(&input.path);
-        
-        if let Some(parent) = path.parent() {
-            if !parent.exists() {
-                if let Err(e) = fs::create_dir_all(parent).await {
-                    return Ok(ToolOutput::error(format!("Failed to create directory: {}", e)));
-                }
+
+        let existing = fs.load(path).await.ok();
+
+        let mut new_content = input.content.clone();
+        if input.normalize_line_endings {
+            if let Some(existing) = &existing {
+                let target = detect_line_ending(existing);
+                new_content = normalize_to_line_ending(&new_content, target);
             }
         }
 
-        let result = if input.append {
-            let existing = fs::read_to_string(path).await.unwrap_or_default();
-            fs::write(path, format!("{}{}", existing, input.content)).await
+        let final_content = if input.append {
+            format!("{}{}", existing.unwrap_or_default(), new_content)
         } else {
-            fs::write(path, &input.content).await
+            new_content
         };
 
+        let result = fs
+            .create_file(path, final_content.as_bytes(), CreateOptions { overwrite: true, ignore_if_exists: false })
+            .await;
+
         match result {
-            Ok(_) => Ok(ToolOutput::text(format!("Successfully wrote to {}", input.path))
-                .with_metadata("path", &input.path)
-                .with_metadata("bytes_written", input.content.len())),
+            Ok(_) => {
+                #[cfg(feature = "search-sync")]
+                if let Some(sync) = crate::index_sync::ctx_file_index_sync(ctx) {
+                    sync.upsert(&input.path, &final_content);
+                }
+
+                Ok(ToolOutput::text(format!("Successfully wrote to {}", input.path))
+                    .with_metadata("path", &input.path)
+                    .with_metadata("bytes_written", input.content.len()))
+            }
             Err(e) => Ok(ToolOutput::error(format!("Failed to write file: {}", e))),
         }
     }
@@ -162,6 +232,28 @@ impl ListDirectoryTool {
                 ToolParameter::boolean()
                     .with_description("List recursively")
                     .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "max_depth",
+                ToolParameter::number().with_description("Maximum recursion depth (recursive only, unbounded if omitted)"),
+            )
+            .with_param(
+                "follow_symlinks",
+                ToolParameter::boolean()
+                    .with_description("Descend into directories reached through a symlink")
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "respect_gitignore",
+                ToolParameter::boolean()
+                    .with_description("Skip entries matched by .gitignore/.ignore files found while descending")
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "compute_dir_sizes",
+                ToolParameter::boolean()
+                    .with_description("Report each directory's aggregate size as the sum of its descendant files")
+                    .with_default(Value::Bool(false)),
             );
         Self { def }
     }
@@ -178,14 +270,23 @@ struct ListDirectoryInput {
     path: String,
     #[serde(default)]
     recursive: bool,
+    max_depth: Option<usize>,
+    #[serde(default)]
+    follow_symlinks: bool,
+    #[serde(default)]
+    respect_gitignore: bool,
+    #[serde(default)]
+    compute_dir_sizes: bool,
 }
 
-#[derive(Serialize, Deserialize)]
-struct DirectoryEntry {
-    name: String,
-    path: String,
-    is_dir: bool,
-    size: Option<u64>,
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct DirectoryEntry {
+    pub(super) name: String,
+    pub(super) path: String,
+    pub(super) is_dir: bool,
+    pub(super) size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) total_size: Option<u64>,
 }
 
 #[async_trait]
@@ -194,32 +295,47 @@ impl Tool for ListDirectoryTool {
         &self.def
     }
 
-    async fn execute(&self, _ctx: &Context, input: Value) -> Result<ToolOutput> {
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
         let input: ListDirectoryInput = serde_json::from_value(input)?;
-        
+        let fs = ctx_fs(ctx);
         let path = Path::new(&input.path);
-        if !path.exists() {
-            return Ok(ToolOutput::error(format!("Directory not found: {}", input.path)));
-        }
-        if !path.is_dir() {
-            return Ok(ToolOutput::error(format!("Not a directory: {}", input.path)));
+
+        let metadata = fs.metadata(path).await?;
+        match metadata {
+            None => return Ok(ToolOutput::error(format!("Directory not found: {}", input.path))),
+            Some(metadata) if !metadata.is_dir => {
+                return Ok(ToolOutput::error(format!("Not a directory: {}", input.path)))
+            }
+            Some(_) => {}
         }
 
-        let mut entries = Vec::new();
-        
-        if input.recursive {
-            collect_entries_recursive(path, &mut entries).await?;
+        let mut entries = if input.recursive {
+            walk::walk_parallel(
+                fs,
+                path.to_path_buf(),
+                walk::WalkOptions {
+                    max_depth: input.max_depth,
+                    follow_symlinks: input.follow_symlinks,
+                    respect_gitignore: input.respect_gitignore,
+                },
+            )
+            .await?
         } else {
-            let mut read_dir = fs::read_dir(path).await?;
-            while let Some(entry) = read_dir.next_entry().await? {
-                let metadata = entry.metadata().await?;
+            let mut entries = Vec::new();
+            for entry in fs.read_dir(path).await? {
                 entries.push(DirectoryEntry {
-                    name: entry.file_name().to_string_lossy().to_string(),
-                    path: entry.path().to_string_lossy().to_string(),
-                    is_dir: metadata.is_dir(),
-                    size: if metadata.is_file() { Some(metadata.len()) } else { None },
+                    name: entry.name,
+                    path: entry.path.to_string_lossy().to_string(),
+                    is_dir: entry.is_dir,
+                    size: entry.size,
+                    total_size: None,
                 });
             }
+            entries
+        };
+
+        if input.compute_dir_sizes {
+            walk::annotate_dir_sizes(&mut entries);
         }
 
         entries.sort_by(|a, b| a.name.cmp(&b.name));
@@ -227,25 +343,6 @@ impl Tool for ListDirectoryTool {
     }
 }
 
-async fn collect_entries_recursive(path: &Path, entries: &mut Vec<DirectoryEntry>) -> Result<()> {
-    let mut read_dir = fs::read_dir(path).await?;
-    while let Some(entry) = read_dir.next_entry().await? {
-        let metadata = entry.metadata().await?;
-        let entry_data = DirectoryEntry {
-            name: entry.file_name().to_string_lossy().to_string(),
-            path: entry.path().to_string_lossy().to_string(),
-            is_dir: metadata.is_dir(),
-            size: if metadata.is_file() { Some(metadata.len()) } else { None },
-        };
-        entries.push(entry_data);
-
-        if metadata.is_dir() {
-            Box::pin(collect_entries_recursive(&entry.path(), entries)).await?;
-        }
-    }
-    Ok(())
-}
-
 pub struct SearchFilesTool {
     def: ToolDefinition,
 }
@@ -290,11 +387,23 @@ impl Tool for SearchFilesTool {
         &self.def
     }
 
-    async fn execute(&self, _ctx: &Context, input: Value) -> Result<ToolOutput> {
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
         let input: SearchFilesInput = serde_json::from_value(input)?;
-        
+
+        // `glob` walks the real filesystem directly; a jailed `Fs` backend
+        // only constrains read/write/list operations on explicit paths, so
+        // the only check made through `ctx`'s `Fs` is that the base path's
+        // metadata lookup does not fail.
+        let fs = ctx_fs(ctx);
+        if fs.metadata(Path::new(&input.path)).await.is_err() {
+            return Err(ToolError::invalid_input(format!(
+                "Base path not accessible: {}",
+                input.path
+            )));
+        }
+
         let full_pattern = format!("{}/{}", input.path, input.pattern);
-        
+
         let matches: Vec<String> = glob::glob(&full_pattern)
             .map_err(|e| ToolError::invalid_input(format!("Invalid pattern: {}", e)))?
             .filter_map(|r| r.ok())
@@ -305,22 +414,322 @@ impl Tool for SearchFilesTool {
     }
 }
 
+pub struct CopyFileTool {
+    def: ToolDefinition,
+}
+
+impl CopyFileTool {
+    pub fn new() -> Self {
+        let def = ToolDefinition::new("copy_file", "Copy a file to a new location")
+            .with_required_param(
+                "source",
+                ToolParameter::string().with_description("Path to the file to copy"),
+            )
+            .with_required_param(
+                "destination",
+                ToolParameter::string().with_description("Path to copy the file to"),
+            )
+            .with_param(
+                "overwrite",
+                ToolParameter::boolean()
+                    .with_description("Overwrite destination if it already exists")
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "ignore_if_exists",
+                ToolParameter::boolean()
+                    .with_description("Silently skip instead of erroring when destination exists")
+                    .with_default(Value::Bool(false)),
+            )
+            .dangerous();
+        Self { def }
+    }
+}
+
+impl Default for CopyFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct CopyFileInput {
+    source: String,
+    destination: String,
+    #[serde(default)]
+    overwrite: bool,
+    #[serde(default)]
+    ignore_if_exists: bool,
+}
+
+#[async_trait]
+impl Tool for CopyFileTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        let input: CopyFileInput = serde_json::from_value(input)?;
+        let fs = ctx_fs(ctx);
+        let source = Path::new(&input.source);
+        let destination = Path::new(&input.destination);
+
+        let bytes = match fs.metadata(source).await? {
+            Some(metadata) if metadata.is_file => metadata.len,
+            Some(_) => return Ok(ToolOutput::error(format!("Not a file: {}", input.source))),
+            None => return Ok(ToolOutput::error(format!("File not found: {}", input.source))),
+        };
+
+        let result = fs
+            .copy_file(
+                source,
+                destination,
+                CopyOptions {
+                    overwrite: input.overwrite,
+                    ignore_if_exists: input.ignore_if_exists,
+                },
+            )
+            .await;
+
+        match result {
+            Ok(()) => Ok(ToolOutput::text(format!(
+                "Copied {} to {}",
+                input.source, input.destination
+            ))
+            .with_metadata("source", &input.source)
+            .with_metadata("destination", &input.destination)
+            .with_metadata("bytes_copied", bytes)),
+            Err(e) => Ok(ToolOutput::error(format!("Failed to copy file: {}", e))),
+        }
+    }
+}
+
+pub struct MoveFileTool {
+    def: ToolDefinition,
+}
+
+impl MoveFileTool {
+    pub fn new() -> Self {
+        let def = ToolDefinition::new("move_file", "Move or rename a file")
+            .with_required_param(
+                "source",
+                ToolParameter::string().with_description("Path to the file to move"),
+            )
+            .with_required_param(
+                "destination",
+                ToolParameter::string().with_description("Path to move the file to"),
+            )
+            .with_param(
+                "overwrite",
+                ToolParameter::boolean()
+                    .with_description("Overwrite destination if it already exists")
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "ignore_if_exists",
+                ToolParameter::boolean()
+                    .with_description("Silently skip instead of erroring when destination exists")
+                    .with_default(Value::Bool(false)),
+            )
+            .dangerous();
+        Self { def }
+    }
+}
+
+impl Default for MoveFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct MoveFileInput {
+    source: String,
+    destination: String,
+    #[serde(default)]
+    overwrite: bool,
+    #[serde(default)]
+    ignore_if_exists: bool,
+}
+
+/// Linux/macOS `EXDEV` ("cross-device link"), the errno `rename(2)` returns
+/// when `from` and `to` live on different filesystems. `std::io::ErrorKind`
+/// has no stable variant for this, so we check the raw errno directly.
+fn is_cross_device(err: &std::io::Error) -> bool {
+    err.raw_os_error() == Some(18)
+}
+
+#[async_trait]
+impl Tool for MoveFileTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        let input: MoveFileInput = serde_json::from_value(input)?;
+        let fs = ctx_fs(ctx);
+        let source = Path::new(&input.source);
+        let destination = Path::new(&input.destination);
+
+        let bytes = match fs.metadata(source).await? {
+            Some(metadata) if metadata.is_file => metadata.len,
+            Some(_) => return Ok(ToolOutput::error(format!("Not a file: {}", input.source))),
+            None => return Ok(ToolOutput::error(format!("File not found: {}", input.source))),
+        };
+
+        let rename_options = RenameOptions {
+            overwrite: input.overwrite,
+            ignore_if_exists: input.ignore_if_exists,
+        };
+
+        let result = match fs.rename(source, destination, rename_options).await {
+            Err(ToolError::Io(e)) if is_cross_device(&e) => {
+                let copy_result = fs
+                    .copy_file(
+                        source,
+                        destination,
+                        CopyOptions {
+                            overwrite: input.overwrite,
+                            ignore_if_exists: input.ignore_if_exists,
+                        },
+                    )
+                    .await;
+                match copy_result {
+                    Ok(()) => fs.remove_file(source, RemoveOptions::default()).await,
+                    Err(e) => Err(e),
+                }
+            }
+            other => other,
+        };
+
+        match result {
+            Ok(()) => {
+                #[cfg(feature = "search-sync")]
+                if let Some(sync) = crate::index_sync::ctx_file_index_sync(ctx) {
+                    sync.remove(&input.source);
+                    if let Ok(content) = fs.load(destination).await {
+                        sync.upsert(&input.destination, &content);
+                    }
+                }
+
+                Ok(ToolOutput::text(format!(
+                    "Moved {} to {}",
+                    input.source, input.destination
+                ))
+                .with_metadata("source", &input.source)
+                .with_metadata("destination", &input.destination)
+                .with_metadata("bytes_moved", bytes))
+            }
+            Err(e) => Ok(ToolOutput::error(format!("Failed to move file: {}", e))),
+        }
+    }
+}
+
+pub struct DeleteFileTool {
+    def: ToolDefinition,
+}
+
+impl DeleteFileTool {
+    pub fn new() -> Self {
+        let def = ToolDefinition::new("delete_file", "Delete a file or directory")
+            .with_required_param(
+                "path",
+                ToolParameter::string().with_description("Path to the file or directory to delete"),
+            )
+            .with_param(
+                "recursive",
+                ToolParameter::boolean()
+                    .with_description("Delete a non-empty directory and its contents")
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "ignore_if_not_exists",
+                ToolParameter::boolean()
+                    .with_description("Silently succeed instead of erroring when the path doesn't exist")
+                    .with_default(Value::Bool(false)),
+            )
+            .dangerous();
+        Self { def }
+    }
+}
+
+impl Default for DeleteFileTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct DeleteFileInput {
+    path: String,
+    #[serde(default)]
+    recursive: bool,
+    #[serde(default)]
+    ignore_if_not_exists: bool,
+}
+
+#[async_trait]
+impl Tool for DeleteFileTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        let input: DeleteFileInput = serde_json::from_value(input)?;
+        let fs = ctx_fs(ctx);
+        let path = Path::new(&input.path);
+
+        let remove_options = RemoveOptions {
+            recursive: input.recursive,
+            ignore_if_not_exists: input.ignore_if_not_exists,
+        };
+
+        let is_dir = match fs.metadata(path).await? {
+            Some(metadata) => metadata.is_dir,
+            None if input.ignore_if_not_exists => {
+                return Ok(ToolOutput::text(format!("{} does not exist, nothing to delete", input.path))
+                    .with_metadata("path", &input.path))
+            }
+            None => return Ok(ToolOutput::error(format!("Path not found: {}", input.path))),
+        };
+
+        let result = if is_dir {
+            fs.remove_dir(path, remove_options).await
+        } else {
+            fs.remove_file(path, remove_options).await
+        };
+
+        match result {
+            Ok(()) => {
+                #[cfg(feature = "search-sync")]
+                if !is_dir {
+                    if let Some(sync) = crate::index_sync::ctx_file_index_sync(ctx) {
+                        sync.remove(&input.path);
+                    }
+                }
+
+                Ok(ToolOutput::text(format!("Deleted {}", input.path)).with_metadata("path", &input.path))
+            }
+            Err(e) => Ok(ToolOutput::error(format!("Failed to delete: {}", e))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::TempDir;
+    use crate::fs::FakeFs;
+
+    fn ctx_with_fake(fs: FakeFs) -> Context {
+        Context::new().with_extension(Arc::new(fs) as Arc<dyn Fs>)
+    }
 
     #[tokio::test]
     async fn test_read_file() {
-        let dir = TempDir::new().unwrap();
-        let file_path = dir.path().join("test.txt");
-        std::fs::write(&file_path, "Hello, World!").unwrap();
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/test.txt", "Hello, World!"));
 
         let tool = ReadFileTool::new();
-        let ctx = Context::new();
-        let input = serde_json::json!({
-            "path": file_path.to_string_lossy()
-        });
+        let input = serde_json::json!({ "path": "/work/test.txt" });
 
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(!output.is_error);
@@ -329,46 +738,77 @@ mod tests {
 
     #[tokio::test]
     async fn test_read_file_not_found() {
+        let ctx = ctx_with_fake(FakeFs::new());
+
         let tool = ReadFileTool::new();
-        let ctx = Context::new();
-        let input = serde_json::json!({
-            "path": "/nonexistent/file.txt"
-        });
+        let input = serde_json::json!({ "path": "/nonexistent/file.txt" });
 
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(output.is_error);
         assert!(output.content.contains("not found"));
     }
 
+    #[tokio::test]
+    async fn test_read_file_reports_line_ending_metadata() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/crlf.txt", "line1\r\nline2\r\n"));
+
+        let tool = ReadFileTool::new();
+        let input = serde_json::json!({ "path": "/work/crlf.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.metadata.get::<String>("line_ending").unwrap(), "crlf");
+    }
+
+    #[tokio::test]
+    async fn test_read_file_decodes_non_utf8_encoding() {
+        let (bytes, _, _) = encoding_rs::WINDOWS_1252.encode("café");
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/latin1.txt", bytes.into_owned()));
+
+        let tool = ReadFileTool::new();
+        let input = serde_json::json!({ "path": "/work/latin1.txt", "encoding": "windows-1252" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.content, "café");
+    }
+
+    #[tokio::test]
+    async fn test_read_file_unknown_encoding_is_an_error() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/a.txt", "hi"));
+
+        let tool = ReadFileTool::new();
+        let input = serde_json::json!({ "path": "/work/a.txt", "encoding": "not-a-real-encoding" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(output.is_error);
+        assert!(output.content.contains("Unknown encoding"));
+    }
+
     #[tokio::test]
     async fn test_write_file() {
-        let dir = TempDir::new().unwrap();
-        let file_path = dir.path().join("output.txt");
+        let ctx = ctx_with_fake(FakeFs::new());
 
         let tool = WriteFileTool::new();
-        let ctx = Context::new();
         let input = serde_json::json!({
-            "path": file_path.to_string_lossy(),
+            "path": "/work/output.txt",
             "content": "Test content"
         });
 
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(!output.is_error);
 
-        let content = std::fs::read_to_string(&file_path).unwrap();
-        assert_eq!(content, "Test content");
+        let fs = ctx_fs(&ctx);
+        assert_eq!(fs.load(Path::new("/work/output.txt")).await.unwrap(), "Test content");
     }
 
     #[tokio::test]
     async fn test_write_file_append() {
-        let dir = TempDir::new().unwrap();
-        let file_path = dir.path().join("append.txt");
-        std::fs::write(&file_path, "First").unwrap();
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/append.txt", "First"));
 
         let tool = WriteFileTool::new();
-        let ctx = Context::new();
         let input = serde_json::json!({
-            "path": file_path.to_string_lossy(),
+            "path": "/work/append.txt",
             "content": "Second",
             "append": true
         });
@@ -376,33 +816,100 @@ mod tests {
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(!output.is_error);
 
-        let content = std::fs::read_to_string(&file_path).unwrap();
-        assert_eq!(content, "FirstSecond");
+        let fs = ctx_fs(&ctx);
+        assert_eq!(fs.load(Path::new("/work/append.txt")).await.unwrap(), "FirstSecond");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_normalizes_to_existing_crlf_convention() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/crlf.txt", "old\r\n"));
+
+        let tool = WriteFileTool::new();
+        let input = serde_json::json!({
+            "path": "/work/crlf.txt",
+            "content": "new line 1\nnew line 2\n",
+            "normalize_line_endings": true
+        });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+
+        let fs = ctx_fs(&ctx);
+        assert_eq!(
+            fs.load(Path::new("/work/crlf.txt")).await.unwrap(),
+            "new line 1\r\nnew line 2\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_does_not_normalize_for_new_files() {
+        let ctx = ctx_with_fake(FakeFs::new());
+
+        let tool = WriteFileTool::new();
+        let input = serde_json::json!({
+            "path": "/work/new.txt",
+            "content": "line 1\nline 2\n",
+            "normalize_line_endings": true
+        });
+
+        tool.execute(&ctx, input).await.unwrap();
+
+        let fs = ctx_fs(&ctx);
+        assert_eq!(fs.load(Path::new("/work/new.txt")).await.unwrap(), "line 1\nline 2\n");
     }
 
     #[tokio::test]
     async fn test_list_directory() {
-        let dir = TempDir::new().unwrap();
-        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
-        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
-        std::fs::create_dir(dir.path().join("subdir")).unwrap();
+        let ctx = ctx_with_fake(
+            FakeFs::new()
+                .with_file("/work/a.txt", "a")
+                .with_file("/work/b.txt", "b")
+                .with_file("/work/subdir/c.txt", "c"),
+        );
+
+        let tool = ListDirectoryTool::new();
+        let input = serde_json::json!({ "path": "/work" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+
+        let entries: Vec<DirectoryEntry> = serde_json::from_str(&output.content).unwrap();
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_directory_recursive_respects_gitignore_and_computes_sizes() {
+        let ctx = ctx_with_fake(
+            FakeFs::new()
+                .with_file("/work/.gitignore", "*.log\n")
+                .with_file("/work/keep.txt", "hello")
+                .with_file("/work/noisy.log", "ignored")
+                .with_file("/work/sub/nested.txt", "world"),
+        );
 
         let tool = ListDirectoryTool::new();
-        let ctx = Context::new();
         let input = serde_json::json!({
-            "path": dir.path().to_string_lossy()
+            "path": "/work",
+            "recursive": true,
+            "respect_gitignore": true,
+            "compute_dir_sizes": true
         });
 
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(!output.is_error);
 
         let entries: Vec<DirectoryEntry> = serde_json::from_str(&output.content).unwrap();
-        assert_eq!(entries.len(), 3);
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert!(names.contains(&"keep.txt"));
+        assert!(!names.contains(&"noisy.log"));
+
+        let sub = entries.iter().find(|e| e.name == "sub").unwrap();
+        assert_eq!(sub.total_size, Some(5));
     }
 
     #[tokio::test]
     async fn test_search_files() {
-        let dir = TempDir::new().unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
         std::fs::write(dir.path().join("test1.txt"), "a").unwrap();
         std::fs::write(dir.path().join("test2.txt"), "b").unwrap();
         std::fs::write(dir.path().join("other.md"), "c").unwrap();
@@ -420,4 +927,106 @@ mod tests {
         let matches: Vec<String> = serde_json::from_str(&output.content).unwrap();
         assert_eq!(matches.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_copy_file() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/a.txt", "hello"));
+
+        let tool = CopyFileTool::new();
+        let input = serde_json::json!({ "source": "/work/a.txt", "destination": "/work/sub/b.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.metadata.get::<u64>("bytes_copied").unwrap(), 5);
+
+        let fs = ctx_fs(&ctx);
+        assert_eq!(fs.load(Path::new("/work/a.txt")).await.unwrap(), "hello");
+        assert_eq!(fs.load(Path::new("/work/sub/b.txt")).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_refuses_existing_destination_by_default() {
+        let ctx = ctx_with_fake(
+            FakeFs::new()
+                .with_file("/work/a.txt", "hello")
+                .with_file("/work/b.txt", "existing"),
+        );
+
+        let tool = CopyFileTool::new();
+        let input = serde_json::json!({ "source": "/work/a.txt", "destination": "/work/b.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(output.is_error);
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_missing_source_is_an_error() {
+        let ctx = ctx_with_fake(FakeFs::new());
+
+        let tool = CopyFileTool::new();
+        let input = serde_json::json!({ "source": "/work/missing.txt", "destination": "/work/b.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(output.is_error);
+        assert!(output.content.contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_move_file() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/a.txt", "hello"));
+
+        let tool = MoveFileTool::new();
+        let input = serde_json::json!({ "source": "/work/a.txt", "destination": "/work/sub/b.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.metadata.get::<u64>("bytes_moved").unwrap(), 5);
+
+        let fs = ctx_fs(&ctx);
+        assert!(fs.metadata(Path::new("/work/a.txt")).await.unwrap().is_none());
+        assert_eq!(fs.load(Path::new("/work/sub/b.txt")).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_delete_file() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/a.txt", "hello"));
+
+        let tool = DeleteFileTool::new();
+        let input = serde_json::json!({ "path": "/work/a.txt" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+
+        let fs = ctx_fs(&ctx);
+        assert!(fs.metadata(Path::new("/work/a.txt")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_directory_requires_recursive_when_not_empty() {
+        let ctx = ctx_with_fake(FakeFs::new().with_file("/work/sub/a.txt", "hello"));
+
+        let tool = DeleteFileTool::new();
+        let input = serde_json::json!({ "path": "/work/sub" });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(output.is_error);
+
+        let input = serde_json::json!({ "path": "/work/sub", "recursive": true });
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+
+        let fs = ctx_fs(&ctx);
+        assert!(fs.metadata(Path::new("/work/sub/a.txt")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_missing_path_ignored_when_requested() {
+        let ctx = ctx_with_fake(FakeFs::new());
+
+        let tool = DeleteFileTool::new();
+        let input = serde_json::json!({ "path": "/work/missing.txt", "ignore_if_not_exists": true });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+    }
 }
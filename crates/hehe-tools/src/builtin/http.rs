@@ -2,12 +2,18 @@ use crate::error::Result;
 use crate::traits::{Tool, ToolOutput};
 use async_trait::async_trait;
 use hehe_core::{Context, ToolDefinition, ToolParameter};
-use reqwest::{header::HeaderMap, Client, Method};
+use rand::Rng;
+use reqwest::{header::HeaderMap, Client, Method, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Duration;
 
+/// Status codes retried by default: request timeout, too-early, too-many-requests,
+/// and the 5xx statuses that are typically transient. Any other 4xx is treated
+/// as a client error that a retry can't fix.
+const DEFAULT_RETRY_ON: &[u16] = &[408, 425, 429, 500, 502, 503, 504];
+
 pub struct HttpRequestTool {
     def: ToolDefinition,
     client: Client,
@@ -43,6 +49,37 @@ impl HttpRequestTool {
                 ToolParameter::integer()
                     .with_description("Request timeout in milliseconds (default: 30000)")
                     .with_default(Value::Number(30000.into())),
+            )
+            .with_param(
+                "max_retries",
+                ToolParameter::integer()
+                    .with_description("Number of retries on transient failure (default: 0, no retry)")
+                    .with_default(Value::Number(0.into())),
+            )
+            .with_param(
+                "retry_base_ms",
+                ToolParameter::integer()
+                    .with_description("Base delay for exponential backoff between retries")
+                    .with_default(Value::Number(RetryPolicy::DEFAULT_BASE_MS.into())),
+            )
+            .with_param(
+                "retry_max_ms",
+                ToolParameter::integer()
+                    .with_description("Cap on the backoff delay between retries")
+                    .with_default(Value::Number(RetryPolicy::DEFAULT_MAX_MS.into())),
+            )
+            .with_param(
+                "retry_on",
+                ToolParameter::array(ToolParameter::integer())
+                    .with_description("HTTP status codes that trigger a retry (default: 408, 425, 429, 500, 502, 503, 504)"),
+            )
+            .with_param(
+                "respect_rate_limit",
+                ToolParameter::boolean()
+                    .with_description(
+                        "On a 429 response, wait until the Retry-After/X-RateLimit-Reset time (capped by the request timeout) and retry once",
+                    )
+                    .with_default(Value::Bool(false)),
             );
 
         let client = Client::builder()
@@ -70,6 +107,13 @@ struct HttpRequestInput {
     body: Option<String>,
     json: Option<Value>,
     timeout_ms: Option<u64>,
+    #[serde(default)]
+    max_retries: u32,
+    retry_base_ms: Option<u64>,
+    retry_max_ms: Option<u64>,
+    retry_on: Option<Vec<u16>>,
+    #[serde(default)]
+    respect_rate_limit: bool,
 }
 
 fn default_method() -> String {
@@ -82,6 +126,120 @@ struct HttpResponse {
     status_text: String,
     headers: HashMap<String, String>,
     body: String,
+    rate_limit: RateLimitInfo,
+}
+
+/// Rate-limit metadata parsed from the response, gathered from both the
+/// conventional `X-RateLimit-*` headers and the hyphenated `X-Rate-Limit-*`
+/// variant some providers use instead.
+#[derive(Default, Serialize)]
+struct RateLimitInfo {
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    /// Unix epoch seconds at which the limit resets.
+    reset: Option<u64>,
+    /// Seconds to wait, from the `Retry-After` header.
+    retry_after_secs: Option<u64>,
+}
+
+fn header_u64(headers: &reqwest::header::HeaderMap, names: &[&str]) -> Option<u64> {
+    names.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    })
+}
+
+fn parse_rate_limit_info(headers: &reqwest::header::HeaderMap) -> RateLimitInfo {
+    RateLimitInfo {
+        limit: header_u64(headers, &["x-ratelimit-limit", "x-rate-limit-limit"]),
+        remaining: header_u64(headers, &["x-ratelimit-remaining", "x-rate-limit-remaining"]),
+        reset: header_u64(headers, &["x-ratelimit-reset", "x-rate-limit-reset"]),
+        retry_after_secs: header_u64(headers, &["retry-after"]),
+    }
+}
+
+/// Duration until `reset` (a Unix epoch second timestamp), or `Duration::ZERO`
+/// if that time has already passed.
+fn duration_until(reset_epoch_secs: u64) -> Duration {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    Duration::from_secs(reset_epoch_secs.saturating_sub(now))
+}
+
+/// Retry behavior for a single `execute` call, built from the request's
+/// `max_retries`/`retry_base_ms`/`retry_max_ms`/`retry_on` fields.
+struct RetryPolicy {
+    max_retries: u32,
+    base_ms: u64,
+    max_ms: u64,
+    retry_on: Vec<u16>,
+}
+
+impl RetryPolicy {
+    const DEFAULT_BASE_MS: u64 = 200;
+    const DEFAULT_MAX_MS: u64 = 10_000;
+
+    fn from_input(input: &HttpRequestInput) -> Self {
+        Self {
+            max_retries: input.max_retries,
+            base_ms: input.retry_base_ms.unwrap_or(Self::DEFAULT_BASE_MS),
+            max_ms: input.retry_max_ms.unwrap_or(Self::DEFAULT_MAX_MS),
+            retry_on: input
+                .retry_on
+                .clone()
+                .unwrap_or_else(|| DEFAULT_RETRY_ON.to_vec()),
+        }
+    }
+
+    fn is_retryable_status(&self, status: u16) -> bool {
+        self.retry_on.contains(&status)
+    }
+}
+
+/// Full-jitter exponential backoff (as used by the AWS SDKs): for 0-indexed
+/// `attempt`, sleep a random duration between 0 and `min(max_ms, base_ms * 2^attempt)`.
+fn full_jitter_backoff(attempt: u32, base_ms: u64, max_ms: u64) -> Duration {
+    let upper = 2u64
+        .checked_pow(attempt)
+        .and_then(|factor| base_ms.checked_mul(factor))
+        .unwrap_or(u64::MAX)
+        .min(max_ms);
+    let delay_ms = rand::thread_rng().gen_range(0..=upper);
+    Duration::from_millis(delay_ms)
+}
+
+/// Parses a `Retry-After` header value, which is either a delta in seconds
+/// or an HTTP-date, into a concrete `Duration` to sleep for. An already-past
+/// date means "retry immediately".
+fn retry_after_delay(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let when = httpdate::parse_http_date(value.trim()).ok()?;
+    Some(
+        when.duration_since(std::time::SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+fn retry_after_from_headers(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    retry_after_delay(value)
+}
+
+fn connect_or_timeout_message(e: &reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Request timed out".to_string()
+    } else if e.is_connect() {
+        format!("Connection failed: {}", e)
+    } else {
+        format!("Request failed: {}", e)
+    }
 }
 
 #[async_trait]
@@ -106,13 +264,15 @@ impl Tool for HttpRequestTool {
             }
         };
 
+        let policy = RetryPolicy::from_input(&input);
+
         let mut request = self.client.request(method, &input.url);
 
         if let Some(timeout_ms) = input.timeout_ms {
             request = request.timeout(Duration::from_millis(timeout_ms));
         }
 
-        if let Some(headers) = input.headers {
+        if let Some(headers) = &input.headers {
             let mut header_map = HeaderMap::new();
             for (key, value) in headers {
                 if let (Ok(name), Ok(val)) = (
@@ -125,53 +285,96 @@ impl Tool for HttpRequestTool {
             request = request.headers(header_map);
         }
 
-        if let Some(json_body) = input.json {
-            request = request.json(&json_body);
-        } else if let Some(body) = input.body {
-            request = request.body(body);
+        if let Some(json_body) = &input.json {
+            request = request.json(json_body);
+        } else if let Some(body) = &input.body {
+            request = request.body(body.clone());
         }
 
-        match request.send().await {
-            Ok(response) => {
-                let status = response.status().as_u16();
-                let status_text = response.status().canonical_reason().unwrap_or("").to_string();
-                let headers: HashMap<String, String> = response
-                    .headers()
-                    .iter()
-                    .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-                    .collect();
-
-                let body = response.text().await.unwrap_or_default();
-
-                let http_response = HttpResponse {
-                    status,
-                    status_text,
-                    headers,
-                    body,
-                };
-
-                let output = ToolOutput::json(&http_response)?
-                    .with_metadata("url", &input.url)
-                    .with_metadata("status", status);
-
-                if status >= 400 {
-                    Ok(ToolOutput {
-                        is_error: true,
-                        ..output
-                    })
-                } else {
-                    Ok(output)
+        let timeout_cap = Duration::from_millis(input.timeout_ms.unwrap_or(30_000));
+
+        let mut attempts: u32 = 0;
+        let mut rate_limited_once = false;
+        let mut pending: Option<RequestBuilder> = Some(request);
+
+        loop {
+            let request = pending.take().expect("loop always repopulates `pending` before iterating");
+            // Only clone ahead of the send if a further retry is still possible,
+            // and only if the body is bufferable (streamed bodies can't be cloned).
+            let might_retry = attempts < policy.max_retries
+                || (input.respect_rate_limit && !rate_limited_once);
+            let retry_clone = if might_retry { request.try_clone() } else { None };
+
+            attempts += 1;
+            let outcome = request.send().await;
+
+            match outcome {
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let rate_limit = parse_rate_limit_info(response.headers());
+                    let retry_after = retry_after_from_headers(response.headers());
+
+                    let rate_limit_retry = status == 429 && input.respect_rate_limit && !rate_limited_once;
+                    if rate_limit_retry && retry_clone.is_some() {
+                        if let Some(delay) = retry_after.or_else(|| rate_limit.reset.map(duration_until)) {
+                            rate_limited_once = true;
+                            tokio::time::sleep(delay.min(timeout_cap)).await;
+                            pending = retry_clone;
+                            continue;
+                        }
+                    }
+
+                    if policy.is_retryable_status(status) && retry_clone.is_some() {
+                        let delay = retry_after
+                            .unwrap_or_else(|| full_jitter_backoff(attempts - 1, policy.base_ms, policy.max_ms));
+                        tokio::time::sleep(delay).await;
+                        pending = retry_clone;
+                        continue;
+                    }
+
+                    let status_text = response.status().canonical_reason().unwrap_or("").to_string();
+                    let headers: HashMap<String, String> = response
+                        .headers()
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+                        .collect();
+
+                    let body = response.text().await.unwrap_or_default();
+
+                    let http_response = HttpResponse {
+                        status,
+                        status_text,
+                        headers,
+                        body,
+                        rate_limit,
+                    };
+
+                    let output = ToolOutput::json(&http_response)?
+                        .with_metadata("url", &input.url)
+                        .with_metadata("status", status)
+                        .with_metadata("attempts", attempts);
+
+                    return Ok(if status >= 400 {
+                        ToolOutput {
+                            is_error: true,
+                            ..output
+                        }
+                    } else {
+                        output
+                    });
+                }
+                Err(e) => {
+                    let retryable = e.is_timeout() || e.is_connect();
+                    if retryable && retry_clone.is_some() {
+                        let delay = full_jitter_backoff(attempts - 1, policy.base_ms, policy.max_ms);
+                        tokio::time::sleep(delay).await;
+                        pending = retry_clone;
+                        continue;
+                    }
+
+                    let message = connect_or_timeout_message(&e);
+                    return Ok(ToolOutput::error(message).with_metadata("attempts", attempts));
                 }
-            }
-            Err(e) => {
-                let message = if e.is_timeout() {
-                    "Request timed out".to_string()
-                } else if e.is_connect() {
-                    format!("Connection failed: {}", e)
-                } else {
-                    format!("Request failed: {}", e)
-                };
-                Ok(ToolOutput::error(message))
             }
         }
     }
@@ -188,4 +391,61 @@ mod tests {
         assert_eq!(def.name, "http_request");
         assert!(!def.dangerous);
     }
+
+    #[test]
+    fn test_full_jitter_backoff_respects_cap() {
+        for attempt in 0..10 {
+            let delay = full_jitter_backoff(attempt, 200, 1_000);
+            assert!(delay <= Duration::from_millis(1_000));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_parses_delta_seconds() {
+        assert_eq!(retry_after_delay("120"), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_retry_after_rejects_garbage() {
+        assert_eq!(retry_after_delay("not-a-date"), None);
+    }
+
+    #[test]
+    fn test_header_u64_checks_all_name_variants() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-rate-limit-remaining", "7".parse().unwrap());
+        assert_eq!(
+            header_u64(&headers, &["x-ratelimit-remaining", "x-rate-limit-remaining"]),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn test_duration_until_saturates_at_zero_in_the_past() {
+        assert_eq!(duration_until(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_default_retry_policy_matches_documented_statuses() {
+        let input = HttpRequestInput {
+            url: "http://example.com".to_string(),
+            method: default_method(),
+            headers: None,
+            body: None,
+            json: None,
+            timeout_ms: None,
+            max_retries: 3,
+            retry_base_ms: None,
+            retry_max_ms: None,
+            retry_on: None,
+            respect_rate_limit: false,
+        };
+        let policy = RetryPolicy::from_input(&input);
+        assert_eq!(policy.base_ms, RetryPolicy::DEFAULT_BASE_MS);
+        assert_eq!(policy.max_ms, RetryPolicy::DEFAULT_MAX_MS);
+        for status in DEFAULT_RETRY_ON {
+            assert!(policy.is_retryable_status(*status));
+        }
+        assert!(!policy.is_retryable_status(404));
+    }
 }
@@ -1,16 +1,27 @@
 mod filesystem;
+mod walk;
 #[cfg(feature = "shell")]
 mod shell;
 #[cfg(feature = "http")]
 mod http;
 mod system;
+#[cfg(feature = "pty")]
+mod pty;
+#[cfg(feature = "watch")]
+mod watch;
 
-pub use filesystem::{ListDirectoryTool, ReadFileTool, SearchFilesTool, WriteFileTool};
+pub use filesystem::{
+    CopyFileTool, DeleteFileTool, ListDirectoryTool, MoveFileTool, ReadFileTool, SearchFilesTool, WriteFileTool,
+};
 #[cfg(feature = "shell")]
-pub use shell::ExecuteShellTool;
+pub use shell::{ExecuteShellTool, ShellOutputEvent, ShellStream};
 #[cfg(feature = "http")]
 pub use http::HttpRequestTool;
 pub use system::GetSystemInfoTool;
+#[cfg(feature = "pty")]
+pub use pty::PtyProcessTool;
+#[cfg(feature = "watch")]
+pub use watch::{FileWatchTool, FileWatcher};
 
 use crate::registry::ToolRegistry;
 use std::sync::Arc;
@@ -20,6 +31,9 @@ pub fn register_all(registry: &mut ToolRegistry) {
     registry.register(Arc::new(WriteFileTool::new())).ok();
     registry.register(Arc::new(ListDirectoryTool::new())).ok();
     registry.register(Arc::new(SearchFilesTool::new())).ok();
+    registry.register(Arc::new(CopyFileTool::new())).ok();
+    registry.register(Arc::new(MoveFileTool::new())).ok();
+    registry.register(Arc::new(DeleteFileTool::new())).ok();
     registry.register(Arc::new(GetSystemInfoTool::new())).ok();
 
     #[cfg(feature = "shell")]
@@ -27,6 +41,9 @@ pub fn register_all(registry: &mut ToolRegistry) {
 
     #[cfg(feature = "http")]
     registry.register(Arc::new(HttpRequestTool::new())).ok();
+
+    #[cfg(feature = "pty")]
+    registry.register(Arc::new(PtyProcessTool::new())).ok();
 }
 
 pub fn create_default_registry() -> ToolRegistry {
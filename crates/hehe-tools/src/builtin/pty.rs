@@ -0,0 +1,313 @@
+use crate::error::{Result, ToolError};
+use crate::traits::{Tool, ToolOutput};
+use async_trait::async_trait;
+use hehe_core::{Context, ToolDefinition, ToolParameter};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+struct PtySession {
+    master: Box<dyn MasterPty + Send>,
+    writer: Mutex<Box<dyn Write + Send>>,
+    child: Mutex<Box<dyn Child + Send + Sync>>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+/// A long-running, PTY-backed process tool. Unlike [`crate::ExecuteShellTool`], which
+/// runs a command to completion and returns, this keeps a process alive across calls:
+/// `start` spawns it attached to a pseudo-terminal, `write` sends it input, `read`
+/// drains whatever output has accumulated since the last read, and `kill` tears it
+/// down. A background thread continuously drains the PTY's output into an in-memory
+/// buffer so `read` never blocks waiting on the child.
+pub struct PtyProcessTool {
+    def: ToolDefinition,
+    sessions: Mutex<HashMap<String, PtySession>>,
+}
+
+impl PtyProcessTool {
+    pub fn new() -> Self {
+        let def = ToolDefinition::new(
+            "pty_process",
+            "Start, interact with, and stop a long-running process attached to a pseudo-terminal",
+        )
+        .with_required_param(
+            "action",
+            ToolParameter::string()
+                .with_description("One of: start, write, read, resize, kill")
+                .with_enum(vec![
+                    Value::String("start".into()),
+                    Value::String("write".into()),
+                    Value::String("read".into()),
+                    Value::String("resize".into()),
+                    Value::String("kill".into()),
+                ]),
+        )
+        .with_param(
+            "session_id",
+            ToolParameter::string()
+                .with_description("Session to operate on; required for all actions but `start`"),
+        )
+        .with_param(
+            "command",
+            ToolParameter::string().with_description("Program to launch (action: start)"),
+        )
+        .with_param(
+            "args",
+            ToolParameter::array(ToolParameter::string())
+                .with_description("Arguments to the program (action: start)"),
+        )
+        .with_param(
+            "working_dir",
+            ToolParameter::string().with_description("Working directory (action: start)"),
+        )
+        .with_param(
+            "input",
+            ToolParameter::string().with_description("Bytes to write to stdin (action: write)"),
+        )
+        .with_param(
+            "cols",
+            ToolParameter::integer().with_description("Terminal width (actions: start, resize)"),
+        )
+        .with_param(
+            "rows",
+            ToolParameter::integer().with_description("Terminal height (actions: start, resize)"),
+        )
+        .dangerous();
+
+        Self {
+            def,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn session_id(input: &PtyProcessInput) -> Result<&str> {
+        input
+            .session_id
+            .as_deref()
+            .ok_or_else(|| ToolError::invalid_input("session_id is required for this action"))
+    }
+
+    fn start(&self, input: &PtyProcessInput) -> Result<ToolOutput> {
+        let command = input
+            .command
+            .as_deref()
+            .ok_or_else(|| ToolError::invalid_input("command is required to start a process"))?;
+
+        let pty_system = native_pty_system();
+        let pair = pty_system
+            .openpty(PtySize {
+                rows: input.rows.unwrap_or(24),
+                cols: input.cols.unwrap_or(80),
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))?;
+
+        let mut cmd = CommandBuilder::new(command);
+        cmd.args(input.args.clone().unwrap_or_default());
+        if let Some(dir) = &input.working_dir {
+            cmd.cwd(dir);
+        }
+
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))?;
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))?;
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let output_for_reader = output.clone();
+        std::thread::spawn(move || {
+            let mut chunk = [0u8; 4096];
+            loop {
+                match reader.read(&mut chunk) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => output_for_reader.lock().unwrap().extend_from_slice(&chunk[..n]),
+                }
+            }
+        });
+
+        let session_id = Uuid::new_v4().to_string();
+        self.sessions.lock().unwrap().insert(
+            session_id.clone(),
+            PtySession {
+                master: pair.master,
+                writer: Mutex::new(writer),
+                child: Mutex::new(child),
+                output,
+            },
+        );
+
+        Ok(ToolOutput::json(&serde_json::json!({ "session_id": session_id }))?)
+    }
+
+    fn with_session<T>(&self, session_id: &str, f: impl FnOnce(&PtySession) -> Result<T>) -> Result<T> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| ToolError::invalid_input(format!("unknown pty session: {session_id}")))?;
+        f(session)
+    }
+
+    fn write(&self, input: &PtyProcessInput) -> Result<ToolOutput> {
+        let session_id = Self::session_id(input)?;
+        let data = input
+            .input
+            .as_deref()
+            .ok_or_else(|| ToolError::invalid_input("input is required to write"))?;
+
+        self.with_session(session_id, |session| {
+            let mut writer = session.writer.lock().unwrap();
+            writer.write_all(data.as_bytes())?;
+            writer.flush()?;
+            Ok(())
+        })?;
+
+        Ok(ToolOutput::text("written"))
+    }
+
+    fn read(&self, input: &PtyProcessInput) -> Result<ToolOutput> {
+        let session_id = Self::session_id(input)?;
+
+        let drained = self.with_session(session_id, |session| {
+            let mut buf = session.output.lock().unwrap();
+            Ok(std::mem::take(&mut *buf))
+        })?;
+
+        Ok(ToolOutput::text(String::from_utf8_lossy(&drained).into_owned()))
+    }
+
+    fn resize(&self, input: &PtyProcessInput) -> Result<ToolOutput> {
+        let session_id = Self::session_id(input)?;
+
+        self.with_session(session_id, |session| {
+            session
+                .master
+                .resize(PtySize {
+                    rows: input.rows.unwrap_or(24),
+                    cols: input.cols.unwrap_or(80),
+                    pixel_width: 0,
+                    pixel_height: 0,
+                })
+                .map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))
+        })?;
+
+        Ok(ToolOutput::text("resized"))
+    }
+
+    fn kill(&self, input: &PtyProcessInput) -> Result<ToolOutput> {
+        let session_id = Self::session_id(input)?;
+
+        let result = {
+            let sessions = self.sessions.lock().unwrap();
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| ToolError::invalid_input(format!("unknown pty session: {session_id}")))?;
+            session.child.lock().unwrap().kill()
+        };
+        result.map_err(|e| ToolError::execution_failed("pty_process", e.to_string()))?;
+
+        self.sessions.lock().unwrap().remove(session_id);
+        Ok(ToolOutput::text("killed"))
+    }
+}
+
+impl Default for PtyProcessTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct PtyProcessInput {
+    action: String,
+    session_id: Option<String>,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    working_dir: Option<String>,
+    input: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
+}
+
+#[async_trait]
+impl Tool for PtyProcessTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        if ctx.is_cancelled() {
+            return Err(ToolError::Cancelled);
+        }
+
+        let input: PtyProcessInput = serde_json::from_value(input)?;
+
+        match input.action.as_str() {
+            "start" => self.start(&input),
+            "write" => self.write(&input),
+            "read" => self.read(&input),
+            "resize" => self.resize(&input),
+            "kill" => self.kill(&input),
+            other => Err(ToolError::invalid_input(format!("unknown action: {other}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pty_process_echo_roundtrip() {
+        let tool = PtyProcessTool::new();
+        let ctx = Context::new();
+
+        let shell = if cfg!(target_os = "windows") { "cmd" } else { "sh" };
+        let start = tool
+            .execute(&ctx, serde_json::json!({"action": "start", "command": shell}))
+            .await
+            .unwrap();
+        let session_id: serde_json::Value = serde_json::from_str(&start.content).unwrap();
+        let session_id = session_id["session_id"].as_str().unwrap().to_string();
+
+        tool.execute(
+            &ctx,
+            serde_json::json!({"action": "write", "session_id": session_id, "input": "echo hello\n"}),
+        )
+        .await
+        .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+        let read = tool
+            .execute(&ctx, serde_json::json!({"action": "read", "session_id": session_id}))
+            .await
+            .unwrap();
+        assert!(read.content.contains("hello"));
+
+        tool.execute(&ctx, serde_json::json!({"action": "kill", "session_id": session_id}))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_definition_is_dangerous() {
+        let tool = PtyProcessTool::new();
+        assert_eq!(tool.definition().name, "pty_process");
+        assert!(tool.definition().dangerous);
+    }
+}
@@ -1,16 +1,27 @@
 use crate::error::{Result, ToolError};
 use crate::traits::{Tool, ToolOutput};
 use async_trait::async_trait;
-use hehe_core::{Context, ToolDefinition, ToolParameter};
+use hehe_core::{Context, Timestamp, ToolDefinition, ToolParameter};
+#[cfg(feature = "pty")]
+use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::io::AsyncReadExt;
+#[cfg(feature = "pty")]
+use std::io::{Read, Write};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
+use tokio::sync::{mpsc, Mutex};
 use tokio::time::timeout;
 
+/// Default terminal size for [`ExecuteShellInput::pty`] mode when `cols`/`rows`
+/// aren't given — the same default most terminal emulators start at.
+const DEFAULT_PTY_COLS: u16 = 80;
+const DEFAULT_PTY_ROWS: u16 = 24;
+
 pub struct ExecuteShellTool {
     def: ToolDefinition,
     default_timeout: Duration,
@@ -37,6 +48,30 @@ impl ExecuteShellTool {
                 "env",
                 ToolParameter::object().with_description("Environment variables to set"),
             )
+            .with_param(
+                "pty",
+                ToolParameter::boolean()
+                    .with_description(
+                        "Run the command attached to a pseudo-terminal instead of plain pipes, \
+                         so programs that behave differently on a real terminal (pagers, REPLs, \
+                         prompts, colorized output) see one. stdout and stderr are merged into a \
+                         single stream, matching what a terminal would show.",
+                    )
+                    .with_default(Value::Bool(false)),
+            )
+            .with_param(
+                "input",
+                ToolParameter::string()
+                    .with_description("Bytes to write to the PTY's master side once the command starts (pty mode only)"),
+            )
+            .with_param(
+                "cols",
+                ToolParameter::integer().with_description("Terminal width in columns (pty mode only, default: 80)"),
+            )
+            .with_param(
+                "rows",
+                ToolParameter::integer().with_description("Terminal height in rows (pty mode only, default: 24)"),
+            )
             .dangerous();
         Self {
             def,
@@ -48,6 +83,117 @@ impl ExecuteShellTool {
         self.default_timeout = timeout;
         self
     }
+
+    /// Like [`Tool::execute`], but reads `stdout`/`stderr` line-by-line as the
+    /// command produces them instead of waiting for it to exit, sending each
+    /// line to `tx` as a [`ShellOutputEvent`] so a caller can show live progress.
+    /// The final [`ShellOutput`] is still returned once the command exits — or,
+    /// if it times out, built from whatever lines were read up to that point
+    /// rather than being discarded.
+    pub async fn execute_streaming(
+        &self,
+        ctx: &Context,
+        input: Value,
+        tx: mpsc::Sender<ShellOutputEvent>,
+    ) -> Result<ShellOutput> {
+        let input: ExecuteShellInput = serde_json::from_value(input)?;
+
+        if ctx.is_cancelled() {
+            return Err(ToolError::Cancelled);
+        }
+
+        let timeout_duration = input
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self.default_timeout);
+
+        let shell = shell_command();
+        let mut cmd = Command::new(shell.0);
+        cmd.arg(shell.1).arg(&input.command);
+
+        if let Some(dir) = &input.working_dir {
+            cmd.current_dir(dir);
+        }
+        if let Some(env) = &input.env {
+            for (key, value) in env {
+                cmd.env(key, value);
+            }
+        }
+
+        cmd.stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null());
+
+        let mut child = cmd.spawn()?;
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+
+        let stdout_buf = Arc::new(Mutex::new(String::new()));
+        let stderr_buf = Arc::new(Mutex::new(String::new()));
+
+        let stdout_task = tokio::spawn(stream_lines(
+            stdout,
+            ShellStream::Stdout,
+            Arc::clone(&stdout_buf),
+            tx.clone(),
+        ));
+        let stderr_task = tokio::spawn(stream_lines(
+            stderr,
+            ShellStream::Stderr,
+            Arc::clone(&stderr_buf),
+            tx.clone(),
+        ));
+
+        let run = async {
+            let _ = stdout_task.await;
+            let _ = stderr_task.await;
+            child.wait().await
+        };
+
+        match timeout(timeout_duration, run).await {
+            Ok(Ok(status)) => Ok(ShellOutput {
+                exit_code: status.code(),
+                stdout: stdout_buf.lock().await.clone(),
+                stderr: stderr_buf.lock().await.clone(),
+                success: status.success(),
+            }),
+            Ok(Err(e)) => Err(ToolError::Io(e)),
+            Err(_) => {
+                let _ = child.start_kill();
+                Ok(ShellOutput {
+                    exit_code: None,
+                    stdout: stdout_buf.lock().await.clone(),
+                    stderr: stderr_buf.lock().await.clone(),
+                    success: false,
+                })
+            }
+        }
+    }
+}
+
+/// Reads `reader` line-by-line, appending each line (plus its trailing newline)
+/// to `buf` and forwarding it through `tx` as it arrives, until the stream ends.
+async fn stream_lines(
+    reader: impl tokio::io::AsyncRead + Unpin,
+    stream: ShellStream,
+    buf: Arc<Mutex<String>>,
+    tx: mpsc::Sender<ShellOutputEvent>,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        {
+            let mut buf = buf.lock().await;
+            buf.push_str(&line);
+            buf.push('\n');
+        }
+        let _ = tx
+            .send(ShellOutputEvent {
+                stream,
+                line,
+                timestamp: Timestamp::now(),
+            })
+            .await;
+    }
 }
 
 impl Default for ExecuteShellTool {
@@ -62,6 +208,11 @@ struct ExecuteShellInput {
     working_dir: Option<String>,
     timeout_ms: Option<u64>,
     env: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pty: bool,
+    input: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -72,77 +223,189 @@ struct ShellOutput {
     success: bool,
 }
 
-#[async_trait]
-impl Tool for ExecuteShellTool {
-    fn definition(&self) -> &ToolDefinition {
-        &self.def
+/// Which of a running command's output streams a [`ShellOutputEvent`] came from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellStream {
+    Stdout,
+    Stderr,
+}
+
+/// One line of output produced by a command running under
+/// [`ExecuteShellTool::execute_streaming`], emitted as soon as it's read rather
+/// than after the command exits.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShellOutputEvent {
+    pub stream: ShellStream,
+    pub line: String,
+    pub timestamp: Timestamp,
+}
+
+/// The shell and flag used to run a caller-supplied command string, platform-dependent.
+fn shell_command() -> (&'static str, &'static str) {
+    if cfg!(target_os = "windows") {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
     }
+}
 
-    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
-        let input: ExecuteShellInput = serde_json::from_value(input)?;
+/// Runs `input.command` with `stdout`/`stderr` buffered separately over plain pipes.
+/// Returns `(success, exit_code, stdout, stderr)`.
+async fn run_piped(input: &ExecuteShellInput) -> std::io::Result<(bool, Option<i32>, String, String)> {
+    let shell = shell_command();
 
-        if ctx.is_cancelled() {
-            return Err(ToolError::Cancelled);
+    let mut cmd = Command::new(shell.0);
+    cmd.arg(shell.1).arg(&input.command);
+
+    if let Some(dir) = &input.working_dir {
+        cmd.current_dir(dir);
+    }
+    if let Some(env) = &input.env {
+        for (key, value) in env {
+            cmd.env(key, value);
         }
+    }
 
-        let shell = if cfg!(target_os = "windows") {
-            ("cmd", "/C")
-        } else {
-            ("sh", "-c")
-        };
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdin(Stdio::null());
 
-        let mut cmd = Command::new(shell.0);
-        cmd.arg(shell.1).arg(&input.command);
+    let mut child = cmd.spawn()?;
 
-        if let Some(dir) = &input.working_dir {
-            cmd.current_dir(dir);
-        }
+    let mut stdout = String::new();
+    let mut stderr = String::new();
 
-        if let Some(env) = &input.env {
+    if let Some(mut stdout_handle) = child.stdout.take() {
+        stdout_handle.read_to_string(&mut stdout).await?;
+    }
+    if let Some(mut stderr_handle) = child.stderr.take() {
+        stderr_handle.read_to_string(&mut stderr).await?;
+    }
+
+    let status = child.wait().await?;
+
+    Ok((status.success(), status.code(), stdout, stderr))
+}
+
+/// Runs `input.command` attached to a pseudo-terminal via `portable_pty` — the same
+/// backend [`crate::builtin::pty::PtyProcessTool`] uses for long-running sessions —
+/// writing `input.input` (if any) to the PTY's master side once the command starts
+/// and reading back the combined, terminal-formatted output a real terminal would
+/// show. Returned as `stdout` with an empty `stderr` to keep [`ShellOutput`]'s shape
+/// unchanged — a PTY has no separate stderr stream to report.
+#[cfg(feature = "pty")]
+async fn run_in_pty(input: &ExecuteShellInput) -> std::io::Result<(bool, Option<i32>, String, String)> {
+    let command = input.command.clone();
+    let working_dir = input.working_dir.clone();
+    let env = input.env.clone();
+    let write_input = input.input.clone();
+    let cols = input.cols.unwrap_or(DEFAULT_PTY_COLS);
+    let rows = input.rows.unwrap_or(DEFAULT_PTY_ROWS);
+
+    tokio::task::spawn_blocking(move || -> std::io::Result<(bool, Option<i32>, String)> {
+        let shell = shell_command();
+
+        let pty_system = native_pty_system();
+        let pair = pty_system
+            .openpty(PtySize { rows, cols, pixel_width: 0, pixel_height: 0 })
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        let mut cmd = CommandBuilder::new(shell.0);
+        cmd.arg(shell.1);
+        cmd.arg(&command);
+        if let Some(dir) = &working_dir {
+            cmd.cwd(dir);
+        }
+        if let Some(env) = &env {
             for (key, value) in env {
                 cmd.env(key, value);
             }
         }
 
-        cmd.stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .stdin(Stdio::null());
+        let mut child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        // The slave side is only needed by the child; dropping our end here
+        // means the PTY's read side sees EOF once the child's copy closes at exit.
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        if let Some(data) = &write_input {
+            let mut writer = pair
+                .master
+                .take_writer()
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            writer.write_all(data.as_bytes())?;
+            writer.flush()?;
+        }
 
-        let timeout_duration = input
-            .timeout_ms
-            .map(Duration::from_millis)
-            .unwrap_or(self.default_timeout);
+        let mut combined = Vec::new();
+        // A master PTY reports the slave's hangup as a read error rather than a
+        // clean EOF once the child exits; that's expected, not a real failure,
+        // so whatever was read up to that point is kept.
+        let _ = reader.read_to_end(&mut combined);
+
+        let status = child.wait().map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        Ok((
+            status.success(),
+            Some(status.exit_code() as i32),
+            String::from_utf8_lossy(&combined).into_owned(),
+        ))
+    })
+    .await
+    .map_err(|e| std::io::Error::other(e.to_string()))?
+    .map(|(success, code, stdout)| (success, code, stdout, String::new()))
+}
 
-        let result = timeout(timeout_duration, async {
-            let mut child = cmd.spawn()?;
+#[cfg(not(feature = "pty"))]
+async fn run_in_pty(_input: &ExecuteShellInput) -> std::io::Result<(bool, Option<i32>, String, String)> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "execute_shell was built without the `pty` feature; pty mode is unavailable",
+    ))
+}
 
-            let mut stdout = String::new();
-            let mut stderr = String::new();
+#[async_trait]
+impl Tool for ExecuteShellTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
 
-            if let Some(mut stdout_handle) = child.stdout.take() {
-                stdout_handle.read_to_string(&mut stdout).await?;
-            }
-            if let Some(mut stderr_handle) = child.stderr.take() {
-                stderr_handle.read_to_string(&mut stderr).await?;
-            }
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        let input: ExecuteShellInput = serde_json::from_value(input)?;
+
+        if ctx.is_cancelled() {
+            return Err(ToolError::Cancelled);
+        }
 
-            let status = child.wait().await?;
+        let timeout_duration = input
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self.default_timeout);
 
-            Ok::<_, std::io::Error>((status, stdout, stderr))
-        })
-        .await;
+        let result = if input.pty {
+            timeout(timeout_duration, run_in_pty(&input)).await
+        } else {
+            timeout(timeout_duration, run_piped(&input)).await
+        };
 
         match result {
-            Ok(Ok((status, stdout, stderr))) => {
-                let exit_code = status.code();
+            Ok(Ok((success, exit_code, stdout, stderr))) => {
                 let output = ShellOutput {
                     exit_code,
                     stdout,
                     stderr,
-                    success: status.success(),
+                    success,
                 };
 
-                if status.success() {
+                if success {
                     ToolOutput::json(&output)?
                         .with_metadata("command", &input.command)
                         .with_metadata("exit_code", exit_code.unwrap_or(-1));
@@ -222,4 +485,62 @@ mod tests {
         let output = tool.execute(&ctx, input).await.unwrap();
         assert!(output.content.contains("timed out"));
     }
+
+    #[tokio::test]
+    async fn test_execute_streaming_emits_lines_as_they_are_produced() {
+        let tool = ExecuteShellTool::new();
+        let ctx = Context::new();
+        let input = serde_json::json!({
+            "command": "echo out_line; echo err_line >&2"
+        });
+        let (tx, mut rx) = mpsc::channel(16);
+
+        let output = tool.execute_streaming(&ctx, input, tx).await.unwrap();
+        assert!(output.success);
+        assert!(output.stdout.contains("out_line"));
+        assert!(output.stderr.contains("err_line"));
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert!(events.iter().any(|e| e.stream == ShellStream::Stdout && e.line == "out_line"));
+        assert!(events.iter().any(|e| e.stream == ShellStream::Stderr && e.line == "err_line"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_streaming_returns_partial_output_on_timeout() {
+        let tool = ExecuteShellTool::new();
+        let ctx = Context::new();
+        let input = serde_json::json!({
+            "command": "echo partial; sleep 10",
+            "timeout_ms": 200
+        });
+        let (tx, _rx) = mpsc::channel(16);
+
+        let output = tool.execute_streaming(&ctx, input, tx).await.unwrap();
+        assert!(!output.success);
+        assert_eq!(output.exit_code, None);
+        assert!(output.stdout.contains("partial"));
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "pty", not(target_os = "windows")))]
+    async fn test_execute_shell_pty_merges_output() {
+        let tool = ExecuteShellTool::new();
+        let ctx = Context::new();
+        let input = serde_json::json!({
+            "command": "echo out; echo err >&2",
+            "pty": true
+        });
+
+        let output = tool.execute(&ctx, input).await.unwrap();
+        assert!(!output.is_error);
+
+        let result: ShellOutput = serde_json::from_str(&output.content).unwrap();
+        assert!(result.success);
+        assert!(result.stdout.contains("out"));
+        assert!(result.stdout.contains("err"));
+        assert!(result.stderr.is_empty());
+    }
 }
@@ -0,0 +1,245 @@
+use crate::error::{Result, ToolError};
+use crate::fs::Fs;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+use super::filesystem::DirectoryEntry;
+
+/// Tuning knobs for [`walk_parallel`], surfaced as `ListDirectoryTool` params.
+#[derive(Clone, Default)]
+pub struct WalkOptions {
+    pub max_depth: Option<usize>,
+    pub follow_symlinks: bool,
+    pub respect_gitignore: bool,
+}
+
+type IgnoreChain = Arc<Vec<Gitignore>>;
+
+/// Walks `root` to completion, fanning directory reads out across tasks and
+/// collecting results through a [`JoinSet`] rather than recursing serially.
+/// Each directory that matches `respect_gitignore`'s rules is pruned before
+/// its children are ever read, and descent stops at `max_depth`.
+pub async fn walk_parallel(fs: Arc<dyn Fs>, root: PathBuf, options: WalkOptions) -> Result<Vec<DirectoryEntry>> {
+    let mut entries = Vec::new();
+    let mut join_set: JoinSet<Result<(Vec<DirectoryEntry>, Vec<(PathBuf, usize, IgnoreChain)>)>> = JoinSet::new();
+
+    join_set.spawn(walk_one_dir(fs.clone(), root, 0, Arc::new(Vec::new()), options.clone()));
+
+    while let Some(joined) = join_set.join_next().await {
+        let (dir_entries, children) =
+            joined.map_err(|e| ToolError::execution_failed("list_directory", e.to_string()))??;
+        entries.extend(dir_entries);
+
+        for (child_dir, depth, chain) in children {
+            join_set.spawn(walk_one_dir(fs.clone(), child_dir, depth, chain, options.clone()));
+        }
+    }
+
+    Ok(entries)
+}
+
+async fn walk_one_dir(
+    fs: Arc<dyn Fs>,
+    dir: PathBuf,
+    depth: usize,
+    mut chain: IgnoreChain,
+    options: WalkOptions,
+) -> Result<(Vec<DirectoryEntry>, Vec<(PathBuf, usize, IgnoreChain)>)> {
+    if options.respect_gitignore {
+        if let Some(gitignore) = build_gitignore(fs.as_ref(), &dir).await {
+            let mut extended = (*chain).clone();
+            extended.push(gitignore);
+            chain = Arc::new(extended);
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut children = Vec::new();
+
+    for raw in fs.read_dir(&dir).await? {
+        if is_ignored(&chain, &raw.path, raw.is_dir) {
+            continue;
+        }
+
+        let can_descend = raw.is_dir
+            && (options.follow_symlinks || !raw.is_symlink)
+            && options.max_depth.map(|max| depth + 1 < max).unwrap_or(true);
+
+        if can_descend {
+            children.push((raw.path.clone(), depth + 1, chain.clone()));
+        }
+
+        entries.push(DirectoryEntry {
+            name: raw.name,
+            path: raw.path.to_string_lossy().to_string(),
+            is_dir: raw.is_dir,
+            size: raw.size,
+            total_size: None,
+        });
+    }
+
+    Ok((entries, children))
+}
+
+/// Builds the combined `Gitignore` matcher for `dir` by parsing any
+/// `.gitignore`/`.ignore` file it contains through `fs` (never touching the
+/// real disk directly, so this works against [`crate::fs::FakeFs`] too).
+/// Returns `None` when neither file is present or yields any usable rule.
+async fn build_gitignore(fs: &dyn Fs, dir: &Path) -> Option<Gitignore> {
+    let mut builder = GitignoreBuilder::new(dir);
+    let mut added_any = false;
+
+    for name in [".gitignore", ".ignore"] {
+        if let Ok(content) = fs.load(&dir.join(name)).await {
+            for line in content.lines() {
+                if builder.add_line(None, line).is_ok() {
+                    added_any = true;
+                }
+            }
+        }
+    }
+
+    if !added_any {
+        return None;
+    }
+
+    builder.build().ok()
+}
+
+fn is_ignored(chain: &[Gitignore], path: &Path, is_dir: bool) -> bool {
+    chain.iter().any(|gitignore| gitignore.matched(path, is_dir).is_ignore())
+}
+
+/// Sums descendant file sizes into each directory entry's `total_size`,
+/// given a flat `entries` list produced by a full recursive walk.
+pub fn annotate_dir_sizes(entries: &mut [DirectoryEntry]) {
+    let totals: Vec<Option<u64>> = entries
+        .iter()
+        .map(|entry| {
+            if !entry.is_dir {
+                return None;
+            }
+            let dir_path = Path::new(&entry.path);
+            Some(
+                entries
+                    .iter()
+                    .filter(|candidate| !candidate.is_dir && Path::new(&candidate.path).starts_with(dir_path))
+                    .filter_map(|candidate| candidate.size)
+                    .sum(),
+            )
+        })
+        .collect();
+
+    for (entry, total) in entries.iter_mut().zip(totals) {
+        entry.total_size = total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::FakeFs;
+
+    #[tokio::test]
+    async fn test_walk_parallel_collects_nested_entries() {
+        let fs: Arc<dyn Fs> = Arc::new(
+            FakeFs::new()
+                .with_file("/repo/a.txt", "a")
+                .with_file("/repo/sub/b.txt", "b"),
+        );
+
+        let entries = walk_parallel(fs, PathBuf::from("/repo"), WalkOptions::default()).await.unwrap();
+
+        let mut names: Vec<_> = entries.iter().map(|e| e.name.clone()).collect();
+        names.sort();
+        assert_eq!(names, vec!["a.txt", "b.txt", "sub"]);
+    }
+
+    #[tokio::test]
+    async fn test_walk_parallel_respects_max_depth() {
+        let fs: Arc<dyn Fs> = Arc::new(
+            FakeFs::new()
+                .with_file("/repo/a.txt", "a")
+                .with_file("/repo/sub/b.txt", "b")
+                .with_file("/repo/sub/deeper/c.txt", "c"),
+        );
+
+        let entries = walk_parallel(
+            fs,
+            PathBuf::from("/repo"),
+            WalkOptions {
+                max_depth: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let names: Vec<_> = entries.iter().map(|e| e.name.clone()).collect();
+        assert!(names.contains(&"a.txt".to_string()));
+        assert!(names.contains(&"sub".to_string()));
+        assert!(!names.contains(&"b.txt".to_string()), "depth 1 should not descend into sub/");
+    }
+
+    #[tokio::test]
+    async fn test_walk_parallel_prunes_gitignored_entries() {
+        let fs: Arc<dyn Fs> = Arc::new(
+            FakeFs::new()
+                .with_file("/repo/.gitignore", "target/\n*.log\n")
+                .with_file("/repo/keep.txt", "keep")
+                .with_file("/repo/debug.log", "noisy")
+                .with_file("/repo/target/binary", "bin"),
+        );
+
+        let entries = walk_parallel(
+            fs,
+            PathBuf::from("/repo"),
+            WalkOptions {
+                respect_gitignore: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let names: Vec<_> = entries.iter().map(|e| e.name.clone()).collect();
+        assert!(names.contains(&"keep.txt".to_string()));
+        assert!(names.contains(&".gitignore".to_string()));
+        assert!(!names.contains(&"debug.log".to_string()));
+        assert!(!names.contains(&"target".to_string()));
+        assert!(!names.contains(&"binary".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_annotate_dir_sizes_sums_descendant_files() {
+        let mut entries = vec![
+            DirectoryEntry {
+                name: "sub".to_string(),
+                path: "/repo/sub".to_string(),
+                is_dir: true,
+                size: None,
+                total_size: None,
+            },
+            DirectoryEntry {
+                name: "a.txt".to_string(),
+                path: "/repo/sub/a.txt".to_string(),
+                is_dir: false,
+                size: Some(10),
+                total_size: None,
+            },
+            DirectoryEntry {
+                name: "b.txt".to_string(),
+                path: "/repo/sub/b.txt".to_string(),
+                is_dir: false,
+                size: Some(5),
+                total_size: None,
+            },
+        ];
+
+        annotate_dir_sizes(&mut entries);
+
+        assert_eq!(entries[0].total_size, Some(15));
+    }
+}
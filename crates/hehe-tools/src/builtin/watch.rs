@@ -0,0 +1,326 @@
+use crate::error::{Result, ToolError};
+use crate::traits::{Tool, ToolOutput};
+use async_trait::async_trait;
+use hehe_core::event::{Event, EventEmitter, FileChangeKind};
+use hehe_core::{Context, ToolDefinition, ToolParameter};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Matches a single `*`-wildcard glob pattern against a path's file name, or, if
+/// the pattern has no wildcard, treats it as a plain extension/suffix (e.g. `.rs`).
+fn matches_filter(path: &Path, pattern: &str) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => name.starts_with(prefix) && name.ends_with(suffix),
+        None => name.ends_with(pattern),
+    }
+}
+
+fn passes_filters(path: &Path, filters: &[String]) -> bool {
+    filters.is_empty() || filters.iter().any(|f| matches_filter(path, f))
+}
+
+/// A single registered watch: owns the OS watcher and a stop flag for its debounce
+/// thread. Dropping this (or calling [`FileWatcher::unwatch`]) tears both down.
+struct WatchHandle {
+    _watcher: RecommendedWatcher,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Watches paths on disk and emits [`hehe_core::event::EventKind::FileChanged`]
+/// events through an [`EventEmitter`] whenever something under them changes.
+///
+/// Raw OS notifications are debounced: a burst of writes to the same file within
+/// the debounce window collapses into a single event, emitted once things go
+/// quiet. Each watch can recurse into subdirectories and can be scoped to paths
+/// matching a set of simple glob/extension filters (e.g. `["*.rs", ".toml"]`).
+/// This is usable standalone as a background event source, or through
+/// [`FileWatchTool`] as a model-callable start/stop surface.
+pub struct FileWatcher {
+    emitter: Arc<dyn EventEmitter>,
+    debounce: Duration,
+    watches: Mutex<HashMap<String, WatchHandle>>,
+}
+
+impl FileWatcher {
+    pub fn new(emitter: Arc<dyn EventEmitter>) -> Self {
+        Self {
+            emitter,
+            debounce: Duration::from_millis(300),
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Start watching `path`, returning a handle id that [`Self::unwatch`] accepts.
+    pub fn watch(
+        &self,
+        path: impl Into<PathBuf>,
+        recursive: bool,
+        filters: Vec<String>,
+    ) -> Result<String> {
+        let path = path.into();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(tx)
+            .map_err(|e| ToolError::execution_failed("file_watch", e.to_string()))?;
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher
+            .watch(&path, mode)
+            .map_err(|e| ToolError::execution_failed("file_watch", e.to_string()))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let debounce = self.debounce;
+        let emitter = self.emitter.clone();
+        let runtime = tokio::runtime::Handle::current();
+        let thread_stop = stop.clone();
+
+        std::thread::spawn(move || {
+            let mut pending: HashSet<(PathBuf, FileChangeKind)> = HashSet::new();
+
+            loop {
+                if thread_stop.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                match rx.recv_timeout(debounce) {
+                    Ok(Ok(raw_event)) => {
+                        let kind = classify(&raw_event.kind);
+                        for changed in raw_event.paths {
+                            if passes_filters(&changed, &filters) {
+                                if let Some(kind) = kind {
+                                    pending.insert((changed, kind));
+                                }
+                            }
+                        }
+                    }
+                    Ok(Err(_)) => continue,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                        if !pending.is_empty() {
+                            let flushed: Vec<_> = pending.drain().collect();
+                            let emitter = emitter.clone();
+                            runtime.spawn(async move {
+                                for (path, kind) in flushed {
+                                    emitter
+                                        .emit(Event::file_changed(path.to_string_lossy(), kind))
+                                        .await;
+                                }
+                            });
+                        }
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        });
+
+        let id = Uuid::new_v4().to_string();
+        self.watches.lock().unwrap().insert(
+            id.clone(),
+            WatchHandle {
+                _watcher: watcher,
+                stop,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Stop a watch and tear down its debounce thread. Returns `false` if `id` was
+    /// already unknown (e.g. previously unwatched).
+    pub fn unwatch(&self, id: &str) -> bool {
+        self.watches.lock().unwrap().remove(id).is_some()
+    }
+
+    pub fn active_watch_count(&self) -> usize {
+        self.watches.lock().unwrap().len()
+    }
+}
+
+fn classify(kind: &notify::EventKind) -> Option<FileChangeKind> {
+    use notify::EventKind as NK;
+    match kind {
+        NK::Create(_) => Some(FileChangeKind::Created),
+        NK::Modify(_) => Some(FileChangeKind::Modified),
+        NK::Remove(_) => Some(FileChangeKind::Removed),
+        _ => None,
+    }
+}
+
+/// Lets the model start and stop [`FileWatcher`] watches by path.
+pub struct FileWatchTool {
+    def: ToolDefinition,
+    watcher: FileWatcher,
+}
+
+impl FileWatchTool {
+    pub fn new(emitter: Arc<dyn EventEmitter>) -> Self {
+        let def = ToolDefinition::new(
+            "file_watch",
+            "Start or stop watching a path on disk for changes, emitting file_changed events",
+        )
+        .with_required_param(
+            "action",
+            ToolParameter::string()
+                .with_description("One of: start, stop")
+                .with_enum(vec![Value::String("start".into()), Value::String("stop".into())]),
+        )
+        .with_param(
+            "path",
+            ToolParameter::string().with_description("Path to watch (action: start)"),
+        )
+        .with_param(
+            "watch_id",
+            ToolParameter::string().with_description("Watch to stop (action: stop)"),
+        )
+        .with_param(
+            "recursive",
+            ToolParameter::boolean().with_description("Watch subdirectories too (action: start, default: true)"),
+        )
+        .with_param(
+            "filters",
+            ToolParameter::array(ToolParameter::string())
+                .with_description("Simple glob/extension filters, e.g. [\"*.rs\", \".toml\"] (action: start)"),
+        );
+
+        Self {
+            def,
+            watcher: FileWatcher::new(emitter),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FileWatchInput {
+    action: String,
+    path: Option<String>,
+    watch_id: Option<String>,
+    #[serde(default)]
+    recursive: Option<bool>,
+    #[serde(default)]
+    filters: Vec<String>,
+}
+
+#[async_trait]
+impl Tool for FileWatchTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        if ctx.is_cancelled() {
+            return Err(ToolError::Cancelled);
+        }
+
+        let input: FileWatchInput = serde_json::from_value(input)?;
+
+        match input.action.as_str() {
+            "start" => {
+                let path = input
+                    .path
+                    .ok_or_else(|| ToolError::invalid_input("path is required to start a watch"))?;
+                let watch_id = self.watcher.watch(path, input.recursive.unwrap_or(true), input.filters)?;
+                Ok(ToolOutput::json(&serde_json::json!({ "watch_id": watch_id }))?)
+            }
+            "stop" => {
+                let watch_id = input
+                    .watch_id
+                    .ok_or_else(|| ToolError::invalid_input("watch_id is required to stop a watch"))?;
+                if self.watcher.unwatch(&watch_id) {
+                    Ok(ToolOutput::text("stopped"))
+                } else {
+                    Err(ToolError::invalid_input(format!("unknown watch_id: {watch_id}")))
+                }
+            }
+            other => Err(ToolError::invalid_input(format!("unknown action: {other}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hehe_core::event::{EventKind, EventSubscriber};
+    use std::sync::Mutex as StdMutex;
+
+    struct CollectingSubscriber {
+        seen: StdMutex<Vec<Event>>,
+    }
+
+    #[async_trait]
+    impl EventEmitter for CollectingSubscriber {
+        async fn emit(&self, event: Event) {
+            self.seen.lock().unwrap().push(event);
+        }
+    }
+
+    #[async_trait]
+    impl EventSubscriber for CollectingSubscriber {
+        fn event_kinds(&self) -> Vec<EventKind> {
+            vec![EventKind::FileChanged]
+        }
+
+        async fn on_event(&self, _event: &Event) -> hehe_core::error::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_matches_filter_wildcard_and_plain() {
+        assert!(matches_filter(Path::new("/tmp/main.rs"), "*.rs"));
+        assert!(!matches_filter(Path::new("/tmp/main.rs"), "*.toml"));
+        assert!(matches_filter(Path::new("/tmp/Cargo.toml"), ".toml"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_emits_event_on_write() {
+        let dir = std::env::temp_dir().join(format!("hehe-watch-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let emitter = Arc::new(CollectingSubscriber {
+            seen: StdMutex::new(Vec::new()),
+        });
+        let watcher = FileWatcher::new(emitter.clone()).with_debounce(Duration::from_millis(50));
+        let watch_id = watcher.watch(&dir, false, vec![]).unwrap();
+
+        std::fs::write(dir.join("file.txt"), b"hello").unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        assert!(!emitter.seen.lock().unwrap().is_empty());
+
+        assert!(watcher.unwatch(&watch_id));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_definition_has_start_and_stop() {
+        let tool = FileWatchTool::new(Arc::new(CollectingSubscriber {
+            seen: StdMutex::new(Vec::new()),
+        }));
+        assert_eq!(tool.definition().name, "file_watch");
+        let action = &tool.definition().parameters.properties.as_ref().unwrap()["action"];
+        assert_eq!(
+            action.enum_values,
+            Some(vec![Value::String("start".into()), Value::String("stop".into())])
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::{Result, ToolError};
 use crate::registry::ToolRegistry;
 use crate::traits::ToolOutput;
+use futures::stream::{self, StreamExt};
 use hehe_core::{Context, ToolCall, ToolCallStatus};
 use serde_json::Value;
 use std::sync::Arc;
@@ -8,6 +9,18 @@ use std::time::Duration;
 use tokio::time::timeout;
 use tracing::{info, warn};
 
+/// Fallback for [`default_concurrency`] on platforms that won't report a core count.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How many tool calls [`ToolExecutor::execute_many`] runs at once when the
+/// caller doesn't tune it: the available CPU parallelism, or
+/// [`DEFAULT_CONCURRENCY`] if that can't be determined.
+pub fn default_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
 pub struct ToolExecutor {
     registry: Arc<ToolRegistry>,
     default_timeout: Duration,
@@ -94,6 +107,56 @@ impl ToolExecutor {
         }
     }
 
+    /// Run a batch of tool calls concurrently, bounded by `concurrency_limit` in flight
+    /// at once, and return their outcomes in the same order the calls were given.
+    ///
+    /// This is the building block for multi-step agentic loops: a single model turn can
+    /// request several independent tool calls, and there's no reason to run them one at a
+    /// time. Dangerous tools that still need confirmation, and calls made after the context
+    /// is cancelled, fail fast without ever reaching [`Tool::execute`]. If `ctx` is cancelled
+    /// while calls are still in flight, each one still running stops being polled as soon as
+    /// its current `.await` point yields, rather than waiting for the whole batch to finish.
+    pub async fn execute_many(
+        &self,
+        ctx: &Context,
+        calls: Vec<ToolCall>,
+        concurrency_limit: usize,
+    ) -> Vec<(ToolCall, Result<ToolOutput>)> {
+        let limit = concurrency_limit.max(1);
+        let total = calls.len();
+
+        let futures = calls.into_iter().enumerate().map(|(idx, mut call)| {
+            let ctx = ctx.child();
+            async move {
+                let result = if ctx.is_cancelled() {
+                    Err(ToolError::Cancelled)
+                } else if self.needs_confirmation(&call.name) {
+                    Err(ToolError::permission_denied(format!(
+                        "tool '{}' is dangerous and requires confirmation before execution",
+                        call.name
+                    )))
+                } else {
+                    tokio::select! {
+                        biased;
+                        _ = ctx.cancellation_token().cancelled() => Err(ToolError::Cancelled),
+                        result = self.execute_call(&ctx, &mut call) => result,
+                    }
+                };
+                (idx, call, result)
+            }
+        });
+
+        let mut ordered: Vec<Option<(ToolCall, Result<ToolOutput>)>> =
+            (0..total).map(|_| None).collect();
+
+        let mut results = stream::iter(futures).buffer_unordered(limit);
+        while let Some((idx, call, result)) = results.next().await {
+            ordered[idx] = Some((call, result));
+        }
+
+        ordered.into_iter().map(|r| r.expect("every index is filled exactly once")).collect()
+    }
+
     pub fn registry(&self) -> &ToolRegistry {
         &self.registry
     }
@@ -215,8 +278,75 @@ mod tests {
         assert!(call.is_pending());
 
         let output = executor.execute_call(&ctx, &mut call).await.unwrap();
-        
+
         assert!(call.is_completed());
         assert!(!output.is_error);
     }
+
+    #[tokio::test]
+    async fn test_execute_many_preserves_order() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(EchoTool::new())).unwrap();
+
+        let executor = ToolExecutor::new(Arc::new(registry));
+        let ctx = Context::new();
+
+        let calls: Vec<ToolCall> = (0..5)
+            .map(|i| ToolCall::new("echo", serde_json::json!({"i": i})))
+            .collect();
+
+        let results = executor.execute_many(&ctx, calls, 2).await;
+
+        assert_eq!(results.len(), 5);
+        for (i, (call, result)) in results.iter().enumerate() {
+            let output = result.as_ref().unwrap();
+            assert!(output.content.contains(&i.to_string()));
+            assert!(call.is_completed());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_many_requires_confirmation() {
+        let mut registry = ToolRegistry::new();
+        let def = ToolDefinition::new("danger", "Does something risky").dangerous();
+        registry
+            .register(Arc::new(EchoTool { def }))
+            .unwrap();
+
+        let executor = ToolExecutor::new(Arc::new(registry));
+        let ctx = Context::new();
+
+        let calls = vec![ToolCall::new("danger", Value::Null)];
+        let results = executor.execute_many(&ctx, calls, 1).await;
+
+        assert!(results[0].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_many_aborts_in_flight_calls_on_cancellation() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(SlowTool::new())).unwrap();
+
+        let executor = Arc::new(ToolExecutor::new(Arc::new(registry)));
+        let ctx = Context::new();
+        let cancel_handle = ctx.clone();
+
+        let calls: Vec<ToolCall> = (0..3).map(|_| ToolCall::new("slow", Value::Null)).collect();
+
+        let exec = Arc::clone(&executor);
+        let handle = tokio::spawn(async move { exec.execute_many(&ctx, calls, 3).await });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel_handle.cancel();
+
+        // SlowTool sleeps for 10s; a 1s timeout proves the batch aborted as
+        // soon as cancellation fired rather than waiting for it to finish.
+        let results = tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("batch should abort promptly once cancelled")
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|(_, r)| matches!(r, Err(ToolError::Cancelled))));
+    }
 }
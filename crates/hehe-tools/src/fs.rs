@@ -0,0 +1,501 @@
+use crate::error::{Result, ToolError};
+use async_trait::async_trait;
+use hehe_core::Context;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Options for [`Fs::create_file`] and [`Fs::create_dir`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CreateOptions {
+    pub overwrite: bool,
+    pub ignore_if_exists: bool,
+}
+
+/// Options for [`Fs::remove_file`] and [`Fs::remove_dir`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RemoveOptions {
+    pub recursive: bool,
+    pub ignore_if_not_exists: bool,
+}
+
+/// Options for [`Fs::rename`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenameOptions {
+    pub overwrite: bool,
+    pub ignore_if_exists: bool,
+}
+
+/// Options for [`Fs::copy_file`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CopyOptions {
+    pub overwrite: bool,
+    pub ignore_if_exists: bool,
+}
+
+/// Metadata about a single filesystem entry, as returned by [`Fs::metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FsMetadata {
+    pub is_dir: bool,
+    pub is_file: bool,
+    pub len: u64,
+}
+
+/// A single entry yielded by [`Fs::read_dir`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: Option<u64>,
+    pub is_symlink: bool,
+}
+
+/// Abstracts file I/O behind a trait so tools can be tested without touching
+/// the real disk and so a host embedding hehe can jail or mock the agent's
+/// filesystem access entirely. [`RealFs`] is the production backend; tools
+/// read the backend to use from `ctx`'s typed extensions, falling back to
+/// [`RealFs`] when none is set.
+#[async_trait]
+pub trait Fs: Send + Sync {
+    async fn create_dir(&self, path: &Path) -> Result<()>;
+
+    async fn create_file(&self, path: &Path, contents: &[u8], options: CreateOptions) -> Result<()>;
+
+    async fn copy_file(&self, from: &Path, to: &Path, options: CopyOptions) -> Result<()>;
+
+    async fn rename(&self, from: &Path, to: &Path, options: RenameOptions) -> Result<()>;
+
+    async fn remove_file(&self, path: &Path, options: RemoveOptions) -> Result<()>;
+
+    async fn remove_dir(&self, path: &Path, options: RemoveOptions) -> Result<()>;
+
+    async fn load(&self, path: &Path) -> Result<String>;
+
+    /// Reads a file's raw bytes without assuming any particular encoding.
+    /// Callers that need to decode with something other than UTF-8 (e.g.
+    /// `ReadFileTool`'s `encoding` parameter) should use this instead of
+    /// [`Fs::load`].
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>>;
+
+    async fn read_dir(&self, path: &Path) -> Result<Vec<DirEntry>>;
+
+    async fn metadata(&self, path: &Path) -> Result<Option<FsMetadata>>;
+}
+
+/// Reads the `Arc<dyn Fs>` attached to `ctx` via [`hehe_core::Context::with_extension`],
+/// defaulting to a shared [`RealFs`] when the context has none set.
+pub fn ctx_fs(ctx: &Context) -> Arc<dyn Fs> {
+    static DEFAULT: std::sync::OnceLock<Arc<dyn Fs>> = std::sync::OnceLock::new();
+
+    ctx.get_extension_typed::<Arc<dyn Fs>>()
+        .cloned()
+        .unwrap_or_else(|| DEFAULT.get_or_init(|| Arc::new(RealFs) as Arc<dyn Fs>).clone())
+}
+
+/// The production [`Fs`] backend: every operation maps directly onto `tokio::fs`.
+pub struct RealFs;
+
+#[async_trait]
+impl Fs for RealFs {
+    async fn create_dir(&self, path: &Path) -> Result<()> {
+        tokio::fs::create_dir_all(path).await?;
+        Ok(())
+    }
+
+    async fn create_file(&self, path: &Path, contents: &[u8], options: CreateOptions) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+
+        if !options.overwrite && tokio::fs::try_exists(path).await? {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!(
+                "File already exists: {}",
+                path.display()
+            )));
+        }
+
+        tokio::fs::write(path, contents).await?;
+        Ok(())
+    }
+
+    async fn copy_file(&self, from: &Path, to: &Path, options: CopyOptions) -> Result<()> {
+        if !options.overwrite && tokio::fs::try_exists(to).await? {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!(
+                "File already exists: {}",
+                to.display()
+            )));
+        }
+        if let Some(parent) = to.parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+        tokio::fs::copy(from, to).await?;
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path, options: RenameOptions) -> Result<()> {
+        if !options.overwrite && tokio::fs::try_exists(to).await? {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!(
+                "File already exists: {}",
+                to.display()
+            )));
+        }
+        if let Some(parent) = to.parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+        tokio::fs::rename(from, to).await?;
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && options.ignore_if_not_exists => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove_dir(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        let result = if options.recursive {
+            tokio::fs::remove_dir_all(path).await
+        } else {
+            tokio::fs::remove_dir(path).await
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && options.ignore_if_not_exists => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn load(&self, path: &Path) -> Result<String> {
+        Ok(tokio::fs::read_to_string(path).await?)
+    }
+
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        Ok(tokio::fs::read(path).await?)
+    }
+
+    async fn read_dir(&self, path: &Path) -> Result<Vec<DirEntry>> {
+        let mut read_dir = tokio::fs::read_dir(path).await?;
+        let mut entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            let is_symlink = entry.file_type().await?.is_symlink();
+            entries.push(DirEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: entry.path(),
+                is_dir: metadata.is_dir(),
+                size: if metadata.is_file() { Some(metadata.len()) } else { None },
+                is_symlink,
+            });
+        }
+        Ok(entries)
+    }
+
+    async fn metadata(&self, path: &Path) -> Result<Option<FsMetadata>> {
+        match tokio::fs::metadata(path).await {
+            Ok(metadata) => Ok(Some(FsMetadata {
+                is_dir: metadata.is_dir(),
+                is_file: metadata.is_file(),
+                len: metadata.len(),
+            })),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum FakeNode {
+    File(Vec<u8>),
+    Dir,
+}
+
+/// An in-memory [`Fs`] backend for tests: no `TempDir`, no real disk access,
+/// fully deterministic. Paths are keyed exactly as given (not canonicalized),
+/// so tests should be consistent about using absolute paths like `/work/a.txt`.
+#[derive(Clone, Default)]
+pub struct FakeFs {
+    nodes: Arc<Mutex<HashMap<PathBuf, FakeNode>>>,
+}
+
+impl FakeFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds the fake filesystem with a file's contents, creating parent
+    /// directories along the way. Handy for test setup without round-tripping
+    /// through `create_file`.
+    pub fn with_file(self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Self {
+        let path = path.into();
+        self.seed_parents(&path);
+        self.nodes.lock().unwrap().insert(path, FakeNode::File(contents.into()));
+        self
+    }
+
+    fn seed_parents(&self, path: &Path) {
+        let mut nodes = self.nodes.lock().unwrap();
+        Self::seed_parents_locked(&mut nodes, path);
+    }
+
+    /// Same as [`Self::seed_parents`], but for callers that already hold the
+    /// `nodes` lock (avoids re-entering `Mutex::lock`, which would deadlock).
+    fn seed_parents_locked(nodes: &mut HashMap<PathBuf, FakeNode>, path: &Path) {
+        let mut ancestor = path.parent();
+        while let Some(dir) = ancestor {
+            if dir.as_os_str().is_empty() {
+                break;
+            }
+            nodes.entry(dir.to_path_buf()).or_insert(FakeNode::Dir);
+            ancestor = dir.parent();
+        }
+    }
+}
+
+#[async_trait]
+impl Fs for FakeFs {
+    async fn create_dir(&self, path: &Path) -> Result<()> {
+        self.seed_parents(path);
+        self.nodes.lock().unwrap().insert(path.to_path_buf(), FakeNode::Dir);
+        Ok(())
+    }
+
+    async fn create_file(&self, path: &Path, contents: &[u8], options: CreateOptions) -> Result<()> {
+        self.seed_parents(path);
+        let mut nodes = self.nodes.lock().unwrap();
+        if !options.overwrite && nodes.contains_key(path) {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!(
+                "File already exists: {}",
+                path.display()
+            )));
+        }
+        nodes.insert(path.to_path_buf(), FakeNode::File(contents.to_vec()));
+        Ok(())
+    }
+
+    async fn copy_file(&self, from: &Path, to: &Path, options: CopyOptions) -> Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        let contents = match nodes.get(from) {
+            Some(FakeNode::File(contents)) => contents.clone(),
+            Some(FakeNode::Dir) => {
+                return Err(ToolError::invalid_input(format!("Not a file: {}", from.display())))
+            }
+            None => return Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound))),
+        };
+        if !options.overwrite && nodes.contains_key(to) {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!("File already exists: {}", to.display())));
+        }
+        Self::seed_parents_locked(&mut nodes, to);
+        nodes.insert(to.to_path_buf(), FakeNode::File(contents));
+        Ok(())
+    }
+
+    async fn rename(&self, from: &Path, to: &Path, options: RenameOptions) -> Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        if !options.overwrite && nodes.contains_key(to) {
+            if options.ignore_if_exists {
+                return Ok(());
+            }
+            return Err(ToolError::invalid_input(format!("File already exists: {}", to.display())));
+        }
+        let node = nodes
+            .remove(from)
+            .ok_or_else(|| ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)))?;
+        Self::seed_parents_locked(&mut nodes, to);
+        nodes.insert(to.to_path_buf(), node);
+        Ok(())
+    }
+
+    async fn remove_file(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        match nodes.remove(path) {
+            Some(_) => Ok(()),
+            None if options.ignore_if_not_exists => Ok(()),
+            None => Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound))),
+        }
+    }
+
+    async fn remove_dir(&self, path: &Path, options: RemoveOptions) -> Result<()> {
+        let mut nodes = self.nodes.lock().unwrap();
+        if !nodes.contains_key(path) {
+            return if options.ignore_if_not_exists {
+                Ok(())
+            } else {
+                Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)))
+            };
+        }
+
+        let has_children = nodes.keys().any(|p| p != path && p.starts_with(path));
+        if has_children && !options.recursive {
+            return Err(ToolError::invalid_input(format!(
+                "Directory not empty: {}",
+                path.display()
+            )));
+        }
+
+        nodes.retain(|p, _| p != path && !p.starts_with(path));
+        Ok(())
+    }
+
+    async fn load(&self, path: &Path) -> Result<String> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(FakeNode::File(contents)) => String::from_utf8(contents.clone())
+                .map_err(|e| ToolError::invalid_input(format!("File is not valid UTF-8: {e}"))),
+            Some(FakeNode::Dir) => Err(ToolError::invalid_input(format!("Not a file: {}", path.display()))),
+            None => Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound))),
+        }
+    }
+
+    async fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(FakeNode::File(contents)) => Ok(contents.clone()),
+            Some(FakeNode::Dir) => Err(ToolError::invalid_input(format!("Not a file: {}", path.display()))),
+            None => Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound))),
+        }
+    }
+
+    async fn read_dir(&self, path: &Path) -> Result<Vec<DirEntry>> {
+        let nodes = self.nodes.lock().unwrap();
+        if !matches!(nodes.get(path), Some(FakeNode::Dir)) {
+            return Err(ToolError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)));
+        }
+
+        let mut entries = Vec::new();
+        for (candidate, node) in nodes.iter() {
+            if candidate.parent() == Some(path) {
+                let (is_dir, size) = match node {
+                    FakeNode::Dir => (true, None),
+                    FakeNode::File(contents) => (false, Some(contents.len() as u64)),
+                };
+                entries.push(DirEntry {
+                    name: candidate.file_name().unwrap().to_string_lossy().to_string(),
+                    path: candidate.clone(),
+                    is_dir,
+                    size,
+                    is_symlink: false,
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn metadata(&self, path: &Path) -> Result<Option<FsMetadata>> {
+        let nodes = self.nodes.lock().unwrap();
+        Ok(nodes.get(path).map(|node| match node {
+            FakeNode::Dir => FsMetadata {
+                is_dir: true,
+                is_file: false,
+                len: 0,
+            },
+            FakeNode::File(contents) => FsMetadata {
+                is_dir: false,
+                is_file: true,
+                len: contents.len() as u64,
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fake_fs_create_and_load_roundtrip() {
+        let fs = FakeFs::new();
+        fs.create_file(Path::new("/work/a.txt"), b"hello", CreateOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fs.load(Path::new("/work/a.txt")).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_fake_fs_create_file_refuses_overwrite_by_default() {
+        let fs = FakeFs::new().with_file("/work/a.txt", "original");
+
+        let err = fs
+            .create_file(Path::new("/work/a.txt"), b"new", CreateOptions::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ToolError::InvalidInput(_)));
+    }
+
+    #[tokio::test]
+    async fn test_fake_fs_read_dir_lists_direct_children() {
+        let fs = FakeFs::new()
+            .with_file("/work/a.txt", "a")
+            .with_file("/work/sub/b.txt", "b");
+
+        let mut entries = fs.read_dir(Path::new("/work")).await.unwrap();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "a.txt");
+        assert!(!entries[0].is_dir);
+        assert_eq!(entries[1].name, "sub");
+        assert!(entries[1].is_dir);
+    }
+
+    #[tokio::test]
+    async fn test_fake_fs_remove_dir_requires_recursive_when_not_empty() {
+        let fs = FakeFs::new().with_file("/work/sub/b.txt", "b");
+
+        let err = fs
+            .remove_dir(Path::new("/work/sub"), RemoveOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidInput(_)));
+
+        fs.remove_dir(
+            Path::new("/work/sub"),
+            RemoveOptions {
+                recursive: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(fs.metadata(Path::new("/work/sub/b.txt")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fake_fs_metadata_missing_path_is_none() {
+        let fs = FakeFs::new();
+        assert!(fs.metadata(Path::new("/nope")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fake_fs_read_bytes_returns_raw_contents() {
+        let fs = FakeFs::new().with_file("/work/a.bin", vec![0xff, 0xfe, b'h', 0]);
+        assert_eq!(fs.read_bytes(Path::new("/work/a.bin")).await.unwrap(), vec![0xff, 0xfe, b'h', 0]);
+    }
+}
@@ -0,0 +1,194 @@
+use hehe_core::Context;
+use hehe_store::{Document, SearchStore};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long [`FileIndexSync`] waits after the most recent change to any path
+/// before flushing, so a burst of writes to the same file (or to many files
+/// in one agent turn) collapses into a single batch update.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+enum PendingChange {
+    Upsert(Document),
+    Remove,
+}
+
+struct SyncState {
+    pending: HashMap<String, PendingChange>,
+    /// Bumped on every scheduled change; a sleeping flush task only runs if
+    /// it's still the most recently scheduled one, so earlier tasks in a
+    /// burst become no-ops instead of each flushing a partial batch.
+    generation: u64,
+}
+
+/// Keeps a [`SearchStore`] index in sync with filesystem edits made through
+/// `hehe-tools`'s file tools. Attach one to [`Context`] via
+/// [`Context::with_extension`] (wrapped in an `Arc`) and the tools that
+/// mutate files — `WriteFileTool`, `MoveFileTool`, `DeleteFileTool` — will
+/// call [`Self::upsert`]/[`Self::remove`] after a successful operation,
+/// debouncing bursts of edits into one `index_documents`/`delete_documents`
+/// call per quiet period rather than reindexing on every single write.
+pub struct FileIndexSync {
+    store: Arc<dyn SearchStore>,
+    index: String,
+    debounce: Duration,
+    state: Arc<Mutex<SyncState>>,
+}
+
+impl FileIndexSync {
+    pub fn new(store: Arc<dyn SearchStore>, index: impl Into<String>) -> Self {
+        Self {
+            store,
+            index: index.into(),
+            debounce: DEFAULT_DEBOUNCE,
+            state: Arc::new(Mutex::new(SyncState {
+                pending: HashMap::new(),
+                generation: 0,
+            })),
+        }
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Schedules `path` to be upserted with `content` once the debounce
+    /// window elapses with no further change to any path.
+    pub fn upsert(&self, path: &str, content: &str) {
+        self.schedule(path, PendingChange::Upsert(Document::new(path, content)));
+    }
+
+    /// Schedules `path` to be removed from the index once the debounce
+    /// window elapses with no further change to any path.
+    pub fn remove(&self, path: &str) {
+        self.schedule(path, PendingChange::Remove);
+    }
+
+    fn schedule(&self, path: &str, change: PendingChange) {
+        let generation = {
+            let mut state = self.state.lock().unwrap();
+            state.pending.insert(path.to_string(), change);
+            state.generation += 1;
+            state.generation
+        };
+
+        let store = self.store.clone();
+        let index = self.index.clone();
+        let state = self.state.clone();
+        let debounce = self.debounce;
+
+        tokio::spawn(async move {
+            tokio::time::sleep(debounce).await;
+
+            let pending = {
+                let mut state = state.lock().unwrap();
+                if state.generation != generation {
+                    // A later change was scheduled while we slept; that
+                    // task's own sleep will flush the merged batch instead.
+                    return;
+                }
+                std::mem::take(&mut state.pending)
+            };
+
+            Self::flush(&store, &index, pending).await;
+        });
+    }
+
+    async fn flush(store: &Arc<dyn SearchStore>, index: &str, pending: HashMap<String, PendingChange>) {
+        let mut upserts = Vec::new();
+        let mut removals = Vec::new();
+        for (path, change) in pending {
+            match change {
+                PendingChange::Upsert(doc) => upserts.push(doc),
+                PendingChange::Remove => removals.push(path),
+            }
+        }
+
+        if !upserts.is_empty() {
+            let _ = store.index_documents(index, &upserts).await;
+        }
+        if !removals.is_empty() {
+            let _ = store.delete_documents(index, &removals).await;
+        }
+    }
+}
+
+/// Reads the [`FileIndexSync`] attached to `ctx` via
+/// [`Context::with_extension`], if any. Tools call this after a successful
+/// file mutation; a `None` result means no sink is configured and the
+/// caller should simply skip indexing.
+pub fn ctx_file_index_sync(ctx: &Context) -> Option<Arc<FileIndexSync>> {
+    ctx.get_extension_typed::<Arc<FileIndexSync>>().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hehe_store::{IndexSchema, SearchFilter};
+
+    async fn wait_for_flush() {
+        tokio::time::sleep(DEFAULT_DEBOUNCE + Duration::from_millis(50)).await;
+    }
+
+    #[tokio::test]
+    async fn test_upsert_is_searchable_after_debounce_elapses() {
+        let store: Arc<dyn SearchStore> = Arc::new(hehe_store::MemorySearchStore::new());
+        store.create_index("files", &IndexSchema::new()).await.unwrap();
+
+        let sync = FileIndexSync::new(store.clone(), "files");
+        sync.upsert("/work/a.txt", "hello world");
+
+        wait_for_flush().await;
+
+        let hits = store.search("files", "hello", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "/work/a.txt");
+    }
+
+    #[tokio::test]
+    async fn test_remove_deletes_from_index_after_debounce_elapses() {
+        let store: Arc<dyn SearchStore> = Arc::new(hehe_store::MemorySearchStore::new());
+        store.create_index("files", &IndexSchema::new()).await.unwrap();
+        store
+            .index_documents("files", &[Document::new("/work/a.txt", "hello world")])
+            .await
+            .unwrap();
+
+        let sync = FileIndexSync::new(store.clone(), "files");
+        sync.remove("/work/a.txt");
+
+        wait_for_flush().await;
+
+        assert_eq!(store.count("files").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_burst_of_writes_to_one_path_coalesces_into_latest_content() {
+        let store: Arc<dyn SearchStore> = Arc::new(hehe_store::MemorySearchStore::new());
+        store.create_index("files", &IndexSchema::new()).await.unwrap();
+
+        let sync = FileIndexSync::new(store.clone(), "files");
+        sync.upsert("/work/a.txt", "first draft");
+        sync.upsert("/work/a.txt", "second draft");
+        sync.upsert("/work/a.txt", "final draft");
+
+        wait_for_flush().await;
+
+        assert_eq!(store.count("files").await.unwrap(), 1);
+        let hits = store.search("files", "final", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        let hits = store
+            .search_with_filter("files", "first", &SearchFilter::default(), None, 10)
+            .await
+            .unwrap();
+        assert!(hits.is_empty(), "superseded drafts should never be indexed");
+    }
+
+    #[tokio::test]
+    async fn test_ctx_file_index_sync_defaults_to_none() {
+        let ctx = Context::new();
+        assert!(ctx_file_index_sync(&ctx).is_none());
+    }
+}
@@ -0,0 +1,366 @@
+use crate::executor::{default_concurrency, ToolExecutor};
+use hehe_core::traits::Lifecycle;
+use hehe_core::{Context, Result as CoreResult, ToolCall, ToolCallId, ToolCallStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
+
+/// A snapshot of one call's progress, broadcast to anyone watching a [`JobBatch`]
+/// run. `fraction` is a coarse 0.0 (dispatched) / 1.0 (terminal) marker today since
+/// individual [`crate::traits::Tool`] implementations have no finer-grained progress
+/// hook yet; `message` carries a short human-readable phase name.
+#[derive(Clone, Debug)]
+pub struct ProgressUpdate {
+    pub tool_call_id: ToolCallId,
+    pub fraction: f32,
+    pub message: String,
+}
+
+/// A durable, re-loadable snapshot of a [`JobBatch`]: enough to resume dispatching
+/// whatever calls hadn't reached a terminal status when it was taken.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobReport {
+    pub id: hehe_core::Id,
+    pub calls: Vec<ToolCall>,
+}
+
+/// Runs a batch of [`ToolCall`]s on a bounded worker pool, with per-call
+/// cancellation, suspend/resume, and a progress feed — the orchestration layer
+/// [`ToolExecutor::execute_many`] doesn't provide on its own.
+///
+/// `JobBatch` implements [`Lifecycle`]: `start` dispatches every non-terminal call
+/// and waits for the batch to drain, `stop` cancels whatever is still in flight.
+pub struct JobBatch {
+    id: hehe_core::Id,
+    executor: Arc<ToolExecutor>,
+    concurrency: usize,
+    calls: Arc<RwLock<Vec<ToolCall>>>,
+    tokens: Arc<Mutex<HashMap<ToolCallId, CancellationToken>>>,
+    progress: broadcast::Sender<ProgressUpdate>,
+    running: Arc<AtomicBool>,
+}
+
+impl JobBatch {
+    pub fn new(executor: Arc<ToolExecutor>, calls: Vec<ToolCall>) -> Self {
+        let (progress, _) = broadcast::channel(256);
+        Self {
+            id: hehe_core::Id::new(),
+            executor,
+            concurrency: default_concurrency(),
+            calls: Arc::new(RwLock::new(calls)),
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+            progress,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn id(&self) -> hehe_core::Id {
+        self.id
+    }
+
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<ProgressUpdate> {
+        self.progress.subscribe()
+    }
+
+    /// Snapshot the batch's current state so it can be persisted and picked back
+    /// up later via [`JobBatch::resume`].
+    pub async fn report(&self) -> JobReport {
+        JobReport {
+            id: self.id,
+            calls: self.calls.read().await.clone(),
+        }
+    }
+
+    /// Rebuild a batch from a checkpoint, marking any call that hadn't reached a
+    /// terminal status as [`ToolCallStatus::Resumed`] so the next [`JobBatch::start`]
+    /// re-dispatches it instead of skipping it as already-done.
+    pub fn resume(report: JobReport, executor: Arc<ToolExecutor>) -> Self {
+        let mut calls = report.calls;
+        for call in calls.iter_mut() {
+            if !call.status.is_terminal() {
+                call.resume();
+            }
+        }
+        Self {
+            id: report.id,
+            ..Self::new(executor, calls)
+        }
+    }
+
+    /// Request cancellation of one call. A no-op if the call has already reached
+    /// a terminal status — cancellation must never overwrite a result that's
+    /// already landed.
+    pub async fn cancel(&self, id: ToolCallId) -> bool {
+        let cancelled = {
+            let mut calls = self.calls.write().await;
+            match calls.iter_mut().find(|c| c.id == id) {
+                Some(call) if !call.status.is_terminal() => {
+                    call.cancel();
+                    true
+                }
+                _ => false,
+            }
+        };
+        if cancelled {
+            if let Some(token) = self.tokens.lock().await.get(&id) {
+                token.cancel();
+            }
+            self.emit_progress(id, 1.0, "cancelled");
+        }
+        cancelled
+    }
+
+    pub async fn cancel_all(&self) {
+        let ids: Vec<ToolCallId> = self.calls.read().await.iter().map(|c| c.id).collect();
+        for id in ids {
+            self.cancel(id).await;
+        }
+    }
+
+    fn emit_progress(&self, tool_call_id: ToolCallId, fraction: f32, message: impl Into<String>) {
+        let _ = self.progress.send(ProgressUpdate {
+            tool_call_id,
+            fraction,
+            message: message.into(),
+        });
+    }
+
+    /// Apply `f` to the stored call, but only if it hasn't already reached a
+    /// terminal status. Guards the cancel-vs-complete race: whichever of
+    /// "cancel()" or "the tool call actually finished" lands first wins, and the
+    /// other becomes a no-op.
+    async fn finish(&self, id: ToolCallId, f: impl FnOnce(&mut ToolCall)) {
+        let mut calls = self.calls.write().await;
+        if let Some(call) = calls.iter_mut().find(|c| c.id == id) {
+            if !call.status.is_terminal() {
+                f(call);
+            }
+        }
+    }
+
+    async fn dispatch_one(&self, ctx: &Context, id: ToolCallId) {
+        let token = CancellationToken::new();
+        self.tokens.lock().await.insert(id, token.clone());
+
+        let (name, input) = {
+            let calls = self.calls.read().await;
+            match calls.iter().find(|c| c.id == id) {
+                Some(call) => (call.name.clone(), call.input.clone()),
+                None => return,
+            }
+        };
+
+        self.finish(id, |call| call.start()).await;
+        self.emit_progress(id, 0.0, "started");
+
+        let call_ctx = ctx.child().with_cancellation(token);
+        let result = self.executor.execute(&call_ctx, &name, input).await;
+
+        match result {
+            Ok(output) if output.is_error => {
+                self.finish(id, move |call| call.fail(output.content)).await;
+            }
+            Ok(output) => {
+                let value: Value = serde_json::to_value(&output.content).unwrap_or(Value::Null);
+                self.finish(id, move |call| call.complete(value)).await;
+            }
+            Err(e) => {
+                self.finish(id, move |call| call.fail(e.to_string())).await;
+            }
+        }
+
+        self.tokens.lock().await.remove(&id);
+        self.emit_progress(id, 1.0, "finished");
+    }
+
+    /// Dispatch every non-terminal call, bounded by [`JobBatch::with_concurrency`]
+    /// in-flight workers at a time, and wait for the batch to drain.
+    pub async fn run(&self) {
+        use futures::stream::{self, StreamExt};
+
+        let ctx = Context::new();
+        let pending: Vec<ToolCallId> = self
+            .calls
+            .read()
+            .await
+            .iter()
+            .filter(|c| !c.status.is_terminal())
+            .map(|c| c.id)
+            .collect();
+
+        self.running.store(true, Ordering::SeqCst);
+        stream::iter(pending.into_iter().map(|id| self.dispatch_one(&ctx, id)))
+            .buffer_unordered(self.concurrency)
+            .for_each(|_| async {})
+            .await;
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub async fn calls(&self) -> Vec<ToolCall> {
+        self.calls.read().await.clone()
+    }
+
+    pub fn is_running_now(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl Lifecycle for JobBatch {
+    async fn start(&mut self) -> CoreResult<()> {
+        self.run().await;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> CoreResult<()> {
+        self.cancel_all().await;
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.is_running_now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::ToolRegistry;
+    use crate::traits::{Tool, ToolOutput};
+    use async_trait::async_trait;
+    use hehe_core::ToolDefinition;
+    use std::time::Duration;
+
+    struct EchoTool {
+        def: ToolDefinition,
+    }
+
+    impl EchoTool {
+        fn new() -> Self {
+            Self {
+                def: ToolDefinition::new("echo", "Echoes input"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn definition(&self) -> &ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(&self, _ctx: &Context, input: Value) -> crate::error::Result<ToolOutput> {
+            Ok(ToolOutput::text(input.to_string()))
+        }
+    }
+
+    struct SlowTool {
+        def: ToolDefinition,
+    }
+
+    impl SlowTool {
+        fn new() -> Self {
+            Self {
+                def: ToolDefinition::new("slow", "A slow tool"),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn definition(&self) -> &ToolDefinition {
+            &self.def
+        }
+
+        async fn execute(&self, _ctx: &Context, _input: Value) -> crate::error::Result<ToolOutput> {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(ToolOutput::text("done"))
+        }
+    }
+
+    fn executor_with(tool: impl Tool + 'static) -> Arc<ToolExecutor> {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(tool)).unwrap();
+        Arc::new(ToolExecutor::new(Arc::new(registry)))
+    }
+
+    #[tokio::test]
+    async fn test_run_completes_all_calls() {
+        let executor = executor_with(EchoTool::new());
+        let calls = vec![
+            ToolCall::new("echo", serde_json::json!({"a": 1})),
+            ToolCall::new("echo", serde_json::json!({"a": 2})),
+        ];
+        let batch = JobBatch::new(executor, calls);
+
+        batch.run().await;
+
+        let calls = batch.calls().await;
+        assert_eq!(calls.len(), 2);
+        assert!(calls.iter().all(|c| c.is_completed()));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_is_noop_after_terminal() {
+        let executor = executor_with(EchoTool::new());
+        let call = ToolCall::new("echo", serde_json::json!({}));
+        let id = call.id;
+        let batch = JobBatch::new(executor, vec![call]);
+
+        batch.run().await;
+        assert!(!batch.cancel(id).await);
+
+        let calls = batch.calls().await;
+        assert!(calls[0].is_completed());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_marks_in_flight_call_cancelled() {
+        let executor = executor_with(SlowTool::new());
+        let call = ToolCall::new("slow", serde_json::json!({}));
+        let id = call.id;
+        let batch = Arc::new(JobBatch::new(executor, vec![call]));
+
+        let runner = tokio::spawn({
+            let batch = Arc::clone(&batch);
+            async move { batch.run().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(batch.cancel(id).await);
+        runner.await.unwrap();
+
+        let calls = batch.calls().await;
+        assert!(calls[0].status == ToolCallStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_resume_only_redispatches_non_terminal_calls() {
+        let executor = executor_with(EchoTool::new());
+        let mut done = ToolCall::new("echo", serde_json::json!({}));
+        done.start();
+        done.complete(Value::Null);
+        let pending = ToolCall::new("echo", serde_json::json!({}));
+        let pending_id = pending.id;
+
+        let report = JobReport {
+            id: hehe_core::Id::new(),
+            calls: vec![done, pending],
+        };
+
+        let batch = JobBatch::resume(report, executor);
+        batch.run().await;
+
+        let calls = batch.calls().await;
+        let resumed = calls.iter().find(|c| c.id == pending_id).unwrap();
+        assert!(resumed.is_completed());
+    }
+}
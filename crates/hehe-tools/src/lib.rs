@@ -2,48 +2,89 @@ pub mod error;
 pub mod traits;
 pub mod registry;
 pub mod executor;
+pub mod fs;
 #[cfg(feature = "builtin")]
 pub mod builtin;
+pub mod job;
+pub mod resolver;
 pub mod sandbox;
+#[cfg(feature = "search-sync")]
+pub mod index_sync;
 
 pub use error::{Result, ToolError};
 pub use traits::{Artifact, ArtifactData, Tool, ToolOutput};
 pub use registry::ToolRegistry;
 pub use executor::ToolExecutor;
+pub use fs::{ctx_fs, CopyOptions, CreateOptions, DirEntry, FakeFs, Fs, FsMetadata, RealFs, RemoveOptions, RenameOptions};
+pub use job::{JobBatch, JobReport, ProgressUpdate};
 pub use sandbox::{NativeSandbox, Sandbox, SandboxConfig};
+#[cfg(all(target_os = "linux", feature = "landlock"))]
+pub use sandbox::LandlockSandbox;
+#[cfg(feature = "docker")]
+pub use sandbox::{ContainerSandbox, ContainerSandboxConfig};
 
 #[cfg(feature = "builtin")]
 pub use builtin::{
-    create_default_registry, register_all, 
-    ListDirectoryTool, ReadFileTool, SearchFilesTool, WriteFileTool,
+    create_default_registry, register_all,
+    CopyFileTool, DeleteFileTool, ListDirectoryTool, MoveFileTool, ReadFileTool, SearchFilesTool, WriteFileTool,
     GetSystemInfoTool,
 };
 
 #[cfg(all(feature = "builtin", feature = "shell"))]
-pub use builtin::ExecuteShellTool;
+pub use builtin::{ExecuteShellTool, ShellOutputEvent, ShellStream};
 
 #[cfg(all(feature = "builtin", feature = "http"))]
 pub use builtin::HttpRequestTool;
 
+#[cfg(all(feature = "builtin", feature = "pty"))]
+pub use builtin::PtyProcessTool;
+
+#[cfg(all(feature = "builtin", feature = "watch"))]
+pub use builtin::{FileWatchTool, FileWatcher};
+
+#[cfg(feature = "search-sync")]
+pub use index_sync::{ctx_file_index_sync, FileIndexSync};
+
+#[cfg(feature = "ytdlp")]
+pub use resolver::{YtDlpConfig, YtDlpResolver};
+
 pub mod prelude {
     pub use crate::error::{Result, ToolError};
     pub use crate::traits::{Artifact, ArtifactData, Tool, ToolOutput};
     pub use crate::registry::ToolRegistry;
     pub use crate::executor::ToolExecutor;
+    pub use crate::fs::{ctx_fs, CopyOptions, CreateOptions, DirEntry, FakeFs, Fs, FsMetadata, RealFs, RemoveOptions, RenameOptions};
+    pub use crate::job::{JobBatch, JobReport, ProgressUpdate};
     pub use crate::sandbox::{NativeSandbox, Sandbox, SandboxConfig};
+    #[cfg(all(target_os = "linux", feature = "landlock"))]
+    pub use crate::sandbox::LandlockSandbox;
+    #[cfg(feature = "docker")]
+    pub use crate::sandbox::{ContainerSandbox, ContainerSandboxConfig};
 
     #[cfg(feature = "builtin")]
     pub use crate::builtin::{
         create_default_registry, register_all,
-        ListDirectoryTool, ReadFileTool, SearchFilesTool, WriteFileTool,
+        CopyFileTool, DeleteFileTool, ListDirectoryTool, MoveFileTool, ReadFileTool, SearchFilesTool, WriteFileTool,
         GetSystemInfoTool,
     };
 
     #[cfg(all(feature = "builtin", feature = "shell"))]
-    pub use crate::builtin::ExecuteShellTool;
+    pub use crate::builtin::{ExecuteShellTool, ShellOutputEvent, ShellStream};
 
     #[cfg(all(feature = "builtin", feature = "http"))]
     pub use crate::builtin::HttpRequestTool;
+
+    #[cfg(all(feature = "builtin", feature = "pty"))]
+    pub use crate::builtin::PtyProcessTool;
+
+    #[cfg(all(feature = "builtin", feature = "watch"))]
+    pub use crate::builtin::{FileWatchTool, FileWatcher};
+
+    #[cfg(feature = "search-sync")]
+    pub use crate::index_sync::{ctx_file_index_sync, FileIndexSync};
+
+    #[cfg(feature = "ytdlp")]
+    pub use crate::resolver::{YtDlpConfig, YtDlpResolver};
 }
 
 #[cfg(test)]
@@ -59,6 +100,9 @@ mod tests {
         assert!(registry.contains("write_file"));
         assert!(registry.contains("list_directory"));
         assert!(registry.contains("search_files"));
+        assert!(registry.contains("copy_file"));
+        assert!(registry.contains("move_file"));
+        assert!(registry.contains("delete_file"));
         assert!(registry.contains("get_system_info"));
     }
 
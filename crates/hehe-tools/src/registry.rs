@@ -1,6 +1,8 @@
 use crate::error::{Result, ToolError};
-use crate::traits::Tool;
-use hehe_core::ToolDefinition;
+use crate::traits::{Tool, ToolOutput};
+use async_trait::async_trait;
+use hehe_core::{Context, ToolDefinition};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -28,6 +30,14 @@ impl ToolRegistry {
         self.register(Arc::from(tool))
     }
 
+    /// Registers `tool` under `{namespace}.{tool.name()}` instead of its bare
+    /// name, via a thin wrapper that overrides `definition().name` but
+    /// delegates everything else. Lets several catalogs (e.g. `fs`, `git`)
+    /// share one registry without name collisions.
+    pub fn register_namespaced(&mut self, namespace: &str, tool: Arc<dyn Tool>) -> Result<()> {
+        self.register(Arc::new(NamespacedTool::new(namespace, tool)))
+    }
+
     pub fn unregister(&mut self, name: &str) -> Option<Arc<dyn Tool>> {
         self.tools.remove(name)
     }
@@ -74,6 +84,61 @@ impl ToolRegistry {
             .map(|(name, _)| name.as_str())
             .collect()
     }
+
+    /// Builds a registry holding just the named tools, cheaply (each tool is
+    /// an `Arc` clone, not a deep copy). Unknown names are silently skipped;
+    /// callers that need to reject them should check [`Self::contains`] first.
+    pub fn subset(&self, names: &[&str]) -> Self {
+        let tools = names
+            .iter()
+            .filter_map(|name| self.tools.get(*name).map(|tool| (name.to_string(), Arc::clone(tool))))
+            .collect();
+        Self { tools }
+    }
+
+    /// Builds a registry holding every tool for which `pred` returns `true`.
+    pub fn filter(&self, pred: impl Fn(&dyn Tool) -> bool) -> Self {
+        let tools = self
+            .tools
+            .iter()
+            .filter(|(_, tool)| pred(tool.as_ref()))
+            .map(|(name, tool)| (name.clone(), Arc::clone(tool)))
+            .collect();
+        Self { tools }
+    }
+}
+
+/// Wraps a [`Tool`] to expose it under a namespaced name (see
+/// [`ToolRegistry::register_namespaced`]) without touching the tool's own
+/// definition.
+struct NamespacedTool {
+    inner: Arc<dyn Tool>,
+    def: ToolDefinition,
+}
+
+impl NamespacedTool {
+    fn new(namespace: &str, inner: Arc<dyn Tool>) -> Self {
+        let def = ToolDefinition {
+            name: format!("{namespace}.{}", inner.definition().name),
+            ..inner.definition().clone()
+        };
+        Self { inner, def }
+    }
+}
+
+#[async_trait]
+impl Tool for NamespacedTool {
+    fn definition(&self) -> &ToolDefinition {
+        &self.def
+    }
+
+    async fn execute(&self, ctx: &Context, input: Value) -> Result<ToolOutput> {
+        self.inner.execute(ctx, input).await
+    }
+
+    fn validate_input(&self, input: &Value) -> Result<()> {
+        self.inner.validate_input(input)
+    }
 }
 
 impl Default for ToolRegistry {
@@ -177,6 +242,44 @@ mod tests {
         assert!(!registry.contains("removable"));
     }
 
+    #[test]
+    fn test_registry_register_namespaced() {
+        let mut registry = ToolRegistry::new();
+        registry
+            .register_namespaced("fs", Arc::new(MockTool::new("read", false)))
+            .unwrap();
+
+        assert!(registry.contains("fs.read"));
+        assert_eq!(registry.get("fs.read").unwrap().name(), "fs.read");
+    }
+
+    #[test]
+    fn test_registry_subset_shares_tools_and_skips_unknown_names() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool::new("a", false))).unwrap();
+        registry.register(Arc::new(MockTool::new("b", false))).unwrap();
+        registry.register(Arc::new(MockTool::new("c", false))).unwrap();
+
+        let subset = registry.subset(&["a", "c", "missing"]);
+
+        assert_eq!(subset.len(), 2);
+        assert!(subset.contains("a"));
+        assert!(subset.contains("c"));
+        assert!(!subset.contains("b"));
+    }
+
+    #[test]
+    fn test_registry_filter_by_predicate() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Arc::new(MockTool::new("safe", false))).unwrap();
+        registry.register(Arc::new(MockTool::new("danger", true))).unwrap();
+
+        let dangerous_only = registry.filter(|t| t.is_dangerous());
+
+        assert_eq!(dangerous_only.len(), 1);
+        assert!(dangerous_only.contains("danger"));
+    }
+
     #[test]
     fn test_registry_definitions() {
         let mut registry = ToolRegistry::new();
@@ -0,0 +1,5 @@
+#[cfg(feature = "ytdlp")]
+pub mod ytdlp;
+
+#[cfg(feature = "ytdlp")]
+pub use ytdlp::{YtDlpConfig, YtDlpResolver};
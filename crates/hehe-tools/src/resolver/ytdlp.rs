@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use hehe_core::error::{Error, Result};
+use hehe_core::resource::{ResourceMeta, ResourceRef, ResourceResolver};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// Configuration for [`YtDlpResolver`]: which `yt-dlp` binary to run, how long to
+/// wait for it, which format to request, and an optional download size cap.
+#[derive(Clone, Debug)]
+pub struct YtDlpConfig {
+    pub binary_path: String,
+    pub socket_timeout: Duration,
+    pub format: String,
+    pub max_bytes: Option<u64>,
+}
+
+impl Default for YtDlpConfig {
+    fn default() -> Self {
+        Self {
+            binary_path: "yt-dlp".to_string(),
+            socket_timeout: Duration::from_secs(30),
+            format: "best".to_string(),
+            max_bytes: None,
+        }
+    }
+}
+
+impl YtDlpConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_binary_path(mut self, path: impl Into<String>) -> Self {
+        self.binary_path = path.into();
+        self
+    }
+
+    pub fn with_socket_timeout(mut self, timeout: Duration) -> Self {
+        self.socket_timeout = timeout;
+        self
+    }
+
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.format = format.into();
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct YtDlpSubtitleTrack {
+    url: String,
+}
+
+#[derive(Deserialize, Default)]
+struct YtDlpInfo {
+    url: Option<String>,
+    ext: Option<String>,
+    duration: Option<f64>,
+    filesize: Option<u64>,
+    filesize_approx: Option<u64>,
+    #[serde(default)]
+    requested_subtitles: HashMap<String, YtDlpSubtitleTrack>,
+    #[serde(default)]
+    automatic_captions: HashMap<String, Vec<YtDlpSubtitleTrack>>,
+}
+
+impl YtDlpInfo {
+    fn subtitle_url(&self) -> Option<&str> {
+        for lang in ["en", "en-US", "en-orig"] {
+            if let Some(track) = self.requested_subtitles.get(lang) {
+                return Some(&track.url);
+            }
+            if let Some(tracks) = self.automatic_captions.get(lang).and_then(|t| t.first()) {
+                return Some(&tracks.url);
+            }
+        }
+        self.requested_subtitles
+            .values()
+            .next()
+            .map(|t| t.url.as_str())
+    }
+
+    fn size(&self) -> Option<u64> {
+        self.filesize.or(self.filesize_approx)
+    }
+}
+
+/// Extracts the plain-text cues from a WebVTT transcript, dropping cue numbers,
+/// timing lines, and consecutive duplicate lines (auto-captions repeat a lot).
+fn vtt_to_text(vtt: &str) -> String {
+    let mut lines = Vec::new();
+    let mut last: Option<&str> = None;
+    for line in vtt.lines() {
+        let line = line.trim();
+        if line.is_empty() || line == "WEBVTT" || line.contains("-->") || line.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if Some(line) != last {
+            lines.push(line);
+            last = Some(line);
+        }
+    }
+    lines.join(" ")
+}
+
+/// A [`ResourceResolver`] that treats [`ResourceRef::Url`] as a hosted video/audio
+/// page (YouTube, etc.) rather than a document to `GET` directly: it shells out to
+/// `yt-dlp --dump-json` to extract a direct media stream URL plus duration and
+/// (when available) an auto-caption transcript, then downloads the stream for
+/// [`ResourceResolver::resolve`]/[`ResourceResolver::resolve_base64`].
+pub struct YtDlpResolver {
+    config: YtDlpConfig,
+    http: reqwest::Client,
+}
+
+impl YtDlpResolver {
+    pub fn new(config: YtDlpConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    async fn run_dump_json(&self, url: &str) -> Result<YtDlpInfo> {
+        let output = timeout(
+            self.config.socket_timeout,
+            Command::new(&self.config.binary_path)
+                .arg("--dump-json")
+                .arg("--no-warnings")
+                .arg("-f")
+                .arg(&self.config.format)
+                .arg(url)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output(),
+        )
+        .await
+        .map_err(|_| Error::Timeout(self.config.socket_timeout.as_millis() as u64))?
+        .map_err(|e| {
+            Error::tool(
+                "ytdlp_resolver",
+                format!("failed to launch '{}': {e}", self.config.binary_path),
+            )
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::tool(
+                "ytdlp_resolver",
+                format!("yt-dlp exited with {}: {}", output.status, stderr.trim()),
+            ));
+        }
+
+        serde_json::from_slice(&output.stdout)
+            .map_err(|e| Error::tool("ytdlp_resolver", format!("failed to parse yt-dlp output: {e}")))
+    }
+
+    async fn fetch_transcript(&self, info: &YtDlpInfo) -> Option<String> {
+        let url = info.subtitle_url()?;
+        let body = self.http.get(url).send().await.ok()?.text().await.ok()?;
+        Some(vtt_to_text(&body))
+    }
+
+    async fn download(&self, direct_url: &str) -> Result<bytes::Bytes> {
+        let response = self
+            .http
+            .get(direct_url)
+            .send()
+            .await
+            .map_err(|e| Error::tool("ytdlp_resolver", format!("failed to fetch media stream: {e}")))?;
+
+        if let Some(max_bytes) = self.config.max_bytes {
+            if let Some(len) = response.content_length() {
+                if len > max_bytes {
+                    return Err(Error::tool(
+                        "ytdlp_resolver",
+                        format!("media stream is {len} bytes, exceeding the {max_bytes} byte cap"),
+                    ));
+                }
+            }
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| Error::tool("ytdlp_resolver", format!("failed to read media stream: {e}")))?;
+
+        if let Some(max_bytes) = self.config.max_bytes {
+            let len = bytes.len() as u64;
+            if len > max_bytes {
+                return Err(Error::tool(
+                    "ytdlp_resolver",
+                    format!("media stream is {len} bytes, exceeding the {max_bytes} byte cap"),
+                ));
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    fn url_of(resource: &ResourceRef) -> Result<&str> {
+        match resource {
+            ResourceRef::Url { url } => Ok(url.as_str()),
+            other => Err(Error::invalid_input(
+                "resource",
+                format!("YtDlpResolver only resolves ResourceRef::Url, got {other:?}"),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl ResourceResolver for YtDlpResolver {
+    async fn resolve(&self, resource: &ResourceRef) -> Result<bytes::Bytes> {
+        let url = Self::url_of(resource)?;
+        let info = self.run_dump_json(url).await?;
+        let direct_url = info
+            .url
+            .as_deref()
+            .ok_or_else(|| Error::tool("ytdlp_resolver", "yt-dlp did not return a direct media URL"))?;
+        self.download(direct_url).await
+    }
+
+    async fn resolve_base64(&self, resource: &ResourceRef) -> Result<String> {
+        let bytes = self.resolve(resource).await?;
+        Ok(hehe_core::utils::encoding::encode_base64(&bytes))
+    }
+
+    async fn metadata(&self, resource: &ResourceRef) -> Result<ResourceMeta> {
+        let url = Self::url_of(resource)?;
+        let info = self.run_dump_json(url).await?;
+
+        let mut meta = ResourceMeta::new();
+        if let Some(ext) = &info.ext {
+            meta = meta.with_media_type(format!("video/{ext}"));
+        }
+        if let Some(size) = info.size() {
+            meta = meta.with_size(size);
+        }
+        Ok(meta)
+    }
+}
+
+impl YtDlpResolver {
+    /// Resolve `resource` into `duration_ms` plus, when auto-captions exist, a
+    /// transcript — the two fields `ResourceMeta` has no place for but
+    /// `AudioContent`/`VideoContent` do.
+    pub async fn resolve_media_details(&self, resource: &ResourceRef) -> Result<MediaDetails> {
+        let url = Self::url_of(resource)?;
+        let info = self.run_dump_json(url).await?;
+        let transcript = self.fetch_transcript(&info).await;
+
+        Ok(MediaDetails {
+            duration_ms: info.duration.map(|d| (d * 1000.0) as u64),
+            transcript,
+        })
+    }
+}
+
+/// Media-specific details `yt-dlp` can surface that don't fit [`ResourceMeta`]:
+/// duration and, for auto-captioned sources, a flattened transcript — meant to
+/// populate `AudioContent::duration_ms`/`transcript` or `VideoContent::duration_ms`.
+#[derive(Clone, Debug, Default)]
+pub struct MediaDetails {
+    pub duration_ms: Option<u64>,
+    pub transcript: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vtt_to_text_strips_timings_and_dupes() {
+        let vtt = "WEBVTT\n\n1\n00:00:00.000 --> 00:00:02.000\nhello there\n\n2\n00:00:02.000 --> 00:00:04.000\nhello there\n\n3\n00:00:04.000 --> 00:00:06.000\ngeneral kenobi\n";
+        let text = vtt_to_text(vtt);
+        assert_eq!(text, "hello there general kenobi");
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = YtDlpConfig::new();
+        assert_eq!(config.binary_path, "yt-dlp");
+        assert_eq!(config.format, "best");
+        assert!(config.max_bytes.is_none());
+    }
+
+    #[test]
+    fn test_url_of_rejects_non_url_resource() {
+        let resource = ResourceRef::content_address("deadbeef");
+        assert!(YtDlpResolver::url_of(&resource).is_err());
+    }
+}
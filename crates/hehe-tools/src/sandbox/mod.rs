@@ -0,0 +1,11 @@
+mod native;
+#[cfg(all(target_os = "linux", feature = "landlock"))]
+mod landlock;
+#[cfg(feature = "docker")]
+mod container;
+
+pub use native::{NativeSandbox, Sandbox, SandboxConfig};
+#[cfg(all(target_os = "linux", feature = "landlock"))]
+pub use landlock::LandlockSandbox;
+#[cfg(feature = "docker")]
+pub use container::{ContainerSandbox, ContainerSandboxConfig};
@@ -126,6 +126,14 @@ pub trait Sandbox: Send + Sync {
         ctx: &Context,
         input: Value,
     ) -> Result<ToolOutput>;
+
+    /// Name of the isolation backend actually enforcing [`Self::config`],
+    /// e.g. `"native"` or `"landlock"`. Mainly useful for logging/diagnostics
+    /// so operators can tell whether a stronger backend like
+    /// [`super::LandlockSandbox`](crate::sandbox::LandlockSandbox) fell back.
+    fn backend_name(&self) -> &'static str {
+        "native"
+    }
 }
 
 pub struct NativeSandbox {